@@ -0,0 +1,603 @@
+/// 1つのファイルを書き換える
+///
+/// 同じディレクトリに一時ファイルを書き出してfsyncしてから, 本来のパスにrenameで上書きする.
+/// 途中で失敗, 中断しても元のファイルか新しいファイルのどちらかが残る
+///
+/// 親ディレクトリは存在していること
+///
+/// # Errors
+/// - 一時ファイルの作成, 書き込み, renameに失敗したとき
+///   - このときファイルは呼び出し前の状態のまま
+pub fn write_file(path: &std::path::Path, content: &[u8]) -> std::io::Result<()> {
+    let tmp_path = sibling_path(path, "tmp");
+    stage(&tmp_path, content)?;
+    if let Err(e) = std::fs::rename(&tmp_path, path) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    sync_parent_dirs([path])
+}
+
+/// 複数のファイルを全て書き換えるか, 1つも書き換えないかのどちらかにする
+///
+/// 1. 書き換えるファイル, 一時ファイル, 退避先をジャーナル`journal`に書き出す
+/// 2. 各ファイルと同じディレクトリに一時ファイルを書き出してfsyncする
+/// 3. 既存のファイルを退避先にハードリンクしてから, 一時ファイルを本来のパスにrenameで上書きする
+/// 4. 親ディレクトリをfsyncしてから, ジャーナルを書き換え完了の状態にし, 退避先とジャーナルを消す
+///
+/// 途中で失敗したときは, その場で呼び出し前の状態に戻す.
+/// プロセスが途中で終了したときはジャーナルが残るので, 次に書き込む前に[`recover`]で戻すこと
+///
+/// `journal`を書き換えるプロセスは1つだけであること. 親ディレクトリは存在していること
+///
+/// # Errors
+/// - ジャーナル, 一時ファイルの書き込み, renameに失敗したとき
+///   - このときファイルは呼び出し前の状態に戻される
+pub fn write_files(
+    journal: &std::path::Path,
+    files: &[(std::path::PathBuf, Vec<u8>)],
+) -> std::io::Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut doc = Journal {
+        state: JournalState::Prepared,
+        entries: files
+            .iter()
+            .map(|(path, _)| JournalEntry {
+                path: path.clone(),
+                tmp: sibling_path(path, "tmp"),
+                backup: path.exists().then(|| sibling_path(path, "bak")),
+            })
+            .collect(),
+    };
+    write_journal(journal, &doc)?;
+
+    let contents = files.iter().map(|(_, content)| content.as_slice());
+    if let Err(e) = apply(&doc.entries, contents) {
+        roll_back(journal, &doc.entries);
+        return Err(e);
+    }
+
+    doc.state = JournalState::Committed;
+    if let Err(e) = write_journal(journal, &doc) {
+        roll_back(journal, &doc.entries);
+        return Err(e);
+    }
+    finish(journal, &doc.entries);
+    Ok(())
+}
+
+/// [`write_files`], [`replace_dirs`]の途中で終了したときに残ったジャーナルから, 一貫した状態に戻す
+///
+/// - 書き換え完了の前に終了していたときは, 呼び出し前の状態に戻す
+/// - 書き換え完了の後に終了していたときは, 残った退避先を消す
+/// - ジャーナルが無いときは何もしない
+///
+/// 書き込みのロックを取った直後, ファイルを読み込む前に呼ぶこと.
+/// 戻したとき`true`
+///
+/// # Errors
+/// - ジャーナルの読み込み, パースに失敗したとき
+/// - ファイルを元に戻せなかったとき. ジャーナルは残す
+pub fn recover(journal: &std::path::Path) -> std::io::Result<bool> {
+    let content = match std::fs::read_to_string(journal) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let doc: Journal = serde_json::from_str(&content)?;
+
+    match doc.state {
+        JournalState::Prepared => {
+            tracing::warn!(
+                "Rolling back an interrupted write of {} paths: {}",
+                doc.entries.len(),
+                journal.display()
+            );
+            restore(&doc.entries)?;
+        }
+        JournalState::Committed => {
+            tracing::info!(
+                "Cleaning up a completed write of {} paths: {}",
+                doc.entries.len(),
+                journal.display()
+            );
+        }
+    }
+    finish(journal, &doc.entries);
+    Ok(true)
+}
+
+/// 書き出し済みのディレクトリで, 複数のディレクトリを全て置き換えるか, 1つも置き換えないかのどちらかにする
+///
+/// `dirs`は`(書き出し済みのディレクトリ, 置き換え先)`の組.
+///
+/// 1. 書き出し済みのディレクトリの中身をfsyncする
+/// 2. 置き換え先, 書き出し済みのディレクトリ, 退避先をジャーナル`journal`に書き出す
+/// 3. 置き換え先を退避先にrenameしてから, 書き出し済みのディレクトリを置き換え先にrenameする
+/// 4. 親ディレクトリをfsyncしてから, ジャーナルを書き換え完了の状態にし, 退避先とジャーナルを消す
+///
+/// ディレクトリごとにrenameするため, 置き換えている間に読むと一部だけ新しいことがある.
+/// 途中で失敗したときはその場で, プロセスが途中で終了したときは次の[`recover`]で呼び出し前の状態に戻す
+///
+/// `journal`を書き換えるプロセスは1つだけであること.
+/// 書き出し済みのディレクトリと置き換え先は同じファイルシステム上にあること
+///
+/// # Errors
+/// - fsync, ジャーナルの書き込み, renameに失敗したとき
+///   - このとき置き換え先は呼び出し前の状態に戻され, 書き出し済みのディレクトリは消される
+pub fn replace_dirs(
+    journal: &std::path::Path,
+    dirs: &[(std::path::PathBuf, std::path::PathBuf)],
+) -> std::io::Result<()> {
+    if dirs.is_empty() {
+        return Ok(());
+    }
+    for (staged_dir, _) in dirs {
+        sync_tree(staged_dir)?;
+    }
+
+    let mut doc = Journal {
+        state: JournalState::Prepared,
+        entries: dirs
+            .iter()
+            .map(|(staged_dir, path)| JournalEntry {
+                path: path.clone(),
+                tmp: staged_dir.clone(),
+                backup: path.exists().then(|| sibling_path(path, "bak")),
+            })
+            .collect(),
+    };
+    write_journal(journal, &doc)?;
+
+    if let Err(e) = swap_dirs(&doc.entries) {
+        roll_back(journal, &doc.entries);
+        return Err(e);
+    }
+
+    doc.state = JournalState::Committed;
+    if let Err(e) = write_journal(journal, &doc) {
+        roll_back(journal, &doc.entries);
+        return Err(e);
+    }
+    finish(journal, &doc.entries);
+    Ok(())
+}
+
+/// [`write_files`], [`replace_dirs`]の書き換えの記録
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Journal {
+    state: JournalState,
+    entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum JournalState {
+    /// 書き換えの途中. 戻すときは呼び出し前の状態にする
+    Prepared,
+    /// 全てのファイル, ディレクトリを書き換えた. 退避先を消すだけでよい
+    Committed,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry {
+    path: std::path::PathBuf,
+    /// 書き出した一時ファイル, ディレクトリ
+    tmp: std::path::PathBuf,
+    /// 元のファイル, ディレクトリの退避先. 元が無かったときは`None`
+    backup: Option<std::path::PathBuf>,
+}
+
+/// 一時ファイルを書き出し, 既存のファイルを退避してから置き換える
+fn apply<'a>(
+    entries: &[JournalEntry],
+    contents: impl Iterator<Item = &'a [u8]>,
+) -> std::io::Result<()> {
+    for (entry, content) in entries.iter().zip(contents) {
+        stage(&entry.tmp, content)?;
+    }
+    for entry in entries {
+        if let Some(backup) = &entry.backup {
+            std::fs::hard_link(&entry.path, backup)?;
+        }
+    }
+    sync_parent_dirs(entries.iter().map(|entry| entry.path.as_path()))?;
+    for entry in entries {
+        std::fs::rename(&entry.tmp, &entry.path)?;
+    }
+    sync_parent_dirs(entries.iter().map(|entry| entry.path.as_path()))
+}
+
+/// 既存のディレクトリを退避してから, 書き出し済みのディレクトリで置き換える
+fn swap_dirs(entries: &[JournalEntry]) -> std::io::Result<()> {
+    for entry in entries {
+        if let Some(backup) = &entry.backup {
+            std::fs::rename(&entry.path, backup)?;
+        }
+        std::fs::rename(&entry.tmp, &entry.path)?;
+    }
+    sync_parent_dirs(entries.iter().map(|entry| entry.path.as_path()))
+}
+
+/// 呼び出し前の状態に戻し, ジャーナルを消す. 戻せなかったときはジャーナルを残す
+fn roll_back(journal: &std::path::Path, entries: &[JournalEntry]) {
+    match restore(entries) {
+        Ok(()) => finish(journal, entries),
+        Err(e) => tracing::error!(
+            "Failed to roll back; run again to recover from {}: {e}",
+            journal.display()
+        ),
+    }
+}
+
+/// 退避先を戻し, 新しく作ったファイル, ディレクトリと一時ファイル, ディレクトリを消す
+fn restore(entries: &[JournalEntry]) -> std::io::Result<()> {
+    for entry in entries.iter().rev() {
+        match &entry.backup {
+            // 退避先が無ければ, まだ置き換えていない
+            Some(backup) if backup.exists() => {
+                // 空でないディレクトリにはrenameできないので, 先に置き換えたものを消す
+                if entry.path.is_dir() {
+                    std::fs::remove_dir_all(&entry.path)?;
+                }
+                std::fs::rename(backup, &entry.path)?;
+            }
+            Some(_) => {}
+            // 一時ファイルが無ければ, 置き換え済みか一時ファイルを書き出す前
+            None if !entry.tmp.exists() => remove_if_exists(&entry.path)?,
+            None => {}
+        }
+        remove_if_exists(&entry.tmp)?;
+    }
+    sync_parent_dirs(entries.iter().map(|entry| entry.path.as_path()))
+}
+
+/// 退避先, 一時ファイル, ジャーナルを消す
+fn finish(journal: &std::path::Path, entries: &[JournalEntry]) {
+    for path in entries
+        .iter()
+        .flat_map(|entry| entry.backup.iter().chain([&entry.tmp]))
+    {
+        if let Err(e) = remove_if_exists(path) {
+            tracing::warn!("Failed to remove {}: {e}", path.display());
+        }
+    }
+    if let Err(e) = remove_if_exists(journal).and_then(|_| sync_parent_dirs([journal]))
+    {
+        tracing::warn!("Failed to remove {}: {e}", journal.display());
+    }
+}
+
+fn write_journal(journal: &std::path::Path, doc: &Journal) -> std::io::Result<()> {
+    let content = serde_json::to_vec_pretty(doc)?;
+    write_file(journal, &content)
+}
+
+fn stage(tmp_path: &std::path::Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(tmp_path)?;
+    let result = file.write_all(content).and_then(|_| file.sync_all());
+    if result.is_err() {
+        let _ = std::fs::remove_file(tmp_path);
+    }
+    result
+}
+
+/// `paths`の親ディレクトリをfsyncして, renameや削除を永続化する
+fn sync_parent_dirs<'a>(
+    paths: impl IntoIterator<Item = &'a std::path::Path>,
+) -> std::io::Result<()> {
+    let dirs = paths
+        .into_iter()
+        .filter_map(std::path::Path::parent)
+        .map(|dir| {
+            if dir.as_os_str().is_empty() {
+                std::path::Path::new(".")
+            } else {
+                dir
+            }
+        })
+        .collect::<std::collections::BTreeSet<_>>();
+    for dir in dirs {
+        sync_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// 存在しないディレクトリには永続化するものが無いので何もしない
+#[cfg(unix)]
+fn sync_dir(dir: &std::path::Path) -> std::io::Result<()> {
+    match std::fs::File::open(dir) {
+        Ok(dir) => dir.sync_all(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// ディレクトリを開いてfsyncできないため何もしない
+#[cfg(not(unix))]
+fn sync_dir(_dir: &std::path::Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// `dir`の中のファイル, ディレクトリを全てfsyncする
+fn sync_tree(dir: &std::path::Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            sync_tree(&entry.path())?;
+        } else {
+            std::fs::File::open(entry.path())?.sync_all()?;
+        }
+    }
+    sync_dir(dir)
+}
+
+/// ファイル, ディレクトリを消す. 存在しないときは何もしない
+fn remove_if_exists(path: &std::path::Path) -> std::io::Result<()> {
+    let result = match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(path),
+        Ok(_) => std::fs::remove_file(path),
+        Err(e) => Err(e),
+    };
+    match result {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// `dir/.name.<suffix>-<pid>`
+fn sibling_path(path: &std::path::Path, suffix: &str) -> std::path::PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{file_name}.{suffix}-{}", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `dir`にあるエントリの数
+    fn count_entries(dir: &std::path::Path) -> usize {
+        std::fs::read_dir(dir).unwrap().count()
+    }
+
+    #[test]
+    fn test_write_file_replaces() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("a.json");
+        std::fs::write(&path, "old").unwrap();
+
+        write_file(&path, b"new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+
+    #[test]
+    fn test_write_files_replaces_and_creates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = tmp.path().join(".journal");
+        let existing = tmp.path().join("a.json");
+        let created = tmp.path().join("b.json");
+        std::fs::write(&existing, "old").unwrap();
+
+        write_files(
+            &journal,
+            &[
+                (existing.clone(), b"new".to_vec()),
+                (created.clone(), b"created".to_vec()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(&created).unwrap(), "created");
+        // ジャーナル, 一時ファイル, 退避ファイルは残らない
+        assert_eq!(count_entries(tmp.path()), 2);
+    }
+
+    #[test]
+    fn test_write_files_leaves_nothing_when_staging_fails() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = tmp.path().join(".journal");
+        let existing = tmp.path().join("a.json");
+        std::fs::write(&existing, "old").unwrap();
+
+        let err = write_files(
+            &journal,
+            &[
+                (existing.clone(), b"new".to_vec()),
+                (tmp.path().join("missing/b.json"), b"x".to_vec()),
+            ],
+        );
+
+        assert!(err.is_err());
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "old");
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+
+    /// `a.json`を置き換え, `b.json`を作る途中で終了した状態を作る
+    ///
+    /// `a.json`は置き換え済み, `b.json`は一時ファイルのまま
+    fn interrupted_write(dir: &std::path::Path, state: JournalState) -> Journal {
+        let existing = dir.join("a.json");
+        let created = dir.join("b.json");
+        std::fs::write(&existing, "old").unwrap();
+
+        let doc = Journal {
+            state,
+            entries: vec![
+                JournalEntry {
+                    path: existing.clone(),
+                    tmp: sibling_path(&existing, "tmp"),
+                    backup: Some(sibling_path(&existing, "bak")),
+                },
+                JournalEntry {
+                    path: created.clone(),
+                    tmp: sibling_path(&created, "tmp"),
+                    backup: None,
+                },
+            ],
+        };
+        write_journal(&dir.join(".journal"), &doc).unwrap();
+        stage(&doc.entries[0].tmp, b"new").unwrap();
+        stage(&doc.entries[1].tmp, b"created").unwrap();
+        std::fs::hard_link(&existing, doc.entries[0].backup.as_ref().unwrap()).unwrap();
+        std::fs::rename(&doc.entries[0].tmp, &existing).unwrap();
+        doc
+    }
+
+    #[test]
+    fn test_recover_rolls_back_prepared_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        interrupted_write(tmp.path(), JournalState::Prepared);
+
+        assert!(recover(&tmp.path().join(".journal")).unwrap());
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.json")).unwrap(),
+            "old"
+        );
+        assert!(!tmp.path().join("b.json").exists());
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+
+    #[test]
+    fn test_recover_removes_new_file_of_prepared_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        let doc = interrupted_write(tmp.path(), JournalState::Prepared);
+        // `b.json`も置き換え済み
+        std::fs::rename(&doc.entries[1].tmp, &doc.entries[1].path).unwrap();
+
+        assert!(recover(&tmp.path().join(".journal")).unwrap());
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.json")).unwrap(),
+            "old"
+        );
+        assert!(!tmp.path().join("b.json").exists());
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+
+    #[test]
+    fn test_recover_cleans_up_committed_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        let doc = interrupted_write(tmp.path(), JournalState::Committed);
+        std::fs::rename(&doc.entries[1].tmp, &doc.entries[1].path).unwrap();
+
+        assert!(recover(&tmp.path().join(".journal")).unwrap());
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.json")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("b.json")).unwrap(),
+            "created"
+        );
+        assert_eq!(count_entries(tmp.path()), 2);
+    }
+
+    #[test]
+    fn test_recover_without_journal() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(!recover(&tmp.path().join(".journal")).unwrap());
+    }
+
+    /// `music`を置き換え, `search`を新しく作る書き出し済みのディレクトリ
+    fn staged_dirs(
+        dir: &std::path::Path,
+    ) -> Vec<(std::path::PathBuf, std::path::PathBuf)> {
+        std::fs::create_dir(dir.join("music")).unwrap();
+        std::fs::write(dir.join("music/old.json"), "old").unwrap();
+        ["music", "search"]
+            .into_iter()
+            .map(|name| {
+                let staged = dir.join(format!(".staging-{name}"));
+                std::fs::create_dir(&staged).unwrap();
+                std::fs::write(staged.join("new.json"), name).unwrap();
+                (staged, dir.join(name))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replace_dirs_swaps_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = staged_dirs(tmp.path());
+
+        replace_dirs(&tmp.path().join(".journal"), &dirs).unwrap();
+
+        let music = tmp.path().join("music");
+        assert!(!music.join("old.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(music.join("new.json")).unwrap(),
+            "music"
+        );
+        assert!(tmp.path().join("search/new.json").exists());
+        // ジャーナル, 退避したディレクトリは残らない
+        assert_eq!(count_entries(tmp.path()), 2);
+    }
+
+    #[test]
+    fn test_replace_dirs_restores_directories_when_rename_fails() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut dirs = staged_dirs(tmp.path());
+        dirs[1].1 = tmp.path().join("missing/search");
+
+        let err = replace_dirs(&tmp.path().join(".journal"), &dirs);
+
+        assert!(err.is_err());
+        let music = tmp.path().join("music");
+        assert_eq!(
+            std::fs::read_to_string(music.join("old.json")).unwrap(),
+            "old"
+        );
+        assert!(!music.join("new.json").exists());
+        // 書き出し済みのディレクトリも消す
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+
+    #[test]
+    fn test_recover_rolls_back_interrupted_replace_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = staged_dirs(tmp.path());
+        let journal = tmp.path().join(".journal");
+        // `music`を置き換えた直後に終了した状態
+        let backup = sibling_path(&dirs[0].1, "bak");
+        let doc = Journal {
+            state: JournalState::Prepared,
+            entries: vec![
+                JournalEntry {
+                    path: dirs[0].1.clone(),
+                    tmp: dirs[0].0.clone(),
+                    backup: Some(backup.clone()),
+                },
+                JournalEntry {
+                    path: dirs[1].1.clone(),
+                    tmp: dirs[1].0.clone(),
+                    backup: None,
+                },
+            ],
+        };
+        write_journal(&journal, &doc).unwrap();
+        std::fs::rename(&dirs[0].1, &backup).unwrap();
+        std::fs::rename(&dirs[0].0, &dirs[0].1).unwrap();
+
+        assert!(recover(&journal).unwrap());
+
+        let music = tmp.path().join("music");
+        assert!(music.join("old.json").exists());
+        assert!(!music.join("new.json").exists());
+        assert_eq!(count_entries(tmp.path()), 1);
+    }
+}
@@ -56,6 +56,12 @@ impl serde::Serialize for Color {
     }
 }
 
+impl crate::json_schema::JsonSchema for Color {
+    fn json_schema(_: &crate::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "pattern": "^[A-Fa-f0-9]{6}$" })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,33 @@
+/// 変更前後のテキストをunified diff形式で返す
+///
+/// - `path`はヘッダ(`--- path`, `+++ path`)に用いる
+/// - 差分が無いときは空文字列
+pub fn unified(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(path, path)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_no_change() {
+        assert_eq!(unified("x.json", "a\nb\n", "a\nb\n"), "");
+    }
+
+    #[test]
+    fn test_unified_changed_line() {
+        let diff = unified("x.json", "a\nb\nc\n", "a\nB\nc\n");
+        assert_eq!(
+            diff,
+            "--- x.json\n+++ x.json\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n"
+        );
+    }
+}
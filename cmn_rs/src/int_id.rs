@@ -0,0 +1,429 @@
+/// 文字列IDから整数IDへの対応を保持するロックファイル
+///
+/// - 名前空間(ライバー, タグなど)ごとに`文字列ID -> 整数ID`を持つ
+/// - コミットしておき, 既存のIDの整数IDをビルドをまたいで変えないために使う
+///
+/// ```json
+/// { "livers": { "mito-tsukino": 0 }, "officialChannels": { "nijisanji-official": 950 } }
+/// ```
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq, Eq,
+)]
+#[serde(transparent)]
+pub struct IntIdLock(
+    std::collections::BTreeMap<String, std::collections::BTreeMap<String, u16>>,
+);
+
+/// 整数IDの名前空間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntIdSpace {
+    /// ロックファイルでのキー
+    pub name: &'static str,
+    /// 割り当てる整数IDの下限
+    pub start: u16,
+}
+
+/// 1つの名前空間の整数IDの割り当て結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntIdAllocation {
+    space: IntIdSpace,
+    ids: std::collections::BTreeMap<String, u16>,
+    /// ロックファイルに無く, 新たに割り当てた, または定義で指定されたID
+    added: Vec<String>,
+    /// 定義での指定がロックファイルと異なるID. (ID, ロックファイルの値)
+    changed: Vec<(String, u16)>,
+    /// ロックファイルにあるが, 定義に無いID
+    removed: Vec<String>,
+}
+
+/// 整数IDの検査で見つかった問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntIdDiagnostic {
+    pub space: &'static str,
+    /// `false`のときは警告
+    pub is_error: bool,
+    pub message: String,
+}
+
+impl IntIdLock {
+    /// ファイルが無いときは空のロックを返す
+    ///
+    /// # Errors
+    /// - ファイルの読み込み, JSONのパースに失敗したとき
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 整形したJSONで書き出す
+    ///
+    /// # Errors
+    /// - ファイルの書き込みに失敗したとき
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let content = self.to_pretty_json()?;
+        crate::atomic_write::write_file(path, content.as_bytes())
+    }
+
+    /// `save`で書き出す内容
+    pub fn to_pretty_json(&self) -> serde_json::Result<String> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        Ok(content)
+    }
+
+    /// 名前空間`space`のID`old`の整数IDを`new`に付け替える. 付け替えたとき`true`
+    ///
+    /// `new`が既にあるときは上書きする
+    pub fn rename(&mut self, space: &str, old: &str, new: &str) -> bool {
+        let Some(ids) = self.0.get_mut(space) else {
+            return false;
+        };
+        let Some(value) = ids.remove(old) else {
+            return false;
+        };
+        ids.insert(new.to_string(), value);
+        true
+    }
+
+    /// `entries`(文字列ID, 定義で指定された整数ID)に整数IDを割り当てる
+    ///
+    /// - 定義で指定されていればその値, 無ければロックファイルの値を使う
+    /// - どちらも無いIDには, ID順に`space.start`以上で未使用の最小の値を割り当てる
+    ///
+    /// # Errors
+    /// - 割り当てる値が`u16`に収まらないとき
+    pub fn allocate<'a>(
+        &self,
+        space: IntIdSpace,
+        entries: impl IntoIterator<Item = (&'a str, Option<u16>)>,
+    ) -> Result<IntIdAllocation, String> {
+        let empty = std::collections::BTreeMap::new();
+        let locked = self.0.get(space.name).unwrap_or(&empty);
+
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        let mut ids = std::collections::BTreeMap::new();
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        let mut pending = Vec::new();
+        for (id, explicit) in entries {
+            let locked_value = locked.get(id).copied();
+            match (explicit, locked_value) {
+                (Some(value), Some(locked_value)) if value != locked_value => {
+                    changed.push((id.to_string(), locked_value));
+                }
+                (_, None) => added.push(id.to_string()),
+                _ => {}
+            }
+            match explicit.or(locked_value) {
+                Some(value) => {
+                    ids.insert(id.to_string(), value);
+                }
+                None => pending.push(id),
+            }
+        }
+
+        let mut used = ids
+            .values()
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut next = space.start;
+        for id in pending {
+            while used.contains(&next) {
+                next = next.checked_add(1).ok_or_else(|| {
+                    format!("no free int id is left for `{id}` in {}", space.name)
+                })?;
+            }
+            used.insert(next);
+            ids.insert(id.to_string(), next);
+        }
+
+        let removed = locked
+            .keys()
+            .filter(|id| !ids.contains_key(id.as_str()))
+            .cloned()
+            .collect();
+
+        Ok(IntIdAllocation {
+            space,
+            ids,
+            added,
+            changed,
+            removed,
+        })
+    }
+
+    /// 割り当て結果で名前空間の内容を置き換える. 定義に無いIDは取り除かれる
+    pub fn apply(&mut self, allocation: &IntIdAllocation) {
+        self.0
+            .insert(allocation.space.name.to_string(), allocation.ids.clone());
+    }
+}
+
+impl IntIdAllocation {
+    pub fn get(&self, id: &str) -> Option<u16> {
+        self.ids.get(id).copied()
+    }
+
+    /// ロックファイルを書き換える必要が無いか
+    pub fn is_locked(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// 同じ整数IDを持つIDの組. 整数ID順
+    pub fn collisions(&self) -> Vec<(u16, Vec<&str>)> {
+        let mut by_value = std::collections::BTreeMap::<u16, Vec<&str>>::new();
+        for (id, &value) in &self.ids {
+            by_value.entry(value).or_default().push(id);
+        }
+        by_value
+            .into_iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .collect()
+    }
+
+    /// `space.start`から最大の整数IDまでの間で使われていない範囲. 両端を含む
+    pub fn gaps(&self) -> Vec<(u16, u16)> {
+        let used = self
+            .ids
+            .values()
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut gaps = Vec::new();
+        let mut expected = self.space.start;
+        for value in used.into_iter().filter(|&value| value >= self.space.start) {
+            if value > expected {
+                gaps.push((expected, value - 1));
+            }
+            expected = value.saturating_add(1);
+        }
+        gaps
+    }
+
+    /// 衝突と空き, `include_lock`のときはロックファイルとの差分を問題として返す
+    pub fn diagnostics(&self, include_lock: bool) -> Vec<IntIdDiagnostic> {
+        let space = self.space.name;
+        let error = |message: String| IntIdDiagnostic {
+            space,
+            is_error: true,
+            message,
+        };
+
+        let mut diagnostics = Vec::new();
+        for (value, ids) in self.collisions() {
+            diagnostics.push(error(format!(
+                "int id {value} is shared by {}",
+                quote_ids(ids.iter().copied())
+            )));
+        }
+        if include_lock {
+            if !self.added.is_empty() {
+                diagnostics.push(error(format!(
+                    "{} not in the lock file",
+                    quote_ids(self.added.iter().map(String::as_str))
+                )));
+            }
+            for (id, locked) in &self.changed {
+                diagnostics.push(error(format!(
+                    "`{id}` has int id {} but the lock file has {locked}",
+                    self.ids[id]
+                )));
+            }
+            if !self.removed.is_empty() {
+                diagnostics.push(error(format!(
+                    "{} in the lock file but no longer defined",
+                    quote_ids(self.removed.iter().map(String::as_str))
+                )));
+            }
+        }
+        for (from, to) in self.gaps() {
+            let range = if from == to {
+                format!("{from}")
+            } else {
+                format!("{from}..={to}")
+            };
+            diagnostics.push(IntIdDiagnostic {
+                space,
+                is_error: false,
+                message: format!("int id {range} is unused"),
+            });
+        }
+        diagnostics
+    }
+}
+
+impl std::fmt::Display for IntIdDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = if self.is_error { "error" } else { "warning" };
+        write!(f, "{severity}[{}]: {}", self.space, self.message)
+    }
+}
+
+/// 割り当て結果を検査し, `write`のときは衝突が無ければロックファイルを更新する
+///
+/// `write`で書き込んだときはロックファイルとの差分を問題として返さない
+///
+/// # Errors
+/// - ロックファイルの書き込みに失敗したとき
+pub fn check_lock(
+    path: &std::path::Path,
+    mut lock: IntIdLock,
+    allocations: &[IntIdAllocation],
+    write: bool,
+) -> std::io::Result<Vec<IntIdDiagnostic>> {
+    let has_collisions = allocations
+        .iter()
+        .any(|allocation| !allocation.collisions().is_empty());
+    let written = write && !has_collisions;
+    if written && !allocations.iter().all(IntIdAllocation::is_locked) {
+        for allocation in allocations {
+            lock.apply(allocation);
+        }
+        lock.save(path)?;
+    }
+
+    Ok(allocations
+        .iter()
+        .flat_map(|allocation| allocation.diagnostics(!written))
+        .collect())
+}
+
+fn quote_ids<'a>(ids: impl Iterator<Item = &'a str>) -> String {
+    ids.map(|id| format!("`{id}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACE: IntIdSpace = IntIdSpace {
+        name: "tags",
+        start: 0,
+    };
+
+    fn lock(entries: &[(&str, u16)]) -> IntIdLock {
+        let ids = entries
+            .iter()
+            .map(|&(id, value)| (id.to_string(), value))
+            .collect();
+        IntIdLock([(SPACE.name.to_string(), ids)].into_iter().collect())
+    }
+
+    #[test]
+    fn test_allocate_keeps_locked_ids_and_fills_free_values() {
+        let lock = lock(&[("a", 0), ("c", 2), ("removed", 5)]);
+
+        let allocation = lock
+            .allocate(SPACE, [("c", None), ("b", None), ("a", None), ("d", None)])
+            .unwrap();
+
+        assert_eq!(allocation.get("a"), Some(0));
+        assert_eq!(allocation.get("c"), Some(2));
+        assert_eq!(allocation.get("b"), Some(1));
+        assert_eq!(allocation.get("d"), Some(3));
+        assert!(allocation.collisions().is_empty());
+        assert!(!allocation.is_locked());
+        assert_eq!(allocation.added, vec!["b", "d"]);
+        assert_eq!(allocation.removed, vec!["removed"]);
+    }
+
+    #[test]
+    fn test_allocate_prefers_explicit_value() {
+        let lock = lock(&[("a", 0), ("b", 1)]);
+
+        let allocation = lock.allocate(SPACE, [("a", None), ("b", Some(0))]).unwrap();
+
+        assert_eq!(allocation.collisions(), vec![(0, vec!["a", "b"])]);
+        assert_eq!(allocation.changed, vec![("b".to_string(), 1)]);
+        assert_eq!(
+            allocation
+                .diagnostics(true)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "error[tags]: int id 0 is shared by `a`, `b`",
+                "error[tags]: `b` has int id 0 but the lock file has 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allocate_starts_at_space_start() {
+        let space = IntIdSpace {
+            name: "officialChannels",
+            start: 950,
+        };
+
+        let allocation = IntIdLock::default()
+            .allocate(space, [("b", None), ("a", None)])
+            .unwrap();
+
+        assert_eq!(allocation.get("a"), Some(950));
+        assert_eq!(allocation.get("b"), Some(951));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let allocation = IntIdLock::default()
+            .allocate(SPACE, [("a", Some(1)), ("b", Some(2)), ("c", Some(6))])
+            .unwrap();
+
+        assert_eq!(allocation.gaps(), vec![(0, 0), (3, 5)]);
+        assert_eq!(
+            allocation
+                .diagnostics(false)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "warning[tags]: int id 0 is unused",
+                "warning[tags]: int id 3..=5 is unused",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_keeps_int_id() {
+        let mut renamed = lock(&[("a", 0), ("b", 1)]);
+        assert!(renamed.rename(SPACE.name, "a", "c"));
+        assert_eq!(renamed, lock(&[("b", 1), ("c", 0)]));
+
+        assert!(!renamed.rename(SPACE.name, "a", "d"));
+        assert!(!renamed.rename("livers", "b", "d"));
+        assert_eq!(renamed, lock(&[("b", 1), ("c", 0)]));
+    }
+
+    #[test]
+    fn test_check_lock_writes_updated_lock() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("int_ids.lock.json");
+        let allocation = IntIdLock::default()
+            .allocate(SPACE, [("a", None), ("b", None)])
+            .unwrap();
+
+        let diagnostics = check_lock(
+            &path,
+            IntIdLock::default(),
+            std::slice::from_ref(&allocation),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error[tags]: `a`, `b` not in the lock file"
+        );
+        assert!(!path.exists());
+
+        let diagnostics =
+            check_lock(&path, IntIdLock::default(), &[allocation], true).unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(IntIdLock::load(&path).unwrap(), lock(&[("a", 0), ("b", 1)]));
+    }
+}
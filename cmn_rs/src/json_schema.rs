@@ -0,0 +1,664 @@
+/// 型からJSON Schema(draft-07)を組み立てる
+///
+/// 入力ファイルの型ごとに実装し, `tools/schema/*.schema.json`を生成する.
+/// serdeのデシリアライズで検証している制約(必須, パターン, 範囲など)をスキーマにも写す
+pub trait JsonSchema {
+    fn json_schema(ctx: &SchemaContext) -> serde_json::Value;
+}
+
+/// スキーマの生成時に埋め込む, 定義データから決まるIDの列挙
+///
+/// 補完のためにライバーID, タグIDなどを`enum`として埋め込む.
+/// 読み込み済みのデータに依らず生成できるよう, 呼び出し側が値を渡す
+#[derive(Debug, Clone, Default)]
+pub struct SchemaContext {
+    enums: std::collections::BTreeMap<&'static str, Vec<String>>,
+}
+
+/// `$schema`と`title`を付けて1つのファイルに書き出すスキーマ
+#[derive(Debug, Clone)]
+pub struct SchemaFile {
+    pub file_name: &'static str,
+    title: &'static str,
+    schema: serde_json::Value,
+}
+
+/// `type: object`のスキーマのビルダー
+///
+/// 未知のフィールドは許可しない(`deny_unknown_fields`)
+#[derive(Debug, Clone, Default)]
+pub struct ObjectSchema {
+    properties: serde_json::Map<String, serde_json::Value>,
+    required: Vec<String>,
+}
+
+const DRAFT_07: &str = "http://json-schema.org/draft-07/schema#";
+/// スキーマファイルを書き出している間だけ`dir`に存在するジャーナルのファイル名
+const JOURNAL_FILE_NAME: &str = ".schema.journal";
+
+impl SchemaContext {
+    /// `name`の列挙を登録する. 値はソートし, 重複を除く
+    pub fn with_enum<I, S>(mut self, name: &'static str, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut values = values.into_iter().map(Into::into).collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        self.enums.insert(name, values);
+        self
+    }
+
+    /// `name`の列挙のいずれかである文字列のスキーマ
+    ///
+    /// 列挙が登録されていないときは空でない文字列とする
+    pub fn id(&self, name: &str) -> serde_json::Value {
+        match self.enums.get(name) {
+            Some(values) => serde_json::json!({ "type": "string", "enum": values }),
+            None => serde_json::json!({ "type": "string", "minLength": 1 }),
+        }
+    }
+}
+
+impl SchemaFile {
+    pub fn new(
+        file_name: &'static str,
+        title: &'static str,
+        schema: serde_json::Value,
+    ) -> Self {
+        Self {
+            file_name,
+            title,
+            schema,
+        }
+    }
+
+    /// ファイルの内容. キーはソートし, 末尾に改行を付ける
+    pub fn render(&self) -> String {
+        let mut document = serde_json::Map::new();
+        document.insert("$schema".to_string(), DRAFT_07.into());
+        document.insert("title".to_string(), self.title.into());
+        if let serde_json::Value::Object(schema) = &self.schema {
+            document.extend(schema.clone());
+        }
+        let mut content = serde_json::to_string_pretty(&sort_keys(
+            serde_json::Value::Object(document),
+        ))
+        .expect("serializing a JSON value will not fail");
+        content.push('\n');
+        content
+    }
+}
+
+impl ObjectSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 省略できないフィールド
+    pub fn required(mut self, name: &str, schema: serde_json::Value) -> Self {
+        self.required.push(name.to_string());
+        self.properties.insert(name.to_string(), schema);
+        self
+    }
+
+    /// 省略できるフィールド. `#[serde(default)]`など
+    pub fn optional(mut self, name: &str, schema: serde_json::Value) -> Self {
+        self.properties.insert(name.to_string(), schema);
+        self
+    }
+
+    /// 省略でき, `null`も受け付けるフィールド. `Option<T>`など
+    pub fn nullable(self, name: &str, schema: serde_json::Value) -> Self {
+        self.optional(name, nullable(schema))
+    }
+
+    pub fn build(self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl JsonSchema for String {
+    fn json_schema(_: &SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string" })
+    }
+}
+
+impl JsonSchema for bool {
+    fn json_schema(_: &SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "boolean" })
+    }
+}
+
+impl JsonSchema for u16 {
+    fn json_schema(_: &SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "integer", "minimum": 0, "maximum": u16::MAX })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn json_schema(ctx: &SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "array", "items": T::json_schema(ctx) })
+    }
+}
+
+/// `schema`か`null`
+pub fn nullable(schema: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+/// キーが`key_pattern`に合う, 値が`value`のオブジェクト. `HashMap<Id, T>`など
+pub fn map_of(key_pattern: &str, value: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "patternProperties": { key_pattern: value },
+        "additionalProperties": false,
+    })
+}
+
+/// `dir`にスキーマファイルを書き出す. 全て書き換えるか, 1つも書き換えない
+///
+/// 前回の書き出しが途中で終了していたときは, 元に戻してから書き出す
+///
+/// # Errors
+/// - ディレクトリの作成, ファイルの書き込みに失敗したとき
+pub fn write_files(
+    dir: &std::path::Path,
+    files: &[SchemaFile],
+) -> std::io::Result<Vec<std::path::PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let journal = dir.join(JOURNAL_FILE_NAME);
+    crate::atomic_write::recover(&journal)?;
+    let outputs = files
+        .iter()
+        .map(|file| (dir.join(file.file_name), file.render().into_bytes()))
+        .collect::<Vec<_>>();
+    crate::atomic_write::write_files(&journal, &outputs)?;
+    Ok(outputs.into_iter().map(|(path, _)| path).collect())
+}
+
+/// `dir`にあるスキーマファイルのうち, 生成した内容と異なる, または存在しないもののパス
+///
+/// # Errors
+/// - ファイルの読み込みに失敗したとき. 存在しないときを除く
+pub fn stale_files(
+    dir: &std::path::Path,
+    files: &[SchemaFile],
+) -> std::io::Result<Vec<std::path::PathBuf>> {
+    let mut stale = Vec::new();
+    for file in files {
+        let path = dir.join(file.file_name);
+        let is_fresh = match std::fs::read_to_string(&path) {
+            Ok(content) => content == file.render(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+            Err(e) => return Err(e),
+        };
+        if !is_fresh {
+            stale.push(path);
+        }
+    }
+    Ok(stale)
+}
+
+/// `schema`サブコマンドの処理. スキーマファイルを書き出し, パスを返す
+///
+/// `check`のときは書き出さず, 古いファイルが無いことを検査する
+///
+/// # Errors
+/// - ファイルの読み書きに失敗したとき
+/// - `check`で古いファイルがあったとき
+pub fn write_or_check(
+    dir: &std::path::Path,
+    files: &[SchemaFile],
+    check: bool,
+) -> Result<Vec<std::path::PathBuf>, String> {
+    if !check {
+        return write_files(dir, files).map_err(|e| e.to_string());
+    }
+    let stale = stale_files(dir, files).map_err(|e| e.to_string())?;
+    if stale.is_empty() {
+        return Ok(files.iter().map(|file| dir.join(file.file_name)).collect());
+    }
+    Err(format!(
+        "{} schema files are out of date (run without --check to regenerate): {}",
+        stale.len(),
+        stale
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// `instance`が`schema`に合わない箇所を`/clips/0/liverIds: ...`の形式で返す
+///
+/// このモジュールで組み立てるキーワードのみ扱う
+pub fn validate(
+    schema: &serde_json::Value,
+    instance: &serde_json::Value,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, instance, "", &mut errors);
+    errors
+}
+
+fn validate_at(
+    schema: &serde_json::Value,
+    instance: &serde_json::Value,
+    pointer: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(any_of) = schema.get("anyOf").and_then(serde_json::Value::as_array) {
+        let is_matched = any_of
+            .iter()
+            .any(|schema| validate(schema, instance).is_empty());
+        if !is_matched {
+            push_error(
+                errors,
+                pointer,
+                "does not match any of the schemas".to_string(),
+            );
+        }
+        return;
+    }
+    if let Some(values) = schema.get("enum").and_then(serde_json::Value::as_array)
+        && !values.contains(instance)
+    {
+        push_error(
+            errors,
+            pointer,
+            format!("{instance} is not one of the allowed values"),
+        );
+    }
+    let Some(ty) = schema.get("type").and_then(serde_json::Value::as_str) else {
+        return;
+    };
+
+    match (ty, instance) {
+        ("null", serde_json::Value::Null) | ("boolean", serde_json::Value::Bool(_)) => {
+        }
+        ("string", serde_json::Value::String(s)) => {
+            let min = schema.get("minLength").and_then(serde_json::Value::as_u64);
+            if min.is_some_and(|min| (s.chars().count() as u64) < min) {
+                push_error(errors, pointer, format!("{instance} is too short"));
+            }
+            if let Some(pattern) =
+                schema.get("pattern").and_then(serde_json::Value::as_str)
+                && !is_match(pattern, s)
+            {
+                push_error(
+                    errors,
+                    pointer,
+                    format!("{instance} does not match `{pattern}`"),
+                );
+            }
+        }
+        ("integer", serde_json::Value::Number(n)) if n.is_u64() || n.is_i64() => {
+            let n = n.as_f64().unwrap_or_default();
+            let minimum = schema.get("minimum").and_then(serde_json::Value::as_f64);
+            let maximum = schema.get("maximum").and_then(serde_json::Value::as_f64);
+            if minimum.is_some_and(|min| n < min) || maximum.is_some_and(|max| n > max)
+            {
+                push_error(errors, pointer, format!("{instance} is out of range"));
+            }
+        }
+        ("array", serde_json::Value::Array(items)) => {
+            let min = schema.get("minItems").and_then(serde_json::Value::as_u64);
+            if min.is_some_and(|min| (items.len() as u64) < min) {
+                push_error(errors, pointer, "too few items".to_string());
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(
+                        item_schema,
+                        item,
+                        &format!("{pointer}/{index}"),
+                        errors,
+                    );
+                }
+            }
+        }
+        ("object", serde_json::Value::Object(map)) => {
+            validate_object(schema, map, pointer, errors);
+        }
+        _ => push_error(errors, pointer, format!("expected {ty}")),
+    }
+}
+
+fn push_error(errors: &mut Vec<String>, pointer: &str, message: String) {
+    errors.push(format!("{pointer}: {message}"));
+}
+
+fn validate_object(
+    schema: &serde_json::Value,
+    map: &serde_json::Map<String, serde_json::Value>,
+    pointer: &str,
+    errors: &mut Vec<String>,
+) {
+    let properties = schema
+        .get("properties")
+        .and_then(serde_json::Value::as_object);
+    let is_closed = schema.get("additionalProperties") == Some(&false.into());
+
+    if let Some(required) = schema.get("required").and_then(serde_json::Value::as_array)
+    {
+        for name in required.iter().filter_map(serde_json::Value::as_str) {
+            if !map.contains_key(name) {
+                errors.push(format!("{pointer}: missing field `{name}`"));
+            }
+        }
+    }
+    for (key, value) in map {
+        let child = format!("{pointer}/{key}");
+        match properties
+            .and_then(|properties| properties.get(key))
+            .or_else(|| pattern_property(schema, key))
+        {
+            Some(schema) => validate_at(schema, value, &child, errors),
+            None if is_closed => {
+                errors.push(format!("{pointer}: unknown field `{key}`"))
+            }
+            None => {}
+        }
+    }
+}
+
+/// `patternProperties`のうち, キーがパターンに合うものの値のスキーマ
+fn pattern_property<'a>(
+    schema: &'a serde_json::Value,
+    key: &str,
+) -> Option<&'a serde_json::Value> {
+    schema
+        .get("patternProperties")
+        .and_then(serde_json::Value::as_object)?
+        .iter()
+        .find(|(pattern, _)| is_match(pattern, key))
+        .map(|(_, schema)| schema)
+}
+
+/// パターンが不正なときは合わないものとする
+fn is_match(pattern: &str, s: &str) -> bool {
+    regex::Regex::new(pattern).is_ok_and(|re| re.is_match(s))
+}
+
+/// `type: object`のスキーマのプロパティ名と, `instance`のキーの食い違いを`/clips/0: ...`の形式で返す
+///
+/// 全てのフィールドを持つ値をシリアライズして渡すと, スキーマの書き漏れ, 綴りの誤りが分かる.
+/// スキーマは文字列で組み立てるため, モデルのフィールドの追加, 改名に追従しているかをテストで確かめる.
+/// 配列の要素, オブジェクトの値, `anyOf`で型の合う候補も再帰的に比べる
+pub fn property_name_mismatches(
+    schema: &serde_json::Value,
+    instance: &serde_json::Value,
+) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    property_name_mismatches_at(schema, instance, "", &mut mismatches);
+    mismatches
+}
+
+fn property_name_mismatches_at(
+    schema: &serde_json::Value,
+    instance: &serde_json::Value,
+    pointer: &str,
+    mismatches: &mut Vec<String>,
+) {
+    if let Some(any_of) = schema.get("anyOf").and_then(serde_json::Value::as_array) {
+        if let Some(schema) = any_of.iter().find(|schema| is_type_of(schema, instance))
+        {
+            property_name_mismatches_at(schema, instance, pointer, mismatches);
+        }
+        return;
+    }
+
+    match instance {
+        serde_json::Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    property_name_mismatches_at(
+                        item_schema,
+                        item,
+                        &format!("{pointer}/{index}"),
+                        mismatches,
+                    );
+                }
+            }
+        }
+        serde_json::Value::Object(map) => {
+            if let Some(properties) = schema
+                .get("properties")
+                .and_then(serde_json::Value::as_object)
+            {
+                let names =
+                    properties.keys().collect::<std::collections::BTreeSet<_>>();
+                let keys = map.keys().collect::<std::collections::BTreeSet<_>>();
+                mismatches.extend(
+                    keys.difference(&names)
+                        .map(|key| format!("{pointer}: `{key}` is not in the schema")),
+                );
+                mismatches.extend(
+                    names.difference(&keys).map(|key| {
+                        format!("{pointer}: `{key}` is not in the instance")
+                    }),
+                );
+            }
+            for (key, value) in map {
+                let schema = schema
+                    .get("properties")
+                    .and_then(|properties| properties.get(key))
+                    .or_else(|| pattern_property(schema, key));
+                if let Some(schema) = schema {
+                    property_name_mismatches_at(
+                        schema,
+                        value,
+                        &format!("{pointer}/{key}"),
+                        mismatches,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `schema`の`type`が`instance`の型か
+fn is_type_of(schema: &serde_json::Value, instance: &serde_json::Value) -> bool {
+    let ty = schema.get("type").and_then(serde_json::Value::as_str);
+    matches!(
+        (ty, instance),
+        (Some("null"), serde_json::Value::Null)
+            | (Some("boolean"), serde_json::Value::Bool(_))
+            | (Some("string"), serde_json::Value::String(_))
+            | (Some("integer" | "number"), serde_json::Value::Number(_))
+            | (Some("array"), serde_json::Value::Array(_))
+            | (Some("object"), serde_json::Value::Object(_))
+    )
+}
+
+/// `json`を`T`として読み込み, シリアライズし直した値
+///
+/// ファイルに書き出す形の値をスキーマと比べるため. 読み込めないときはpanicする
+#[cfg(any(test, feature = "test-helpers"))]
+pub fn round_trip<T>(json: &str) -> serde_json::Value
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let value: T = serde_json::from_str(json).expect("instance should deserialize");
+    serde_json::to_value(value).expect("instance should serialize")
+}
+
+/// `preserve_order`の有無によらず出力を安定させるため, キーを再帰的にソートする
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip_schema(ctx: &SchemaContext) -> serde_json::Value {
+        ObjectSchema::new()
+            .required("songTitle", serde_json::json!({ "type": "string" }))
+            .required(
+                "liverIds",
+                serde_json::json!({ "type": "array", "items": ctx.id("livers"), "minItems": 1 }),
+            )
+            .nullable(
+                "volumePercent",
+                serde_json::json!({ "type": "integer", "minimum": 1, "maximum": 100 }),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_render_adds_header_and_sorts_keys() {
+        let ctx =
+            SchemaContext::default().with_enum("livers", ["yugamin", "riku-tazumi"]);
+        let content =
+            SchemaFile::new("clip.schema.json", "Clip", clip_schema(&ctx)).render();
+
+        assert!(content.starts_with(
+            "{\n  \"$schema\": \"http://json-schema.org/draft-07/schema#\",\n  \"additionalProperties\": false,"
+        ));
+        assert!(content.ends_with("}\n"));
+        assert!(content.contains("\"title\": \"Clip\""));
+        let livers = content.find("\"riku-tazumi\"").unwrap();
+        assert!(livers < content.find("\"yugamin\"").unwrap());
+    }
+
+    #[test]
+    fn test_id_without_enum_is_non_empty_string() {
+        assert_eq!(
+            SchemaContext::default().id("livers"),
+            serde_json::json!({ "type": "string", "minLength": 1 })
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_pointer() {
+        let ctx = SchemaContext::default().with_enum("livers", ["yugamin"]);
+        let schema = clip_schema(&ctx);
+
+        let valid = serde_json::json!({ "songTitle": "a", "liverIds": ["yugamin"], "volumePercent": null });
+        assert_eq!(validate(&schema, &valid), Vec::<String>::new());
+
+        let invalid = serde_json::json!({
+            "liverIds": ["yugamin", "unknown"],
+            "volumePercent": 0,
+            "extra": true,
+        });
+        assert_eq!(
+            validate(&schema, &invalid),
+            vec![
+                ": missing field `songTitle`",
+                ": unknown field `extra`",
+                "/liverIds/1: \"unknown\" is not one of the allowed values",
+                "/volumePercent: does not match any of the schemas",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_property_name_mismatches() {
+        let schema = clip_schema(&SchemaContext::default());
+        let instance = serde_json::json!({ "songTitle": "a", "liverIds": [], "volumePercent": 80 });
+        assert_eq!(
+            property_name_mismatches(&schema, &instance),
+            Vec::<String>::new()
+        );
+
+        let instance = serde_json::json!({ "songTitle": "a", "liverIDs": [] });
+        assert_eq!(
+            property_name_mismatches(&schema, &instance),
+            vec![
+                ": `liverIDs` is not in the schema",
+                ": `liverIds` is not in the instance",
+                ": `volumePercent` is not in the instance",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_property_name_mismatches_in_nested_values() {
+        let schema = map_of(
+            "^[a-z]+$",
+            serde_json::json!({
+                "type": "array",
+                "items": nullable(clip_schema(&SchemaContext::default())),
+            }),
+        );
+        let instance = serde_json::json!({
+            "a": [
+                null,
+                { "songTitle": "a", "liverIds": [], "volumePercent": null },
+                { "songTitle": "a", "liverIds": [] },
+            ],
+        });
+        assert_eq!(
+            property_name_mismatches(&schema, &instance),
+            vec!["/a/2: `volumePercent` is not in the instance"]
+        );
+    }
+
+    #[test]
+    fn test_validate_patterns() {
+        let schema = map_of(
+            "^[a-z]+$",
+            serde_json::json!({ "type": "string", "pattern": "^[0-9]{2}$" }),
+        );
+        let instance = serde_json::json!({ "ab": "12", "cd": "123", "EF": "12" });
+        assert_eq!(
+            validate(&schema, &instance),
+            vec![
+                ": unknown field `EF`",
+                "/cd: \"123\" does not match `^[0-9]{2}$`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            SchemaFile::new(
+                "a.schema.json",
+                "A",
+                map_of("^[a-z]+$", serde_json::json!({})),
+            ),
+            SchemaFile::new(
+                "b.schema.json",
+                "B",
+                serde_json::json!({ "type": "string" }),
+            ),
+        ];
+        assert_eq!(stale_files(dir.path(), &files).unwrap().len(), 2);
+
+        write_files(dir.path(), &files).unwrap();
+        assert!(stale_files(dir.path(), &files).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("b.schema.json"), "{}\n").unwrap();
+        assert_eq!(
+            stale_files(dir.path(), &files).unwrap(),
+            vec![dir.path().join("b.schema.json")]
+        );
+    }
+}
@@ -0,0 +1,628 @@
+/// ソース上の位置(バイトオフセット)を保持したJSONの値
+///
+/// `serde_json::Value`と違い, 値ごとにファイル内の範囲を持つ.
+/// エディタでの補完や診断, エラーの行と列の表示に使う
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonNode {
+    pub span: Span,
+    pub value: JsonValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    /// 元の表記のまま持つ
+    Number(String),
+    /// エスケープを解いた文字列
+    String(String),
+    Array(Vec<JsonNode>),
+    /// 記述順. 重複したキーもそのまま持つ
+    Object(Vec<JsonMember>),
+}
+
+/// オブジェクトの1メンバー
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonMember {
+    pub key: String,
+    /// 引用符を含むキーの範囲
+    pub key_span: Span,
+    pub value: JsonNode,
+}
+
+/// `[start, end)`のバイトオフセット. 文字列は引用符を含む
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// JSONの構文エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonSyntaxError {
+    /// エラーを検出したバイトオフセット
+    pub offset: usize,
+    pub message: String,
+}
+
+/// 行頭のオフセットの一覧. バイトオフセットと行, 列を相互に変換する
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+/// 0始まりの行と列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// 入れ子の上限. これより深いJSONはエラーにする
+const MAX_DEPTH: usize = 128;
+
+/// JSONをパースする
+///
+/// # Errors
+/// - JSONとして不正なとき
+pub fn parse(text: &str) -> Result<JsonNode, JsonSyntaxError> {
+    let mut parser = Parser {
+        text,
+        bytes: text.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    // BOMは読み飛ばす
+    if text.starts_with('\u{FEFF}') {
+        parser.pos = '\u{FEFF}'.len_utf8();
+    }
+    parser.skip_whitespace();
+    let node = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.bytes.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(node)
+}
+
+impl std::fmt::Display for JsonSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for JsonSyntaxError {}
+
+impl Span {
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+impl JsonNode {
+    /// オブジェクトのとき, `key`の値. 重複したキーは最初のもの
+    pub fn get(&self, key: &str) -> Option<&JsonNode> {
+        self.member(key).map(|member| &member.value)
+    }
+
+    /// オブジェクトのとき, `key`のメンバー
+    pub fn member(&self, key: &str) -> Option<&JsonMember> {
+        match &self.value {
+            JsonValue::Object(members) => {
+                members.iter().find(|member| member.key == key)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonNode]> {
+        match &self.value {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[JsonMember]> {
+        match &self.value {
+            JsonValue::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// 位置情報を捨てて`serde_json::Value`にする
+    pub fn to_value(&self) -> serde_json::Value {
+        match &self.value {
+            JsonValue::Null => serde_json::Value::Null,
+            JsonValue::Bool(b) => serde_json::Value::Bool(*b),
+            JsonValue::Number(n) => {
+                serde_json::from_str(n).unwrap_or(serde_json::Value::Null)
+            }
+            JsonValue::String(s) => serde_json::Value::String(s.clone()),
+            JsonValue::Array(items) => {
+                serde_json::Value::Array(items.iter().map(Self::to_value).collect())
+            }
+            JsonValue::Object(members) => serde_json::Value::Object(
+                members
+                    .iter()
+                    .map(|member| (member.key.clone(), member.value.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { line_starts }
+    }
+
+    /// バイトオフセットの行と, 行頭からの文字数
+    pub fn line_column(&self, text: &str, offset: usize) -> LineColumn {
+        let (line, line_start) = self.line_of(offset);
+        let column = text
+            .get(line_start..offset.min(text.len()))
+            .map_or(0, |s| s.chars().count());
+        LineColumn { line, column }
+    }
+
+    /// バイトオフセットの行と, 行頭からのUTF-16のコード単位数(LSPの`Position`)
+    pub fn line_column_utf16(&self, text: &str, offset: usize) -> LineColumn {
+        let (line, line_start) = self.line_of(offset);
+        let column = text
+            .get(line_start..offset.min(text.len()))
+            .map_or(0, |s| s.encode_utf16().count());
+        LineColumn { line, column }
+    }
+
+    /// 行と, 行頭からのUTF-16のコード単位数をバイトオフセットにする
+    ///
+    /// 行末, 文末を超えるときは行末, 文末に丸める
+    pub fn offset_utf16(&self, text: &str, position: LineColumn) -> usize {
+        let Some(&line_start) = self.line_starts.get(position.line) else {
+            return text.len();
+        };
+        let line_end = self
+            .line_starts
+            .get(position.line + 1)
+            .map_or(text.len(), |next| next - 1);
+        let mut units = 0;
+        for (i, c) in text[line_start..line_end].char_indices() {
+            if units >= position.column {
+                return line_start + i;
+            }
+            units += c.len_utf16();
+        }
+        line_end
+    }
+
+    /// 0始まりの`line`行目の範囲. 改行文字は含まない
+    ///
+    /// 行数を超えるときは文末の空の範囲
+    pub fn line_span(&self, text: &str, line: usize) -> Span {
+        let Some(&start) = self.line_starts.get(line) else {
+            return Span {
+                start: text.len(),
+                end: text.len(),
+            };
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(text.len(), |next| next - 1);
+        let end = if text[start..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        };
+        Span { start, end }
+    }
+
+    /// 0始まりの行番号と, その行頭のオフセット
+    fn line_of(&self, offset: usize) -> (usize, usize) {
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        (line, self.line_starts[line])
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> JsonSyntaxError {
+        JsonSyntaxError {
+            offset: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonNode, JsonSyntaxError> {
+        let start = self.pos;
+        let value = match self.peek() {
+            None => return Err(self.error("EOF while parsing a value")),
+            Some(b'{') => self.nested(Self::parse_object)?,
+            Some(b'[') => self.nested(Self::parse_array)?,
+            Some(b'"') => JsonValue::String(self.parse_string()?),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true))?,
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false))?,
+            Some(b'n') => self.parse_literal("null", JsonValue::Null)?,
+            Some(b'-' | b'0'..=b'9') => self.parse_number()?,
+            Some(_) => return Err(self.error("expected value")),
+        };
+        Ok(JsonNode {
+            span: Span {
+                start,
+                end: self.pos,
+            },
+            value,
+        })
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<JsonValue, JsonSyntaxError>,
+    ) -> Result<JsonValue, JsonSyntaxError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("recursion limit exceeded"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, JsonSyntaxError> {
+        // `{`
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key_start = self.pos;
+            match self.peek() {
+                Some(b'"') => {}
+                Some(b'}') if !members.is_empty() => {
+                    return Err(self.error("trailing comma"));
+                }
+                None => return Err(self.error("EOF while parsing an object")),
+                Some(_) => return Err(self.error("key must be a string")),
+            }
+            let key = self.parse_string()?;
+            let key_span = Span {
+                start: key_start,
+                end: self.pos,
+            };
+
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected `:`"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let value = self.parse_value()?;
+            members.push(JsonMember {
+                key,
+                key_span,
+                value,
+            });
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                None => return Err(self.error("EOF while parsing an object")),
+                Some(_) => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, JsonSyntaxError> {
+        // `[`
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b']') {
+                return Err(self.error("trailing comma"));
+            }
+            items.push(self.parse_value()?);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                None => return Err(self.error("EOF while parsing a list")),
+                Some(_) => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn parse_literal(
+        &mut self,
+        literal: &str,
+        value: JsonValue,
+    ) -> Result<JsonValue, JsonSyntaxError> {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected value"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, JsonSyntaxError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        Ok(JsonValue::Number(self.text[start..self.pos].to_string()))
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonSyntaxError> {
+        // `"`
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let Some(i) = rest.find(['"', '\\']).filter(|i| {
+                // 制御文字はエスケープが必要
+                !rest[..*i].bytes().any(|b| b < 0x20)
+            }) else {
+                return match rest.bytes().position(|b| b < 0x20) {
+                    Some(i) => {
+                        self.pos += i;
+                        Err(self.error("control character while parsing a string"))
+                    }
+                    None => {
+                        self.pos = self.bytes.len();
+                        Err(self.error("EOF while parsing a string"))
+                    }
+                };
+            };
+            out.push_str(&rest[..i]);
+            self.pos += i;
+
+            if self.peek() == Some(b'"') {
+                self.pos += 1;
+                return Ok(out);
+            }
+
+            // `\`
+            self.pos += 1;
+            let escaped = match self.peek() {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => {
+                    self.pos += 1;
+                    let c = self.parse_unicode_escape()?;
+                    out.push(c);
+                    continue;
+                }
+                None => return Err(self.error("EOF while parsing a string")),
+                Some(_) => return Err(self.error("invalid escape")),
+            };
+            out.push(escaped);
+            self.pos += 1;
+        }
+    }
+
+    /// `\u`の直後から. サロゲートペアは2つ続けて読む
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonSyntaxError> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(u32::from(high))
+                .ok_or_else(|| self.error("lone trailing surrogate in hex escape"));
+        }
+
+        if !self.text[self.pos..].starts_with("\\u") {
+            return Err(self.error("lone leading surrogate in hex escape"));
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("invalid unicode code point"));
+        }
+        let code =
+            0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode code point"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u16, JsonSyntaxError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid escape"))?;
+        self.pos += 4;
+        u16::from_str_radix(hex, 16).map_err(|_| self.error("invalid escape"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_records_spans() {
+        let text = r#"[{"videoId": "ZeFvqdvutb4", "clips": [1, -2.5e3, true, null]}]"#;
+        let node = parse(text).unwrap();
+
+        let video = &node.as_array().unwrap()[0];
+        let video_id = video.get("videoId").unwrap();
+        assert_eq!(video_id.as_str(), Some("ZeFvqdvutb4"));
+        assert_eq!(
+            &text[video_id.span.start..video_id.span.end],
+            "\"ZeFvqdvutb4\""
+        );
+
+        let member = video.member("clips").unwrap();
+        assert_eq!(
+            &text[member.key_span.start..member.key_span.end],
+            "\"clips\""
+        );
+        let clips = member.value.as_array().unwrap();
+        assert_eq!(&text[clips[1].span.start..clips[1].span.end], "-2.5e3");
+
+        assert_eq!(
+            node.to_value(),
+            serde_json::from_str::<serde_json::Value>(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_unescapes_strings() {
+        let node = parse(r#""a\"b\\c\nあ🎤""#).unwrap();
+        assert_eq!(node.as_str(), Some("a\"b\\c\nあ🎤"));
+    }
+
+    #[test]
+    fn test_parse_errors_have_offsets() {
+        let cases = [
+            ("[1, 2,]", 6, "trailing comma"),
+            ("{\"a\" 1}", 5, "expected `:`"),
+            ("{\"a\": 1", 7, "EOF while parsing an object"),
+            ("[1] 2", 4, "trailing characters"),
+            ("\"abc", 4, "EOF while parsing a string"),
+            ("{1: 2}", 1, "key must be a string"),
+            ("01", 1, "trailing characters"),
+        ];
+
+        for (text, offset, message) in cases {
+            let err = parse(text).unwrap_err();
+            assert_eq!(
+                (err.offset, err.message.as_str()),
+                (offset, message),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_deep_nesting() {
+        let text = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            parse(&text).unwrap_err().message,
+            "recursion limit exceeded"
+        );
+    }
+
+    #[test]
+    fn test_line_index() {
+        let text = "{\n  \"ja\": \"🎤あ\",\n  \"x\": 1\n}";
+        let index = LineIndex::new(text);
+        let offset = text.find('あ').unwrap();
+
+        assert_eq!(
+            index.line_column(text, offset),
+            LineColumn {
+                line: 1,
+                column: 10
+            }
+        );
+        assert_eq!(
+            index.line_column_utf16(text, offset),
+            LineColumn {
+                line: 1,
+                column: 11
+            }
+        );
+        assert_eq!(
+            index.offset_utf16(
+                text,
+                LineColumn {
+                    line: 1,
+                    column: 11
+                }
+            ),
+            offset
+        );
+        // 行末を超える列は行末に丸める
+        assert_eq!(
+            index.offset_utf16(
+                text,
+                LineColumn {
+                    line: 2,
+                    column: 99
+                }
+            ),
+            text.rfind('\n').unwrap()
+        );
+    }
+}
@@ -1,4 +1,13 @@
+pub mod atomic_write;
 pub mod color;
+pub mod diff;
+pub mod int_id;
+pub mod json_schema;
+pub mod json_span;
 pub mod min_json;
+pub mod search_key;
+pub mod source_diagnostic;
+pub mod text;
+#[cfg(feature = "logging")]
 pub mod tracing;
 pub mod yt;
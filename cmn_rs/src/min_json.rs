@@ -6,6 +6,18 @@ impl DatasetBuildId {
         &self.0
     }
 
+    /// 各ドメインの入力集合ハッシュ(`hash-inputs`の出力)からビルドIDを決める
+    ///
+    /// `cliplayer:dataset-build-id`に続けて`<domain>:<hash>`を1行ずつ並べた文字列のSHA-256.
+    /// 入力が同じなら同じIDになるので, ビルドが必要かどうかの判定にも使える
+    pub fn from_input_hashes(input_hashes: &[(&str, &str)]) -> Self {
+        let mut source = String::from("cliplayer:dataset-build-id");
+        for (domain, hash) in input_hashes {
+            source.push_str(&format!("\n{domain}:{hash}"));
+        }
+        Self(sha256_hex(source.as_bytes()))
+    }
+
     fn is_valid(value: &str) -> bool {
         let bytes = value.as_bytes();
         if !(8..=128).contains(&bytes.len()) {
@@ -190,6 +202,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dataset_build_id_from_input_hashes() {
+        let id = super::DatasetBuildId::from_input_hashes(&[
+            ("artist", "aaa"),
+            ("tag", "bbb"),
+            ("song", "ccc"),
+            ("music", "ddd"),
+        ]);
+
+        // 旧`tools/build.sh`がsha256sumで求めていた値と同じ
+        assert_eq!(
+            id.as_str(),
+            "4940dd9a9c65606f789eaa1fe30d8e4fdeb723e8eeb4bc5234d135cdaac147ac"
+        );
+        assert!(id.as_str().parse::<super::DatasetBuildId>().is_ok());
+    }
+
     #[test]
     fn test_input_set_hash_builder_is_stable_for_same_entries() {
         let mut left = super::InputSetHashBuilder::new("test-domain");
@@ -0,0 +1,574 @@
+/// エンティティ(ライバー, 公式チャンネル, タグ)の検索キーを組み立てる
+///
+/// - 元のキー(`ja`, `jah`, `en`, `aliases`)に加え, 入力の揺れを吸収する表記を生成する
+/// - 生成した表記には, どの変換でどのキーから作ったかを`origin`として持たせる
+/// - 同じエンティティの中で重複するキーは最初の1つだけ残す
+///
+/// ```
+/// let mut keys = cmn_rs::search_key::SearchKeys::default();
+/// keys.push("月ノ美兎");
+/// keys.push("つきのみと");
+/// keys.push("Tsukino Mito");
+///
+/// let keys = keys.into_keys();
+/// assert!(keys.iter().any(|key| key.key == "ツキノミト"));
+/// assert!(keys.iter().any(|key| key.key == "tsukinomito"));
+/// assert!(keys.iter().any(|key| key.key == "tukinomito"));
+/// assert!(keys.iter().any(|key| key.key == "tsukino mito"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchKeys {
+    /// 登録順の元のキーと, aliasかどうか
+    originals: Vec<(String, bool)>,
+}
+
+/// 検索インデックスの1エントリ分のキー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchKey {
+    pub key: String,
+    pub is_alias: bool,
+    /// 生成した表記のとき`Some`
+    pub origin: Option<KeyOrigin>,
+}
+
+/// 生成した表記の由来
+///
+/// 出力JSONでは各エントリに`variant`, `variantOf`として平坦化して書き出す想定
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyOrigin {
+    pub variant: KeyVariant,
+    /// 変換元の元のキー
+    pub variant_of: String,
+}
+
+/// 表記の生成方法
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyVariant {
+    /// 全角英数字と半角カナを畳み込んだもの
+    Width,
+    /// カタカナをひらがなにしたもの
+    Hiragana,
+    /// ひらがなをカタカナにしたもの
+    Katakana,
+    /// かなだけのキーのヘボン式ローマ字
+    ///
+    /// 長音を省いたもの(`ゆうき` -> `yuki`)と, 省かないもの(`yuuki`)の2つ
+    Romaji,
+    /// かなだけのキーを, IMEでよく打つ綴り(`si`, `tu`, `nn`など)にしたもの
+    RomajiIme,
+    /// 英字を小文字にしたもの
+    Lowercase,
+}
+
+impl SearchKeys {
+    /// alias以外の元のキーを追加する
+    pub fn push(&mut self, key: &str) {
+        self.originals.push((key.to_string(), false));
+    }
+
+    /// aliasの元のキーを追加する
+    pub fn push_alias(&mut self, key: &str) {
+        self.originals.push((key.to_string(), true));
+    }
+
+    /// 元のキー, 生成した表記の順に並べて返す
+    ///
+    /// - 表記は元のキーのaliasかどうかを引き継ぐ
+    /// - 元のキーと同じ表記は元のキーを優先する
+    pub fn into_keys(self) -> Vec<SearchKey> {
+        let mut seen = std::collections::BTreeSet::new();
+        let mut keys = Vec::new();
+
+        for (key, is_alias) in &self.originals {
+            if !key.is_empty() && seen.insert(key.clone()) {
+                keys.push(SearchKey {
+                    key: key.clone(),
+                    is_alias: *is_alias,
+                    origin: None,
+                });
+            }
+        }
+
+        for (key, is_alias) in &self.originals {
+            for (variant, value) in variants(key) {
+                if !value.is_empty() && seen.insert(value.clone()) {
+                    keys.push(SearchKey {
+                        key: value,
+                        is_alias: *is_alias,
+                        origin: Some(KeyOrigin {
+                            variant,
+                            variant_of: key.clone(),
+                        }),
+                    });
+                }
+            }
+        }
+
+        keys
+    }
+}
+
+/// 1つのキーから生成する表記
+///
+/// 幅の畳み込みを最初に行い, 以降の変換はその結果に対して行う
+fn variants(key: &str) -> Vec<(KeyVariant, String)> {
+    let mut variants = Vec::new();
+
+    let folded = crate::text::fold_width(key);
+    if folded != key {
+        variants.push((KeyVariant::Width, folded.clone()));
+    }
+
+    // 漢字まじりのキー(`月ノ美兎`)のかなだけを変えた表記は入力されないので作らない
+    let hiragana = crate::text::to_hiragana(&folded);
+    if !has_kanji(&folded) {
+        if hiragana != folded {
+            variants.push((KeyVariant::Hiragana, hiragana.clone()));
+        }
+        let katakana = crate::text::to_katakana(&folded);
+        if katakana != folded {
+            variants.push((KeyVariant::Katakana, katakana));
+        }
+    }
+
+    for style in [
+        RomajiStyle::Hepburn,
+        RomajiStyle::HepburnLiteral,
+        RomajiStyle::Ime,
+    ] {
+        if let Some(romaji) = to_romaji(&hiragana, style) {
+            let variant = match style {
+                RomajiStyle::Hepburn | RomajiStyle::HepburnLiteral => {
+                    KeyVariant::Romaji
+                }
+                RomajiStyle::Ime => KeyVariant::RomajiIme,
+            };
+            variants.push((variant, romaji));
+        }
+    }
+
+    let lowercase = folded.to_lowercase();
+    if lowercase != folded {
+        variants.push((KeyVariant::Lowercase, lowercase));
+    }
+
+    variants
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RomajiStyle {
+    /// 長音(`ou`, `uu`, `ー`)を省く
+    Hepburn,
+    /// 長音をそのまま綴る
+    HepburnLiteral,
+    /// IMEの綴り. `ー`は`-`, `ん`は`nn`
+    Ime,
+}
+
+/// ひらがなとスペースと`ー`だけの文字列をローマ字にする. それ以外を含むときは`None`
+fn to_romaji(hiragana: &str, style: RomajiStyle) -> Option<String> {
+    let chars = hiragana.chars().collect::<Vec<_>>();
+    if !chars.iter().any(|c| is_hiragana(*c)) {
+        return None;
+    }
+
+    let ime = style == RomajiStyle::Ime;
+    let mut out = String::new();
+    let mut sokuon = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            'っ' => {
+                if sokuon && ime {
+                    out.push_str("xtu");
+                }
+                sokuon = true;
+                i += 1;
+                continue;
+            }
+            ' ' => {
+                if sokuon && ime {
+                    out.push_str("xtu");
+                }
+                sokuon = false;
+                out.push(' ');
+                i += 1;
+                continue;
+            }
+            'ー' => {
+                match style {
+                    RomajiStyle::Hepburn => {}
+                    RomajiStyle::HepburnLiteral => {
+                        if let Some(vowel) = out.chars().last().filter(|c| is_vowel(*c))
+                        {
+                            out.push(vowel);
+                        }
+                    }
+                    RomajiStyle::Ime => out.push('-'),
+                }
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let (syllable, len) = match chars.get(i + 1).and_then(|next| digraph(c, *next))
+        {
+            Some((hepburn, ime_spelling)) => {
+                (if ime { ime_spelling } else { hepburn }, 2)
+            }
+            None => {
+                let (hepburn, ime_spelling) = syllable(c)?;
+                (if ime { ime_spelling } else { hepburn }, 1)
+            }
+        };
+
+        if sokuon {
+            if !ime && syllable.starts_with("ch") {
+                out.push('t');
+            } else if let Some(first) =
+                syllable.chars().next().filter(|c| !is_vowel(*c))
+            {
+                out.push(first);
+            } else if ime {
+                out.push_str("xtu");
+            }
+            sokuon = false;
+        }
+
+        let is_long_vowel = style == RomajiStyle::Hepburn
+            && c == 'う'
+            && len == 1
+            && matches!(out.chars().last(), Some('o' | 'u'));
+        if !is_long_vowel {
+            out.push_str(syllable);
+        }
+        i += len;
+    }
+
+    if sokuon && ime {
+        out.push_str("xtu");
+    }
+
+    Some(out)
+}
+
+fn has_kanji(s: &str) -> bool {
+    s.chars()
+        .any(|c| matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '々'))
+}
+
+fn is_hiragana(c: char) -> bool {
+    matches!(c, '\u{3041}'..='\u{3096}')
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+/// 1文字の(ヘボン式, IME)の綴り
+fn syllable(c: char) -> Option<(&'static str, &'static str)> {
+    let spelling = match c {
+        'あ' => ("a", "a"),
+        'い' => ("i", "i"),
+        'う' => ("u", "u"),
+        'え' => ("e", "e"),
+        'お' => ("o", "o"),
+        'か' => ("ka", "ka"),
+        'き' => ("ki", "ki"),
+        'く' => ("ku", "ku"),
+        'け' => ("ke", "ke"),
+        'こ' => ("ko", "ko"),
+        'さ' => ("sa", "sa"),
+        'し' => ("shi", "si"),
+        'す' => ("su", "su"),
+        'せ' => ("se", "se"),
+        'そ' => ("so", "so"),
+        'た' => ("ta", "ta"),
+        'ち' => ("chi", "ti"),
+        'つ' => ("tsu", "tu"),
+        'て' => ("te", "te"),
+        'と' => ("to", "to"),
+        'な' => ("na", "na"),
+        'に' => ("ni", "ni"),
+        'ぬ' => ("nu", "nu"),
+        'ね' => ("ne", "ne"),
+        'の' => ("no", "no"),
+        'は' => ("ha", "ha"),
+        'ひ' => ("hi", "hi"),
+        'ふ' => ("fu", "hu"),
+        'へ' => ("he", "he"),
+        'ほ' => ("ho", "ho"),
+        'ま' => ("ma", "ma"),
+        'み' => ("mi", "mi"),
+        'む' => ("mu", "mu"),
+        'め' => ("me", "me"),
+        'も' => ("mo", "mo"),
+        'や' => ("ya", "ya"),
+        'ゆ' => ("yu", "yu"),
+        'よ' => ("yo", "yo"),
+        'ら' => ("ra", "ra"),
+        'り' => ("ri", "ri"),
+        'る' => ("ru", "ru"),
+        'れ' => ("re", "re"),
+        'ろ' => ("ro", "ro"),
+        'わ' => ("wa", "wa"),
+        'ゐ' => ("i", "wyi"),
+        'ゑ' => ("e", "wye"),
+        'を' => ("o", "wo"),
+        'ん' => ("n", "nn"),
+        'が' => ("ga", "ga"),
+        'ぎ' => ("gi", "gi"),
+        'ぐ' => ("gu", "gu"),
+        'げ' => ("ge", "ge"),
+        'ご' => ("go", "go"),
+        'ざ' => ("za", "za"),
+        'じ' => ("ji", "zi"),
+        'ず' => ("zu", "zu"),
+        'ぜ' => ("ze", "ze"),
+        'ぞ' => ("zo", "zo"),
+        'だ' => ("da", "da"),
+        'ぢ' => ("ji", "di"),
+        'づ' => ("zu", "du"),
+        'で' => ("de", "de"),
+        'ど' => ("do", "do"),
+        'ば' => ("ba", "ba"),
+        'び' => ("bi", "bi"),
+        'ぶ' => ("bu", "bu"),
+        'べ' => ("be", "be"),
+        'ぼ' => ("bo", "bo"),
+        'ぱ' => ("pa", "pa"),
+        'ぴ' => ("pi", "pi"),
+        'ぷ' => ("pu", "pu"),
+        'ぺ' => ("pe", "pe"),
+        'ぽ' => ("po", "po"),
+        'ゔ' => ("vu", "vu"),
+        'ぁ' => ("a", "xa"),
+        'ぃ' => ("i", "xi"),
+        'ぅ' => ("u", "xu"),
+        'ぇ' => ("e", "xe"),
+        'ぉ' => ("o", "xo"),
+        'ゃ' => ("ya", "xya"),
+        'ゅ' => ("yu", "xyu"),
+        'ょ' => ("yo", "xyo"),
+        'ゎ' => ("wa", "xwa"),
+        'ゕ' => ("ka", "xka"),
+        'ゖ' => ("ke", "xke"),
+        _ => return None,
+    };
+    Some(spelling)
+}
+
+/// 小書きのかなを伴う2文字の(ヘボン式, IME)の綴り
+fn digraph(c: char, small: char) -> Option<(&'static str, &'static str)> {
+    let spelling = match (c, small) {
+        ('き', 'ゃ') => ("kya", "kya"),
+        ('き', 'ゅ') => ("kyu", "kyu"),
+        ('き', 'ょ') => ("kyo", "kyo"),
+        ('ぎ', 'ゃ') => ("gya", "gya"),
+        ('ぎ', 'ゅ') => ("gyu", "gyu"),
+        ('ぎ', 'ょ') => ("gyo", "gyo"),
+        ('し', 'ゃ') => ("sha", "sya"),
+        ('し', 'ゅ') => ("shu", "syu"),
+        ('し', 'ょ') => ("sho", "syo"),
+        ('し', 'ぇ') => ("she", "sye"),
+        ('じ', 'ゃ') => ("ja", "zya"),
+        ('じ', 'ゅ') => ("ju", "zyu"),
+        ('じ', 'ょ') => ("jo", "zyo"),
+        ('じ', 'ぇ') => ("je", "zye"),
+        ('ち', 'ゃ') => ("cha", "tya"),
+        ('ち', 'ゅ') => ("chu", "tyu"),
+        ('ち', 'ょ') => ("cho", "tyo"),
+        ('ち', 'ぇ') => ("che", "tye"),
+        ('ぢ', 'ゃ') => ("ja", "dya"),
+        ('ぢ', 'ゅ') => ("ju", "dyu"),
+        ('ぢ', 'ょ') => ("jo", "dyo"),
+        ('に', 'ゃ') => ("nya", "nya"),
+        ('に', 'ゅ') => ("nyu", "nyu"),
+        ('に', 'ょ') => ("nyo", "nyo"),
+        ('ひ', 'ゃ') => ("hya", "hya"),
+        ('ひ', 'ゅ') => ("hyu", "hyu"),
+        ('ひ', 'ょ') => ("hyo", "hyo"),
+        ('び', 'ゃ') => ("bya", "bya"),
+        ('び', 'ゅ') => ("byu", "byu"),
+        ('び', 'ょ') => ("byo", "byo"),
+        ('ぴ', 'ゃ') => ("pya", "pya"),
+        ('ぴ', 'ゅ') => ("pyu", "pyu"),
+        ('ぴ', 'ょ') => ("pyo", "pyo"),
+        ('み', 'ゃ') => ("mya", "mya"),
+        ('み', 'ゅ') => ("myu", "myu"),
+        ('み', 'ょ') => ("myo", "myo"),
+        ('り', 'ゃ') => ("rya", "rya"),
+        ('り', 'ゅ') => ("ryu", "ryu"),
+        ('り', 'ょ') => ("ryo", "ryo"),
+        ('ふ', 'ぁ') => ("fa", "fa"),
+        ('ふ', 'ぃ') => ("fi", "fi"),
+        ('ふ', 'ぇ') => ("fe", "fe"),
+        ('ふ', 'ぉ') => ("fo", "fo"),
+        ('ふ', 'ゅ') => ("fyu", "fyu"),
+        ('て', 'ぃ') => ("ti", "thi"),
+        ('て', 'ゅ') => ("tyu", "thu"),
+        ('で', 'ぃ') => ("di", "dhi"),
+        ('で', 'ゅ') => ("dyu", "dhu"),
+        ('と', 'ぅ') => ("tu", "twu"),
+        ('ど', 'ぅ') => ("du", "dwu"),
+        ('う', 'ぃ') => ("wi", "wi"),
+        ('う', 'ぇ') => ("we", "we"),
+        ('う', 'ぉ') => ("wo", "who"),
+        ('い', 'ぇ') => ("ye", "ye"),
+        ('つ', 'ぁ') => ("tsa", "tsa"),
+        ('ゔ', 'ぁ') => ("va", "va"),
+        ('ゔ', 'ぃ') => ("vi", "vi"),
+        ('ゔ', 'ぇ') => ("ve", "ve"),
+        ('ゔ', 'ぉ') => ("vo", "vo"),
+        _ => return None,
+    };
+    Some(spelling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(keys: &[(&str, bool)]) -> Vec<SearchKey> {
+        let mut search_keys = SearchKeys::default();
+        for (key, is_alias) in keys {
+            if *is_alias {
+                search_keys.push_alias(key);
+            } else {
+                search_keys.push(key);
+            }
+        }
+        search_keys.into_keys()
+    }
+
+    fn find<'a>(keys: &'a [SearchKey], key: &str) -> &'a SearchKey {
+        keys.iter()
+            .find(|search_key| search_key.key == key)
+            .unwrap_or_else(|| panic!("`{key}` should be generated: {keys:?}"))
+    }
+
+    #[test]
+    fn test_search_keys_keep_originals_first() {
+        let keys = expand(&[
+            ("月ノ美兎", false),
+            ("つきのみと", false),
+            ("いいんちょう", true),
+        ]);
+
+        assert_eq!(keys[0].key, "月ノ美兎");
+        assert_eq!(keys[1].key, "つきのみと");
+        assert_eq!(keys[2].key, "いいんちょう");
+        assert!(keys[2].is_alias);
+        assert!(keys[..3].iter().all(|key| key.origin.is_none()));
+        assert!(keys[3..].iter().all(|key| key.origin.is_some()));
+    }
+
+    #[test]
+    fn test_search_keys_mark_variant_origin() {
+        let keys = expand(&[("つきのみと", false), ("Tsukino Mito", false)]);
+
+        assert_eq!(
+            find(&keys, "ツキノミト").origin,
+            Some(KeyOrigin {
+                variant: KeyVariant::Katakana,
+                variant_of: "つきのみと".to_string(),
+            })
+        );
+        assert_eq!(
+            find(&keys, "tsukinomito").origin.as_ref().unwrap().variant,
+            KeyVariant::Romaji
+        );
+        assert_eq!(
+            find(&keys, "tukinomito").origin.as_ref().unwrap().variant,
+            KeyVariant::RomajiIme
+        );
+        assert_eq!(
+            find(&keys, "tsukino mito").origin.as_ref().unwrap().variant,
+            KeyVariant::Lowercase
+        );
+    }
+
+    #[test]
+    fn test_search_keys_variants_inherit_alias_and_skip_duplicates() {
+        let keys = expand(&[("みと", false), ("ミト", true)]);
+
+        // カタカナの表記は元のキー(alias)と同じなので追加しない
+        assert_eq!(keys.iter().filter(|key| key.key == "ミト").count(), 1);
+        assert!(find(&keys, "ミト").origin.is_none());
+        assert!(!find(&keys, "mito").is_alias);
+    }
+
+    #[test]
+    fn test_search_keys_skip_kana_variants_of_kanji_keys() {
+        let keys = expand(&[
+            ("月ノ美兎", false),
+            ("3Dお披露目", false),
+            ("にじ3D", false),
+        ]);
+
+        assert!(keys.iter().all(|key| key.key != "月の美兎"));
+        assert!(keys.iter().all(|key| key.key != "3Dオ披露目"));
+        assert_eq!(
+            find(&keys, "ニジ3D").origin.as_ref().unwrap().variant,
+            KeyVariant::Katakana
+        );
+    }
+
+    #[test]
+    fn test_search_keys_skip_empty_keys() {
+        let keys = expand(&[("", false), ("Kanae", false), ("Kanae", false)]);
+
+        assert_eq!(keys.iter().filter(|key| key.origin.is_none()).count(), 1);
+        assert!(keys.iter().all(|key| !key.key.is_empty()));
+    }
+
+    #[test]
+    fn test_to_romaji() {
+        let cases = [
+            ("つきのみと", "tsukinomito", "tsukinomito", "tukinomito"),
+            ("ゆうきちひろ", "yukichihiro", "yuukichihiro", "yuukitihiro"),
+            (
+                "けんもちとうや",
+                "kenmochitoya",
+                "kenmochitouya",
+                "kennmotitouya",
+            ),
+            ("がっくん", "gakkun", "gakkun", "gakkunn"),
+            ("まっちゃ", "matcha", "matcha", "mattya"),
+            ("どーら", "dora", "doora", "do-ra"),
+            (
+                "しすたーくれあ",
+                "shisutakurea",
+                "shisutaakurea",
+                "sisuta-kurea",
+            ),
+            ("ふぃーな", "fina", "fiina", "fi-na"),
+            ("じゃっじ", "jajji", "jajji", "zyazzi"),
+        ];
+
+        for (kana, hepburn, literal, ime) in cases {
+            assert_eq!(
+                to_romaji(kana, RomajiStyle::Hepburn).as_deref(),
+                Some(hepburn)
+            );
+            assert_eq!(
+                to_romaji(kana, RomajiStyle::HepburnLiteral).as_deref(),
+                Some(literal)
+            );
+            assert_eq!(to_romaji(kana, RomajiStyle::Ime).as_deref(), Some(ime));
+        }
+    }
+
+    #[test]
+    fn test_to_romaji_rejects_non_kana() {
+        assert_eq!(to_romaji("月ノ美兎", RomajiStyle::Hepburn), None);
+        assert_eq!(to_romaji("にじ3D", RomajiStyle::Hepburn), None);
+        assert_eq!(to_romaji("Kanae", RomajiStyle::Hepburn), None);
+        assert_eq!(to_romaji("ー", RomajiStyle::Hepburn), None);
+    }
+}
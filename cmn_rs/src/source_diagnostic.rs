@@ -0,0 +1,472 @@
+/// 位置を求めるために読み込んだJSONファイル
+///
+/// `serde_json`のエラーは行と列しか持たないため, 値の範囲の木と突き合わせて
+/// エラーの原因になった値(またはキー)の範囲を求める
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: std::path::PathBuf,
+    pub text: String,
+    line_index: crate::json_span::LineIndex,
+    /// JSONとして不正なときは`None`
+    root: Option<crate::json_span::JsonNode>,
+}
+
+/// ファイル上の位置を持つエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDiagnostic {
+    pub path: std::path::PathBuf,
+    /// ファイルを読めなかったときなど, 位置を特定できないときは`None`
+    pub position: Option<SourcePosition>,
+    pub message: String,
+}
+
+/// エラーの位置. 行と列は1始まりで, 列は文字数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+    /// 範囲の終わりの列(含まない). 複数行にまたがるときは開始行の行末
+    pub end_column: usize,
+    /// 抜粋として表示する開始行の内容
+    pub line_text: String,
+}
+
+/// 複数の[`SourceDiagnostic`]をまとめたもの
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDiagnostics(pub Vec<SourceDiagnostic>);
+
+/// [`SourceDiagnostic`]の表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiagnosticFormat {
+    /// Excerpt of the offending line with the range underlined
+    Human,
+    /// GitHub Actions workflow commands (`::error file=...,line=...::`)
+    Github,
+}
+
+impl SourceFile {
+    pub fn new(path: impl Into<std::path::PathBuf>, text: String) -> Self {
+        let line_index = crate::json_span::LineIndex::new(&text);
+        let root = crate::json_span::parse(&text).ok();
+        Self {
+            path: path.into(),
+            text,
+            line_index,
+            root,
+        }
+    }
+
+    pub fn root(&self) -> Option<&crate::json_span::JsonNode> {
+        self.root.as_ref()
+    }
+
+    pub fn slice(&self, span: crate::json_span::Span) -> &str {
+        &self.text[span.start..span.end]
+    }
+
+    /// `span`の位置のエラー
+    pub fn diagnostic(
+        &self,
+        span: crate::json_span::Span,
+        message: impl Into<String>,
+    ) -> SourceDiagnostic {
+        let start = self.line_index.line_column(&self.text, span.start);
+        let line = self.line_index.line_span(&self.text, start.line);
+        let end = self
+            .line_index
+            .line_column(&self.text, span.end.clamp(span.start, line.end));
+        SourceDiagnostic {
+            path: self.path.clone(),
+            position: Some(SourcePosition {
+                line: start.line + 1,
+                column: start.column + 1,
+                end_column: end.column.max(start.column + 1) + 1,
+                line_text: self.slice(line).to_string(),
+            }),
+            message: message.into(),
+        }
+    }
+
+    /// `serde_json`のエラーの位置にある値の範囲
+    ///
+    /// `base`は`serde_json`に渡した文字列のファイル内の開始位置. 位置を持たないエラーは`None`
+    pub fn serde_error_span(
+        &self,
+        base: usize,
+        err: &serde_json::Error,
+    ) -> Option<crate::json_span::Span> {
+        if err.line() == 0 {
+            return None;
+        }
+        // `serde_json`の列は, その行で読み終えたバイト数
+        let mut line_start = base;
+        for _ in 1..err.line() {
+            line_start += self.text[line_start..].find('\n')? + 1;
+        }
+        let offset = (line_start + err.column()).min(self.text.len());
+        // 最後に読んだ文字を含む値が原因
+        let last = offset.saturating_sub(1).max(base);
+        Some(
+            self.root
+                .as_ref()
+                .and_then(|root| deepest_span(root, last))
+                .unwrap_or(crate::json_span::Span {
+                    start: last,
+                    end: last + 1,
+                }),
+        )
+    }
+
+    /// `serde_json`のエラーを位置付きにする
+    ///
+    /// `base`は[`Self::serde_error_span`]と同じ
+    pub fn serde_diagnostic(
+        &self,
+        base: usize,
+        err: &serde_json::Error,
+    ) -> SourceDiagnostic {
+        let message = serde_error_message(err);
+        match self.serde_error_span(base, err) {
+            Some(span) => self.diagnostic(span, message),
+            None => SourceDiagnostic {
+                path: self.path.clone(),
+                position: None,
+                message,
+            },
+        }
+    }
+
+    /// `span`と同じ範囲の値
+    pub fn node_at(
+        &self,
+        span: crate::json_span::Span,
+    ) -> Option<&crate::json_span::JsonNode> {
+        fn find(
+            node: &crate::json_span::JsonNode,
+            span: crate::json_span::Span,
+        ) -> Option<&crate::json_span::JsonNode> {
+            if node.span == span {
+                return Some(node);
+            }
+            if !(node.span.start <= span.start && span.end <= node.span.end) {
+                return None;
+            }
+            match &node.value {
+                crate::json_span::JsonValue::Array(items) => {
+                    items.iter().find_map(|item| find(item, span))
+                }
+                crate::json_span::JsonValue::Object(members) => {
+                    members.iter().find_map(|member| find(&member.value, span))
+                }
+                _ => None,
+            }
+        }
+        find(self.root.as_ref()?, span)
+    }
+}
+
+/// `offset`を含む最も深い値, またはキーの範囲
+fn deepest_span(
+    node: &crate::json_span::JsonNode,
+    offset: usize,
+) -> Option<crate::json_span::Span> {
+    if !node.span.contains(offset) {
+        return None;
+    }
+    let inner = match &node.value {
+        crate::json_span::JsonValue::Array(items) => {
+            items.iter().find_map(|item| deepest_span(item, offset))
+        }
+        crate::json_span::JsonValue::Object(members) => {
+            members.iter().find_map(|member| {
+                if member.key_span.contains(offset) {
+                    Some(member.key_span)
+                } else {
+                    deepest_span(&member.value, offset)
+                }
+            })
+        }
+        _ => None,
+    };
+    Some(inner.unwrap_or(node.span))
+}
+
+/// `serde_json`のエラーメッセージから末尾の` at line N column M`を除く
+pub fn serde_error_message(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let suffix = format!(" at line {} column {}", err.line(), err.column());
+    match message.strip_suffix(&suffix) {
+        Some(stripped) => stripped.to_string(),
+        None => message,
+    }
+}
+
+impl SourceDiagnostic {
+    /// 位置を特定できないエラー
+    pub fn without_position(
+        path: impl Into<std::path::PathBuf>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            position: None,
+            message: message.into(),
+        }
+    }
+
+    /// 指定した形式の文字列. 末尾に改行を含まない
+    pub fn render(&self, format: DiagnosticFormat) -> String {
+        match format {
+            DiagnosticFormat::Human => self.render_human(),
+            DiagnosticFormat::Github => self.render_github(),
+        }
+    }
+
+    /// rustcのようにエラーの行を抜き出して下線を引く
+    ///
+    /// ```text
+    /// error: invalid liver: ghost
+    ///   --> input/a.json:12:21
+    ///    |
+    /// 12 |       "liverIds": ["ghost"],
+    ///    |                   ^^^^^^^
+    /// ```
+    fn render_human(&self) -> String {
+        let path = self.path.display();
+        let Some(position) = &self.position else {
+            return format!("error: {}\n  --> {path}", self.message);
+        };
+
+        let line_number = position.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let chars = position.line_text.chars().collect::<Vec<_>>();
+        let start = (position.column - 1).min(chars.len());
+        let end = (position.end_column - 1).clamp(start, chars.len());
+        // タブはそのまま残し, 全角文字は2桁とみなして下線の位置を合わせる
+        let indent = chars[..start]
+            .iter()
+            .map(|&c| match c {
+                '\t' => "\t".to_string(),
+                c => " ".repeat(display_width(c)),
+            })
+            .collect::<String>();
+        let carets = "^".repeat(
+            chars[start..end]
+                .iter()
+                .map(|&c| display_width(c))
+                .sum::<usize>()
+                .max(1),
+        );
+
+        format!(
+            "error: {message}\n\
+            {gutter}--> {path}:{line}:{column}\n\
+            {gutter} |\n\
+            {line_number} | {text}\n\
+            {gutter} | {indent}{carets}",
+            message = self.message,
+            line = position.line,
+            column = position.column,
+            text = position.line_text,
+        )
+    }
+
+    /// GitHub Actionsのエラー注釈
+    fn render_github(&self) -> String {
+        let mut properties = vec![format!(
+            "file={}",
+            escape_property(&self.path.to_string_lossy())
+        )];
+        if let Some(position) = &self.position {
+            properties.push(format!("line={}", position.line));
+            properties.push(format!("col={}", position.column));
+            properties.push(format!("endColumn={}", position.end_column));
+        }
+        format!(
+            "::error {}::{}",
+            properties.join(","),
+            escape_data(&self.message)
+        )
+    }
+}
+
+impl std::fmt::Display for SourceDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.position {
+            Some(position) => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.path.display(),
+                position.line,
+                position.column,
+                self.message
+            ),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl std::error::Error for SourceDiagnostic {}
+
+impl SourceDiagnostics {
+    /// 各エラーを指定した形式で改行区切りにする
+    pub fn render(&self, format: DiagnosticFormat) -> String {
+        let separator = match format {
+            DiagnosticFormat::Human => "\n\n",
+            DiagnosticFormat::Github => "\n",
+        };
+        self.0
+            .iter()
+            .map(|diagnostic| diagnostic.render(format))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+impl std::fmt::Display for SourceDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|diagnostic| diagnostic.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+impl std::error::Error for SourceDiagnostics {}
+
+impl DiagnosticFormat {
+    /// 環境変数`GITHUB_ACTIONS`が`true`(GitHub Actionsの中)なら`Github`
+    pub fn detect() -> Self {
+        if std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true") {
+            Self::Github
+        } else {
+            Self::Human
+        }
+    }
+}
+
+/// 端末での表示幅. 東アジアの全角文字と絵文字を2とする簡易的なもの
+fn display_width(c: char) -> usize {
+    match u32::from(c) {
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// ワークフローコマンドのメッセージのエスケープ
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// ワークフローコマンドのプロパティ値のエスケープ
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    const TEXT: &str = "[\n  {\n    \"videoId\": \"ZeFvqdvutb4\",\n    \"liverIds\": [\"ghost\"]\n  }\n]\n";
+
+    #[test]
+    fn test_serde_error_points_to_value() {
+        let file = super::SourceFile::new("input/a.json", TEXT.to_string());
+
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(rename_all = "camelCase", deny_unknown_fields)]
+        struct Video {
+            #[allow(dead_code)]
+            video_id: String,
+        }
+        let err = serde_json::from_str::<Vec<Video>>(TEXT).unwrap_err();
+        let diagnostic = file.serde_diagnostic(0, &err);
+        assert_eq!(
+            diagnostic.message,
+            "unknown field `liverIds`, expected `videoId`"
+        );
+        let position = diagnostic.position.unwrap();
+        assert_eq!(
+            (position.line, position.column, position.end_column),
+            (4, 5, 15)
+        );
+        assert_eq!(position.line_text, "    \"liverIds\": [\"ghost\"]");
+    }
+
+    #[test]
+    fn test_serde_error_in_slice() {
+        let file = super::SourceFile::new("input/a.json", TEXT.to_string());
+        let video = &file.root().unwrap().as_array().unwrap()[0];
+        let liver = &video.get("liverIds").unwrap().as_array().unwrap()[0];
+
+        let err = serde_json::from_str::<std::collections::HashMap<String, u32>>(
+            file.slice(video.span),
+        )
+        .unwrap_err();
+        assert_eq!(
+            file.serde_error_span(video.span.start, &err),
+            Some(video.get("videoId").unwrap().span)
+        );
+        assert_eq!(file.node_at(liver.span), Some(liver));
+
+        let err = serde_json::from_value::<u32>(serde_json::json!("x")).unwrap_err();
+        assert_eq!(file.serde_diagnostic(0, &err).position, None);
+    }
+
+    #[test]
+    fn test_render_human() {
+        let text = "{\"songTitle\": \"歌\", \"startTime\": 1}";
+        let file = super::SourceFile::new("input/a.json", text.to_string());
+        let span = file.root().unwrap().get("startTime").unwrap().span;
+        let diagnostic = file.diagnostic(span, "invalid type");
+        // `歌`は2桁として下線の位置を合わせる
+        let expected = [
+            "error: invalid type".to_string(),
+            " --> input/a.json:1:33".to_string(),
+            "  |".to_string(),
+            format!("1 | {text}"),
+            format!("  | {}^", " ".repeat(33)),
+        ];
+        assert_eq!(
+            diagnostic.render(super::DiagnosticFormat::Human),
+            expected.join("\n")
+        );
+        assert_eq!(diagnostic.to_string(), "input/a.json:1:33: invalid type");
+    }
+
+    #[test]
+    fn test_render_github() {
+        let file = super::SourceFile::new("input/a,b.json", TEXT.to_string());
+        let span = file.root().unwrap().as_array().unwrap()[0]
+            .get("videoId")
+            .unwrap()
+            .span;
+        let diagnostics = super::SourceDiagnostics(vec![
+            file.diagnostic(span, "100% wrong\nreally"),
+            super::SourceDiagnostic::without_position("input/b.json", "not found"),
+        ]);
+        assert_eq!(
+            diagnostics.render(super::DiagnosticFormat::Github),
+            "::error file=input/a%2Cb.json,line=3,col=16,endColumn=29::100%25 wrong%0Areally\n\
+            ::error file=input/b.json::not found"
+        );
+    }
+}
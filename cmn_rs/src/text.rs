@@ -0,0 +1,290 @@
+/// 検索向けに文字列を正規化する
+///
+/// 1. NFKC正規化(全角英数字, 半角カナ, 互換文字を畳み込む)
+/// 2. 装飾記号(`「」`, `。`, `♪`, 絵文字など)をスペースにする
+/// 3. カタカナをひらがなにする
+/// 4. 小文字にする
+/// 5. 連続する空白を1つにまとめ, 前後の空白を除く
+///
+/// 楽曲タイトルの索引作成と検索時のクエリで同じ結果になるよう, 環境に依存する処理は使わない.
+/// native, wasmのどちらでも`text/golden/titles.json`と同じ結果になること
+///
+/// ```
+/// assert_eq!(cmn_rs::text::normalize("【歌枠】３周年だ！"), "歌枠 3周年だ");
+/// assert_eq!(cmn_rs::text::normalize("シャッター"), "しゃったー");
+/// assert_eq!(cmn_rs::text::normalize("Hello, Again 〜昔からある場所〜"), "hello again 昔からある場所");
+/// ```
+pub fn normalize(s: &str) -> String {
+    let s = nfkc(s);
+    let s = strip_decorations(&s);
+    let s = to_hiragana(&s);
+    let s = fold_case(&s);
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 表記ゆれを無視して照合するためのキー. `normalize`した上で空白も取り除く
+///
+/// ライバー名, 曲名などを完全一致や編集距離で比べるときに使う
+///
+/// ```
+/// assert_eq!(cmn_rs::text::match_key("田角　陸"), "田角陸");
+/// assert_eq!(cmn_rs::text::match_key("Fuwa Fuwa Time!"), "fuwafuwatime");
+/// assert_eq!(cmn_rs::text::match_key("ロウワー"), "ろうわー");
+/// ```
+pub fn match_key(s: &str) -> String {
+    normalize(s).replace(' ', "")
+}
+
+/// `normalize`した文字列を文字`n`-gramに分割する
+///
+/// - 空白で区切った語ごとに分割し, 語をまたぐn-gramは作らない
+/// - `n`文字以下の語はそのまま1つのトークンにする
+/// - `n`が0のときは空
+///
+/// ```
+/// assert_eq!(cmn_rs::text::tokenize("ケセラセラ", 2), ["けせ", "せら", "らせ", "せら"]);
+/// assert_eq!(cmn_rs::text::tokenize("I WITH", 2), ["i", "wi", "it", "th"]);
+/// ```
+pub fn tokenize(s: &str, n: usize) -> Vec<String> {
+    ngrams(&normalize(s), n)
+}
+
+/// 文字`n`-gramに分割する. 正規化はしない
+pub fn ngrams(s: &str, n: usize) -> Vec<String> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut grams = Vec::new();
+    for word in s.split_whitespace() {
+        let chars = word.chars().collect::<Vec<_>>();
+        if chars.len() <= n {
+            grams.push(word.to_string());
+        } else {
+            grams.extend(chars.windows(n).map(|gram| gram.iter().collect::<String>()));
+        }
+    }
+    grams
+}
+
+/// NFKC正規化
+pub fn nfkc(s: &str) -> String {
+    unicode_normalization::UnicodeNormalization::nfkc(s).collect()
+}
+
+/// 小文字にする
+pub fn fold_case(s: &str) -> String {
+    s.to_lowercase()
+}
+
+/// 装飾記号をスペースにする
+///
+/// 括弧, 句読点, 中黒, 波ダッシュ, 音符や星などの記号, 絵文字が対象.
+/// `%`, `&`, `+`などの意味を持つ記号と, 長音符`ー`は残す
+pub fn strip_decorations(s: &str) -> String {
+    s.chars()
+        .map(|c| if is_decoration(c) { ' ' } else { c })
+        .filter(|c| !is_invisible(*c))
+        .collect()
+}
+
+fn is_decoration(c: char) -> bool {
+    matches!(
+        c,
+        '!' | '"'
+            | '\''
+            | '('
+            | ')'
+            | '*'
+            | ','
+            | '-'
+            | '.'
+            | '/'
+            | ':'
+            | ';'
+            | '<'
+            | '='
+            | '>'
+            | '?'
+            | '['
+            | '\\'
+            | ']'
+            | '^'
+            | '_'
+            | '`'
+            | '{'
+            | '|'
+            | '}'
+            | '~'
+            | '#'
+            // 一般句読点(ダッシュ, 引用符, 三点リーダなど)
+            | '\u{2010}'..='\u{205E}'
+            // 矢印, 記号, 罫線, 図形, その他の記号, 装飾記号
+            | '\u{2190}'..='\u{21FF}'
+            | '\u{2500}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            // CJKの記号と句読点(`、`, `。`, `「」`, `【】`, `〜`など). `々`, `〆`は除く
+            | '\u{3001}'..='\u{3004}'
+            | '\u{3008}'..='\u{3020}'
+            | '\u{3030}'
+            | '・'
+            // 絵文字
+            | '\u{1F000}'..='\u{1FAFF}'
+    )
+}
+
+/// 絵文字の異体字セレクタ, ゼロ幅接合子など, 表示されない文字
+fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200D}' | '\u{FE00}'..='\u{FE0F}')
+}
+
+/// 全角英数字記号と全角スペースを半角に, 半角カナを全角にする
+///
+/// NFKCと違い, 幅以外の互換文字(`①`, `㈱`など)はそのまま残す
+pub fn fold_width(s: &str) -> String {
+    /// U+FF61..=U+FF9F に対応する全角文字
+    const HALF_WIDTH_KATAKANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\u{FF01}'..='\u{FF5E}' => {
+                out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c));
+            }
+            '\u{3000}' => out.push(' '),
+            '\u{FF9E}' | '\u{FF9F}' => {
+                let semi = c == '\u{FF9F}';
+                match out.chars().last().and_then(|base| voice(base, semi)) {
+                    Some(voiced) => {
+                        out.pop();
+                        out.push(voiced);
+                    }
+                    None => out.push(if semi { '゜' } else { '゛' }),
+                }
+            }
+            '\u{FF61}'..='\u{FF9D}' => {
+                let index = (c as u32 - 0xFF61) as usize;
+                out.push(HALF_WIDTH_KATAKANA.chars().nth(index).unwrap_or(c));
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 全角カナに濁点(`semi`のときは半濁点)を付ける
+fn voice(base: char, semi: bool) -> Option<char> {
+    const VOICEABLE: &str = "カキクケコサシスセソタチツテトハヒフヘホ";
+    const SEMI_VOICEABLE: &str = "ハヒフヘホ";
+
+    match (base, semi) {
+        ('ウ', false) => Some('ヴ'),
+        (base, false) if VOICEABLE.contains(base) => char::from_u32(base as u32 + 1),
+        (base, true) if SEMI_VOICEABLE.contains(base) => {
+            char::from_u32(base as u32 + 2)
+        }
+        _ => None,
+    }
+}
+
+/// カタカナをひらがなにする. 対応するひらがなの無い文字(`ヷ`など)はそのまま残す
+pub fn to_hiragana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' | '\u{30FD}' | '\u{30FE}' => {
+                char::from_u32(c as u32 - 0x60).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+/// ひらがなをカタカナにする
+pub fn to_katakana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{3041}'..='\u{3096}' | '\u{309D}' | '\u{309E}' => {
+                char::from_u32(c as u32 + 0x60).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+    struct GoldenCase {
+        input: String,
+        normalized: String,
+        bigrams: Vec<String>,
+    }
+
+    #[test]
+    fn test_fold_width() {
+        assert_eq!(fold_width("ＮＩＪＩ３Ｄ"), "NIJI3D");
+        assert_eq!(fold_width("にじ　さんじ"), "にじ さんじ");
+        assert_eq!(fold_width("ﾂｷﾉﾐﾄ"), "ツキノミト");
+        assert_eq!(fold_width("ｶﾞｸ ﾊﾟﾝ ｳﾞｨ"), "ガク パン ヴィ");
+        assert_eq!(fold_width("ｱﾞ"), "ア゛");
+    }
+
+    #[test]
+    fn test_kana_conversion() {
+        assert_eq!(to_katakana("くろのわーる"), "クロノワール");
+        assert_eq!(to_hiragana("クロノワール"), "くろのわーる");
+        assert_eq!(to_hiragana("ヴァ ChroNoiR"), "ゔぁ ChroNoiR");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("ＭＯＯＮ　ＰＲＩＤＥ"), "moon pride");
+        assert_eq!(normalize("ﾓｴﾁｬｯｶﾌｧｲｱ"), "もえちゃっかふぁいあ");
+        assert_eq!(normalize("  命に嫌われている。 "), "命に嫌われている");
+        assert_eq!(normalize("ぼなぺてぃーと♡S"), "ぼなぺてぃーと s");
+        assert_eq!(normalize("歌枠🎤✨️"), "歌枠");
+        assert_eq!(normalize("佐々木"), "佐々木");
+        assert_eq!(normalize("17％の確率"), "17%の確率");
+    }
+
+    #[test]
+    fn test_match_key() {
+        assert_eq!(match_key("Tazumi Riku"), "tazumiriku");
+        assert_eq!(match_key("田角　陸"), "田角陸");
+        assert_eq!(match_key("Fuwa Fuwa Time!"), "fuwafuwatime");
+        assert_eq!(match_key("ＩＤＯＬ"), "idol");
+        assert_eq!(match_key("アイドル"), "あいどる");
+        assert_eq!(match_key("ロウワー"), "ろうわー");
+        assert_eq!(match_key(" 　"), "");
+    }
+
+    #[test]
+    fn test_ngrams() {
+        assert_eq!(
+            ngrams("ふわふわ時間", 2),
+            ["ふわ", "わふ", "ふわ", "わ時", "時間"]
+        );
+        assert_eq!(ngrams("恋 ab", 2), ["恋", "ab"]);
+        assert_eq!(ngrams("abc", 3), ["abc"]);
+        assert!(ngrams("abc", 0).is_empty());
+        assert!(ngrams("", 2).is_empty());
+    }
+
+    /// `music/data`の実際のタイトルから作った正規化結果
+    #[test]
+    fn test_normalize_golden() {
+        let cases: Vec<GoldenCase> =
+            serde_json::from_str(include_str!("text/golden/titles.json")).unwrap();
+
+        for case in cases {
+            let actual = GoldenCase {
+                normalized: normalize(&case.input),
+                bigrams: tokenize(&case.input, 2),
+                input: case.input.clone(),
+            };
+            assert_eq!(actual, case);
+        }
+    }
+}
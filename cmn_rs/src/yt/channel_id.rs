@@ -43,6 +43,12 @@ impl ChannelId {
     }
 }
 
+impl crate::json_schema::JsonSchema for ChannelId {
+    fn json_schema(_: &crate::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "pattern": "^UC[A-Za-z0-9_-]{22}$" })
+    }
+}
+
 impl std::fmt::Display for ChannelId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
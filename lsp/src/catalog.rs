@@ -0,0 +1,124 @@
+/// 補完, ホバー, 診断で参照するライバー, タグ, 登録済みの動画
+///
+/// ライバーとタグは`artistctl`, `tagctl`が読み込んだデータから作る
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    pub(crate) livers: std::collections::BTreeMap<String, LiverEntry>,
+    pub(crate) tags: std::collections::BTreeMap<String, TagEntry>,
+    /// 楽曲情報に登録済みの動画. (動画id, タイトル)
+    pub(crate) library_videos: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LiverEntry {
+    pub(crate) ja: String,
+    pub(crate) jah: String,
+    pub(crate) en: String,
+    pub(crate) aliases: Vec<String>,
+    /// `RRGGBB`
+    pub(crate) color: String,
+    pub(crate) is_graduated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TagEntry {
+    pub(crate) ja: String,
+    pub(crate) en: String,
+    pub(crate) parent: Option<String>,
+}
+
+impl Catalog {
+    /// ライバー, タグと`music_root`の楽曲情報を読み込む
+    ///
+    /// 楽曲情報を読み込めないときは, 登録済みの動画を空として続ける
+    pub fn load(music_root: &std::path::Path) -> Self {
+        let library_videos = match load_library_videos(music_root) {
+            Ok(videos) => videos,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load music library from `{}`: {e}",
+                    music_root.display()
+                );
+                std::collections::BTreeMap::new()
+            }
+        };
+        Self::with_library_videos(library_videos)
+    }
+
+    pub(crate) fn with_library_videos(
+        library_videos: std::collections::BTreeMap<String, String>,
+    ) -> Self {
+        let livers = artistctl::model::LOADED_LIVER_DATA
+            .clone()
+            .into_iter()
+            .map(|(id, liver)| {
+                let liver = liver.into_inner();
+                let entry = LiverEntry {
+                    ja: liver.ja,
+                    jah: liver.jah,
+                    en: liver.en,
+                    aliases: liver.aliases,
+                    color: liver.color.to_string(),
+                    is_graduated: liver.is_graduated,
+                };
+                (id.as_str().to_string(), entry)
+            })
+            .collect();
+
+        let tags = tagctl::model::LOADED_VIDEO_TAG_DATA
+            .iter()
+            .map(|(id, tag)| {
+                let entry = TagEntry {
+                    ja: tag.ja().to_string(),
+                    en: tag.en().to_string(),
+                    parent: tag.parent().map(|parent| parent.as_str().to_string()),
+                };
+                (id.as_str().to_string(), entry)
+            })
+            .collect();
+
+        Self {
+            livers,
+            tags,
+            library_videos,
+        }
+    }
+}
+
+fn load_library_videos(
+    music_root: &std::path::Path,
+) -> anyhow::Result<std::collections::BTreeMap<String, String>> {
+    let library = musictl::music_file::MusicLibraryRepository::load(music_root)?;
+    let videos = library
+        .into_videos()?
+        .into_sorted_vec()
+        .into_iter()
+        .map(|video| (video.video_id_string(), video.get_title().to_string()))
+        .collect();
+    Ok(videos)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_with_library_videos_reads_loaded_data() {
+        let catalog = super::Catalog::with_library_videos(Default::default());
+
+        let liver = &catalog.livers["riku-tazumi"];
+        assert_eq!(liver.ja, "田角陸");
+        assert_eq!(liver.color, "111111");
+        assert!(catalog.livers["yudorikku"].is_graduated);
+
+        assert_eq!(catalog.tags["3d-debut"].parent.as_deref(), Some("3d"));
+        assert_eq!(catalog.tags["karaoke"].parent, None);
+    }
+
+    #[test]
+    fn test_load_without_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let catalog = super::Catalog::load(dir.path());
+
+        assert!(catalog.library_videos.is_empty());
+        assert_eq!(catalog.livers.len(), 3);
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Debug, clap::Parser)]
+#[command(version, about, long_about = None)]
+pub struct Cli {
+    /// Directory of the music data used for video ID completion and duplicate checks.
+    #[arg(long, value_name = "DIR", default_value_t = musictl::cfg::default_music_root_dir())]
+    pub(crate) music_root_dir: String,
+
+    /// Tracing level for the log file. stdout is reserved for the LSP messages.
+    #[arg(long, value_name = "LEVEL")]
+    pub(crate) file_tracing_level: Option<cmn_rs::tracing::TracingLevel>,
+}
+
+impl Cli {
+    pub fn file_level(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.file_tracing_level
+            .map(|lv| lv.into_tracing_level_filter())
+    }
+}
@@ -0,0 +1,165 @@
+pub(crate) const SORT_CLIPS_TITLE: &str = "Sort clips by startTime";
+
+/// 選択範囲にかかる動画のうち, クリップが`startTime`順でないものを並べ替える
+///
+/// 要素の間の区切り(改行, インデント)は元のまま, 要素の文字列だけを入れ替える.
+/// `startTime`を読めないクリップがある動画は対象にしない
+pub(crate) fn code_actions(
+    doc: &crate::document::Document,
+    uri: &lsp_types::Uri,
+    range: cmn_rs::json_span::Span,
+) -> Vec<lsp_types::CodeActionOrCommand> {
+    let Some(videos) = doc.videos() else {
+        return Vec::new();
+    };
+
+    let edits = videos
+        .iter()
+        .filter(|video| video.span.start <= range.end && range.start <= video.span.end)
+        .filter_map(|video| sort_clips_edit(doc, video))
+        .collect::<Vec<_>>();
+    if edits.is_empty() {
+        return Vec::new();
+    }
+
+    let action = lsp_types::CodeAction {
+        title: SORT_CLIPS_TITLE.to_string(),
+        kind: Some(lsp_types::CodeActionKind::REFACTOR_REWRITE),
+        edit: Some(lsp_types::WorkspaceEdit {
+            changes: Some([(uri.clone(), edits)].into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    vec![lsp_types::CodeActionOrCommand::CodeAction(action)]
+}
+
+fn sort_clips_edit(
+    doc: &crate::document::Document,
+    video: &cmn_rs::json_span::JsonNode,
+) -> Option<lsp_types::TextEdit> {
+    let clips = video.get("clips")?.as_array()?;
+    let start_secs = clips
+        .iter()
+        .map(|clip| {
+            musictl::validate::parse_duration_secs(clip.get("startTime")?.as_str()?)
+                .ok()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if start_secs.is_sorted() {
+        return None;
+    }
+
+    // 安定ソートで, 同じ時間のクリップの順は保つ
+    let mut order = (0..clips.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| start_secs[i]);
+
+    let first = clips.first()?.span;
+    let last = clips.last()?.span;
+    let mut new_text = String::new();
+    for (position, &i) in order.iter().enumerate() {
+        new_text.push_str(doc.slice(clips[i].span));
+        if let Some(next) = clips.get(position + 1) {
+            let separator = cmn_rs::json_span::Span {
+                start: clips[position].span.end,
+                end: next.span.start,
+            };
+            new_text.push_str(doc.slice(separator));
+        }
+    }
+
+    Some(lsp_types::TextEdit {
+        range: doc.range(cmn_rs::json_span::Span {
+            start: first.start,
+            end: last.end,
+        }),
+        new_text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    fn sort(text: &str, range: cmn_rs::json_span::Span) -> Option<String> {
+        let doc = crate::document::Document::new(text.to_string());
+        let uri: lsp_types::Uri = "file:///input/a.json".parse().unwrap();
+        let actions = super::code_actions(&doc, &uri, range);
+        let [lsp_types::CodeActionOrCommand::CodeAction(action)] = actions.as_slice()
+        else {
+            assert!(actions.is_empty());
+            return None;
+        };
+        assert_eq!(action.title, super::SORT_CLIPS_TITLE);
+
+        let mut edits =
+            action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri].clone();
+        // 後ろから適用してオフセットをずらさない
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+        let mut sorted = text.to_string();
+        for edit in edits {
+            let start = doc.offset(edit.range.start);
+            let end = doc.offset(edit.range.end);
+            sorted.replace_range(start..end, &edit.new_text);
+        }
+        Some(sorted)
+    }
+
+    fn whole(text: &str) -> cmn_rs::json_span::Span {
+        cmn_rs::json_span::Span {
+            start: 0,
+            end: text.len(),
+        }
+    }
+
+    #[test]
+    fn test_sort_clips_keeps_layout() {
+        let text = r#"[
+  {
+    "videoId": "ZeFvqdvutb4",
+    "clips": [
+      { "songTitle": "b", "startTime": "PT1M", "endTime": "PT2M" },
+      { "songTitle": "c", "startTime": "PT1H", "endTime": "PT1H1M" },
+      { "songTitle": "a", "startTime": "PT10S", "endTime": "PT20S" }
+    ]
+  }
+]"#;
+        let expected = r#"[
+  {
+    "videoId": "ZeFvqdvutb4",
+    "clips": [
+      { "songTitle": "a", "startTime": "PT10S", "endTime": "PT20S" },
+      { "songTitle": "b", "startTime": "PT1M", "endTime": "PT2M" },
+      { "songTitle": "c", "startTime": "PT1H", "endTime": "PT1H1M" }
+    ]
+  }
+]"#;
+        assert_eq!(sort(text, whole(text)).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_sort_clips_only_in_range() {
+        let text = r#"[
+  {"videoId": "AAAAAAAAAAA", "clips": [{"startTime": "PT2S"}, {"startTime": "PT1S"}]},
+  {"videoId": "BBBBBBBBBBB", "clips": [{"startTime": "PT4S"}, {"startTime": "PT3S"}]}
+]"#;
+        let cursor = text.find("BBBB").unwrap();
+        let sorted = sort(
+            text,
+            cmn_rs::json_span::Span {
+                start: cursor,
+                end: cursor,
+            },
+        )
+        .unwrap();
+        assert!(sorted.contains(r#"[{"startTime": "PT2S"}, {"startTime": "PT1S"}]"#));
+        assert!(sorted.contains(r#"[{"startTime": "PT3S"}, {"startTime": "PT4S"}]"#));
+    }
+
+    #[test]
+    fn test_no_action_when_sorted_or_unreadable() {
+        let sorted = r#"[{"clips": [{"startTime": "PT1S"}, {"startTime": "PT2S"}]}]"#;
+        assert_eq!(sort(sorted, whole(sorted)), None);
+
+        let unreadable = r#"[{"clips": [{"startTime": "PT2S"}, {"startTime": "2"}]}]"#;
+        assert_eq!(sort(unreadable, whole(unreadable)), None);
+    }
+}
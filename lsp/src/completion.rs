@@ -0,0 +1,166 @@
+/// カーソル位置の補完候補
+///
+/// - `liverIds`: ライバーid
+/// - `videoTags`, `clipTags`: タグid
+/// - `videoId`, `clippedVideoId`: 楽曲情報に登録済みの動画id
+pub(crate) fn completions(
+    doc: &crate::document::Document,
+    offset: usize,
+    catalog: &crate::catalog::Catalog,
+) -> Vec<lsp_types::CompletionItem> {
+    let Some(ctx) = crate::cursor::context_at(&doc.text, offset) else {
+        return Vec::new();
+    };
+    let Some(key) = ctx.key.as_deref() else {
+        return Vec::new();
+    };
+
+    let candidates: Vec<Candidate> = match key {
+        "liverIds" => catalog
+            .livers
+            .iter()
+            .map(|(id, liver)| Candidate {
+                id,
+                detail: if liver.is_graduated {
+                    format!("{} ({}) 卒業", liver.ja, liver.en)
+                } else {
+                    format!("{} ({})", liver.ja, liver.en)
+                },
+                filter_text: std::iter::once(id.as_str())
+                    .chain([liver.ja.as_str(), liver.jah.as_str(), liver.en.as_str()])
+                    .chain(liver.aliases.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                kind: lsp_types::CompletionItemKind::VALUE,
+            })
+            .collect(),
+        "videoTags" | "clipTags" => catalog
+            .tags
+            .iter()
+            .map(|(id, tag)| Candidate {
+                id,
+                detail: format!("{} ({})", tag.ja, tag.en),
+                filter_text: format!("{id} {} {}", tag.ja, tag.en),
+                kind: lsp_types::CompletionItemKind::ENUM_MEMBER,
+            })
+            .collect(),
+        "videoId" | "clippedVideoId" => catalog
+            .library_videos
+            .iter()
+            .map(|(id, title)| Candidate {
+                id,
+                detail: title.clone(),
+                filter_text: format!("{id} {title}"),
+                kind: lsp_types::CompletionItemKind::REFERENCE,
+            })
+            .collect(),
+        _ => return Vec::new(),
+    };
+
+    candidates
+        .into_iter()
+        .map(|candidate| candidate.into_item(doc, offset, ctx.string))
+        .collect()
+}
+
+struct Candidate<'a> {
+    id: &'a str,
+    detail: String,
+    filter_text: String,
+    kind: lsp_types::CompletionItemKind,
+}
+
+impl Candidate<'_> {
+    /// 文字列の中では中身を置き換え, 外では引用符ごと挿入する
+    fn into_item(
+        self,
+        doc: &crate::document::Document,
+        offset: usize,
+        string: Option<cmn_rs::json_span::Span>,
+    ) -> lsp_types::CompletionItem {
+        let (span, new_text) = match string {
+            Some(span) => (span, self.id.to_string()),
+            None => (
+                cmn_rs::json_span::Span {
+                    start: offset,
+                    end: offset,
+                },
+                format!("\"{}\"", self.id),
+            ),
+        };
+        lsp_types::CompletionItem {
+            label: self.id.to_string(),
+            kind: Some(self.kind),
+            detail: Some(self.detail),
+            filter_text: Some(self.filter_text),
+            text_edit: Some(lsp_types::CompletionTextEdit::Edit(lsp_types::TextEdit {
+                range: doc.range(span),
+                new_text,
+            })),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn catalog() -> crate::catalog::Catalog {
+        crate::catalog::Catalog::with_library_videos(
+            [("ZeFvqdvutb4".to_string(), "歌枠".to_string())].into(),
+        )
+    }
+
+    /// `|`をカーソルとして補完候補のラベルと置き換える文字列を返す
+    fn complete(text_with_cursor: &str) -> Vec<(String, String)> {
+        let offset = text_with_cursor.find('|').unwrap();
+        let doc = crate::document::Document::new(text_with_cursor.replacen('|', "", 1));
+        super::completions(&doc, offset, &catalog())
+            .into_iter()
+            .map(|item| {
+                let Some(lsp_types::CompletionTextEdit::Edit(edit)) = item.text_edit
+                else {
+                    panic!("text edit is missing");
+                };
+                (item.label, edit.new_text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_complete_liver_ids() {
+        let items = complete(r#"[{"clips": [{"liverIds": ["|"]}]}]"#);
+        assert_eq!(
+            items,
+            vec![
+                ("riku-tazumi".to_string(), "riku-tazumi".to_string()),
+                ("yudorikku".to_string(), "yudorikku".to_string()),
+                ("yugamin".to_string(), "yugamin".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_complete_tags_outside_string() {
+        let items = complete("[{\"videoTags\": [|");
+        assert_eq!(items.len(), 4);
+        assert!(items.contains(&("karaoke".to_string(), "\"karaoke\"".to_string())));
+
+        let items = complete(r#"[{"clips": [{"clipTags": ["3|"]}]}]"#);
+        assert_eq!(items.len(), 4);
+    }
+
+    #[test]
+    fn test_complete_video_ids() {
+        let items = complete(r#"[{"videoId": "|"}]"#);
+        assert_eq!(
+            items,
+            vec![("ZeFvqdvutb4".to_string(), "ZeFvqdvutb4".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_no_completion_for_other_keys() {
+        assert!(complete(r#"[{"clips": [{"songTitle": "|"}]}]"#).is_empty());
+        assert!(complete(r#"[{"|"#).is_empty());
+    }
+}
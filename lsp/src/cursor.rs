@@ -0,0 +1,195 @@
+/// 補完するカーソル位置の文脈
+///
+/// 入力途中のJSONはパースできないことが多いため, 先頭からカーソルまでを字句単位で走査して求める
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CursorContext {
+    /// カーソルの位置が値になるメンバーのキー. 配列の要素のときは配列を値に持つメンバーのキー
+    ///
+    /// オブジェクトのキーを入力しているときは`None`
+    pub(crate) key: Option<String>,
+    /// カーソルが文字列の中にあるとき, その文字列の引用符を除いた範囲
+    ///
+    /// 閉じ引用符が無いときはカーソルまで
+    pub(crate) string: Option<cmn_rs::json_span::Span>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    is_object: bool,
+    /// このコンテナを値に持つメンバーのキー
+    key: Option<String>,
+    /// オブジェクトで, 次の文字列がキーになるか
+    expects_key: bool,
+    /// オブジェクトで, 直前に読んだキー
+    last_key: Option<String>,
+}
+
+impl Frame {
+    /// このコンテナの中の値が属するキー
+    fn value_key(&self) -> Option<String> {
+        if self.is_object {
+            self.last_key.clone()
+        } else {
+            self.key.clone()
+        }
+    }
+}
+
+/// `offset`の位置の文脈. コンテナの外のときは`None`
+pub(crate) fn context_at(text: &str, offset: usize) -> Option<CursorContext> {
+    let bytes = text.as_bytes();
+    let offset = offset.min(bytes.len());
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut pos = 0;
+
+    while pos < offset {
+        match bytes[pos] {
+            b'"' => {
+                let content_start = pos + 1;
+                let (content_end, closed) = scan_string(bytes, content_start);
+                // 閉じ引用符の手前までにカーソルがあれば文字列の中
+                if offset <= content_end {
+                    let frame = stack.last()?;
+                    let key = if frame.is_object && frame.expects_key {
+                        None
+                    } else {
+                        frame.value_key()
+                    };
+                    return Some(CursorContext {
+                        key,
+                        string: Some(cmn_rs::json_span::Span {
+                            start: content_start,
+                            end: content_end,
+                        }),
+                    });
+                }
+                if let Some(frame) = stack.last_mut()
+                    && frame.is_object
+                    && frame.expects_key
+                {
+                    frame.last_key = Some(unescape(&text[content_start..content_end]));
+                    frame.expects_key = false;
+                }
+                pos = if closed { content_end + 1 } else { content_end };
+                continue;
+            }
+            open @ (b'{' | b'[') => {
+                let key = stack.last().and_then(|frame| {
+                    // 配列の中の配列は要素の型が変わるため, キーを引き継がない
+                    frame.is_object.then(|| frame.value_key()).flatten()
+                });
+                stack.push(Frame {
+                    is_object: open == b'{',
+                    key,
+                    expects_key: open == b'{',
+                    last_key: None,
+                });
+            }
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => {
+                if let Some(frame) = stack.last_mut()
+                    && frame.is_object
+                {
+                    frame.expects_key = true;
+                    frame.last_key = None;
+                }
+            }
+            b':' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expects_key = false;
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+
+    let frame = stack.last()?;
+    Some(CursorContext {
+        key: if frame.is_object && frame.expects_key {
+            None
+        } else {
+            frame.value_key()
+        },
+        string: None,
+    })
+}
+
+/// 文字列の中身の終わり(閉じ引用符の位置)と, 閉じているか
+///
+/// 改行で打ち切る. 入力途中の文字列が以降の行を飲み込まないようにするため
+fn scan_string(bytes: &[u8], start: usize) -> (usize, bool) {
+    let mut pos = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'"' => return (pos, true),
+            b'\\' => pos += 2,
+            b'\n' => return (pos, false),
+            _ => pos += 1,
+        }
+    }
+    (bytes.len(), false)
+}
+
+/// キーの比較に使う. 不正なエスケープはそのまま残す
+fn unescape(raw: &str) -> String {
+    serde_json::from_str::<String>(&format!("\"{raw}\""))
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `|`をカーソルとして文脈を求める
+    fn context(text_with_cursor: &str) -> Option<CursorContext> {
+        let offset = text_with_cursor.find('|').unwrap();
+        let text = text_with_cursor.replacen('|', "", 1);
+        context_at(&text, offset)
+    }
+
+    #[test]
+    fn test_context_in_array_string() {
+        let ctx =
+            context(r#"[{"clips": [{"liverIds": ["riku-tazumi", "yu|"]}]}]"#).unwrap();
+        assert_eq!(ctx.key.as_deref(), Some("liverIds"));
+
+        let text = r#"[{"clips": [{"liverIds": ["riku-tazumi", "yu"]}]}]"#;
+        let start = text.find("yu\"").unwrap();
+        assert_eq!(
+            ctx.string,
+            Some(cmn_rs::json_span::Span {
+                start,
+                end: start + 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_context_in_incomplete_document() {
+        let ctx = context("[\n  {\n    \"videoTags\": [|\n").unwrap();
+        assert_eq!(ctx.key.as_deref(), Some("videoTags"));
+        assert_eq!(ctx.string, None);
+
+        let ctx = context("[\n  {\n    \"videoId\": \"Ze|\n    \"clips\": []").unwrap();
+        assert_eq!(ctx.key.as_deref(), Some("videoId"));
+        assert!(ctx.string.is_some());
+    }
+
+    #[test]
+    fn test_context_after_closed_members() {
+        let ctx = context(r#"[{"videoTags": ["karaoke"], "clips": [{"songTitle": "a[\"]", "clipTags": [|]}]}]"#)
+            .unwrap();
+        assert_eq!(ctx.key.as_deref(), Some("clipTags"));
+
+        let ctx = context(r#"[{"videoTags": ["karaoke"], "|"#).unwrap();
+        assert_eq!(ctx.key, None);
+    }
+
+    #[test]
+    fn test_context_outside_containers() {
+        assert_eq!(context("|[]"), None);
+    }
+}
@@ -0,0 +1,553 @@
+const SOURCE: &str = "cliplayer";
+
+/// メタデータのJSONファイル. ファイル名で見分ける
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MetadataFile {
+    Livers,
+    Groups,
+    OfficialChannels,
+    Tags,
+    Songs,
+}
+
+impl MetadataFile {
+    pub(crate) fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "livers.json" => Some(Self::Livers),
+            "groups.json" => Some(Self::Groups),
+            "official_channels.json" => Some(Self::OfficialChannels),
+            "tags.json" => Some(Self::Tags),
+            "songs.json" => Some(Self::Songs),
+            _ => None,
+        }
+    }
+
+    /// `artistctl` / `tagctl` / `songctl`と同じ型で読み込む. 読み込み時の検証も含む
+    fn deserialize(self, text: &str) -> Result<(), serde_json::Error> {
+        match self {
+            Self::Livers => {
+                serde_json::from_str::<artistctl::model::Livers>(text).map(drop)
+            }
+            Self::Groups => {
+                serde_json::from_str::<artistctl::model::Groups>(text).map(drop)
+            }
+            Self::OfficialChannels => {
+                serde_json::from_str::<artistctl::model::OfficialChannels>(text)
+                    .map(drop)
+            }
+            Self::Tags => {
+                serde_json::from_str::<tagctl::model::VideoTags>(text).map(drop)
+            }
+            Self::Songs => {
+                serde_json::from_str::<songctl::model::Songs>(text).map(drop)
+            }
+        }
+    }
+}
+
+/// ファイルの診断
+///
+/// 値ごとに分かる誤りはその値の位置に出す. それ以外は`musictl`の検証のエラーを動画, クリップの先頭に出す.
+/// メタデータのファイルは読み込みのエラーを原因の値の位置に出す
+///
+/// - `metadata`: メタデータのファイルのとき, その種類
+/// - `other_inputs`: 同じディレクトリの他の入力ファイルの動画. (動画id, ファイル名)
+pub(crate) fn diagnostics(
+    doc: &crate::document::Document,
+    metadata: Option<MetadataFile>,
+    catalog: &crate::catalog::Catalog,
+    other_inputs: &std::collections::BTreeMap<String, String>,
+) -> Vec<lsp_types::Diagnostic> {
+    let mut out = Diagnostics {
+        doc,
+        items: Vec::new(),
+    };
+
+    if let Err(e) = &doc.root {
+        let span = cmn_rs::json_span::Span {
+            start: e.offset,
+            end: e.offset,
+        };
+        out.error(span, format!("invalid JSON: {}", e.message));
+        return out.items;
+    }
+    if let Some(metadata) = metadata {
+        check_metadata(&mut out, metadata);
+        return out.items;
+    }
+    let Some(videos) = doc.videos() else {
+        return out.items;
+    };
+
+    // (動画id, 最初に出てきた位置)
+    let mut seen_video_ids = std::collections::HashMap::new();
+    for video in videos {
+        let before = out.items.len();
+        check_video(&mut out, video, catalog, other_inputs, &mut seen_video_ids);
+
+        if out.items.len() == before
+            && let Err(e) =
+                musictl::validate::validate_anonymous_video(&video.to_value())
+        {
+            out.error(head_span(video, "videoId"), e);
+        }
+    }
+    out.items
+}
+
+/// 入力ファイルの動画id. 他の入力ファイルとの重複の検出に使う
+///
+/// パースできないファイル, 入力ファイルでないJSONは空
+pub(crate) fn input_video_ids(text: &str) -> Vec<String> {
+    let Ok(root) = cmn_rs::json_span::parse(text) else {
+        return Vec::new();
+    };
+    root.as_array()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|video| video.get("videoId")?.as_str().map(str::to_string))
+        .collect()
+}
+
+struct Diagnostics<'a> {
+    doc: &'a crate::document::Document,
+    items: Vec<lsp_types::Diagnostic>,
+}
+
+impl Diagnostics<'_> {
+    fn push(
+        &mut self,
+        span: cmn_rs::json_span::Span,
+        severity: lsp_types::DiagnosticSeverity,
+        message: String,
+    ) {
+        self.items.push(lsp_types::Diagnostic {
+            range: self.doc.range(span),
+            severity: Some(severity),
+            source: Some(SOURCE.to_string()),
+            message,
+            ..Default::default()
+        });
+    }
+
+    fn error(&mut self, span: cmn_rs::json_span::Span, message: String) {
+        self.push(span, lsp_types::DiagnosticSeverity::ERROR, message);
+    }
+}
+
+fn check_metadata(out: &mut Diagnostics, metadata: MetadataFile) {
+    let Err(e) = metadata.deserialize(&out.doc.text) else {
+        return;
+    };
+    let source = cmn_rs::source_diagnostic::SourceFile::new("", out.doc.text.clone());
+    let span = source
+        .serde_error_span(0, &e)
+        .unwrap_or(cmn_rs::json_span::Span { start: 0, end: 1 });
+    out.error(span, cmn_rs::source_diagnostic::serde_error_message(&e));
+}
+
+fn check_video(
+    out: &mut Diagnostics,
+    video: &cmn_rs::json_span::JsonNode,
+    catalog: &crate::catalog::Catalog,
+    other_inputs: &std::collections::BTreeMap<String, String>,
+    seen_video_ids: &mut std::collections::HashMap<String, usize>,
+) {
+    if let Some(node) = video.get("videoId")
+        && let Some(id) = node.as_str()
+    {
+        if let Err(e) = musictl::validate::validate_video_id(id) {
+            out.error(node.span, format!("{e}: `{id}`"));
+        } else if let Some(&first) = seen_video_ids.get(id) {
+            out.error(
+                node.span,
+                format!(
+                    "duplicate video ID `{id}`: already used at line {}",
+                    out.doc.line_number(first)
+                ),
+            );
+        } else {
+            seen_video_ids.insert(id.to_string(), node.span.start);
+            if let Some(file) = other_inputs.get(id) {
+                out.error(
+                    node.span,
+                    format!("duplicate video ID `{id}`: also in input file `{file}`"),
+                );
+            }
+            if catalog.library_videos.contains_key(id) {
+                out.push(
+                    node.span,
+                    lsp_types::DiagnosticSeverity::WARNING,
+                    format!(
+                        "video ID `{id}` is already in the music library; \
+                        `musictl add` rejects it without `--allow-overwrite-existing-video`"
+                    ),
+                );
+            }
+        }
+    }
+
+    check_ids(out, video.get("videoTags"), |id| {
+        (!catalog.tags.contains_key(id)).then(|| format!("unknown video tag `{id}`"))
+    });
+
+    let Some(clips) = video.get("clips").and_then(|clips| clips.as_array()) else {
+        return;
+    };
+    for clip in clips {
+        let before = out.items.len();
+        check_clip(out, clip, catalog);
+
+        if out.items.len() == before
+            && let Err(e) = musictl::validate::validate_anonymous_clip(&clip.to_value())
+        {
+            out.error(head_span(clip, "songTitle"), e);
+        }
+    }
+    check_overlaps(out, clips);
+}
+
+fn check_clip(
+    out: &mut Diagnostics,
+    clip: &cmn_rs::json_span::JsonNode,
+    catalog: &crate::catalog::Catalog,
+) {
+    let start = clip.get("startTime");
+    let end = clip.get("endTime");
+    let mut times_are_valid = true;
+    for node in [start, end].into_iter().flatten() {
+        if let Some(s) = node.as_str()
+            && let Err(e) = musictl::validate::parse_duration_secs(s)
+        {
+            out.error(node.span, format!("{e}: `{s}`"));
+            times_are_valid = false;
+        }
+    }
+    if times_are_valid
+        && let (Some(start), Some(end)) = (start, end)
+        && let (Some(start_str), Some(end_str)) = (start.as_str(), end.as_str())
+        && let Err(e) = musictl::validate::validate_clip_time_range(start_str, end_str)
+    {
+        out.error(
+            cmn_rs::json_span::Span {
+                start: start.span.start.min(end.span.start),
+                end: start.span.end.max(end.span.end),
+            },
+            e,
+        );
+    }
+
+    check_ids(out, clip.get("liverIds"), |id| {
+        (!catalog.livers.contains_key(id)).then(|| format!("unknown liver ID `{id}`"))
+    });
+    check_ids(out, clip.get("clipTags"), |id| {
+        (!catalog.tags.contains_key(id)).then(|| format!("unknown clip tag `{id}`"))
+    });
+
+    if let Some(node) = clip.get("clippedVideoId")
+        && let Some(id) = node.as_str()
+        && let Err(e) = musictl::validate::validate_video_id(id)
+    {
+        out.error(node.span, format!("{e}: `{id}`"));
+    }
+}
+
+/// 文字列の配列の要素を1つずつ検証する
+fn check_ids(
+    out: &mut Diagnostics,
+    node: Option<&cmn_rs::json_span::JsonNode>,
+    check: impl Fn(&str) -> Option<String>,
+) {
+    let Some(items) = node.and_then(|node| node.as_array()) else {
+        return;
+    };
+    for item in items {
+        if let Some(id) = item.as_str()
+            && let Some(message) = check(id)
+        {
+            out.error(item.span, message);
+        }
+    }
+}
+
+/// 時間が重なっているクリップ. 開始が後のクリップの`startTime`に出す
+fn check_overlaps(out: &mut Diagnostics, clips: &[cmn_rs::json_span::JsonNode]) {
+    let mut ranges = clips
+        .iter()
+        .filter_map(|clip| {
+            let start = clip.get("startTime")?;
+            let start_secs =
+                musictl::validate::parse_duration_secs(start.as_str()?).ok()?;
+            let end_secs =
+                musictl::validate::parse_duration_secs(clip.get("endTime")?.as_str()?)
+                    .ok()?;
+            let title = clip
+                .get("songTitle")
+                .and_then(|title| title.as_str())
+                .unwrap_or_default();
+            Some((start_secs, end_secs, start.span, title))
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|&(start_secs, ..)| start_secs);
+
+    for pair in ranges.windows(2) {
+        let (_, prev_end, _, prev_title) = pair[0];
+        let (next_start, _, next_span, _) = pair[1];
+        if prev_end > next_start {
+            out.error(next_span, format!("clip overlaps with `{prev_title}`"));
+        }
+    }
+}
+
+/// 動画, クリップ全体のエラーを出す位置. `key`の値, 無ければ先頭の1文字
+fn head_span(node: &cmn_rs::json_span::JsonNode, key: &str) -> cmn_rs::json_span::Span {
+    node.get(key).map_or(
+        cmn_rs::json_span::Span {
+            start: node.span.start,
+            end: node.span.start + 1,
+        },
+        |value| value.span,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    fn catalog() -> crate::catalog::Catalog {
+        crate::catalog::Catalog::with_library_videos(
+            [("AAAAAAAAAAA".to_string(), "登録済み".to_string())].into(),
+        )
+    }
+
+    /// (メッセージ, 範囲の文字列)
+    fn diagnose(
+        text: &str,
+        other_inputs: &[(&str, &str)],
+    ) -> Vec<(lsp_types::DiagnosticSeverity, String, String)> {
+        let doc = crate::document::Document::new(text.to_string());
+        let other_inputs = other_inputs
+            .iter()
+            .map(|(id, file)| (id.to_string(), file.to_string()))
+            .collect();
+        super::diagnostics(&doc, None, &catalog(), &other_inputs)
+            .into_iter()
+            .map(|d| {
+                let span = cmn_rs::json_span::Span {
+                    start: doc.offset(d.range.start),
+                    end: doc.offset(d.range.end),
+                };
+                (d.severity.unwrap(), d.message, doc.slice(span).to_string())
+            })
+            .collect()
+    }
+
+    fn video(video_id: &str, clips: &str) -> String {
+        format!(
+            r#"{{"videoId": "{video_id}", "videoTags": ["karaoke"], "clips": [{clips}]}}"#
+        )
+    }
+
+    fn clip(title: &str, start: &str, end: &str, liver: &str) -> String {
+        format!(
+            r#"{{"songTitle": "{title}", "liverIds": ["{liver}"], "startTime": "{start}", "endTime": "{end}"}}"#
+        )
+    }
+
+    #[test]
+    fn test_valid_input_has_no_diagnostics() {
+        let text = format!(
+            "[{}]",
+            video(
+                "ZeFvqdvutb4",
+                &[
+                    clip("a", "PT10S", "PT1M", "riku-tazumi"),
+                    clip("b", "PT1M", "PT2M", "yugamin")
+                ]
+                .join(",")
+            )
+        );
+        assert_eq!(diagnose(&text, &[]), vec![]);
+    }
+
+    #[test]
+    fn test_invalid_times() {
+        let text = format!(
+            "[{}]",
+            video(
+                "ZeFvqdvutb4",
+                &[
+                    clip("a", "PT1M", "PT10S", "riku-tazumi"),
+                    clip("b", "1:30", "PT2M", "yugamin")
+                ]
+                .join(",")
+            )
+        );
+        let diagnostics = diagnose(&text, &[]);
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert!(diagnostics[0].1.starts_with("invalid clip time range"));
+        assert_eq!(diagnostics[0].2, r#""PT1M", "endTime": "PT10S""#);
+        assert_eq!(
+            (diagnostics[1].1.as_str(), diagnostics[1].2.as_str()),
+            ("Duration must start with 'PT': `1:30`", "\"1:30\"")
+        );
+    }
+
+    #[test]
+    fn test_unknown_ids() {
+        let text = format!(
+            r#"[{{"videoId": "ZeFvqdvutb4", "videoTags": ["karaoke", "nope"], "clips": [{}]}}]"#,
+            r#"{"songTitle": "a", "liverIds": ["ghost"], "clipTags": ["3d", "nope2"], "startTime": "PT1S", "endTime": "PT2S"}"#
+        );
+        let messages = diagnose(&text, &[])
+            .into_iter()
+            .map(|(_, message, range)| (message, range))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "unknown video tag `nope`".to_string(),
+                    "\"nope\"".to_string()
+                ),
+                (
+                    "unknown liver ID `ghost`".to_string(),
+                    "\"ghost\"".to_string()
+                ),
+                (
+                    "unknown clip tag `nope2`".to_string(),
+                    "\"nope2\"".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_video_ids() {
+        let clips = clip("a", "PT1S", "PT2S", "yugamin");
+        let text = format!(
+            "[\n{},\n{},\n{},\n{}\n]",
+            video("ZeFvqdvutb4", &clips),
+            video("ZeFvqdvutb4", &clips),
+            video("BBBBBBBBBBB", &clips),
+            video("AAAAAAAAAAA", &clips),
+        );
+        let diagnostics = diagnose(&text, &[("BBBBBBBBBBB", "other.json")]);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    "duplicate video ID `ZeFvqdvutb4`: already used at line 2"
+                        .to_string(),
+                    "\"ZeFvqdvutb4\"".to_string()
+                ),
+                (
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    "duplicate video ID `BBBBBBBBBBB`: also in input file `other.json`"
+                        .to_string(),
+                    "\"BBBBBBBBBBB\"".to_string()
+                ),
+                (
+                    lsp_types::DiagnosticSeverity::WARNING,
+                    "video ID `AAAAAAAAAAA` is already in the music library; \
+                    `musictl add` rejects it without `--allow-overwrite-existing-video`"
+                        .to_string(),
+                    "\"AAAAAAAAAAA\"".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlap_and_musictl_fallback() {
+        let text = format!(
+            "[{},{}]",
+            video(
+                "ZeFvqdvutb4",
+                &[
+                    clip("a", "PT10S", "PT1M", "riku-tazumi"),
+                    clip("b", "PT30S", "PT2M", "yugamin")
+                ]
+                .join(",")
+            ),
+            video("BBBBBBBBBBB", ""),
+        );
+        let diagnostics = diagnose(&text, &[]);
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert_eq!(
+            (diagnostics[0].1.as_str(), diagnostics[0].2.as_str()),
+            ("clip overlaps with `a`", "\"PT30S\"")
+        );
+        // クリップが無い動画は`musictl`の検証のエラーを動画idに出す
+        assert!(diagnostics[1].1.contains("has no clips"), "{diagnostics:?}");
+        assert_eq!(diagnostics[1].2, "\"BBBBBBBBBBB\"");
+    }
+
+    #[test]
+    fn test_syntax_error_and_other_json() {
+        let diagnostics = diagnose("[{\"videoId\": }]", &[]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].1, "invalid JSON: expected value");
+
+        assert!(diagnose(r#"{"riku-tazumi": {"ja": "田角陸"}}"#, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_metadata_errors_point_at_the_value() {
+        let diagnose = |metadata, text: &str| {
+            let doc = crate::document::Document::new(text.to_string());
+            super::diagnostics(&doc, Some(metadata), &catalog(), &Default::default())
+                .into_iter()
+                .map(|d| {
+                    let span = cmn_rs::json_span::Span {
+                        start: doc.offset(d.range.start),
+                        end: doc.offset(d.range.end),
+                    };
+                    (d.message, doc.slice(span).to_string())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let tags = r#"{"karaoke": {"ja": "歌枠", "en": "Karaoke", "color": "red"}}"#;
+        let diagnostics = diagnose(super::MetadataFile::Tags, tags);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert!(
+            diagnostics[0].0.contains("unknown field `color`"),
+            "{diagnostics:?}"
+        );
+        assert_eq!(diagnostics[0].1, "\"color\"");
+
+        let groups = r#"{"g": {"ja": "グループ", "en": "Group", "kind": "unit", "members": ["unknown-liver"]}}"#;
+        let diagnostics = diagnose(super::MetadataFile::Groups, groups);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        // 配列の要素のエラーは`serde_json`が配列を読み終えた位置を返すため, 配列を指す
+        assert_eq!(diagnostics[0].1, "[\"unknown-liver\"]");
+
+        // ルートが配列でも入力ファイルとしては扱わない
+        assert_eq!(diagnose(super::MetadataFile::Songs, "[]").len(), 1);
+    }
+
+    #[test]
+    fn test_metadata_file_from_path() {
+        assert_eq!(
+            super::MetadataFile::from_path(std::path::Path::new(
+                "metadata/artist/data/livers.json"
+            )),
+            Some(super::MetadataFile::Livers)
+        );
+        assert_eq!(
+            super::MetadataFile::from_path(std::path::Path::new(
+                "music/data/input/livers.json.bak"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_input_video_ids() {
+        let text = format!("[{}]", video("ZeFvqdvutb4", ""));
+        assert_eq!(super::input_video_ids(&text), vec!["ZeFvqdvutb4"]);
+        assert!(super::input_video_ids("[").is_empty());
+        assert!(super::input_video_ids("{}").is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+/// エディタで開いているJSONファイル
+///
+/// 位置の変換は全てバイトオフセットを介す. LSPの列はUTF-16のコード単位
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    pub(crate) text: String,
+    line_index: cmn_rs::json_span::LineIndex,
+    pub(crate) root:
+        Result<cmn_rs::json_span::JsonNode, cmn_rs::json_span::JsonSyntaxError>,
+}
+
+/// カーソル位置の文字列と, それを値に持つメンバーのキー
+///
+/// 配列の要素のときは配列を値に持つメンバーのキー
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StringAt<'a> {
+    pub(crate) key: &'a str,
+    pub(crate) node: &'a cmn_rs::json_span::JsonNode,
+}
+
+impl Document {
+    pub(crate) fn new(text: String) -> Self {
+        let line_index = cmn_rs::json_span::LineIndex::new(&text);
+        let root = cmn_rs::json_span::parse(&text);
+        Self {
+            text,
+            line_index,
+            root,
+        }
+    }
+
+    /// 入力ファイルの動画の一覧. ルートが配列でないときは`None`
+    ///
+    /// `musictl`の入力ファイル以外のJSONは解析しない
+    pub(crate) fn videos(&self) -> Option<&[cmn_rs::json_span::JsonNode]> {
+        self.root.as_ref().ok()?.as_array()
+    }
+
+    pub(crate) fn slice(&self, span: cmn_rs::json_span::Span) -> &str {
+        &self.text[span.start..span.end]
+    }
+
+    pub(crate) fn position(&self, offset: usize) -> lsp_types::Position {
+        let lc = self.line_index.line_column_utf16(&self.text, offset);
+        lsp_types::Position {
+            line: saturating_u32(lc.line),
+            character: saturating_u32(lc.column),
+        }
+    }
+
+    pub(crate) fn range(&self, span: cmn_rs::json_span::Span) -> lsp_types::Range {
+        lsp_types::Range {
+            start: self.position(span.start),
+            end: self.position(span.end),
+        }
+    }
+
+    pub(crate) fn offset(&self, position: lsp_types::Position) -> usize {
+        self.line_index.offset_utf16(
+            &self.text,
+            cmn_rs::json_span::LineColumn {
+                line: position.line as usize,
+                column: position.character as usize,
+            },
+        )
+    }
+
+    /// 1始まりの行番号. メッセージで別の場所を示すときに使う
+    pub(crate) fn line_number(&self, offset: usize) -> usize {
+        self.line_index.line_column(&self.text, offset).line + 1
+    }
+
+    /// `offset`にある文字列の値
+    pub(crate) fn string_at(&self, offset: usize) -> Option<StringAt<'_>> {
+        let root = self.root.as_ref().ok()?;
+        find_string(root, "", offset)
+    }
+}
+
+fn find_string<'a>(
+    node: &'a cmn_rs::json_span::JsonNode,
+    key: &'a str,
+    offset: usize,
+) -> Option<StringAt<'a>> {
+    use cmn_rs::json_span::JsonValue;
+
+    if !node.span.contains(offset) {
+        return None;
+    }
+    match &node.value {
+        JsonValue::String(_) => Some(StringAt { key, node }),
+        JsonValue::Array(items) => {
+            items.iter().find_map(|item| find_string(item, key, offset))
+        }
+        JsonValue::Object(members) => members
+            .iter()
+            .find_map(|member| find_string(&member.value, &member.key, offset)),
+        _ => None,
+    }
+}
+
+fn saturating_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_string_at_reports_owner_key() {
+        let text =
+            r#"[{"videoId": "ZeFvqdvutb4", "clips": [{"liverIds": ["yugamin"]}]}]"#;
+        let doc = super::Document::new(text.to_string());
+
+        let at = doc.string_at(text.find("yugamin").unwrap()).unwrap();
+        assert_eq!((at.key, at.node.as_str()), ("liverIds", Some("yugamin")));
+
+        let at = doc.string_at(text.find("ZeFv").unwrap()).unwrap();
+        assert_eq!(at.key, "videoId");
+
+        assert!(doc.string_at(text.find("clips").unwrap() + 8).is_none());
+    }
+
+    #[test]
+    fn test_position_roundtrip() {
+        let text = "[\n  {\"songTitle\": \"🎤歌\"}\n]";
+        let doc = super::Document::new(text.to_string());
+        let offset = text.find('歌').unwrap();
+
+        let position = doc.position(offset);
+        assert_eq!(
+            position,
+            lsp_types::Position {
+                line: 1,
+                character: 19
+            }
+        );
+        assert_eq!(doc.offset(position), offset);
+        assert_eq!(doc.line_number(offset), 2);
+    }
+}
@@ -0,0 +1,108 @@
+/// カーソル位置の文字列の説明
+///
+/// - ライバーid: 名前と色
+/// - タグid: 名前と親タグ
+/// - 動画id: 楽曲情報に登録済みのときはタイトル
+/// - `startTime`, `endTime`: `h:mm:ss`表記
+pub(crate) fn hover(
+    doc: &crate::document::Document,
+    offset: usize,
+    catalog: &crate::catalog::Catalog,
+) -> Option<lsp_types::Hover> {
+    let at = doc.string_at(offset)?;
+    let value = at.node.as_str()?;
+
+    let markdown = match at.key {
+        "liverIds" => {
+            let liver = catalog.livers.get(value)?;
+            let mut lines = vec![
+                format!("**{}** ({})", liver.ja, liver.en),
+                format!("color: `#{}`", liver.color),
+            ];
+            if liver.is_graduated {
+                lines.push("卒業済み".to_string());
+            }
+            lines.join("\n\n")
+        }
+        "videoTags" | "clipTags" => {
+            let tag = catalog.tags.get(value)?;
+            let mut lines = vec![format!("**{}** ({})", tag.ja, tag.en)];
+            if let Some(parent) = &tag.parent {
+                lines.push(format!("parent: `{parent}`"));
+            }
+            lines.join("\n\n")
+        }
+        "videoId" | "clippedVideoId" => {
+            let title = catalog.library_videos.get(value)?;
+            format!("**{title}**\n\nhttps://www.youtube.com/watch?v={value}")
+        }
+        "startTime" | "endTime" => {
+            let secs = musictl::validate::parse_duration_secs(value).ok()?;
+            format!(
+                "`{}:{:02}:{:02}` ({secs}s)",
+                secs / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            )
+        }
+        _ => return None,
+    };
+
+    Some(lsp_types::Hover {
+        contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: markdown,
+        }),
+        range: Some(doc.range(at.node.span)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    fn hover_text(text: &str, needle: &str) -> Option<String> {
+        let catalog = crate::catalog::Catalog::with_library_videos(
+            [("ZeFvqdvutb4".to_string(), "歌枠".to_string())].into(),
+        );
+        let doc = crate::document::Document::new(text.to_string());
+        let hover = super::hover(&doc, text.find(needle).unwrap(), &catalog)?;
+        let lsp_types::HoverContents::Markup(markup) = hover.contents else {
+            panic!("hover must be markdown");
+        };
+        Some(markup.value)
+    }
+
+    const TEXT: &str = r#"[{
+        "videoId": "ZeFvqdvutb4",
+        "videoTags": ["3d-debut"],
+        "clips": [{
+            "songTitle": "曲",
+            "liverIds": ["yudorikku", "unknown"],
+            "startTime": "PT1H2M3S",
+            "endTime": "PT1H5M"
+        }]
+    }]"#;
+
+    #[test]
+    fn test_hover_liver() {
+        assert_eq!(
+            hover_text(TEXT, "yudorikku").unwrap(),
+            "**ユードリック** (Yudorikku)\n\ncolor: `#333333`\n\n卒業済み"
+        );
+        assert_eq!(hover_text(TEXT, "unknown"), None);
+    }
+
+    #[test]
+    fn test_hover_tag_video_and_time() {
+        assert_eq!(
+            hover_text(TEXT, "3d-debut").unwrap(),
+            "**3Dお披露目** (3D Debut)\n\nparent: `3d`"
+        );
+        assert!(
+            hover_text(TEXT, "ZeFvqdvutb4")
+                .unwrap()
+                .starts_with("**歌枠**")
+        );
+        assert_eq!(hover_text(TEXT, "PT1H2M3S").unwrap(), "`1:02:03` (3723s)");
+        assert_eq!(hover_text(TEXT, "曲"), None);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod catalog;
+pub mod cli;
+pub mod server;
+
+mod code_action;
+mod completion;
+mod cursor;
+mod diagnostics;
+mod document;
+mod hover;
@@ -0,0 +1,18 @@
+fn main() {
+    use clap::Parser;
+
+    let cli = cliplayer_lsp::cli::Cli::parse();
+    // 標準出力はLSPの通信に使うため, ログはファイルにのみ出力する
+    let _tracing_guard = cmn_rs::tracing::apply_tracing_settings(
+        "cliplayer-lsp",
+        None,
+        cli.file_level(),
+        true,
+    );
+    tracing::debug!("Command line arguments: {:?}", cli);
+
+    if let Err(error) = cliplayer_lsp::server::run_stdio(&cli) {
+        tracing::error!("Server failed: {error}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,339 @@
+/// 標準入出力でLSPサーバーを起動し, `exit`まで処理する
+///
+/// # Errors
+/// - クライアントとの通信に失敗したとき
+pub fn run_stdio(cli: &crate::cli::Cli) -> anyhow::Result<()> {
+    let catalog =
+        crate::catalog::Catalog::load(std::path::Path::new(&cli.music_root_dir));
+    tracing::info!(
+        "Loaded {} livers, {} tags and {} library videos",
+        catalog.livers.len(),
+        catalog.tags.len(),
+        catalog.library_videos.len()
+    );
+
+    let (connection, io_threads) = lsp_server::Connection::stdio();
+    run(&connection, catalog)?;
+    // 送信側を閉じないと書き込みのスレッドが終わらない
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// 初期化から`shutdown`までのメッセージを処理する
+///
+/// # Errors
+/// - クライアントとの通信に失敗したとき
+pub fn run(
+    connection: &lsp_server::Connection,
+    catalog: crate::catalog::Catalog,
+) -> anyhow::Result<()> {
+    connection.initialize(serde_json::to_value(server_capabilities())?)?;
+    let mut server = Server {
+        catalog,
+        documents: std::collections::HashMap::new(),
+    };
+
+    for message in &connection.receiver {
+        match message {
+            lsp_server::Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(response.into())?;
+            }
+            lsp_server::Message::Notification(notification) => {
+                for outgoing in server.handle_notification(notification) {
+                    connection.sender.send(outgoing.into())?;
+                }
+            }
+            lsp_server::Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn server_capabilities() -> lsp_types::ServerCapabilities {
+    lsp_types::ServerCapabilities {
+        text_document_sync: Some(lsp_types::TextDocumentSyncCapability::Kind(
+            lsp_types::TextDocumentSyncKind::FULL,
+        )),
+        completion_provider: Some(lsp_types::CompletionOptions {
+            trigger_characters: Some(vec!["\"".to_string()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
+        code_action_provider: Some(lsp_types::CodeActionProviderCapability::Simple(
+            true,
+        )),
+        ..Default::default()
+    }
+}
+
+struct Server {
+    catalog: crate::catalog::Catalog,
+    documents: std::collections::HashMap<lsp_types::Uri, crate::document::Document>,
+}
+
+impl Server {
+    fn handle_request(&self, request: lsp_server::Request) -> lsp_server::Response {
+        use lsp_types::request::Request;
+
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            lsp_types::request::Completion::METHOD => extract::<
+                lsp_types::request::Completion,
+            >(request)
+            .map(|params| {
+                let position = params.text_document_position;
+                self.documents
+                    .get(&position.text_document.uri)
+                    .map(|doc| {
+                        let offset = doc.offset(position.position);
+                        lsp_types::CompletionResponse::Array(
+                            crate::completion::completions(doc, offset, &self.catalog),
+                        )
+                    })
+                    .map_or(serde_json::Value::Null, to_json)
+            }),
+            lsp_types::request::HoverRequest::METHOD => {
+                extract::<lsp_types::request::HoverRequest>(request).map(|params| {
+                    let position = params.text_document_position_params;
+                    self.documents
+                        .get(&position.text_document.uri)
+                        .and_then(|doc| {
+                            let offset = doc.offset(position.position);
+                            crate::hover::hover(doc, offset, &self.catalog)
+                        })
+                        .map_or(serde_json::Value::Null, to_json)
+                })
+            }
+            lsp_types::request::CodeActionRequest::METHOD => extract::<
+                lsp_types::request::CodeActionRequest,
+            >(request)
+            .map(|params| {
+                let uri = params.text_document.uri;
+                self.documents
+                    .get(&uri)
+                    .map(|doc| {
+                        let range = cmn_rs::json_span::Span {
+                            start: doc.offset(params.range.start),
+                            end: doc.offset(params.range.end),
+                        };
+                        crate::code_action::code_actions(doc, &uri, range)
+                    })
+                    .map_or(serde_json::Value::Null, to_json)
+            }),
+            method => Err(lsp_server::ResponseError {
+                code: lsp_server::ErrorCode::MethodNotFound as i32,
+                message: format!("unsupported request: {method}"),
+                data: None,
+            }),
+        };
+
+        match result {
+            Ok(result) => lsp_server::Response::new_ok(id, result),
+            Err(error) => lsp_server::Response {
+                id,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    /// 開いている文書を更新し, 診断を送る通知を返す
+    fn handle_notification(
+        &mut self,
+        notification: lsp_server::Notification,
+    ) -> Vec<lsp_server::Notification> {
+        use lsp_types::notification::Notification;
+
+        match notification.method.as_str() {
+            lsp_types::notification::DidOpenTextDocument::METHOD => {
+                let Ok(params) = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(
+                        lsp_types::notification::DidOpenTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                let document = params.text_document;
+                self.documents.insert(
+                    document.uri,
+                    crate::document::Document::new(document.text),
+                );
+                self.publish_all()
+            }
+            lsp_types::notification::DidChangeTextDocument::METHOD => {
+                let Ok(params) = notification
+                    .extract::<lsp_types::DidChangeTextDocumentParams>(
+                        lsp_types::notification::DidChangeTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                // 全文同期なので最後の変更が文書全体
+                let Some(change) = params.content_changes.into_iter().last() else {
+                    return Vec::new();
+                };
+                self.documents.insert(
+                    params.text_document.uri,
+                    crate::document::Document::new(change.text),
+                );
+                self.publish_all()
+            }
+            lsp_types::notification::DidCloseTextDocument::METHOD => {
+                let Ok(params) = notification
+                    .extract::<lsp_types::DidCloseTextDocumentParams>(
+                        lsp_types::notification::DidCloseTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                let mut notifications = self.publish_all();
+                notifications.push(publish(uri, Vec::new()));
+                notifications
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 開いている全ての文書の診断
+    ///
+    /// 別の文書の変更で他の入力ファイルとの重複が変わるため, 全て出し直す
+    fn publish_all(&self) -> Vec<lsp_server::Notification> {
+        self.documents
+            .iter()
+            .map(|(uri, doc)| {
+                let metadata = uri_to_path(uri).and_then(|path| {
+                    crate::diagnostics::MetadataFile::from_path(&path)
+                });
+                let other_inputs = self.other_inputs(uri);
+                let diagnostics = crate::diagnostics::diagnostics(
+                    doc,
+                    metadata,
+                    &self.catalog,
+                    &other_inputs,
+                );
+                publish(uri.clone(), diagnostics)
+            })
+            .collect()
+    }
+
+    /// `uri`と同じディレクトリの他の入力ファイルの動画. (動画id, ファイル名)
+    ///
+    /// 開いている文書は編集中の内容を使う
+    fn other_inputs(
+        &self,
+        uri: &lsp_types::Uri,
+    ) -> std::collections::BTreeMap<String, String> {
+        let Some(path) = uri_to_path(uri) else {
+            return std::collections::BTreeMap::new();
+        };
+        let Some(dir) = path.parent() else {
+            return std::collections::BTreeMap::new();
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return std::collections::BTreeMap::new();
+        };
+
+        let open_documents = self
+            .documents
+            .iter()
+            .filter_map(|(uri, doc)| Some((uri_to_path(uri)?, doc)))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let mut other_inputs = std::collections::BTreeMap::new();
+        let mut other_paths = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|other| {
+                other != &path && other.extension().is_some_and(|ext| ext == "json")
+            })
+            .collect::<Vec<_>>();
+        other_paths.sort();
+        for other in other_paths {
+            let text = match open_documents.get(&other) {
+                Some(doc) => doc.text.clone(),
+                None => match std::fs::read_to_string(&other) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        tracing::warn!("Failed to read `{}`: {e}", other.display());
+                        continue;
+                    }
+                },
+            };
+            let file_name = other
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            for id in crate::diagnostics::input_video_ids(&text) {
+                other_inputs.entry(id).or_insert_with(|| file_name.clone());
+            }
+        }
+        other_inputs
+    }
+}
+
+fn extract<R>(
+    request: lsp_server::Request,
+) -> Result<R::Params, lsp_server::ResponseError>
+where
+    R: lsp_types::request::Request,
+{
+    request
+        .extract::<R::Params>(R::METHOD)
+        .map(|(_, params)| params)
+        .map_err(|e| lsp_server::ResponseError {
+            code: lsp_server::ErrorCode::InvalidParams as i32,
+            message: format!("invalid params for {}: {e:?}", R::METHOD),
+            data: None,
+        })
+}
+
+fn to_json(value: impl serde::Serialize) -> serde_json::Value {
+    serde_json::to_value(value).expect("LSP types are serializable")
+}
+
+fn publish(
+    uri: lsp_types::Uri,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+) -> lsp_server::Notification {
+    use lsp_types::notification::Notification;
+
+    lsp_server::Notification::new(
+        lsp_types::notification::PublishDiagnostics::METHOD.to_string(),
+        lsp_types::PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        },
+    )
+}
+
+/// `file`スキームのURIのパス
+fn uri_to_path(uri: &lsp_types::Uri) -> Option<std::path::PathBuf> {
+    if !uri.scheme()?.as_str().eq_ignore_ascii_case("file") {
+        return None;
+    }
+    let path = uri.path().as_estr().decode().into_string().ok()?;
+    Some(std::path::PathBuf::from(path.into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_uri_to_path() {
+        let uri: lsp_types::Uri =
+            "file:///music/data/input/%E6%AD%8C.json".parse().unwrap();
+        assert_eq!(
+            super::uri_to_path(&uri),
+            Some(std::path::PathBuf::from("/music/data/input/歌.json"))
+        );
+
+        let uri: lsp_types::Uri = "untitled:Untitled-1".parse().unwrap();
+        assert_eq!(super::uri_to_path(&uri), None);
+    }
+}
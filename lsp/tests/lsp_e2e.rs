@@ -0,0 +1,230 @@
+const MONTHLY_FILE_JSON: &str = r#"[
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "title": "Test Karaoke Stream",
+    "channelId": "UC1111111111111111111111",
+    "publishedAt": "2026-01-19T13:23:27Z",
+    "syncedAt": "2026-04-22T01:57:28Z",
+    "duration": "PT1H0M0S",
+    "privacyStatus": "public",
+    "embeddable": true,
+    "videoTags": ["karaoke"],
+    "clips": [
+      {
+        "songTitle": "fuwafuwa time",
+        "liverIds": ["riku-tazumi"],
+        "startTime": "PT3M2S",
+        "endTime": "PT6M56S",
+        "uuid": "11786ebd-4b42-428b-81f8-ecf791887326"
+      }
+    ]
+  }
+]
+"#;
+
+const OTHER_INPUT_JSON: &str = r#"[
+  {
+    "videoId": "BBBBBBBBBBB",
+    "clips": [
+      { "songTitle": "a", "liverIds": ["yugamin"], "startTime": "PT1S", "endTime": "PT2S" }
+    ]
+  }
+]
+"#;
+
+const OPENED_INPUT_JSON: &str = r#"[
+  {
+    "videoId": "BBBBBBBBBBB",
+    "videoTags": ["karaoke"],
+    "clips": [
+      { "songTitle": "a", "liverIds": ["ghost"], "startTime": "PT1S", "endTime": "PT2S" }
+    ]
+  },
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "clips": [
+      { "songTitle": "b", "liverIds": [""], "startTime": "PT1S", "endTime": "PT2S" }
+    ]
+  }
+]
+"#;
+
+fn write_text_file(path: &std::path::Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(path, content).unwrap();
+}
+
+struct Client {
+    child: std::process::Child,
+    stdin: std::process::ChildStdin,
+    stdout: std::io::BufReader<std::process::ChildStdout>,
+    next_id: i32,
+}
+
+impl Client {
+    fn request(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> serde_json::Value {
+        self.next_id += 1;
+        let id = lsp_server::RequestId::from(self.next_id);
+        lsp_server::Message::Request(lsp_server::Request::new(
+            id.clone(),
+            method.to_string(),
+            params,
+        ))
+        .write(&mut self.stdin)
+        .unwrap();
+
+        loop {
+            match self.read() {
+                lsp_server::Message::Response(response) if response.id == id => {
+                    assert!(response.error.is_none(), "{response:?}");
+                    return response.result.unwrap_or_default();
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: serde_json::Value) {
+        lsp_server::Message::Notification(lsp_server::Notification::new(
+            method.to_string(),
+            params,
+        ))
+        .write(&mut self.stdin)
+        .unwrap();
+    }
+
+    fn read(&mut self) -> lsp_server::Message {
+        lsp_server::Message::read(&mut self.stdout)
+            .unwrap()
+            .expect("server closed stdout")
+    }
+
+    /// `method`の通知が来るまで読み進める
+    fn wait_notification(&mut self, method: &str) -> serde_json::Value {
+        loop {
+            if let lsp_server::Message::Notification(notification) = self.read()
+                && notification.method == method
+            {
+                return notification.params;
+            }
+        }
+    }
+}
+
+fn spawn(tempdir: &std::path::Path, music_root: &std::path::Path) -> Client {
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_cliplayer-lsp"))
+        .arg("--music-root-dir")
+        .arg(music_root)
+        // ログのディレクトリをテスト用のディレクトリに作らせる
+        .current_dir(tempdir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdin = child.stdin.take().unwrap();
+    let stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    Client {
+        child,
+        stdin,
+        stdout,
+        next_id: 0,
+    }
+}
+
+#[test]
+fn test_lsp_session_over_stdio() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let music_root = tempdir.path().join("music");
+    let input_dir = tempdir.path().join("input");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    write_text_file(&input_dir.join("other.json"), OTHER_INPUT_JSON);
+    let opened_path = input_dir.join("opened.json");
+    write_text_file(&opened_path, OPENED_INPUT_JSON);
+    let uri = format!("file://{}", opened_path.display());
+
+    let mut client = spawn(tempdir.path(), &music_root);
+    let initialized = client.request(
+        "initialize",
+        serde_json::json!({ "processId": null, "rootUri": null, "capabilities": {} }),
+    );
+    assert_eq!(initialized["capabilities"]["hoverProvider"], true);
+    client.notify("initialized", serde_json::json!({}));
+
+    client.notify(
+        "textDocument/didOpen",
+        serde_json::json!({
+            "textDocument": {
+                "uri": uri,
+                "languageId": "json",
+                "version": 1,
+                "text": OPENED_INPUT_JSON,
+            }
+        }),
+    );
+    let published = client.wait_notification("textDocument/publishDiagnostics");
+    assert_eq!(published["uri"], uri);
+    let mut messages = published["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["message"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "duplicate video ID `BBBBBBBBBBB`: also in input file `other.json`",
+            "unknown liver ID ``",
+            "unknown liver ID `ghost`",
+            "video ID `cFc9Ywpk0QU` is already in the music library; \
+            `musictl add` rejects it without `--allow-overwrite-existing-video`",
+        ]
+    );
+
+    // 2本目の動画の`"liverIds": [""]`の引用符の中
+    let (line, character) = OPENED_INPUT_JSON
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, line.find("[\"\"]")?)))
+        .next()
+        .unwrap();
+    let completion = client.request(
+        "textDocument/completion",
+        serde_json::json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character + 2 },
+        }),
+    );
+    let labels = completion
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, vec!["riku-tazumi", "yudorikku", "yugamin"]);
+
+    let hover = client.request(
+        "textDocument/hover",
+        serde_json::json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 9, "character": 17 },
+        }),
+    );
+    assert!(
+        hover["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("**Test Karaoke Stream**"),
+        "{hover}"
+    );
+
+    client.request("shutdown", serde_json::Value::Null);
+    client.notify("exit", serde_json::Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
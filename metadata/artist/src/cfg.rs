@@ -18,6 +18,14 @@ pub(crate) fn liver_data_path() -> std::path::PathBuf {
     data_path("livers.json", "LIVER_SET_PATH")
 }
 
+pub(crate) fn group_data_path() -> std::path::PathBuf {
+    data_path("groups.json", "GROUP_SET_PATH")
+}
+
 pub(crate) fn official_channel_data_path() -> std::path::PathBuf {
     data_path("official_channels.json", "OFFICIAL_CHANNEL_PATH")
 }
+
+pub(crate) fn int_id_lock_path() -> std::path::PathBuf {
+    data_path("int_ids.lock.json", "ARTIST_INT_ID_LOCK_PATH")
+}
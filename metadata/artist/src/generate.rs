@@ -8,24 +8,85 @@ pub fn snippet(music_code_snippets_path: String) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// ライバー定義(`livers.json`の内容)から, 書き換え後のスニペットファイルの内容を返す
+///
+/// - ファイルには書き込まない
+/// - 定義をまだファイルに保存していない段階(IDの変更時など)で用いる
+pub fn render_snippet(
+    livers_json: &str,
+    music_code_snippets_path: &std::path::Path,
+) -> anyhow::Result<String> {
+    let livers_data: crate::model::Livers = serde_json::from_str(livers_json)?;
+    crate::output::Snippet::load(music_code_snippets_path)?.render(&livers_data)
+}
+
 pub fn hash_inputs() -> anyhow::Result<String> {
     let livers = canonical_livers(crate::model::LOADED_LIVER_DATA.clone());
     let official_channels =
         canonical_official_channels(crate::model::LOADED_OFFICIAL_CHANNEL_DATA.clone());
+    let groups = canonical_groups(crate::model::LOADED_GROUP_DATA.clone());
 
     let mut builder =
         cmn_rs::min_json::InputSetHashBuilder::new("cliplayer:artist-inputs");
     builder.add_serializable("livers", &livers)?;
     builder.add_serializable("official_channels", &official_channels)?;
+    builder.add_serializable("groups", &groups)?;
     Ok(builder.finish_hex())
 }
 
+/// `hash_inputs`, `minify`が読み込んだ定義ファイル
+///
+/// テストでは定義ファイルを読まないため空
+pub fn definition_paths() -> Vec<std::path::PathBuf> {
+    if cfg!(any(test, feature = "test-helpers")) {
+        return Vec::new();
+    }
+    vec![
+        crate::cfg::liver_data_path(),
+        crate::cfg::official_channel_data_path(),
+        crate::cfg::group_data_path(),
+    ]
+}
+
+/// ライバー, 公式チャンネルの整数idを検査する
+///
+/// `write`のときは衝突が無ければロックファイルを更新する
+pub fn check_ids(write: bool) -> anyhow::Result<Vec<cmn_rs::int_id::IntIdDiagnostic>> {
+    check_ids_at(&crate::cfg::int_id_lock_path(), write)
+}
+
+/// [`check_ids`]のロックファイルを`path`にしたもの
+pub fn check_ids_at(
+    path: &std::path::Path,
+    write: bool,
+) -> anyhow::Result<Vec<cmn_rs::int_id::IntIdDiagnostic>> {
+    let livers = &crate::model::LOADED_LIVER_DATA;
+    let official_channels = &crate::model::LOADED_OFFICIAL_CHANNEL_DATA;
+
+    let lock = cmn_rs::int_id::IntIdLock::load(path)?;
+    let allocations = [
+        lock.allocate(crate::model::Livers::INT_ID_SPACE, livers.int_id_entries()),
+        lock.allocate(
+            crate::model::OfficialChannels::INT_ID_SPACE,
+            official_channels.int_id_entries(),
+        ),
+    ]
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(anyhow::Error::msg)?;
+
+    Ok(cmn_rs::int_id::check_lock(path, lock, &allocations, write)?)
+}
+
+#[allow(clippy::too_many_arguments)] // 出力ファイルごとにCLIの引数を受け取るため
 pub fn minify(
     output_dir: String,
     livers_search_index_file_name: String,
     channels_file_name: String,
     livers_file_name: String,
     official_channels_file_name: String,
+    official_channels_search_index_file_name: String,
+    groups_file_name: String,
     dataset_build_id: cmn_rs::min_json::DatasetBuildId,
 ) -> anyhow::Result<()> {
     tracing::info!("Start generate artist min data...");
@@ -33,15 +94,19 @@ pub fn minify(
     let livers_data: crate::model::Livers = crate::model::LOADED_LIVER_DATA.clone();
     let official_channels_data: crate::model::OfficialChannels =
         crate::model::LOADED_OFFICIAL_CHANNEL_DATA.clone();
+    let groups_data: crate::model::Groups = crate::model::LOADED_GROUP_DATA.clone();
 
     minify_impl(
         livers_data,
         official_channels_data,
+        groups_data,
         &output_dir,
         &livers_search_index_file_name,
         &channels_file_name,
         &livers_file_name,
         &official_channels_file_name,
+        &official_channels_search_index_file_name,
+        &groups_file_name,
         dataset_build_id,
     )?;
 
@@ -60,19 +125,25 @@ fn generate_snippet_impl(
 fn minify_impl(
     livers_data: crate::model::Livers,
     official_channels_data: crate::model::OfficialChannels,
+    groups_data: crate::model::Groups,
     output_dir: &str,
     livers_search_index_file_name: &str,
     channels_file_name: &str,
     livers_file_name: &str,
     official_channels_file_name: &str,
+    official_channels_search_index_file_name: &str,
+    groups_file_name: &str,
     dataset_build_id: cmn_rs::min_json::DatasetBuildId,
 ) -> anyhow::Result<()> {
     let output_artists = crate::output::LiversSearchIndex::new(livers_data.clone());
     let channels =
-        crate::output::Channels::new(&livers_data, official_channels_data.clone());
+        crate::output::Channels::new(&livers_data, official_channels_data.clone())?;
     let livers = crate::output::OutputLivers::new(livers_data);
+    let official_channels_search_index =
+        crate::output::OfficialChannelsSearchIndex::new(official_channels_data.clone());
     let official_channels =
         crate::output::OfficialChannels::new(official_channels_data);
+    let groups = crate::output::OutputGroups::new(groups_data);
     let build_metadata = build_metadata(dataset_build_id);
 
     let path = output_path(output_dir, livers_search_index_file_name);
@@ -87,6 +158,12 @@ fn minify_impl(
     let path = output_path(output_dir, official_channels_file_name);
     official_channels.output_json(&path, &build_metadata)?;
 
+    let path = output_path(output_dir, official_channels_search_index_file_name);
+    official_channels_search_index.output_json(&path, &build_metadata)?;
+
+    let path = output_path(output_dir, groups_file_name);
+    groups.output_json(&path, &build_metadata)?;
+
     Ok(())
 }
 
@@ -121,6 +198,17 @@ fn canonical_livers(
     entries
 }
 
+fn canonical_groups(
+    groups: crate::model::Groups,
+) -> Vec<(String, crate::model::Group)> {
+    let mut entries = groups
+        .into_iter()
+        .map(|(id, group)| (id.as_str().to_string(), group))
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| left.0.cmp(&right.0));
+    entries
+}
+
 fn canonical_official_channels(
     official_channels: crate::model::OfficialChannels,
 ) -> Vec<(String, crate::model::OfficialChannel)> {
@@ -1,9 +1,12 @@
+mod group;
 mod liver;
 mod official_channel;
 
+pub use group::LOADED_GROUP_DATA;
 pub use liver::LOADED_LIVER_DATA;
 pub use liver::Livers;
 pub use official_channel::LOADED_OFFICIAL_CHANNEL_DATA;
 
+pub use group::{Group, GroupId, GroupKind, Groups};
 pub use liver::{ExternalArtistsName, Liver, LiverId, LiverIds};
 pub use official_channel::{OfficialChannel, OfficialChannels, OfficialId};
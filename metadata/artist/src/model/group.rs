@@ -0,0 +1,5 @@
+mod data;
+mod loader;
+
+pub use data::{Group, GroupId, GroupKind, Groups};
+pub use loader::LOADED_GROUP_DATA;
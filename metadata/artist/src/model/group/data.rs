@@ -0,0 +1,279 @@
+/// ライバーのグループ(ユニット, 期生など)のhashmap
+///
+/// (group_id, Group)
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Groups(std::collections::HashMap<GroupId, Group>);
+
+/// メンバーが空でないこと, 重複しないことを検証するため
+impl<'de> serde::Deserialize<'de> for Groups {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw: std::collections::HashMap<GroupId, Group> =
+            serde::Deserialize::deserialize(deserializer)?;
+        let groups = Groups(raw);
+        groups
+            .validate_members()
+            .map_err(serde::de::Error::custom)?;
+        Ok(groups)
+    }
+}
+
+/// ライバーのグループ
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Group {
+    /// 日本語での名前
+    ja: String,
+    /// 英語での名前
+    en: String,
+    /// グループの種類
+    kind: GroupKind,
+    /// メンバーのライバーID. 定義順を保つ
+    members: Vec<crate::model::LiverId>,
+}
+
+/// グループの種類
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupKind {
+    /// ユニット
+    Unit,
+    /// 同時期にデビューした期生
+    Generation,
+}
+
+/// グループID
+///
+/// 小文字英数字をハイフン1つで区切った形式(kebab-case)であることを保証
+#[derive(serde::Serialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GroupId(String);
+
+/// `Group`の各フィールド. 最小化したJSONなどの出力に使う
+pub struct GroupInner {
+    /// 日本語での名前
+    pub ja: String,
+    /// 英語での名前
+    pub en: String,
+    /// グループの種類
+    pub kind: GroupKind,
+    /// メンバーのライバーID. 定義順を保つ
+    pub members: Vec<crate::model::LiverId>,
+}
+
+impl Groups {
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// ソート済みのIDリストを返す
+    pub fn sorted_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.0.keys().map(GroupId::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// 指定したグループのメンバー. 存在しない場合はNone
+    pub fn members_of(&self, id: &str) -> Option<&[crate::model::LiverId]> {
+        self.0.get(id).map(|group| group.members.as_slice())
+    }
+
+    fn validate_members(&self) -> Result<(), String> {
+        for (group_id, group) in &self.0 {
+            if group.members.is_empty() {
+                return Err(format!("group `{}` has no members", group_id.as_str()));
+            }
+            let mut members = group.members.iter().collect::<Vec<_>>();
+            members.sort();
+            if let Some(pair) = members.windows(2).find(|pair| pair[0] == pair[1]) {
+                return Err(format!(
+                    "group `{}` has duplicate member `{}`",
+                    group_id.as_str(),
+                    pair[0].as_str()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for Groups {
+    type Item = (GroupId, Group);
+    type IntoIter = std::collections::hash_map::IntoIter<GroupId, Group>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Group {
+    pub fn into_inner(self) -> GroupInner {
+        GroupInner {
+            ja: self.ja,
+            en: self.en,
+            kind: self.kind,
+            members: self.members,
+        }
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for Groups {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::map_of(crate::schema::ID_PATTERN, Group::json_schema(ctx))
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for Group {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        // メンバーは空でなく, 重複しない(`Groups::validate_members`)
+        let members = serde_json::json!({
+            "type": "array",
+            "items": crate::model::LiverId::json_schema(ctx),
+            "minItems": 1,
+            "uniqueItems": true,
+        });
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("ja", String::json_schema(ctx))
+            .required("en", String::json_schema(ctx))
+            .required("kind", GroupKind::json_schema(ctx))
+            .required("members", members)
+            .build()
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for GroupKind {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "enum": ["unit", "generation"] })
+    }
+}
+
+impl GroupId {
+    pub(crate) fn new<'a, T: Into<std::borrow::Cow<'a, str>>>(
+        id: T,
+    ) -> Result<Self, String> {
+        let id = id.into();
+        if !Self::is_valid_group_id(&id) {
+            Err(format!("invalid group id: `{id}` (expected kebab-case)"))
+        } else {
+            Ok(GroupId(id.into_owned()))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 小文字英数字をハイフン1つで区切ったものか
+    fn is_valid_group_id(id: &str) -> bool {
+        id.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+    }
+}
+
+// デシリアライズ時にもIDの形式を確認するためのカスタムデシリアライザ
+impl<'de> serde::Deserialize<'de> for GroupId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id: String = serde::Deserialize::deserialize(deserializer)?;
+        Self::new(id).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::borrow::Borrow<str> for GroupId {
+    fn borrow(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_ids_and_members_of() {
+        let groups = &crate::model::LOADED_GROUP_DATA;
+        assert_eq!(groups.sorted_ids(), vec!["test-gen-1", "test-unit"]);
+        assert_eq!(
+            groups.members_of("test-unit"),
+            Some(
+                [
+                    crate::model::LiverId::self_2(),
+                    crate::model::LiverId::self_1()
+                ]
+                .as_slice()
+            )
+        );
+        assert_eq!(groups.members_of("unknown"), None);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_member() {
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "kind": "unit", "members": ["unknown"] }
+        }"#;
+        let err = serde_json::from_str::<Groups>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid liver: unknown"));
+    }
+
+    #[test]
+    fn test_group_id_new() {
+        assert!(GroupId::new("test-unit").is_ok());
+        assert!(GroupId::new("gen-1").is_ok());
+        for invalid in ["", " ", "test unit", "Test-Unit", "-unit", "unit-", "a--b"] {
+            assert!(
+                GroupId::new(invalid).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_group_id() {
+        let json = r#"{
+            "": { "ja": "a", "en": "a", "kind": "unit", "members": ["yugamin"] }
+        }"#;
+        let err = serde_json::from_str::<Groups>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid group id: ``"));
+
+        let json = r#"{
+            "Test Unit": { "ja": "a", "en": "a", "kind": "unit", "members": ["yugamin"] }
+        }"#;
+        let err = serde_json::from_str::<Groups>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid group id: `Test Unit`"));
+        // 位置を持つ
+        assert!(err.line() > 0);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_empty_and_duplicate_members() {
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "kind": "unit", "members": [] }
+        }"#;
+        let err = serde_json::from_str::<Groups>(json).unwrap_err();
+        assert!(err.to_string().contains("group `a` has no members"));
+
+        let json = r#"{
+            "a": {
+                "ja": "a",
+                "en": "a",
+                "kind": "generation",
+                "members": ["yugamin", "riku-tazumi", "yugamin"]
+            }
+        }"#;
+        let err = serde_json::from_str::<Groups>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("group `a` has duplicate member `yugamin`")
+        );
+    }
+}
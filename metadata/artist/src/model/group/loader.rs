@@ -0,0 +1,46 @@
+/// グループとそのメンバー
+///
+/// - `GROUP_SET_PATH` 環境変数で指定されたファイルから読み込む
+/// - 未指定時は `data/groups.json` を読み込む
+#[cfg(not(any(test, feature = "test-helpers")))]
+pub static LOADED_GROUP_DATA: once_cell::sync::Lazy<super::Groups> =
+    once_cell::sync::Lazy::new(|| {
+        let path = crate::cfg::group_data_path();
+        let path_str = path.to_string_lossy().into_owned();
+        let data = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "Failed to read groups data from {}. reason: {e}",
+                path.display()
+            )
+        });
+        let data: super::Groups = serde_json::from_str(&data).unwrap();
+        tracing::info!("Loaded {} groups from {}", data.len(), path_str);
+        tracing::trace!("Loaded groups data: {:#?}", data);
+        data
+    });
+
+/// グループとそのメンバー
+#[cfg(any(test, feature = "test-helpers"))]
+pub static LOADED_GROUP_DATA: once_cell::sync::Lazy<super::Groups> =
+    once_cell::sync::Lazy::new(|| {
+        const GROUP_DATA: &str = r#"
+        {
+            "test-unit": {
+                "ja": "テストユニット",
+                "en": "Test Unit",
+                "kind": "unit",
+                "members": ["yugamin", "riku-tazumi"]
+            },
+            "test-gen-1": {
+                "ja": "テスト1期生",
+                "en": "Test 1st Generation",
+                "kind": "generation",
+                "members": ["riku-tazumi", "yugamin", "yudorikku"]
+            }
+        }"#;
+        let groups: super::Groups =
+            serde_json::from_str(GROUP_DATA).expect("will not fail");
+        tracing::info!("Loaded {} groups from test data", groups.len());
+        tracing::trace!("Loaded groups data: {:#?}", groups);
+        groups
+    });
@@ -7,6 +7,8 @@ pub struct Livers(std::collections::HashMap<super::LiverId, Liver>);
 /// デシリアライズ時は LiverId のバリデーションを一時的に迂回するため
 /// `HashMap<String, Liver>` として読んでから変換する。
 /// (LiverId::new() が LOADED_LIVER_DATA にアクセスするためデッドロックを防ぐ)
+///
+/// チャンネルIDがライバー間で重複しないことも検証する
 impl<'de> serde::Deserialize<'de> for Livers {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -20,7 +22,11 @@ impl<'de> serde::Deserialize<'de> for Livers {
             .into_iter()
             .map(|(id, liver)| (super::LiverId::from_raw(id), liver))
             .collect::<HashMap<super::LiverId, Liver>>();
-        Ok(Livers(map))
+        let livers = Livers(map);
+        livers
+            .validate_channels()
+            .map_err(serde::de::Error::custom)?;
+        Ok(livers)
     }
 }
 
@@ -37,13 +43,18 @@ pub struct Liver {
     en: String,
     /// alias
     aliases: Vec<String>,
-    /// チャンネルid
+    /// メインのチャンネルid
     channel_id: cmn_rs::yt::ChannelId,
+    /// サブチャンネル, 移行前のチャンネルなど, メイン以外のチャンネルid
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    sub_channel_ids: Vec<cmn_rs::yt::ChannelId>,
     /// カラー
     color: cmn_rs::color::Color,
-    /// 整数id
+    /// 整数id. 省略時はロックファイルから割り当てる
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    int_id: u16,
+    int_id: Option<u16>,
     /// 卒業したか
     #[serde(skip_serializing_if = "is_false")]
     #[serde(default)]
@@ -60,12 +71,19 @@ pub struct LiverInner {
     pub en: String,
     pub aliases: Vec<String>,
     pub channel_id: cmn_rs::yt::ChannelId,
+    pub sub_channel_ids: Vec<cmn_rs::yt::ChannelId>,
     pub color: cmn_rs::color::Color,
-    pub int_id: u16,
+    pub int_id: Option<u16>,
     pub is_graduated: bool,
 }
 
 impl Livers {
+    pub(crate) const INT_ID_SPACE: cmn_rs::int_id::IntIdSpace =
+        cmn_rs::int_id::IntIdSpace {
+            name: "livers",
+            start: 0,
+        };
+
     pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
@@ -90,6 +108,47 @@ impl Livers {
     pub(crate) fn get_ja_name(&self, id: &super::LiverId) -> Option<String> {
         self.0.get(id).map(|liver| liver.ja.clone())
     }
+
+    /// (ライバーID, 定義で指定された整数id)
+    pub(crate) fn int_id_entries(&self) -> impl Iterator<Item = (&str, Option<u16>)> {
+        self.0
+            .iter()
+            .map(|(liver_id, liver)| (liver_id.as_str(), liver.int_id))
+    }
+
+    /// 1つのチャンネルが複数のライバー, 同じライバーに重複して登録されていないか
+    fn validate_channels(&self) -> Result<(), String> {
+        // エラーメッセージを安定させるため, ID順に検査する
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(liver_id, _)| *liver_id);
+
+        let mut owners = std::collections::HashMap::new();
+        for (liver_id, liver) in entries {
+            for channel_id in liver.channel_ids() {
+                if let Some(owner) = owners.insert(channel_id, liver_id) {
+                    return Err(if owner == liver_id {
+                        format!(
+                            "liver `{}` has duplicate channel `{channel_id}`",
+                            liver_id.as_str()
+                        )
+                    } else {
+                        format!(
+                            "channel `{channel_id}` is used by both `{}` and `{}`",
+                            owner.as_str(),
+                            liver_id.as_str()
+                        )
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for Livers {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::map_of(crate::schema::ID_PATTERN, Liver::json_schema(ctx))
+    }
 }
 
 impl IntoIterator for Livers {
@@ -102,6 +161,11 @@ impl IntoIterator for Livers {
 }
 
 impl Liver {
+    /// メインのチャンネルを先頭に, 全てのチャンネルid
+    pub fn channel_ids(&self) -> impl Iterator<Item = &cmn_rs::yt::ChannelId> {
+        std::iter::once(&self.channel_id).chain(&self.sub_channel_ids)
+    }
+
     pub fn into_inner(self) -> LiverInner {
         LiverInner {
             ja: self.ja,
@@ -109,6 +173,7 @@ impl Liver {
             en: self.en,
             aliases: self.aliases,
             channel_id: self.channel_id,
+            sub_channel_ids: self.sub_channel_ids,
             color: self.color,
             int_id: self.int_id,
             is_graduated: self.is_graduated,
@@ -116,6 +181,49 @@ impl Liver {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for Liver {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        let channel_id = cmn_rs::yt::ChannelId::json_schema(ctx);
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("ja", String::json_schema(ctx))
+            .required("jah", String::json_schema(ctx))
+            .required("en", String::json_schema(ctx))
+            .required("aliases", Vec::<String>::json_schema(ctx))
+            .required("channelId", channel_id.clone())
+            .optional(
+                "subChannelIds",
+                Vec::<cmn_rs::yt::ChannelId>::json_schema(ctx),
+            )
+            .required("color", cmn_rs::color::Color::json_schema(ctx))
+            .nullable("intId", u16::json_schema(ctx))
+            .optional("isGraduated", bool::json_schema(ctx))
+            .build()
+    }
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+impl Livers {
+    /// サブチャンネル`UC2222222222222222222222`を持つ`riku-tazumi`のみ
+    pub fn self_with_sub_channel() -> Self {
+        Self(std::collections::HashMap::from([(
+            super::LiverId::self_1(),
+            Liver::self_1_with_sub_channel(),
+        )]))
+    }
+
+    /// 別名`Yugamin`を持つ`riku-tazumi`と, 英語名が`Yugamin`の`yugamin`
+    pub fn self_with_shared_name() -> Self {
+        let riku = Liver {
+            aliases: vec!["Yugamin".to_string()],
+            ..Liver::self_1()
+        };
+        Self(std::collections::HashMap::from([
+            (super::LiverId::self_1(), riku),
+            (super::LiverId::self_2(), Liver::self_2()),
+        ]))
+    }
+}
+
 #[cfg(any(test, feature = "test-helpers"))]
 #[allow(dead_code)] // because these cau be used by other crates with `test-helpers` feature
 impl Liver {
@@ -127,11 +235,20 @@ impl Liver {
             aliases: vec!["りっくん".to_string()],
             channel_id: cmn_rs::yt::ChannelId::test_id_1(),
             color: cmn_rs::color::Color::from_rgb(0x11, 0x11, 0x11),
-            int_id: 1,
+            sub_channel_ids: vec![],
+            int_id: Some(1),
             is_graduated: false,
         }
     }
 
+    /// `self_1`にサブチャンネル`UC2222222222222222222222`を加えたもの
+    pub(crate) fn self_1_with_sub_channel() -> Self {
+        Self {
+            sub_channel_ids: vec![cmn_rs::yt::ChannelId::test_id_2()],
+            ..Self::self_1()
+        }
+    }
+
     /// ゆがみん
     pub(crate) fn self_2() -> Self {
         Self {
@@ -141,7 +258,8 @@ impl Liver {
             aliases: vec![],
             channel_id: cmn_rs::yt::ChannelId::test_id_2(),
             color: cmn_rs::color::Color::from_rgb(0x22, 0x22, 0x22),
-            int_id: 2,
+            sub_channel_ids: vec![],
+            int_id: Some(2),
             is_graduated: false,
         }
     }
@@ -155,7 +273,8 @@ impl Liver {
             aliases: vec![],
             channel_id: cmn_rs::yt::ChannelId::test_id_3(),
             color: cmn_rs::color::Color::from_rgb(0x33, 0x33, 0x33),
-            int_id: 3,
+            sub_channel_ids: vec![],
+            int_id: Some(3),
             is_graduated: true,
         }
     }
@@ -180,4 +299,51 @@ mod tests {
         let sorted = livers.sorted_ids();
         assert_eq!(sorted, vec!["riku-tazumi", "yugamin"]);
     }
+
+    #[test]
+    fn test_channel_ids_lists_primary_first() {
+        let liver = Liver::self_1_with_sub_channel();
+
+        let channel_ids = liver
+            .channel_ids()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            channel_ids,
+            vec!["UC1111111111111111111111", "UC2222222222222222222222"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_duplicate_channels() {
+        let liver = |channel_id: &str, sub_channel_id: &str| {
+            format!(
+                r#"{{
+                    "ja": "a", "jah": "a", "en": "a", "aliases": [],
+                    "channelId": "{channel_id}",
+                    "subChannelIds": ["{sub_channel_id}"],
+                    "color": "111111"
+                }}"#
+            )
+        };
+        let channel_1 = "UC1111111111111111111111";
+        let channel_2 = "UC2222222222222222222222";
+
+        let json = format!(r#"{{"a": {}}}"#, liver(channel_1, channel_1));
+        let err = serde_json::from_str::<Livers>(&json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("liver `a` has duplicate channel `UC1111111111111111111111`")
+        );
+
+        let json = format!(
+            r#"{{"a": {}, "b": {}}}"#,
+            liver(channel_1, channel_2),
+            liver("UC3333333333333333333333", channel_2)
+        );
+        let err = serde_json::from_str::<Livers>(&json).unwrap_err();
+        assert!(err.to_string().contains(
+            "channel `UC2222222222222222222222` is used by both `a` and `b`"
+        ));
+    }
 }
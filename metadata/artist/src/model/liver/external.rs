@@ -84,6 +84,16 @@ impl<'de> serde::Deserialize<'de> for ExternalArtistsName {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for ExternalArtistsName {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({
+            "type": "array",
+            "items": { "type": "string", "minLength": 1 },
+            "minItems": 1,
+        })
+    }
+}
+
 // MARK: For Tests
 
 #[cfg(any(test, feature = "test-helpers"))]
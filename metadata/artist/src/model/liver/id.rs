@@ -31,6 +31,12 @@ impl LiverId {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for LiverId {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        ctx.id(crate::schema::LIVER_IDS)
+    }
+}
+
 // MARK: For Tests
 
 // デシリアライズ時にも`Self`の存在条件を確認するためのカスタムデシリアライザ
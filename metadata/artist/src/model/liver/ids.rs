@@ -65,6 +65,16 @@ impl<'de> serde::Deserialize<'de> for LiverIds {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for LiverIds {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({
+            "type": "array",
+            "items": super::LiverId::json_schema(ctx),
+            "minItems": 1,
+        })
+    }
+}
+
 #[cfg(any(test, feature = "test-helpers"))]
 impl LiverIds {
     fn new_for_test(liver_ids: Vec<super::LiverId>) -> Result<Self, &'static str> {
@@ -15,8 +15,10 @@ pub struct OfficialChannel {
     aliases: Vec<String>,
     /// チャンネルid
     channel_id: cmn_rs::yt::ChannelId,
-    /// 整数id
-    int_id: u16,
+    /// 整数id. 省略時はロックファイルから割り当てる
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    int_id: Option<u16>,
 }
 
 #[derive(
@@ -41,9 +43,31 @@ pub struct OfficialChannelsInner {
 }
 
 impl OfficialChannels {
+    pub(crate) const INT_ID_SPACE: cmn_rs::int_id::IntIdSpace =
+        cmn_rs::int_id::IntIdSpace {
+            name: "officialChannels",
+            start: 950,
+        };
+
     pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// (公式チャンネルID, 定義で指定された整数id)
+    pub(crate) fn int_id_entries(&self) -> impl Iterator<Item = (&str, Option<u16>)> {
+        self.0
+            .iter()
+            .map(|(official_id, channel)| (official_id.as_str(), channel.int_id))
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for OfficialChannels {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::map_of(
+            crate::schema::ID_PATTERN,
+            OfficialChannel::json_schema(ctx),
+        )
+    }
 }
 
 impl IntoIterator for OfficialChannels {
@@ -67,6 +91,19 @@ impl OfficialChannel {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for OfficialChannel {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("ja", String::json_schema(ctx))
+            .required("jah", String::json_schema(ctx))
+            .required("en", String::json_schema(ctx))
+            .required("aliases", Vec::<String>::json_schema(ctx))
+            .required("channelId", cmn_rs::yt::ChannelId::json_schema(ctx))
+            .nullable("intId", u16::json_schema(ctx))
+            .build()
+    }
+}
+
 impl OfficialId {
     pub(crate) fn as_str(&self) -> &str {
         &self.0
@@ -27,7 +27,7 @@ pub static LOADED_OFFICIAL_CHANNEL_DATA: once_cell::sync::Lazy<
                 "jah": "てすとちゃんねるいち",
                 "en": "Test Channel 1",
                 "aliases": ["てすといち"],
-                "channelId": "UC1111111111111111111111",
+                "channelId": "UC4444444444444444444444",
                 "intId": 950
             },
             "test-channel-2": {
@@ -35,7 +35,7 @@ pub static LOADED_OFFICIAL_CHANNEL_DATA: once_cell::sync::Lazy<
                 "jah": "てすとちゃんねるに",
                 "en": "Test Channel 2",
                 "aliases": ["てすとに"],
-                "channelId": "UC2222222222222222222222",
+                "channelId": "UC5555555555555555555555",
                 "intId": 951
             },
             "test-channel-3": {
@@ -43,7 +43,7 @@ pub static LOADED_OFFICIAL_CHANNEL_DATA: once_cell::sync::Lazy<
                 "jah": "てすとちゃんねるさん",
                 "en": "Test Channel 3",
                 "aliases": ["てすとさん"],
-                "channelId": "UC3333333333333333333333",
+                "channelId": "UC6666666666666666666666",
                 "intId": 952
             }
         }"#;
@@ -1,13 +1,25 @@
 mod artist_search_index;
 mod channels;
+mod groups;
 mod livers;
 mod minified_json;
 mod official_channels;
+mod official_channels_search_index;
 mod snippet;
 
 pub(crate) use artist_search_index::LiversSearchIndex;
 pub(crate) use channels::Channels;
+pub(crate) use groups::OutputGroups;
 pub(crate) use livers::OutputLivers;
 pub(crate) use minified_json::BuildMetadata;
 pub(crate) use official_channels::OfficialChannels;
+pub(crate) use official_channels_search_index::OfficialChannelsSearchIndex;
 pub(crate) use snippet::Snippet;
+
+pub const MIN_LIVERS_SEARCH_INDEX_FILE_NAME: &str = "livers_search_index.min.json";
+pub const MIN_CHANNELS_FILE_NAME: &str = "channels.min.json";
+pub const MIN_LIVERS_FILE_NAME: &str = "livers.min.json";
+pub const MIN_OFFICIAL_CHANNELS_FILE_NAME: &str = "official_channels.min.json";
+pub const MIN_OFFICIAL_CHANNELS_SEARCH_INDEX_FILE_NAME: &str =
+    "official_channels_search_index.min.json";
+pub const MIN_GROUPS_FILE_NAME: &str = "groups.min.json";
@@ -8,6 +8,9 @@ struct LiverSearchIndexInner {
     liver_id: crate::model::LiverId,
     #[serde(skip_serializing_if = "Option::is_none")]
     is_alias: Option<bool>,
+    /// 生成した表記のとき, `variant`と`variantOf`
+    #[serde(flatten)]
+    origin: Option<cmn_rs::search_key::KeyOrigin>,
 }
 
 impl LiversSearchIndex {
@@ -16,13 +19,11 @@ impl LiversSearchIndex {
 
         for (liver_id, liver) in livers.into_iter() {
             let liver = liver.into_inner();
+            let mut keys = cmn_rs::search_key::SearchKeys::default();
 
-            Self::push_non_aliases(
-                [liver.ja, liver.jah, liver.en],
-                &mut index,
-                &liver_id,
-            );
-            Self::push_aliases(liver.aliases, &mut index, &liver_id);
+            Self::push_non_aliases([liver.ja, liver.jah, liver.en], &mut keys);
+            Self::push_aliases(liver.aliases, &mut keys);
+            Self::push_entries(keys, &mut index, &liver_id);
         }
 
         index.sort_by(|a, b| {
@@ -32,30 +33,30 @@ impl LiversSearchIndex {
         Self(index)
     }
 
-    fn push_non_aliases(
-        key: [String; 3],
-        index: &mut Vec<LiverSearchIndexInner>,
-        liver_id: &crate::model::LiverId,
-    ) {
+    fn push_non_aliases(key: [String; 3], keys: &mut cmn_rs::search_key::SearchKeys) {
         for string_non_empty in key {
-            index.push(LiverSearchIndexInner {
-                key: string_non_empty.clone(),
-                liver_id: liver_id.clone(),
-                is_alias: None,
-            });
+            keys.push(&string_non_empty);
         }
     }
 
-    fn push_aliases(
-        keys: Vec<String>,
+    fn push_aliases(aliases: Vec<String>, keys: &mut cmn_rs::search_key::SearchKeys) {
+        for key in aliases {
+            keys.push_alias(&key);
+        }
+    }
+
+    /// 元のキーと生成した表記をまとめてインデックスに追加する
+    fn push_entries(
+        keys: cmn_rs::search_key::SearchKeys,
         index: &mut Vec<LiverSearchIndexInner>,
         liver_id: &crate::model::LiverId,
     ) {
-        for key in keys {
+        for key in keys.into_keys() {
             index.push(LiverSearchIndexInner {
-                key,
+                key: key.key,
                 liver_id: liver_id.clone(),
-                is_alias: Some(true),
+                is_alias: key.is_alias.then_some(true),
+                origin: key.origin,
             });
         }
     }
@@ -75,17 +76,23 @@ mod tests {
 
     #[test]
     fn test_artist_search_index_push_non_aliases() {
+        let mut keys = cmn_rs::search_key::SearchKeys::default();
         let mut index = Vec::new();
         let liver_id = crate::model::LiverId::self_1();
-        let keys = [
+        let non_aliases = [
             "Test Artist".to_string(),
             "てすとあーてぃすと".to_string(),
             "Test Artist (JPN)".to_string(),
         ];
 
-        LiversSearchIndex::push_non_aliases(keys, &mut index, &liver_id);
+        LiversSearchIndex::push_non_aliases(non_aliases, &mut keys);
+        LiversSearchIndex::push_entries(keys, &mut index, &liver_id);
 
-        assert_eq!(index.len(), 3);
+        let originals = index
+            .iter()
+            .filter(|entry| entry.origin.is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(originals.len(), 3);
         for entry in &index {
             assert_eq!(entry.liver_id, liver_id);
             assert!(entry.is_alias.is_none());
@@ -94,16 +101,55 @@ mod tests {
 
     #[test]
     fn test_artist_search_index_push_aliases() {
+        let mut keys = cmn_rs::search_key::SearchKeys::default();
         let mut index = Vec::new();
         let liver_id = crate::model::LiverId::self_2();
         let aliases = vec!["Alias 1".to_string(), "Alias 2".to_string()];
 
-        LiversSearchIndex::push_aliases(aliases, &mut index, &liver_id);
+        LiversSearchIndex::push_aliases(aliases, &mut keys);
+        LiversSearchIndex::push_entries(keys, &mut index, &liver_id);
 
-        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index.iter().filter(|entry| entry.origin.is_none()).count(),
+            2
+        );
         for entry in &index {
             assert_eq!(entry.liver_id, liver_id);
             assert!(entry.is_alias.is_some());
         }
     }
+
+    #[test]
+    fn test_artist_search_index_serializes_variant_origin() {
+        let mut keys = cmn_rs::search_key::SearchKeys::default();
+        let mut index = Vec::new();
+        let liver_id = crate::model::LiverId::self_1();
+
+        LiversSearchIndex::push_non_aliases(
+            [
+                "月ノ美兎".to_string(),
+                "つきのみと".to_string(),
+                "Tsukino Mito".to_string(),
+            ],
+            &mut keys,
+        );
+        LiversSearchIndex::push_entries(keys, &mut index, &liver_id);
+
+        let json = serde_json::to_value(&index).unwrap();
+        let entries = json.as_array().unwrap();
+        let original = entries
+            .iter()
+            .find(|entry| entry["key"] == "つきのみと")
+            .unwrap();
+        assert!(original.get("variant").is_none());
+        assert!(original.get("variantOf").is_none());
+
+        let romaji = entries
+            .iter()
+            .find(|entry| entry["key"] == "tsukinomito")
+            .unwrap();
+        assert_eq!(romaji["variant"], "romaji");
+        assert_eq!(romaji["variantOf"], "つきのみと");
+        assert_eq!(romaji["liverId"], liver_id.as_str());
+    }
 }
@@ -4,23 +4,31 @@ pub(crate) struct Channels(
 );
 
 #[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 struct ChannelsValue {
     id: String,
     /// "liver" or "official"
     kind: String,
+    /// ライバーのメイン以外のチャンネルか
+    #[serde(skip_serializing_if = "is_false")]
+    is_sub_channel: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 impl Channels {
     pub(crate) fn new(
         livers: &crate::model::Livers,
         official_channels: crate::model::OfficialChannels,
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
         let mut channels = std::collections::BTreeMap::new();
 
         Self::add_livers(&mut channels, livers.clone());
-        Self::add_official_channels(&mut channels, official_channels);
+        Self::add_official_channels(&mut channels, official_channels)?;
 
-        Self(channels)
+        Ok(Self(channels))
     }
 
     fn add_livers(
@@ -29,26 +37,44 @@ impl Channels {
     ) {
         const KIND_LIVER: &str = "liver";
 
+        // ライバー間のチャンネルの重複は`Livers`の読み込み時に弾かれている
         for (liver_id, liver) in livers.into_iter() {
+            let liver = liver.into_inner();
             channels.insert(
-                liver.into_inner().channel_id,
-                ChannelsValue::new(liver_id.as_str(), KIND_LIVER),
+                liver.channel_id,
+                ChannelsValue::new(liver_id.as_str(), KIND_LIVER, false),
             );
+            for sub_channel_id in liver.sub_channel_ids {
+                channels.insert(
+                    sub_channel_id,
+                    ChannelsValue::new(liver_id.as_str(), KIND_LIVER, true),
+                );
+            }
         }
     }
 
     fn add_official_channels(
         channels: &mut std::collections::BTreeMap<cmn_rs::yt::ChannelId, ChannelsValue>,
         official_channels: crate::model::OfficialChannels,
-    ) {
+    ) -> anyhow::Result<()> {
         const KIND_OFFICIAL: &str = "official";
 
         for (official_id, official_channel) in official_channels.into_iter() {
+            let channel_id = official_channel.into_inner().channel_id;
+            if let Some(existing) = channels.get(&channel_id) {
+                anyhow::bail!(
+                    "channel `{channel_id}` is used by both {} `{}` and official channel `{}`",
+                    existing.kind,
+                    existing.id,
+                    official_id.as_str()
+                );
+            }
             channels.insert(
-                official_channel.into_inner().channel_id,
-                ChannelsValue::new(official_id.as_str(), KIND_OFFICIAL),
+                channel_id,
+                ChannelsValue::new(official_id.as_str(), KIND_OFFICIAL, false),
             );
         }
+        Ok(())
     }
 
     pub(crate) fn output_json(
@@ -61,10 +87,62 @@ impl Channels {
 }
 
 impl ChannelsValue {
-    fn new(id: &str, kind: &str) -> Self {
+    fn new(id: &str, kind: &str, is_sub_channel: bool) -> Self {
         Self {
             id: id.to_string(),
             kind: kind.to_string(),
+            is_sub_channel,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn official_channels(channel_id: &str) -> crate::model::OfficialChannels {
+        serde_json::from_str(&format!(
+            r#"{{
+                "official": {{
+                    "ja": "公式", "jah": "こうしき", "en": "Official", "aliases": [],
+                    "channelId": "{channel_id}",
+                    "intId": 950
+                }}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_channels_map_sub_channels_to_liver() {
+        let livers = crate::model::Livers::self_with_sub_channel();
+        let channels =
+            Channels::new(&livers, official_channels("UC3333333333333333333333"))
+                .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&channels).unwrap(),
+            serde_json::json!({
+                "UC1111111111111111111111": {"id": "riku-tazumi", "kind": "liver"},
+                "UC2222222222222222222222": {
+                    "id": "riku-tazumi",
+                    "kind": "liver",
+                    "isSubChannel": true
+                },
+                "UC3333333333333333333333": {"id": "official", "kind": "official"},
+            })
+        );
+    }
+
+    #[test]
+    fn test_channels_reject_official_channel_of_liver() {
+        let livers = crate::model::Livers::self_with_sub_channel();
+        let err = Channels::new(&livers, official_channels("UC2222222222222222222222"))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "channel `UC2222222222222222222222` is used by both liver `riku-tazumi` and official channel `official`"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+/// minifyして出力する用
+#[derive(serde::Serialize, Debug, Clone)]
+pub(crate) struct OutputGroups(
+    std::collections::BTreeMap<crate::model::GroupId, OutputGroup>,
+);
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OutputGroup {
+    ja: String,
+    en: String,
+    kind: crate::model::GroupKind,
+    members: Vec<crate::model::LiverId>,
+}
+
+impl OutputGroups {
+    pub(crate) fn new(groups: crate::model::Groups) -> Self {
+        let mut map = std::collections::BTreeMap::new();
+
+        for (group_id, group) in groups.into_iter() {
+            let group = group.into_inner();
+            let output_group = OutputGroup {
+                ja: group.ja,
+                en: group.en,
+                kind: group.kind,
+                members: group.members,
+            };
+            map.insert(group_id, output_group);
+        }
+        Self(map)
+    }
+
+    pub(crate) fn output_json(
+        &self,
+        path: &std::path::Path,
+        metadata: &crate::output::BuildMetadata,
+    ) -> anyhow::Result<()> {
+        crate::output::minified_json::write_json(path, self, metadata)
+    }
+}
@@ -13,6 +13,8 @@ struct OutputLiver {
     en: String,
     color: cmn_rs::color::Color,
     channel_id: cmn_rs::yt::ChannelId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sub_channel_ids: Vec<cmn_rs::yt::ChannelId>,
     #[serde(skip_serializing_if = "is_false")]
     is_graduated: bool,
 }
@@ -33,6 +35,7 @@ impl OutputLivers {
                 en: liver.en,
                 color: liver.color,
                 channel_id: liver.channel_id,
+                sub_channel_ids: liver.sub_channel_ids,
                 is_graduated: liver.is_graduated,
             };
             map.insert(liver_id, output_artist);
@@ -0,0 +1,104 @@
+#[derive(serde::Serialize, Debug, Clone)]
+pub(crate) struct OfficialChannelsSearchIndex(Vec<OfficialChannelSearchIndexInner>);
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OfficialChannelSearchIndexInner {
+    key: String,
+    official_id: crate::model::OfficialId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_alias: Option<bool>,
+    #[serde(flatten)]
+    origin: Option<cmn_rs::search_key::KeyOrigin>,
+}
+
+impl OfficialChannelsSearchIndex {
+    pub(crate) fn new(official_channels: crate::model::OfficialChannels) -> Self {
+        let mut index = Vec::new();
+
+        for (official_id, official_channel) in official_channels.into_iter() {
+            let official_channel = official_channel.into_inner();
+            let mut keys = cmn_rs::search_key::SearchKeys::default();
+
+            for key in [
+                official_channel.ja,
+                official_channel.jah,
+                official_channel.en,
+            ] {
+                keys.push(&key);
+            }
+            for alias in official_channel.aliases {
+                keys.push_alias(&alias);
+            }
+
+            for key in keys.into_keys() {
+                index.push(OfficialChannelSearchIndexInner {
+                    key: key.key,
+                    official_id: official_id.clone(),
+                    is_alias: key.is_alias.then_some(true),
+                    origin: key.origin,
+                });
+            }
+        }
+
+        index.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then_with(|| a.official_id.cmp(&b.official_id))
+        });
+
+        Self(index)
+    }
+
+    pub(crate) fn output_json(
+        &self,
+        path: &std::path::Path,
+        metadata: &crate::output::BuildMetadata,
+    ) -> anyhow::Result<()> {
+        crate::output::minified_json::write_json(path, self, metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_official_channels_search_index_generates_variants() {
+        let official_channels = crate::model::LOADED_OFFICIAL_CHANNEL_DATA.clone();
+        let index = OfficialChannelsSearchIndex::new(official_channels);
+
+        let json = serde_json::to_value(&index).unwrap();
+        let entries = json.as_array().unwrap();
+        assert!(entries.iter().any(|entry| entry.get("variant").is_none()));
+        assert!(
+            entries
+                .iter()
+                .filter(|entry| entry.get("variant").is_some())
+                .all(|entry| entry["variantOf"].is_string())
+        );
+        assert!(entries.iter().all(|entry| entry["officialId"].is_string()));
+
+        let romaji = entries
+            .iter()
+            .find(|entry| entry["key"] == "tesutochanneruichi")
+            .unwrap();
+        assert_eq!(romaji["officialId"], "test-channel-1");
+        assert_eq!(romaji["variant"], "romaji");
+        assert_eq!(romaji["variantOf"], "てすとちゃんねるいち");
+
+        let alias_romaji = entries
+            .iter()
+            .find(|entry| entry["key"] == "tesutoichi")
+            .unwrap();
+        assert_eq!(alias_romaji["isAlias"], true);
+
+        let keys = entries
+            .iter()
+            .map(|entry| entry["key"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+}
@@ -56,6 +56,15 @@ impl Snippet {
         Ok(())
     }
 
+    /// `livers`をもとに書き換えたスニペットを, ファイルに書き出す形式の文字列で返す
+    pub(crate) fn render(
+        mut self,
+        livers: &crate::model::Livers,
+    ) -> anyhow::Result<String> {
+        self.rewrite_body(livers)?;
+        Ok(serde_json::to_string_pretty(&self)?)
+    }
+
     fn rewrite_body(&mut self, livers: &crate::model::Livers) -> anyhow::Result<()> {
         use anyhow::Context;
 
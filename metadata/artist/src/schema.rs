@@ -0,0 +1,198 @@
+/// ライバーIDの列挙の名前
+pub const LIVER_IDS: &str = "liverIds";
+
+/// 定義ファイルのキー(ライバーID, 公式チャンネルID, グループID)のパターン
+pub(crate) const ID_PATTERN: &str = "^[a-z0-9-]+$";
+
+/// `livers.json`, `official_channels.json`, `groups.json`のスキーマ
+///
+/// グループのメンバーの補完のため, `ctx`にライバーIDの列挙を加えておく
+pub fn files(
+    ctx: &cmn_rs::json_schema::SchemaContext,
+) -> Vec<cmn_rs::json_schema::SchemaFile> {
+    use cmn_rs::json_schema::JsonSchema;
+
+    vec![
+        cmn_rs::json_schema::SchemaFile::new(
+            "livers.schema.json",
+            "Livers",
+            crate::model::Livers::json_schema(ctx),
+        ),
+        cmn_rs::json_schema::SchemaFile::new(
+            "official_channels.schema.json",
+            "OfficialChannels",
+            crate::model::OfficialChannels::json_schema(ctx),
+        ),
+        cmn_rs::json_schema::SchemaFile::new(
+            "groups.schema.json",
+            "Groups",
+            crate::model::Groups::json_schema(ctx),
+        ),
+    ]
+}
+
+/// ライバーIDの列挙を加える
+///
+/// テストでも実際のIDで生成できるよう, `LOADED_LIVER_DATA`でなく定義ファイルを読む
+pub fn with_liver_ids(
+    ctx: cmn_rs::json_schema::SchemaContext,
+) -> anyhow::Result<cmn_rs::json_schema::SchemaContext> {
+    use anyhow::Context;
+
+    let path = crate::cfg::liver_data_path();
+    let content = std::fs::read_to_string(&path).with_context(|| {
+        format!("Failed to read livers data from {}", path.display())
+    })?;
+    let livers: crate::model::Livers = serde_json::from_str(&content)?;
+    Ok(ctx.with_enum(LIVER_IDS, livers.sorted_ids()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn committed_schema_dir() -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tools/schema")
+    }
+
+    fn ctx() -> cmn_rs::json_schema::SchemaContext {
+        with_liver_ids(cmn_rs::json_schema::SchemaContext::default()).unwrap()
+    }
+
+    /// 全てのフィールドを持つ値をシリアライズし, スキーマに合うこと, プロパティ名が一致することを確かめる
+    fn assert_schema_covers_every_field<T>(json: &str)
+    where
+        T: cmn_rs::json_schema::JsonSchema
+            + serde::Serialize
+            + serde::de::DeserializeOwned,
+    {
+        let schema = T::json_schema(&cmn_rs::json_schema::SchemaContext::default());
+        let instance = cmn_rs::json_schema::round_trip::<T>(json);
+        assert_eq!(
+            cmn_rs::json_schema::property_name_mismatches(&schema, &instance),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &instance),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_committed_schemas_are_up_to_date() {
+        let stale =
+            cmn_rs::json_schema::stale_files(&committed_schema_dir(), &files(&ctx()))
+                .unwrap();
+        assert!(
+            stale.is_empty(),
+            "schema files are out of date, run `metadata schema`: {stale:?}"
+        );
+    }
+
+    #[test]
+    fn test_data_files_match_schemas() {
+        let data_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let read = |name: &str| std::fs::read_to_string(data_dir.join(name)).unwrap();
+        let livers = read("livers.json");
+        let official_channels = read("official_channels.json");
+        let groups = read("groups.json");
+        // 定義ファイルそのものと, モデルで読み込んでシリアライズし直した値を検証する.
+        // グループのメンバーはテスト用のライバーで検証されるため, 読み込み直さない
+        let instances = [
+            vec![
+                serde_json::from_str(&livers).unwrap(),
+                cmn_rs::json_schema::round_trip::<crate::model::Livers>(&livers),
+            ],
+            vec![
+                serde_json::from_str(&official_channels).unwrap(),
+                cmn_rs::json_schema::round_trip::<crate::model::OfficialChannels>(
+                    &official_channels,
+                ),
+            ],
+            vec![serde_json::from_str(&groups).unwrap()],
+        ];
+        for (file, instances) in files(&ctx()).iter().zip(instances) {
+            let schema: serde_json::Value =
+                serde_json::from_str(&file.render()).unwrap();
+            for instance in instances {
+                assert_eq!(
+                    cmn_rs::json_schema::validate(&schema, &instance),
+                    Vec::<String>::new(),
+                    "{}",
+                    file.file_name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_liver_schema_covers_every_field() {
+        assert_schema_covers_every_field::<crate::model::Livers>(
+            r#"{"riku-tazumi": {
+                "ja": "田角陸",
+                "jah": "たずみりく",
+                "en": "Tazumi Riku",
+                "aliases": ["りっくん"],
+                "channelId": "UC1111111111111111111111",
+                "subChannelIds": ["UC2222222222222222222222"],
+                "color": "111111",
+                "intId": 1,
+                "isGraduated": true
+            }}"#,
+        );
+    }
+
+    #[test]
+    fn test_official_channel_schema_covers_every_field() {
+        assert_schema_covers_every_field::<crate::model::OfficialChannels>(
+            r#"{"nijisanji": {
+                "ja": "にじさんじ",
+                "jah": "にじさんじ",
+                "en": "NIJISANJI",
+                "aliases": ["にじ"],
+                "channelId": "UC3333333333333333333333",
+                "intId": 950
+            }}"#,
+        );
+    }
+
+    #[test]
+    fn test_group_schema_covers_every_field() {
+        assert_schema_covers_every_field::<crate::model::Groups>(
+            r#"{"group": {
+                "ja": "グループ",
+                "en": "Group",
+                "kind": "unit",
+                "members": ["riku-tazumi", "yugamin"]
+            }}"#,
+        );
+    }
+
+    #[test]
+    fn test_group_members_use_liver_ids() {
+        let ctx = cmn_rs::json_schema::SchemaContext::default()
+            .with_enum(LIVER_IDS, ["riku-tazumi", "yugamin"]);
+        let schema =
+            <crate::model::Groups as cmn_rs::json_schema::JsonSchema>::json_schema(
+                &ctx,
+            );
+        let groups = serde_json::json!({
+            "group": {"ja": "グループ", "en": "Group", "kind": "unit", "members": ["unknown"]}
+        });
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &groups),
+            vec!["/group/members/0: \"unknown\" is not one of the allowed values"]
+        );
+    }
+
+    #[test]
+    fn test_with_liver_ids_reads_definition_file() {
+        let ctx =
+            with_liver_ids(cmn_rs::json_schema::SchemaContext::default()).unwrap();
+        let schema = ctx.id(LIVER_IDS);
+        let ids = schema["enum"].as_array().unwrap();
+        assert!(!ids.is_empty());
+        // テスト用の`LOADED_LIVER_DATA`ではなく, 定義ファイルのIDであること
+        assert!(!ids.contains(&"riku-tazumi".into()));
+    }
+}
@@ -0,0 +1,72 @@
+pub fn snippet(code_snippets_path: String) -> anyhow::Result<()> {
+    tracing::info!("Start generate song snippet...");
+
+    let songs = crate::model::LOADED_SONG_DATA.clone();
+    generate_snippet_impl(&songs, &code_snippets_path)?;
+
+    tracing::info!("Generating song snippet completed successfully");
+    Ok(())
+}
+
+/// `hash_inputs`, `minify`が読み込んだ定義ファイル
+///
+/// テストでは定義ファイルを読まないため空
+pub fn definition_paths() -> Vec<std::path::PathBuf> {
+    if cfg!(any(test, feature = "test-helpers")) {
+        return Vec::new();
+    }
+    vec![crate::cfg::song_data_path()]
+}
+
+pub fn hash_inputs() -> anyhow::Result<String> {
+    let songs = canonical_songs(&crate::model::LOADED_SONG_DATA);
+
+    let mut builder =
+        cmn_rs::min_json::InputSetHashBuilder::new("cliplayer:song-inputs");
+    builder.add_serializable("songs", &songs)?;
+    Ok(builder.finish_hex())
+}
+
+pub fn minify(
+    output_dir: String,
+    min_songs_file_name: String,
+    dataset_build_id: cmn_rs::min_json::DatasetBuildId,
+) -> anyhow::Result<()> {
+    tracing::info!("Start generate song min data...");
+
+    let songs = crate::model::LOADED_SONG_DATA.clone();
+    minify_impl(&songs, &output_dir, &min_songs_file_name, dataset_build_id)?;
+
+    tracing::info!("Generating song min data completed successfully");
+    Ok(())
+}
+
+fn generate_snippet_impl(
+    songs: &crate::model::Songs,
+    code_snippets_path: &str,
+) -> anyhow::Result<()> {
+    let snippets_path = std::path::Path::new(code_snippets_path);
+
+    let mut snippet = crate::output::Snippet::load(snippets_path)?;
+    snippet.output_json(snippets_path, songs)
+}
+
+fn minify_impl(
+    songs: &crate::model::Songs,
+    output_dir: &str,
+    min_songs_file_name: &str,
+    dataset_build_id: cmn_rs::min_json::DatasetBuildId,
+) -> anyhow::Result<()> {
+    let output = crate::output::MinSongs::new(songs);
+    let metadata = crate::output::BuildMetadata::new(dataset_build_id);
+    let path = std::path::Path::new(output_dir).join(min_songs_file_name);
+    output.output_json(&path, &metadata)
+}
+
+fn canonical_songs(songs: &crate::model::Songs) -> Vec<(String, crate::model::Song)> {
+    songs
+        .sorted_iter()
+        .into_iter()
+        .map(|(id, song)| (id.as_str().to_string(), song.clone()))
+        .collect()
+}
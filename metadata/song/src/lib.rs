@@ -0,0 +1,5 @@
+mod cfg;
+
+pub mod generate;
+pub mod model;
+pub mod output;
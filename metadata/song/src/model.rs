@@ -0,0 +1,3 @@
+mod song;
+
+pub use song::{LOADED_SONG_DATA, Song, SongId, SongMatch, Songs};
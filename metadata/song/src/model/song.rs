@@ -0,0 +1,7 @@
+mod data;
+mod loader;
+mod matching;
+
+pub use data::{Song, SongId, Songs};
+pub use loader::LOADED_SONG_DATA;
+pub use matching::SongMatch;
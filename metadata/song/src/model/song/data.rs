@@ -0,0 +1,196 @@
+/// 楽曲のモデル
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct Songs(std::collections::HashMap<SongId, Song>);
+
+impl Songs {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&SongId, &Song)> {
+        self.0.iter()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn is_contains_song_id(&self, id: &str) -> bool {
+        self.0.keys().any(|song_id| song_id.as_str() == id)
+    }
+
+    /// 楽曲IDの順で返す
+    pub(crate) fn sorted_iter(&self) -> Vec<(&SongId, &Song)> {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|left, right| left.0.cmp(right.0));
+        entries
+    }
+
+    /// Return a sorted list of song IDs
+    pub fn sorted_ids(&self) -> Vec<&str> {
+        self.sorted_iter()
+            .into_iter()
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+}
+
+/// デシリアライズ時は SongId のバリデーションを一時的に迂回するため
+/// `HashMap<String, Song>` として読んでから変換する。
+/// (SongId::new() が LOADED_SONG_DATA にアクセスするためデッドロックを防ぐ)
+impl<'de> serde::Deserialize<'de> for Songs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use std::collections::HashMap;
+
+        let raw: HashMap<String, Song> = serde::Deserialize::deserialize(deserializer)?;
+        let map = raw
+            .into_iter()
+            .map(|(id, song)| (SongId::from_raw(id), song))
+            .collect::<HashMap<SongId, Song>>();
+        Ok(Songs(map))
+    }
+}
+
+/// 楽曲
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Song {
+    /// 日本語の曲名
+    pub(crate) ja: String,
+    /// 英語の曲名
+    pub(crate) en: String,
+    /// 表記揺れ. クリップの曲名との照合に用いる
+    #[serde(default)]
+    pub(crate) aliases: Vec<String>,
+    /// 原曲のアーティスト名
+    #[serde(default)]
+    pub(crate) original_artists: Vec<String>,
+}
+
+impl Song {
+    /// 照合に用いる曲名. 日本語, 英語, 表記揺れの順
+    pub(crate) fn titles(&self) -> impl Iterator<Item = &str> {
+        [self.ja.as_str(), self.en.as_str()]
+            .into_iter()
+            .chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// 楽曲ID
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SongId(String);
+
+impl SongId {
+    pub fn new<'a, T: Into<std::borrow::Cow<'a, str>>>(id: T) -> Result<Self, String> {
+        let id = id.into();
+        if !Self::is_valid_song_id(&id) {
+            Err(format!("invalid song: {id}"))
+        } else {
+            Ok(SongId(id.into_owned()))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// バリデーションなしで SongId を生成。LOADED_SONG_DATA の初期化時のみ使用。
+    pub(super) fn from_raw(id: String) -> Self {
+        SongId(id)
+    }
+
+    fn is_valid_song_id(id: &str) -> bool {
+        crate::model::LOADED_SONG_DATA.is_contains_song_id(id)
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for SongId {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "minLength": 1 })
+    }
+}
+
+impl std::fmt::Display for SongId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SongId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id: String = serde::Deserialize::deserialize(deserializer)?;
+        Self::new(id).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+impl SongId {
+    /// `fuwafuwa-time`
+    pub fn self_1() -> Self {
+        Self::new("fuwafuwa-time").unwrap()
+    }
+
+    /// `idol`
+    pub fn self_2() -> Self {
+        Self::new("idol").unwrap()
+    }
+
+    /// `kaze-ni-naru`
+    pub fn self_3() -> Self {
+        Self::new("kaze-ni-naru").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_ids() {
+        let sorted = crate::model::LOADED_SONG_DATA.sorted_ids();
+
+        assert_eq!(sorted, vec!["fuwafuwa-time", "idol", "kaze-ni-naru"]);
+    }
+
+    #[test]
+    fn new_valid_ids_succeed() {
+        assert_eq!(SongId::self_1().as_str(), "fuwafuwa-time");
+        assert_eq!(SongId::self_2().as_str(), "idol");
+        assert_eq!(SongId::self_3().as_str(), "kaze-ni-naru");
+    }
+
+    #[test]
+    fn new_invalid_ids_fail() {
+        assert!(SongId::new("invalid-song").is_err());
+        assert!(SongId::new("").is_err());
+    }
+
+    #[test]
+    fn deserialize_valid() {
+        let json = r#""idol""#;
+        let song_id: SongId = serde_json::from_str(json).expect("deserialize failed");
+        assert_eq!(song_id.as_str(), "idol");
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        let json = r#""invalid-song""#;
+        let result: Result<SongId, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialize_song_defaults() {
+        let json = r#"{"ja": "曲", "en": "Song"}"#;
+        let song: Song = serde_json::from_str(json).unwrap();
+        assert!(song.aliases.is_empty());
+        assert!(song.original_artists.is_empty());
+        assert_eq!(song.titles().collect::<Vec<_>>(), vec!["曲", "Song"]);
+
+        let json = r#"{"ja": "曲", "en": "Song", "artist": "x"}"#;
+        assert!(serde_json::from_str::<Song>(json).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+/// 楽曲とその周辺情報
+///
+/// - `SONG_SET_PATH` 環境変数で指定されたファイルから読み込む
+/// - 未指定時は `data/songs.json` を読み込む
+#[cfg(not(any(test, feature = "test-helpers")))]
+pub static LOADED_SONG_DATA: once_cell::sync::Lazy<super::Songs> =
+    once_cell::sync::Lazy::new(|| {
+        let path = crate::cfg::song_data_path();
+        let path_str = path.to_string_lossy().into_owned();
+        let data = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "Failed to read songs data from {}. reason: {e}",
+                path.display()
+            )
+        });
+        let data: super::Songs = serde_json::from_str(&data).unwrap();
+        tracing::info!("Loaded {} songs from {}", data.len(), path_str);
+        tracing::trace!("Loaded songs data: {:#?}", data);
+        data
+    });
+
+#[cfg(any(test, feature = "test-helpers"))]
+pub static LOADED_SONG_DATA: once_cell::sync::Lazy<super::Songs> =
+    once_cell::sync::Lazy::new(|| {
+        const SONG_DATA: &str = r#"
+        {
+            "fuwafuwa-time": {
+                "ja": "ふわふわ時間",
+                "en": "Fuwa Fuwa Time",
+                "aliases": ["fuwafuwa time"],
+                "originalArtists": ["放課後ティータイム"]
+            },
+            "idol": {
+                "ja": "アイドル",
+                "en": "Idol",
+                "originalArtists": ["YOASOBI"]
+            },
+            "kaze-ni-naru": {
+                "ja": "風になる",
+                "en": "Kaze ni Naru",
+                "originalArtists": ["つじあやの"]
+            }
+        }"#;
+        let songs: super::Songs = serde_json::from_str(SONG_DATA).unwrap();
+        tracing::info!("Loaded {} songs from test data", songs.len());
+        tracing::trace!("Loaded songs data: {:#?}", songs);
+        songs
+    });
@@ -0,0 +1,109 @@
+// クリップの曲名(自由記述)から楽曲IDを推定する
+// 表記揺れを吸収するため, 正規化した文字列同士の編集距離で類似度を測る
+
+/// 曲名に最も近い楽曲
+#[derive(Debug, Clone, PartialEq)]
+pub struct SongMatch {
+    song_id: super::SongId,
+    /// 類似度. 0.0-1.0で, 1.0は正規化後に完全一致
+    score: f64,
+}
+
+impl SongMatch {
+    pub fn get_song_id(&self) -> &super::SongId {
+        &self.song_id
+    }
+
+    pub fn get_score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl super::Songs {
+    /// `title`に最も近い楽曲を返す
+    ///
+    /// - 日本語, 英語の曲名, 表記揺れのうち最も近いものの類似度を楽曲の類似度とする
+    /// - 類似度が同じときは楽曲IDの順で先のもの
+    /// - 楽曲が1つもないとき, `title`が正規化後に空のときは`None`
+    pub fn find_best_match(&self, title: &str) -> Option<SongMatch> {
+        let title = cmn_rs::text::match_key(title);
+        if title.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<SongMatch> = None;
+        for (song_id, song) in self.sorted_iter() {
+            let score = song
+                .titles()
+                .map(|candidate| {
+                    similarity(&title, &cmn_rs::text::match_key(candidate))
+                })
+                .fold(0.0, f64::max);
+            if best.as_ref().is_none_or(|best| best.score < score) {
+                best = Some(SongMatch {
+                    song_id: song_id.clone(),
+                    score,
+                });
+            }
+        }
+        best
+    }
+}
+
+/// 編集距離をもとにした類似度. 0.0-1.0
+fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / max_len as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("abc", "abc"), 1.0);
+        assert_eq!(similarity("abcd", "abce"), 0.75);
+        assert_eq!(similarity("abc", "xyz"), 0.0);
+        assert_eq!(similarity("", ""), 0.0);
+    }
+
+    #[test]
+    fn test_find_best_match() {
+        let songs = &crate::model::LOADED_SONG_DATA;
+
+        let found = songs.find_best_match("ふわふわ時間").unwrap();
+        assert_eq!(found.get_song_id().as_str(), "fuwafuwa-time");
+        assert_eq!(found.get_score(), 1.0);
+
+        // 表記揺れ
+        let found = songs.find_best_match("Fuwafuwa Time").unwrap();
+        assert_eq!(found.get_song_id().as_str(), "fuwafuwa-time");
+        assert_eq!(found.get_score(), 1.0);
+
+        let found = songs.find_best_match("アイドル (cover)").unwrap();
+        assert_eq!(found.get_song_id().as_str(), "idol");
+        assert!(found.get_score() < 1.0);
+
+        assert!(songs.find_best_match("!!").is_none());
+    }
+}
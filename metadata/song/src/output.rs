@@ -0,0 +1,9 @@
+mod minified_json;
+mod snippet;
+mod songs_min;
+
+pub(crate) use minified_json::BuildMetadata;
+pub(crate) use snippet::Snippet;
+pub(crate) use songs_min::MinSongs;
+
+pub const MIN_SONGS_FILE_NAME: &str = "songs.min.json";
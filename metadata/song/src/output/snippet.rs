@@ -0,0 +1,101 @@
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub(crate) struct Snippet {
+    #[serde(rename = "SongsSnippet")]
+    songs_data: SongDataSnippet,
+    #[serde(flatten)]
+    other: serde_json::Value,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+struct SongDataSnippet {
+    body: Vec<String>,
+    #[serde(flatten)]
+    other: serde_json::Value,
+}
+
+impl Snippet {
+    pub(crate) fn load(
+        songs_data_code_snippets_path: &std::path::Path,
+    ) -> anyhow::Result<Self> {
+        let path = songs_data_code_snippets_path;
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to read snippet file: {} | source: {}",
+                path.display(),
+                e
+            )
+        })?;
+
+        serde_json::from_str(&content).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to deserialize from {} | source: {}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    pub(crate) fn output_json(
+        &mut self,
+        songs_data_code_snippets_path: &std::path::Path,
+        songs: &crate::model::Songs,
+    ) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        self.rewrite_body(songs)?;
+
+        let path = songs_data_code_snippets_path;
+
+        let file = std::fs::File::create(path).with_context(|| {
+            format!("Failed to create/truncate file: {}", path.display())
+        })?;
+        serde_json::to_writer_pretty(file, &self).with_context(|| {
+            format!("Failed to write JSON to file: {}", path.display())
+        })?;
+
+        Ok(())
+    }
+
+    fn rewrite_body(&mut self, songs: &crate::model::Songs) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let song_ids = songs.sorted_ids();
+        let songs_str = song_ids.join(",");
+
+        let body = self
+            .songs_data
+            .body
+            .get_mut(0)
+            .context("Failed to access `body` of songs_data")?;
+
+        *body = format!("\"songId\": \"${{1|{songs_str}|}}\",");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_body_updates_first_entry() {
+        let mut snippet = Snippet {
+            songs_data: SongDataSnippet {
+                body: vec!["placeholder".to_string(), "".to_string()],
+                other: serde_json::json!({
+                    "prefix": "song",
+                }),
+            },
+            other: serde_json::json!({}),
+        };
+
+        snippet
+            .rewrite_body(&crate::model::LOADED_SONG_DATA)
+            .expect("rewrite should succeed");
+
+        assert_eq!(
+            snippet.songs_data.body[0],
+            "\"songId\": \"${1|fuwafuwa-time,idol,kaze-ni-naru|}\","
+        );
+    }
+}
@@ -0,0 +1,59 @@
+/// minifyして出力する用
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub(crate) struct MinSongs(std::collections::BTreeMap<String, MinSong>);
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct MinSong {
+    ja: String,
+    en: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    original_artists: Vec<String>,
+}
+
+impl MinSongs {
+    pub(crate) fn new(songs: &crate::model::Songs) -> Self {
+        let mut map = std::collections::BTreeMap::new();
+
+        for (song_id, song) in songs.iter() {
+            map.insert(
+                song_id.as_str().to_string(),
+                MinSong {
+                    ja: song.ja.clone(),
+                    en: song.en.clone(),
+                    aliases: song.aliases.clone(),
+                    original_artists: song.original_artists.clone(),
+                },
+            );
+        }
+
+        Self(map)
+    }
+
+    pub(crate) fn output_json(
+        &self,
+        path: &std::path::Path,
+        metadata: &crate::output::BuildMetadata,
+    ) -> anyhow::Result<()> {
+        crate::output::minified_json::write_json(path, &self.0, metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_songs_serializes_as_object_map() {
+        let out = MinSongs::new(&crate::model::LOADED_SONG_DATA);
+        let json = serde_json::to_string(&out.0).expect("serialize should succeed");
+
+        assert!(json.contains(
+            r#""idol":{"ja":"アイドル","en":"Idol","originalArtists":["YOASOBI"]}"#
+        ));
+        assert!(json.contains(r#""aliases":["fuwafuwa time"]"#));
+    }
+}
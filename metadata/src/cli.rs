@@ -14,6 +14,20 @@ pub enum Command {
     Artist(ArtistCommand),
     /// Generate video-tag-related metadata.
     Tag(TagCommand),
+    /// Generate song-related metadata.
+    Song(SongCommand),
+    /// Generate JSON Schemas of livers.json, official_channels.json, groups.json and tags.json.
+    Schema(SchemaArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SchemaArgs {
+    /// Directory for the schema files
+    #[arg(long, default_value_t = default_schema_output_dir())]
+    pub(crate) output_dir: String,
+    /// Fail if the schema files are out of date instead of writing them
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) check: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -30,6 +44,10 @@ pub enum ArtistMode {
     Snippet(ArtistSnippetArgs),
     /// Hash the source input set used for artist-derived artifacts.
     HashInputs,
+    /// Rename a liver ID in livers.json, music data and snippets at once.
+    Rename(ArtistRenameArgs),
+    /// Check int IDs of livers and official channels against the lock file.
+    CheckIds(CheckIdsArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -49,6 +67,12 @@ pub struct ArtistMinifyArgs {
     /// File name for the official channels info output
     #[arg(long, default_value_t = default_min_official_channels_file_name())]
     pub(crate) min_official_channels_file_name: String,
+    /// File name for the official channels search index output
+    #[arg(long, default_value_t = default_min_official_channels_search_index_file_name())]
+    pub(crate) min_official_channels_search_index_file_name: String,
+    /// File name for the liver groups output
+    #[arg(long, default_value_t = default_min_groups_file_name())]
+    pub(crate) min_groups_file_name: String,
     /// Opaque data build ID shared by every generated min JSON in the same build
     #[arg(long, value_name = "ID")]
     pub(crate) dataset_build_id: cmn_rs::min_json::DatasetBuildId,
@@ -61,6 +85,45 @@ pub struct ArtistSnippetArgs {
     pub(crate) music_code_snippets_path: String,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct ArtistRenameArgs {
+    /// Current liver ID
+    pub(crate) old: String,
+    /// New liver ID
+    pub(crate) new: String,
+    /// Path to the liver definition file
+    #[arg(long, default_value_t = default_livers_path())]
+    pub(crate) livers_path: String,
+    /// Path to the liver group definition file whose members reference the ID
+    #[arg(long, default_value_t = default_groups_path())]
+    pub(crate) groups_path: String,
+    /// Path to the VS Code code-snippets file to update
+    #[arg(long, default_value_t = default_music_code_snippets_path())]
+    pub(crate) music_code_snippets_path: String,
+    /// Path to the int ID lock file whose entry is moved to the new ID
+    #[arg(long, default_value_t = default_artist_int_id_lock_path())]
+    pub(crate) int_id_lock_path: String,
+    #[command(flatten)]
+    pub(crate) rename: RenameArgs,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct RenameArgs {
+    /// Directory whose JSON files (month files and input files) reference the ID
+    #[arg(long, default_value_t = default_music_data_dir())]
+    pub(crate) music_data_dir: String,
+    /// Print the changes as a diff without writing any file
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) dry_run: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CheckIdsArgs {
+    /// Allocate int IDs for new entries and update the lock file
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) write: bool,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct TagCommand {
     #[command(subcommand)]
@@ -75,6 +138,10 @@ pub enum TagMode {
     Snippet(TagSnippetArgs),
     /// Hash the source input set used for tag-derived artifacts.
     HashInputs,
+    /// Rename a video tag ID in tags.json, music data and snippets at once.
+    Rename(TagRenameArgs),
+    /// Check int IDs of video tags against the lock file.
+    CheckIds(CheckIdsArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -85,6 +152,9 @@ pub struct TagMinifyArgs {
     /// File name for the minified tags output
     #[arg(long, default_value_t = default_min_tags_file_name())]
     pub(crate) min_tags_file_name: String,
+    /// File name for the tags search index output
+    #[arg(long, default_value_t = default_min_tags_search_index_file_name())]
+    pub(crate) min_tags_search_index_file_name: String,
     /// Opaque data build ID shared by every generated min JSON in the same build
     #[arg(long, value_name = "ID")]
     pub(crate) dataset_build_id: cmn_rs::min_json::DatasetBuildId,
@@ -97,24 +167,87 @@ pub struct TagSnippetArgs {
     pub(crate) code_snippets_path: String,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct TagRenameArgs {
+    /// Current video tag ID
+    pub(crate) old: String,
+    /// New video tag ID
+    pub(crate) new: String,
+    /// Path to the video tag definition file
+    #[arg(long, default_value_t = default_tags_path())]
+    pub(crate) tags_path: String,
+    /// Path to the VS Code code-snippets file to update
+    #[arg(long, default_value_t = default_tag_code_snippets_path())]
+    pub(crate) code_snippets_path: String,
+    /// Path to the int ID lock file whose entry is moved to the new ID
+    #[arg(long, default_value_t = default_tag_int_id_lock_path())]
+    pub(crate) int_id_lock_path: String,
+    #[command(flatten)]
+    pub(crate) rename: RenameArgs,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SongCommand {
+    #[command(subcommand)]
+    pub(crate) mode: SongMode,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SongMode {
+    /// Generate minified JSON for the frontend.
+    Minify(SongMinifyArgs),
+    /// Generate/update VS Code snippets.
+    Snippet(SongSnippetArgs),
+    /// Hash the source input set used for song-derived artifacts.
+    HashInputs,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SongMinifyArgs {
+    /// Directory for minified data output
+    #[arg(long, default_value_t = default_song_output_dir())]
+    pub(crate) output_dir: String,
+    /// File name for the minified songs output
+    #[arg(long, default_value_t = default_min_songs_file_name())]
+    pub(crate) min_songs_file_name: String,
+    /// Opaque data build ID shared by every generated min JSON in the same build
+    #[arg(long, value_name = "ID")]
+    pub(crate) dataset_build_id: cmn_rs::min_json::DatasetBuildId,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SongSnippetArgs {
+    /// Path to the VS Code code-snippets file to update
+    #[arg(long, default_value_t = default_song_code_snippets_path())]
+    pub(crate) code_snippets_path: String,
+}
+
 fn default_artist_output_dir() -> String {
     "public/music".to_string()
 }
 
 fn default_min_livers_search_index_file_name() -> String {
-    "livers_search_index.min.json".to_string()
+    artistctl::output::MIN_LIVERS_SEARCH_INDEX_FILE_NAME.to_string()
 }
 
 fn default_min_channels_file_name() -> String {
-    "channels.min.json".to_string()
+    artistctl::output::MIN_CHANNELS_FILE_NAME.to_string()
 }
 
 fn default_min_livers_file_name() -> String {
-    "livers.min.json".to_string()
+    artistctl::output::MIN_LIVERS_FILE_NAME.to_string()
 }
 
 fn default_min_official_channels_file_name() -> String {
-    "official_channels.min.json".to_string()
+    artistctl::output::MIN_OFFICIAL_CHANNELS_FILE_NAME.to_string()
+}
+
+fn default_min_official_channels_search_index_file_name() -> String {
+    artistctl::output::MIN_OFFICIAL_CHANNELS_SEARCH_INDEX_FILE_NAME.to_string()
+}
+
+fn default_min_groups_file_name() -> String {
+    artistctl::output::MIN_GROUPS_FILE_NAME.to_string()
 }
 
 fn default_music_code_snippets_path() -> String {
@@ -130,7 +263,51 @@ fn default_tag_output_dir() -> String {
 }
 
 fn default_min_tags_file_name() -> String {
-    "tags.min.json".to_string()
+    tagctl::output::MIN_TAGS_FILE_NAME.to_string()
+}
+
+fn default_min_tags_search_index_file_name() -> String {
+    tagctl::output::MIN_TAGS_SEARCH_INDEX_FILE_NAME.to_string()
+}
+
+fn default_song_output_dir() -> String {
+    "public/music".to_string()
+}
+
+fn default_min_songs_file_name() -> String {
+    songctl::output::MIN_SONGS_FILE_NAME.to_string()
+}
+
+fn default_song_code_snippets_path() -> String {
+    ".vscode/songs.code-snippets".to_string()
+}
+
+fn default_schema_output_dir() -> String {
+    "tools/schema".to_string()
+}
+
+fn default_livers_path() -> String {
+    "metadata/artist/data/livers.json".to_string()
+}
+
+fn default_groups_path() -> String {
+    "metadata/artist/data/groups.json".to_string()
+}
+
+fn default_artist_int_id_lock_path() -> String {
+    "metadata/artist/data/int_ids.lock.json".to_string()
+}
+
+fn default_tags_path() -> String {
+    "metadata/tag/data/tags.json".to_string()
+}
+
+fn default_tag_int_id_lock_path() -> String {
+    "metadata/tag/data/int_ids.lock.json".to_string()
+}
+
+fn default_music_data_dir() -> String {
+    "music/data".to_string()
 }
 
 impl Cli {
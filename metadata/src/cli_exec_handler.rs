@@ -7,6 +7,8 @@ pub fn cli_exec_handler(cli: crate::cli::Cli) -> Result<(), String> {
                 args.min_channels_file_name,
                 args.min_livers_file_name,
                 args.min_official_channels_file_name,
+                args.min_official_channels_search_index_file_name,
+                args.min_groups_file_name,
                 args.dataset_build_id,
             )
             .map_err(|error| error.to_string()),
@@ -20,11 +22,28 @@ pub fn cli_exec_handler(cli: crate::cli::Cli) -> Result<(), String> {
                 println!("{hash}");
                 Ok(())
             }
+            crate::cli::ArtistMode::Rename(args) => rename(
+                crate::rename::IdKind::Liver,
+                &args.old,
+                &args.new,
+                crate::rename::RenameTargets {
+                    definition_path: args.livers_path.into(),
+                    referencing_definition_paths: vec![args.groups_path.into()],
+                    music_data_dir: args.rename.music_data_dir.into(),
+                    code_snippets_path: args.music_code_snippets_path.into(),
+                    int_id_lock_path: args.int_id_lock_path.into(),
+                },
+                args.rename.dry_run,
+            ),
+            crate::cli::ArtistMode::CheckIds(args) => {
+                check_ids(artistctl::generate::check_ids(args.write), args.write)
+            }
         },
         crate::cli::Command::Tag(command) => match command.mode {
             crate::cli::TagMode::Minify(args) => tagctl::generate::minify(
                 args.output_dir,
                 args.min_tags_file_name,
+                args.min_tags_search_index_file_name,
                 args.dataset_build_id,
             )
             .map_err(|error| error.to_string()),
@@ -38,6 +57,122 @@ pub fn cli_exec_handler(cli: crate::cli::Cli) -> Result<(), String> {
                 println!("{hash}");
                 Ok(())
             }
+            crate::cli::TagMode::Rename(args) => rename(
+                crate::rename::IdKind::VideoTag,
+                &args.old,
+                &args.new,
+                crate::rename::RenameTargets {
+                    definition_path: args.tags_path.into(),
+                    referencing_definition_paths: vec![],
+                    music_data_dir: args.rename.music_data_dir.into(),
+                    code_snippets_path: args.code_snippets_path.into(),
+                    int_id_lock_path: args.int_id_lock_path.into(),
+                },
+                args.rename.dry_run,
+            ),
+            crate::cli::TagMode::CheckIds(args) => {
+                check_ids(tagctl::generate::check_ids(args.write), args.write)
+            }
+        },
+        crate::cli::Command::Song(command) => match command.mode {
+            crate::cli::SongMode::Minify(args) => songctl::generate::minify(
+                args.output_dir,
+                args.min_songs_file_name,
+                args.dataset_build_id,
+            )
+            .map_err(|error| error.to_string()),
+            crate::cli::SongMode::Snippet(args) => {
+                songctl::generate::snippet(args.code_snippets_path)
+                    .map_err(|error| error.to_string())
+            }
+            crate::cli::SongMode::HashInputs => {
+                let hash = songctl::generate::hash_inputs()
+                    .map_err(|error| error.to_string())?;
+                println!("{hash}");
+                Ok(())
+            }
         },
+        crate::cli::Command::Schema(args) => schema(args),
+    }
+}
+
+/// 定義ファイルのJSON Schemaを書き出す. `--check`のときは古いファイルがあれば失敗にする
+fn schema(args: crate::cli::SchemaArgs) -> Result<(), String> {
+    let ctx = tagctl::schema::with_video_tag_ids(Default::default())
+        .and_then(artistctl::schema::with_liver_ids)
+        .map_err(|error| error.to_string())?;
+    let files = artistctl::schema::files(&ctx)
+        .into_iter()
+        .chain(tagctl::schema::files(&ctx))
+        .collect::<Vec<_>>();
+    let paths = cmn_rs::json_schema::write_or_check(
+        std::path::Path::new(&args.output_dir),
+        &files,
+        args.check,
+    )?;
+    let verb = if args.check { "Up to date" } else { "Wrote" };
+    for path in paths {
+        println!("{verb}: {}", path.display());
+    }
+    Ok(())
+}
+
+/// 問題を1行ずつ表示し, エラーがあれば失敗にする
+fn check_ids(
+    diagnostics: anyhow::Result<Vec<cmn_rs::int_id::IntIdDiagnostic>>,
+    write: bool,
+) -> Result<(), String> {
+    let diagnostics = diagnostics.map_err(|error| error.to_string())?;
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error).count();
+    let warnings = diagnostics.len() - errors;
+    if errors > 0 {
+        let hint = if write {
+            ""
+        } else {
+            " (run with --write to update the lock file)"
+        };
+        return Err(format!("{errors} int id errors found{hint}"));
+    }
+    println!("Int IDs are consistent ({warnings} warnings).");
+    Ok(())
+}
+
+fn rename(
+    kind: crate::rename::IdKind,
+    old: &str,
+    new: &str,
+    targets: crate::rename::RenameTargets,
+    dry_run: bool,
+) -> Result<(), String> {
+    let _lock = if dry_run {
+        None
+    } else {
+        targets
+            .lock_music_root()
+            .map_err(|error| error.to_string())?
+    };
+    let plan = crate::rename::plan_rename(kind, old, new, &targets)
+        .map_err(|error| error.to_string())?;
+
+    if dry_run {
+        print!("{}", plan.diff());
+        println!(
+            "Dry run: renaming {kind} `{old}` to `{new}` would change {} references in {} files.",
+            plan.reference_count(),
+            plan.file_count()
+        );
+        return Ok(());
     }
+
+    let (references, files) = (plan.reference_count(), plan.file_count());
+    plan.apply(&targets.journal_path())
+        .map_err(|error| error.to_string())?;
+    println!(
+        "Renamed {kind} `{old}` to `{new}`: {references} references in {files} files."
+    );
+    Ok(())
 }
@@ -1,2 +1,3 @@
 pub mod cli;
 pub mod cli_exec_handler;
+pub mod rename;
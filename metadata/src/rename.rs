@@ -0,0 +1,390 @@
+mod json_ids;
+mod plan;
+
+pub use plan::RenamePlan;
+
+/// 名前を変更するIDの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    /// `livers.json`のライバーID
+    Liver,
+    /// `tags.json`の動画タグID
+    VideoTag,
+}
+
+/// IDの変更で書き換えるファイルの場所
+#[derive(Debug, Clone)]
+pub struct RenameTargets {
+    /// IDを定義しているファイル. `livers.json`, `tags.json`
+    pub definition_path: std::path::PathBuf,
+    /// IDを参照している他の定義ファイル. ライバーIDなら`groups.json`
+    pub referencing_definition_paths: Vec<std::path::PathBuf>,
+    /// 楽曲情報のディレクトリ. 配下の全ての`.json`(月別ファイル, 入力ファイル)が対象
+    pub music_data_dir: std::path::PathBuf,
+    /// IDの一覧を持つVS Codeのスニペットファイル
+    pub code_snippets_path: std::path::PathBuf,
+    /// 整数IDのロックファイル. 旧IDのエントリを新IDに移す
+    pub int_id_lock_path: std::path::PathBuf,
+}
+
+impl RenameTargets {
+    /// `musictl`と同じ楽曲情報のルートのロックを取る
+    ///
+    /// 月別ファイルを書き換えるので, 計画から書き込みまでの間は保持すること.
+    /// 月別ファイルのディレクトリが無いときは`None`.
+    /// いずれの場合も, 前回の書き込みが途中で終了していたときは元に戻してから返す
+    ///
+    /// # Errors
+    /// - 他のプロセスがロックを保持しているとき
+    /// - 途中で終了した書き込みを元に戻せないとき
+    pub fn lock_music_root(
+        &self,
+    ) -> anyhow::Result<Option<musictl::music_file::MusicRootLock>> {
+        let music_root = self.music_data_dir.join(MUSIC_ROOT_DIR_NAME);
+        if !music_root.is_dir() {
+            cmn_rs::atomic_write::recover(&self.journal_path())?;
+            return Ok(None);
+        }
+        Ok(Some(musictl::music_file::MusicRootLock::acquire(
+            &music_root,
+        )?))
+    }
+
+    /// 書き込みのジャーナル
+    ///
+    /// 月別ファイルのディレクトリがあるときは`musictl`と共有し, ロックを取ったときに元に戻せるようにする.
+    /// 無いときは定義ファイルと同じディレクトリに置く
+    pub fn journal_path(&self) -> std::path::PathBuf {
+        let music_root = self.music_data_dir.join(MUSIC_ROOT_DIR_NAME);
+        if music_root.is_dir() {
+            music_root.join(musictl::cfg::MUSIC_ROOT_JOURNAL_FILE_NAME)
+        } else {
+            self.definition_path
+                .with_file_name(musictl::cfg::MUSIC_ROOT_JOURNAL_FILE_NAME)
+        }
+    }
+}
+
+/// `music_data_dir`のうち月別ファイルを置くディレクトリ
+const MUSIC_ROOT_DIR_NAME: &str = "music";
+
+impl IdKind {
+    /// 楽曲情報でIDを参照している配列のキー
+    fn reference_keys(&self) -> &'static [&'static str] {
+        match self {
+            IdKind::Liver => &["liverIds"],
+            IdKind::VideoTag => &["videoTags", "clipTags"],
+        }
+    }
+
+    /// 定義ファイルの中で他のIDを参照している値のキー
+    fn definition_reference_keys(&self) -> &'static [&'static str] {
+        match self {
+            IdKind::Liver => &[],
+            IdKind::VideoTag => &["parent"],
+        }
+    }
+
+    /// 整数IDのロックファイルでの名前空間
+    fn int_id_space_name(&self) -> &'static str {
+        match self {
+            IdKind::Liver => "livers",
+            IdKind::VideoTag => "tags",
+        }
+    }
+
+    /// 他の定義ファイルでIDを参照している配列のキー
+    fn referencing_definition_keys(&self) -> &'static [&'static str] {
+        match self {
+            IdKind::Liver => &["members"],
+            IdKind::VideoTag => &[],
+        }
+    }
+
+    fn render_snippet(
+        &self,
+        definition: &str,
+        code_snippets_path: &std::path::Path,
+    ) -> anyhow::Result<String> {
+        match self {
+            IdKind::Liver => {
+                artistctl::generate::render_snippet(definition, code_snippets_path)
+            }
+            IdKind::VideoTag => {
+                tagctl::generate::render_snippet(definition, code_snippets_path)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for IdKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdKind::Liver => write!(f, "liver"),
+            IdKind::VideoTag => write!(f, "video tag"),
+        }
+    }
+}
+
+/// IDを`old`から`new`に変更するための書き換え内容を作る
+///
+/// ファイルには書き込まない. 書き込みは`RenamePlan::apply`で行う
+///
+/// # Errors
+/// - `new`がIDとして不正な形式のとき
+/// - `old`が定義されていない, または`new`が既に定義されているとき
+/// - 対象のファイルの読み込み, パースに失敗したとき
+pub fn plan_rename(
+    kind: IdKind,
+    old: &str,
+    new: &str,
+    targets: &RenameTargets,
+) -> anyhow::Result<RenamePlan> {
+    if !is_valid_id_format(new) {
+        anyhow::bail!(
+            "invalid {kind} id `{new}`: use lowercase letters, digits and single hyphens"
+        );
+    }
+
+    let mut plan = RenamePlan::default();
+
+    let definition_path = &targets.definition_path;
+    let definition = read_to_string(definition_path)?;
+    let defined_ids = serde_json::from_str::<
+        std::collections::BTreeMap<String, serde::de::IgnoredAny>,
+    >(&definition)?;
+    if !defined_ids.contains_key(old) {
+        anyhow::bail!(
+            "{kind} `{old}` is not defined in {}",
+            definition_path.display()
+        );
+    }
+    if defined_ids.contains_key(new) {
+        anyhow::bail!(
+            "{kind} `{new}` is already defined in {}",
+            definition_path.display()
+        );
+    }
+    let (mut new_definition, mut replaced) =
+        json_ids::rename_top_level_key(&definition, old, new)?;
+    for key in kind.definition_reference_keys() {
+        let (renamed, count) = json_ids::rename_values(&new_definition, key, old, new)?;
+        new_definition = renamed;
+        replaced += count;
+    }
+
+    let snippets_path = &targets.code_snippets_path;
+    let new_snippets = kind.render_snippet(&new_definition, snippets_path)?;
+    plan.push(
+        definition_path.clone(),
+        definition,
+        new_definition,
+        replaced,
+    );
+    plan.push(
+        snippets_path.clone(),
+        read_to_string(snippets_path)?,
+        new_snippets,
+        0,
+    );
+
+    // 整数IDを変えないよう, ロックファイルのエントリも同時に書き換える
+    let lock_path = &targets.int_id_lock_path;
+    if lock_path.exists() {
+        let content = read_to_string(lock_path)?;
+        let mut lock: cmn_rs::int_id::IntIdLock = serde_json::from_str(&content)
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to parse {}: {e}", lock_path.display())
+            })?;
+        if lock.rename(kind.int_id_space_name(), old, new) {
+            plan.push(lock_path.clone(), content, lock.to_pretty_json()?, 1);
+        }
+    }
+
+    for path in &targets.referencing_definition_paths {
+        let content = read_to_string(path)?;
+        let (new_content, replaced) = rename_array_items_by_keys(
+            &content,
+            kind.referencing_definition_keys(),
+            old,
+            new,
+            path,
+        )?;
+        plan.push(path.clone(), content, new_content, replaced);
+    }
+
+    for path in json_files_in(&targets.music_data_dir)? {
+        let content = read_to_string(&path)?;
+        let (new_content, replaced) = rename_array_items_by_keys(
+            &content,
+            kind.reference_keys(),
+            old,
+            new,
+            &path,
+        )?;
+        plan.push(path, content, new_content, replaced);
+    }
+
+    Ok(plan)
+}
+
+/// `keys`それぞれを持つ配列の要素のうち, `old`を`new`に書き換える
+fn rename_array_items_by_keys(
+    content: &str,
+    keys: &[&str],
+    old: &str,
+    new: &str,
+    path: &std::path::Path,
+) -> anyhow::Result<(String, usize)> {
+    let mut new_content = content.to_string();
+    let mut replaced = 0;
+    for key in keys {
+        let (renamed, count) =
+            json_ids::rename_array_items(&new_content, key, old, new).map_err(|e| {
+                anyhow::anyhow!("Failed to parse {}: {e}", path.display())
+            })?;
+        new_content = renamed;
+        replaced += count;
+    }
+    Ok((new_content, replaced))
+}
+
+/// IDとして妥当な形式か. 小文字英数字をハイフン1つで区切ったもの
+fn is_valid_id_format(id: &str) -> bool {
+    !id.is_empty()
+        && id.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+fn read_to_string(path: &std::path::Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))
+}
+
+/// `dir`配下の全ての`.json`ファイルをパス順に返す
+fn json_files_in(dir: &std::path::Path) -> anyhow::Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = std::fs::read_dir(&dir).map_err(|e| {
+            anyhow::anyhow!("Failed to read dir {}: {e}", dir.display())
+        })?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `check-ids`と同じ検査. 定義の全てのIDがロックファイルと一致しているか
+    fn int_id_diagnostics(
+        definition_path: &std::path::Path,
+        lock_path: &std::path::Path,
+        space: cmn_rs::int_id::IntIdSpace,
+    ) -> Vec<String> {
+        let definition: std::collections::BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(definition_path).unwrap())
+                .unwrap();
+        let entries = definition.iter().map(|(id, value)| {
+            let int_id = value["intId"].as_u64().map(|v| u16::try_from(v).unwrap());
+            (id.as_str(), int_id)
+        });
+        let lock = cmn_rs::int_id::IntIdLock::load(lock_path).unwrap();
+        let allocation = lock.allocate(space, entries).unwrap();
+        cmn_rs::int_id::check_lock(lock_path, lock, &[allocation], false)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_moves_int_id_lock_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let targets = RenameTargets {
+            definition_path: tmp.path().join("tags.json"),
+            referencing_definition_paths: vec![],
+            music_data_dir: tmp.path().join("music/data"),
+            code_snippets_path: tmp.path().join("tags.code-snippets"),
+            int_id_lock_path: tmp.path().join("int_ids.lock.json"),
+        };
+        std::fs::write(
+            &targets.definition_path,
+            r#"{
+  "karaoke": { "ja": "歌枠", "en": "karaoke" },
+  "3d": { "ja": "3D", "en": "3D" }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &targets.code_snippets_path,
+            r#"{"VideoTagsSnippet": {"prefix": "vtag", "body": ["placeholder"], "description": ""}}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(&targets.music_data_dir).unwrap();
+        std::fs::write(
+            &targets.int_id_lock_path,
+            "{\n  \"tags\": {\n    \"3d\": 1,\n    \"karaoke\": 0\n  }\n}\n",
+        )
+        .unwrap();
+        let space = cmn_rs::int_id::IntIdSpace {
+            name: "tags",
+            start: 0,
+        };
+        assert!(
+            int_id_diagnostics(
+                &targets.definition_path,
+                &targets.int_id_lock_path,
+                space
+            )
+            .is_empty()
+        );
+
+        let plan =
+            plan_rename(IdKind::VideoTag, "karaoke", "utawaku", &targets).unwrap();
+        assert_eq!(plan.file_count(), 3);
+        plan.apply(&targets.journal_path()).unwrap();
+
+        // 整数IDは変わらず, `check-ids`も通る
+        assert_eq!(
+            std::fs::read_to_string(&targets.int_id_lock_path).unwrap(),
+            "{\n  \"tags\": {\n    \"3d\": 1,\n    \"utawaku\": 0\n  }\n}\n"
+        );
+        assert_eq!(
+            int_id_diagnostics(
+                &targets.definition_path,
+                &targets.int_id_lock_path,
+                space
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_is_valid_id_format() {
+        assert!(is_valid_id_format("riku-tazumi"));
+        assert!(is_valid_id_format("niji-3d"));
+        assert!(!is_valid_id_format(""));
+        assert!(!is_valid_id_format("Riku"));
+        assert!(!is_valid_id_format("riku--tazumi"));
+        assert!(!is_valid_id_format("-riku"));
+        assert!(!is_valid_id_format("riku tazumi"));
+    }
+}
@@ -0,0 +1,259 @@
+// 書式を保ったままJSON中のIDを書き換える
+// serdeで読み書きするとインデントやキーの順序が変わるため, 文字列リテラルのみを置換する
+
+/// 文字列リテラルが現れた位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StrPosition<'a> {
+    /// オブジェクトのキー. `depth`は最上位のオブジェクトが1
+    Key { depth: usize },
+    /// 配列の要素. `array_key`はその配列を値に持つキー
+    ArrayItem { array_key: Option<&'a str> },
+    /// オブジェクトの値. `key`はその値を持つキー
+    Value { key: Option<&'a str> },
+}
+
+#[derive(Debug)]
+enum Frame {
+    Object {
+        /// 次に現れる文字列がキーか
+        expect_key: bool,
+        /// 直前に読んだキー
+        last_key: Option<String>,
+    },
+    Array {
+        /// この配列を値に持つキー
+        key: Option<String>,
+    },
+}
+
+/// 最上位のオブジェクトのキー`old`を`new`に書き換える
+///
+/// - 戻り値は(書き換え後の内容, 書き換えた数)
+pub(super) fn rename_top_level_key(
+    content: &str,
+    old: &str,
+    new: &str,
+) -> anyhow::Result<(String, usize)> {
+    rewrite_strings(content, |position, value| {
+        (position == StrPosition::Key { depth: 1 } && value == old)
+            .then(|| new.to_string())
+    })
+}
+
+/// キー`array_key`を持つ配列の要素のうち, `old`を`new`に書き換える
+///
+/// - 配列はどの深さにあってもよい
+/// - 戻り値は(書き換え後の内容, 書き換えた数)
+pub(super) fn rename_array_items(
+    content: &str,
+    array_key: &str,
+    old: &str,
+    new: &str,
+) -> anyhow::Result<(String, usize)> {
+    rewrite_strings(content, |position, value| {
+        (position
+            == StrPosition::ArrayItem {
+                array_key: Some(array_key),
+            }
+            && value == old)
+            .then(|| new.to_string())
+    })
+}
+
+/// キー`key`の文字列の値のうち, `old`を`new`に書き換える
+///
+/// - オブジェクトはどの深さにあってもよい
+/// - 戻り値は(書き換え後の内容, 書き換えた数)
+pub(super) fn rename_values(
+    content: &str,
+    key: &str,
+    old: &str,
+    new: &str,
+) -> anyhow::Result<(String, usize)> {
+    rewrite_strings(content, |position, value| {
+        (position == StrPosition::Value { key: Some(key) } && value == old)
+            .then(|| new.to_string())
+    })
+}
+
+/// 文字列リテラルを走査し, `replace`が`Some`を返したものを置き換える
+fn rewrite_strings(
+    content: &str,
+    mut replace: impl FnMut(StrPosition, &str) -> Option<String>,
+) -> anyhow::Result<(String, usize)> {
+    // 以降の走査は正しいJSONであることを前提とする
+    serde_json::from_str::<serde::de::IgnoredAny>(content)?;
+
+    let bytes = content.as_bytes();
+    let mut out = String::with_capacity(content.len());
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut replaced = 0;
+    let mut copied_until = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => {
+                let key = match stack.last() {
+                    Some(Frame::Object { last_key, .. }) => last_key.clone(),
+                    _ => None,
+                };
+                stack.push(if bytes[i] == b'{' {
+                    Frame::Object {
+                        expect_key: true,
+                        last_key: None,
+                    }
+                } else {
+                    Frame::Array { key }
+                });
+                i += 1;
+            }
+            b'}' | b']' => {
+                stack.pop();
+                i += 1;
+            }
+            b',' => {
+                if let Some(Frame::Object { expect_key, .. }) = stack.last_mut() {
+                    *expect_key = true;
+                }
+                i += 1;
+            }
+            b':' => {
+                if let Some(Frame::Object { expect_key, .. }) = stack.last_mut() {
+                    *expect_key = false;
+                }
+                i += 1;
+            }
+            b'"' => {
+                let end = string_end(bytes, i);
+                let literal = &content[i..end];
+                let value: String = serde_json::from_str(literal)?;
+
+                let depth = stack.len();
+                let position = match stack.last() {
+                    Some(Frame::Object {
+                        expect_key: true, ..
+                    }) => StrPosition::Key { depth },
+                    Some(Frame::Array { key }) => StrPosition::ArrayItem {
+                        array_key: key.as_deref(),
+                    },
+                    Some(Frame::Object { last_key, .. }) => StrPosition::Value {
+                        key: last_key.as_deref(),
+                    },
+                    None => StrPosition::Value { key: None },
+                };
+
+                if let Some(new) = replace(position, &value) {
+                    out.push_str(&content[copied_until..i]);
+                    out.push_str(&serde_json::to_string(&new)?);
+                    copied_until = end;
+                    replaced += 1;
+                }
+                if let Some(Frame::Object {
+                    expect_key: true,
+                    last_key,
+                }) = stack.last_mut()
+                {
+                    *last_key = Some(value);
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    out.push_str(&content[copied_until..]);
+
+    Ok((out, replaced))
+}
+
+/// `start`の`"`から始まる文字列リテラルの終端(閉じ`"`の次)の位置
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONTH_FILE: &str = r#"[
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "title": "liverIds \"old\"",
+    "videoTags": ["karaoke", "old"],
+    "clips": [
+      {
+        "songTitle": "old",
+        "liverIds": [
+          "old",
+          "other"
+        ],
+        "externalArtistsName": ["old"]
+      }
+    ]
+  }
+]
+"#;
+
+    #[test]
+    fn test_rename_array_items_keeps_format() {
+        let (out, replaced) =
+            rename_array_items(MONTH_FILE, "liverIds", "old", "new").unwrap();
+        assert_eq!(replaced, 1);
+        assert_eq!(
+            out,
+            MONTH_FILE.replace("          \"old\",", "          \"new\",")
+        );
+
+        let (out, replaced) =
+            rename_array_items(MONTH_FILE, "videoTags", "old", "new").unwrap();
+        assert_eq!(replaced, 1);
+        assert!(out.contains(r#""videoTags": ["karaoke", "new"],"#));
+        assert!(out.contains(r#""songTitle": "old","#));
+    }
+
+    #[test]
+    fn test_rename_top_level_key() {
+        let content = r#"{
+  "old": { "ja": "old", "nested": { "old": 1 } },
+  "other": {}
+}"#;
+        let (out, replaced) = rename_top_level_key(content, "old", "new").unwrap();
+        assert_eq!(replaced, 1);
+        assert_eq!(
+            out,
+            r#"{
+  "new": { "ja": "old", "nested": { "old": 1 } },
+  "other": {}
+}"#
+        );
+    }
+
+    #[test]
+    fn test_rename_values() {
+        let content = r#"{
+  "old": { "ja": "old", "intId": 0 },
+  "child": { "ja": "child", "parent": "old", "tags": ["old"] }
+}"#;
+        let (out, replaced) = rename_values(content, "parent", "old", "new").unwrap();
+        assert_eq!(replaced, 1);
+        assert_eq!(
+            out,
+            content.replace(r#""parent": "old""#, r#""parent": "new""#)
+        );
+    }
+
+    #[test]
+    fn test_rewrite_strings_rejects_invalid_json() {
+        assert!(rename_top_level_key("{\"old\": ", "old", "new").is_err());
+    }
+}
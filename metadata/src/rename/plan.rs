@@ -0,0 +1,73 @@
+/// IDの変更に伴うファイルの書き換え内容
+#[derive(Debug, Clone, Default)]
+pub struct RenamePlan {
+    changes: Vec<FileChange>,
+}
+
+/// 1ファイル分の書き換え内容
+#[derive(Debug, Clone)]
+struct FileChange {
+    path: std::path::PathBuf,
+    before: String,
+    after: String,
+    /// 書き換えた参照の数
+    replaced: usize,
+}
+
+impl RenamePlan {
+    /// 書き換えるファイルを追加する. 内容が変わらないときは何もしない
+    pub(super) fn push(
+        &mut self,
+        path: std::path::PathBuf,
+        before: String,
+        after: String,
+        replaced: usize,
+    ) {
+        if before != after {
+            self.changes.push(FileChange {
+                path,
+                before,
+                after,
+                replaced,
+            });
+        }
+    }
+
+    /// 書き換えるファイルの数
+    pub fn file_count(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// 書き換える参照の数
+    pub fn reference_count(&self) -> usize {
+        self.changes.iter().map(|change| change.replaced).sum()
+    }
+
+    /// 全ての書き換えをunified diff形式で返す
+    pub fn diff(&self) -> String {
+        self.changes
+            .iter()
+            .map(|change| {
+                cmn_rs::diff::unified(
+                    &change.path.to_string_lossy(),
+                    &change.before,
+                    &change.after,
+                )
+            })
+            .collect()
+    }
+
+    /// 全てのファイルを書き換える
+    ///
+    /// いずれかの書き込みに失敗したときは, どのファイルも書き換えない.
+    /// 途中で終了したときは`journal`から元に戻せる
+    pub fn apply(self, journal: &std::path::Path) -> anyhow::Result<()> {
+        let files = self
+            .changes
+            .into_iter()
+            .map(|change| (change.path, change.after.into_bytes()))
+            .collect::<Vec<_>>();
+        cmn_rs::atomic_write::write_files(journal, &files)
+            .map_err(|e| anyhow::anyhow!("Failed to write renamed files: {e}"))
+    }
+}
@@ -17,3 +17,7 @@ fn data_path(default_file_name: &str, env_key: &str) -> std::path::PathBuf {
 pub(crate) fn video_tag_data_path() -> std::path::PathBuf {
     data_path("tags.json", "VIDEO_TAG_SET_PATH")
 }
+
+pub(crate) fn int_id_lock_path() -> std::path::PathBuf {
+    data_path("int_ids.lock.json", "VIDEO_TAG_INT_ID_LOCK_PATH")
+}
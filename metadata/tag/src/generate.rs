@@ -8,27 +8,100 @@ pub fn snippet(code_snippets_path: String) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 動画タグ定義(`tags.json`の内容)から, 書き換え後のスニペットファイルの内容を返す
+///
+/// - ファイルには書き込まない
+pub fn render_snippet(
+    video_tags_json: &str,
+    code_snippets_path: &std::path::Path,
+) -> anyhow::Result<String> {
+    let video_tags: crate::model::VideoTags = serde_json::from_str(video_tags_json)?;
+    crate::output::Snippet::load(code_snippets_path)?.render(&video_tags)
+}
+
+/// `hash_inputs`, `minify`が読み込んだ定義ファイル
+///
+/// テストでは定義ファイルを読まないため空
+pub fn definition_paths() -> Vec<std::path::PathBuf> {
+    if cfg!(any(test, feature = "test-helpers")) {
+        return Vec::new();
+    }
+    vec![crate::cfg::video_tag_data_path()]
+}
+
 pub fn hash_inputs() -> anyhow::Result<String> {
+    hash_inputs_at(&crate::cfg::int_id_lock_path())
+}
+
+/// [`hash_inputs`]のロックファイルを`int_id_lock_path`にしたもの
+pub fn hash_inputs_at(int_id_lock_path: &std::path::Path) -> anyhow::Result<String> {
     let video_tags = canonical_video_tags(&crate::model::LOADED_VIDEO_TAG_DATA);
+    // 整数IDの割り当ては`tags.min.json`に含まれる
+    let int_id_lock = cmn_rs::int_id::IntIdLock::load(int_id_lock_path)?;
 
     let mut builder =
         cmn_rs::min_json::InputSetHashBuilder::new("cliplayer:tag-inputs");
     builder.add_serializable("video_tags", &video_tags)?;
+    builder.add_serializable("int_ids", &int_id_lock)?;
     Ok(builder.finish_hex())
 }
 
+/// 動画タグの整数IDを検査する
+///
+/// `write`のときは衝突が無ければロックファイルを更新する
+pub fn check_ids(write: bool) -> anyhow::Result<Vec<cmn_rs::int_id::IntIdDiagnostic>> {
+    check_ids_at(&crate::cfg::int_id_lock_path(), write)
+}
+
+/// [`check_ids`]のロックファイルを`path`にしたもの
+pub fn check_ids_at(
+    path: &std::path::Path,
+    write: bool,
+) -> anyhow::Result<Vec<cmn_rs::int_id::IntIdDiagnostic>> {
+    let lock = cmn_rs::int_id::IntIdLock::load(path)?;
+    let allocation = crate::model::LOADED_VIDEO_TAG_DATA.allocate_int_ids(&lock)?;
+
+    Ok(cmn_rs::int_id::check_lock(
+        path,
+        lock,
+        &[allocation],
+        write,
+    )?)
+}
+
 pub fn minify(
     output_dir: String,
     min_tags_file_name: String,
+    min_tags_search_index_file_name: String,
+    dataset_build_id: cmn_rs::min_json::DatasetBuildId,
+) -> anyhow::Result<()> {
+    minify_at(
+        &crate::cfg::int_id_lock_path(),
+        output_dir,
+        min_tags_file_name,
+        min_tags_search_index_file_name,
+        dataset_build_id,
+    )
+}
+
+/// [`minify`]のロックファイルを`int_id_lock_path`にしたもの
+pub fn minify_at(
+    int_id_lock_path: &std::path::Path,
+    output_dir: String,
+    min_tags_file_name: String,
+    min_tags_search_index_file_name: String,
     dataset_build_id: cmn_rs::min_json::DatasetBuildId,
 ) -> anyhow::Result<()> {
     tracing::info!("Start generate tag min data...");
 
     let video_tags = crate::model::LOADED_VIDEO_TAG_DATA.clone();
+    let int_id_lock = cmn_rs::int_id::IntIdLock::load(int_id_lock_path)?;
     minify_impl(
         &video_tags,
+        &int_id_lock,
         &output_dir,
         &min_tags_file_name,
+        &min_tags_search_index_file_name,
         dataset_build_id,
     )?;
 
@@ -48,14 +121,30 @@ fn generate_snippet_impl(
 
 fn minify_impl(
     video_tags: &crate::model::VideoTags,
+    int_id_lock: &cmn_rs::int_id::IntIdLock,
     output_dir: &str,
     min_tags_file_name: &str,
+    min_tags_search_index_file_name: &str,
     dataset_build_id: cmn_rs::min_json::DatasetBuildId,
 ) -> anyhow::Result<()> {
-    let output = crate::output::MinVideoTags::new(video_tags);
+    let int_ids = video_tags.allocate_int_ids(int_id_lock)?;
+    if let Some((value, ids)) = int_ids.collisions().first() {
+        anyhow::bail!("video tag int id {value} is shared by {}", ids.join(", "));
+    }
+    if !int_ids.is_locked() {
+        tracing::warn!(
+            "int_ids.lock.json is out of date; run `metadata tag check-ids --write`"
+        );
+    }
+
+    let output = crate::output::MinVideoTags::new(video_tags, &int_ids);
     let metadata = crate::output::BuildMetadata::new(dataset_build_id);
     let path = std::path::Path::new(output_dir).join(min_tags_file_name);
-    output.output_json(&path, &metadata)
+    output.output_json(&path, &metadata)?;
+
+    let search_index = crate::output::TagsSearchIndex::new(video_tags);
+    let path = std::path::Path::new(output_dir).join(min_tags_search_index_file_name);
+    search_index.output_json(&path, &metadata)
 }
 
 fn canonical_video_tags(
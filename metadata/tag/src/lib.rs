@@ -3,3 +3,4 @@ mod cfg;
 pub mod generate;
 pub mod model;
 pub mod output;
+pub mod schema;
@@ -3,7 +3,13 @@
 pub struct VideoTags(std::collections::HashMap<VideoTagId, VideoTag>);
 
 impl VideoTags {
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (&VideoTagId, &VideoTag)> {
+    pub(crate) const INT_ID_SPACE: cmn_rs::int_id::IntIdSpace =
+        cmn_rs::int_id::IntIdSpace {
+            name: "tags",
+            start: 0,
+        };
+
+    pub fn iter(&self) -> impl Iterator<Item = (&VideoTagId, &VideoTag)> {
         self.0.iter()
     }
 
@@ -26,6 +32,70 @@ impl VideoTags {
         ids.sort_unstable();
         ids
     }
+
+    /// (タグID, 定義で指定された整数ID)
+    pub(crate) fn int_id_entries(&self) -> impl Iterator<Item = (&str, Option<u16>)> {
+        self.0.iter().map(|(id, tag)| (id.as_str(), tag.int_id))
+    }
+
+    /// 整数IDをロックファイルの内容をもとに割り当てる
+    pub(crate) fn allocate_int_ids(
+        &self,
+        lock: &cmn_rs::int_id::IntIdLock,
+    ) -> anyhow::Result<cmn_rs::int_id::IntIdAllocation> {
+        lock.allocate(Self::INT_ID_SPACE, self.int_id_entries())
+            .map_err(anyhow::Error::msg)
+    }
+
+    /// 親タグのID. 親が無い, または未定義のタグのとき`None`
+    pub fn parent_of(&self, id: &str) -> Option<&VideoTagId> {
+        self.0.get(id).and_then(|tag| tag.parent.as_ref())
+    }
+
+    /// 祖先タグのIDを近い順に返す. 自身は含まない
+    pub fn ancestors_of(&self, id: &str) -> Vec<&VideoTagId> {
+        let mut ancestors = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parent_of(current) {
+            ancestors.push(parent);
+            current = parent.as_str();
+        }
+        ancestors
+    }
+
+    /// フロントでの再生をブロックするタグか. 未定義のタグは`false`
+    pub fn is_blocked(&self, id: &str) -> bool {
+        self.0
+            .get(id)
+            .is_some_and(|tag| tag.blocked.unwrap_or(false))
+    }
+
+    /// 親タグが定義済みで, 親をたどって循環しないことを検証
+    fn validate_hierarchy(&self) -> Result<(), String> {
+        for id in self.sorted_ids() {
+            let mut path = vec![id];
+            let mut current = id;
+            while let Some(parent) = self.0.get(current).and_then(|t| t.parent.as_ref())
+            {
+                let parent = parent.as_str();
+                if !self.0.contains_key(parent) {
+                    return Err(format!(
+                        "parent `{parent}` of video tag `{current}` is not defined"
+                    ));
+                }
+                if path.contains(&parent) {
+                    path.push(parent);
+                    return Err(format!(
+                        "video tag hierarchy has a cycle: {}",
+                        path.join(" -> ")
+                    ));
+                }
+                path.push(parent);
+                current = parent;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// デシリアライズ時は VideoTagId のバリデーションを一時的に迂回するため
@@ -44,7 +114,11 @@ impl<'de> serde::Deserialize<'de> for VideoTags {
             .into_iter()
             .map(|(id, tag)| (VideoTagId::from_raw(id), tag))
             .collect::<HashMap<VideoTagId, VideoTag>>();
-        Ok(VideoTags(map))
+        let video_tags = VideoTags(map);
+        video_tags
+            .validate_hierarchy()
+            .map_err(serde::de::Error::custom)?;
+        Ok(video_tags)
     }
 }
 
@@ -59,8 +133,54 @@ pub struct VideoTag {
     pub(crate) en: String,
     /// フロントでの再生をブロックするか
     pub(crate) blocked: Option<bool>,
-    /// 整数ID
-    pub(crate) int_id: u16,
+    /// 整数ID. 省略時はロックファイルから割り当てる
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) int_id: Option<u16>,
+    /// 親タグ. このタグを付けた動画, クリップは親タグも持つ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "deserialize_parent")]
+    pub(crate) parent: Option<VideoTagId>,
+}
+
+impl cmn_rs::json_schema::JsonSchema for VideoTags {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::map_of("^[a-z0-9-]+$", VideoTag::json_schema(ctx))
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for VideoTag {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("ja", String::json_schema(ctx))
+            .required("en", String::json_schema(ctx))
+            .nullable("blocked", bool::json_schema(ctx))
+            .nullable("intId", u16::json_schema(ctx))
+            .nullable("parent", VideoTagId::json_schema(ctx))
+            .build()
+    }
+}
+
+impl VideoTag {
+    pub fn ja(&self) -> &str {
+        &self.ja
+    }
+
+    pub fn en(&self) -> &str {
+        &self.en
+    }
+
+    pub fn parent(&self) -> Option<&VideoTagId> {
+        self.parent.as_ref()
+    }
+}
+
+/// 親タグの存在は`VideoTags`の読み込み時にまとめて検証する
+fn deserialize_parent<'de, D>(deserializer: D) -> Result<Option<VideoTagId>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let parent: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(parent.map(VideoTagId::from_raw))
 }
 
 /// 動画タグID
@@ -91,6 +211,18 @@ impl VideoTagId {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for VideoTagId {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        ctx.id(crate::schema::VIDEO_TAG_IDS)
+    }
+}
+
+impl std::borrow::Borrow<str> for VideoTagId {
+    fn borrow(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for VideoTagId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -117,6 +249,11 @@ impl VideoTagId {
     pub fn self_3() -> Self {
         Self::new("acoustic").unwrap()
     }
+
+    /// `3d-debut`. 親タグは`3d`
+    pub fn self_4() -> Self {
+        Self::new("3d-debut").unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -127,7 +264,78 @@ mod tests {
     fn test_sorted_ids() {
         let sorted = crate::model::LOADED_VIDEO_TAG_DATA.sorted_ids();
 
-        assert_eq!(sorted, vec!["3d", "acoustic", "karaoke"]);
+        assert_eq!(sorted, vec!["3d", "3d-debut", "acoustic", "karaoke"]);
+    }
+
+    #[test]
+    fn test_parent_and_ancestors() {
+        let tags = &crate::model::LOADED_VIDEO_TAG_DATA;
+        assert_eq!(
+            tags.parent_of("3d-debut").map(VideoTagId::as_str),
+            Some("3d")
+        );
+        assert_eq!(tags.parent_of("3d"), None);
+        assert_eq!(tags.parent_of("unknown"), None);
+
+        let ancestors = tags.ancestors_of("3d-debut");
+        assert_eq!(ancestors, vec![&VideoTagId::self_2()]);
+        assert!(tags.ancestors_of("karaoke").is_empty());
+    }
+
+    #[test]
+    fn test_is_blocked() {
+        let tags = &crate::model::LOADED_VIDEO_TAG_DATA;
+        assert!(tags.is_blocked("acoustic"));
+        assert!(!tags.is_blocked("karaoke"));
+        assert!(!tags.is_blocked("unknown"));
+    }
+
+    #[test]
+    fn test_deserialize_nested_hierarchy() {
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "intId": 0 },
+            "b": { "ja": "b", "en": "b", "intId": 1, "parent": "a" },
+            "c": { "ja": "c", "en": "c", "intId": 2, "parent": "b" }
+        }"#;
+        let tags: VideoTags = serde_json::from_str(json).unwrap();
+        let ancestors = tags
+            .ancestors_of("c")
+            .into_iter()
+            .map(VideoTagId::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(ancestors, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_parent() {
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "intId": 0, "parent": "missing" }
+        }"#;
+        let err = serde_json::from_str::<VideoTags>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("parent `missing` of video tag `a` is not defined")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_cycle() {
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "intId": 0, "parent": "c" },
+            "b": { "ja": "b", "en": "b", "intId": 1, "parent": "a" },
+            "c": { "ja": "c", "en": "c", "intId": 2, "parent": "b" }
+        }"#;
+        let err = serde_json::from_str::<VideoTags>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("video tag hierarchy has a cycle: a -> c -> b -> a")
+        );
+
+        let json = r#"{
+            "a": { "ja": "a", "en": "a", "intId": 0, "parent": "a" }
+        }"#;
+        let err = serde_json::from_str::<VideoTags>(json).unwrap_err();
+        assert!(err.to_string().contains("cycle: a -> a"));
     }
 
     #[test]
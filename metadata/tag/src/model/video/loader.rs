@@ -39,6 +39,12 @@ pub static LOADED_VIDEO_TAG_DATA: once_cell::sync::Lazy<super::VideoTags> =
                 "en": "acoustic",
                 "blocked": true,
                 "intId": 2
+            },
+            "3d-debut": {
+                "ja": "3Dお披露目",
+                "en": "3D Debut",
+                "intId": 3,
+                "parent": "3d"
             }
         }"#;
         let video_tags: super::VideoTags =
@@ -1,7 +1,12 @@
 mod minified_json;
 mod snippet;
+mod tags_search_index;
 mod video_tags_min;
 
 pub(crate) use minified_json::BuildMetadata;
 pub(crate) use snippet::Snippet;
+pub(crate) use tags_search_index::TagsSearchIndex;
 pub(crate) use video_tags_min::MinVideoTags;
+
+pub const MIN_TAGS_FILE_NAME: &str = "tags.min.json";
+pub const MIN_TAGS_SEARCH_INDEX_FILE_NAME: &str = "tags_search_index.min.json";
@@ -56,6 +56,15 @@ impl Snippet {
         Ok(())
     }
 
+    /// `video_tags`をもとに書き換えたスニペットを, ファイルに書き出す形式の文字列で返す
+    pub(crate) fn render(
+        mut self,
+        video_tags: &crate::model::VideoTags,
+    ) -> anyhow::Result<String> {
+        self.rewrite_body(video_tags)?;
+        Ok(serde_json::to_string_pretty(&self)?)
+    }
+
     fn rewrite_body(
         &mut self,
         video_tags: &crate::model::VideoTags,
@@ -98,7 +107,7 @@ mod tests {
 
         assert_eq!(
             snippet.video_tags_data.body[0],
-            "\"${1|3d,acoustic,karaoke|}\","
+            "\"${1|3d,3d-debut,acoustic,karaoke|}\","
         );
     }
 }
@@ -0,0 +1,69 @@
+/// タグ名の検索インデックス
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub(crate) struct TagsSearchIndex(Vec<TagSearchIndexEntry>);
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TagSearchIndexEntry {
+    key: String,
+    tag_id: String,
+    #[serde(flatten)]
+    origin: Option<cmn_rs::search_key::KeyOrigin>,
+}
+
+impl TagsSearchIndex {
+    pub(crate) fn new(video_tags: &crate::model::VideoTags) -> Self {
+        let mut index = Vec::new();
+
+        for (tag_id, tag) in video_tags.iter() {
+            let mut keys = cmn_rs::search_key::SearchKeys::default();
+            keys.push(&tag.ja);
+            keys.push(&tag.en);
+
+            for key in keys.into_keys() {
+                index.push(TagSearchIndexEntry {
+                    key: key.key,
+                    tag_id: tag_id.as_str().to_string(),
+                    origin: key.origin,
+                });
+            }
+        }
+
+        index.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.tag_id.cmp(&b.tag_id)));
+
+        Self(index)
+    }
+
+    pub(crate) fn output_json(
+        &self,
+        path: &std::path::Path,
+        metadata: &crate::output::BuildMetadata,
+    ) -> anyhow::Result<()> {
+        crate::output::minified_json::write_json(path, &self.0, metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_search_index_generates_variants() {
+        let index = TagsSearchIndex::new(&crate::model::LOADED_VIDEO_TAG_DATA);
+        let json = serde_json::to_value(&index.0).expect("serialize should succeed");
+        let entries = json.as_array().unwrap();
+
+        let original = entries.iter().find(|entry| entry["key"] == "3D").unwrap();
+        assert_eq!(original["tagId"], "3d");
+        assert!(original.get("variant").is_none());
+
+        let lowercase = entries
+            .iter()
+            .find(|entry| entry["key"] == "3d debut")
+            .unwrap();
+        assert_eq!(lowercase["tagId"], "3d-debut");
+        assert_eq!(lowercase["variant"], "lowercase");
+        assert_eq!(lowercase["variantOf"], "3D Debut");
+    }
+}
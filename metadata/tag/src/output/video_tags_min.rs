@@ -11,10 +11,15 @@ struct MinVideoTag {
     int_id: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
     blocked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
 }
 
 impl MinVideoTags {
-    pub(crate) fn new(video_tags: &crate::model::VideoTags) -> Self {
+    pub(crate) fn new(
+        video_tags: &crate::model::VideoTags,
+        int_ids: &cmn_rs::int_id::IntIdAllocation,
+    ) -> Self {
         let mut map = std::collections::BTreeMap::new();
 
         for (tag_id, tag) in video_tags.iter() {
@@ -23,8 +28,11 @@ impl MinVideoTags {
                 MinVideoTag {
                     ja: tag.ja.clone(),
                     en: tag.en.clone(),
-                    int_id: tag.int_id,
+                    int_id: int_ids
+                        .get(tag_id.as_str())
+                        .expect("every tag has an allocated int id"),
                     blocked: tag.blocked,
+                    parent: tag.parent.as_ref().map(|id| id.as_str().to_string()),
                 },
             );
         }
@@ -47,7 +55,11 @@ mod tests {
 
     #[test]
     fn test_min_video_tags_serializes_as_object_map() {
-        let out = MinVideoTags::new(&crate::model::LOADED_VIDEO_TAG_DATA);
+        let video_tags = &crate::model::LOADED_VIDEO_TAG_DATA;
+        let int_ids = video_tags
+            .allocate_int_ids(&cmn_rs::int_id::IntIdLock::default())
+            .unwrap();
+        let out = MinVideoTags::new(video_tags, &int_ids);
         let json = serde_json::to_string(&out.0).expect("serialize should succeed");
 
         // keys come from tag ids
@@ -58,5 +70,9 @@ mod tests {
         assert!(json.contains("\"intId\""));
         assert!(json.contains("\"ja\""));
         assert!(json.contains("\"en\""));
+
+        // parent is written only for child tags
+        assert_eq!(json.matches("\"parent\"").count(), 1);
+        assert!(json.contains("\"parent\":\"3d\""));
     }
 }
@@ -0,0 +1,115 @@
+/// 動画タグIDの列挙の名前
+pub const VIDEO_TAG_IDS: &str = "videoTagIds";
+
+/// `tags.json`のスキーマ. 親タグの補完のため, `ctx`にタグIDの列挙を加えておく
+pub fn files(
+    ctx: &cmn_rs::json_schema::SchemaContext,
+) -> Vec<cmn_rs::json_schema::SchemaFile> {
+    vec![cmn_rs::json_schema::SchemaFile::new(
+        "video_tags.schema.json",
+        "VideoTags",
+        <crate::model::VideoTags as cmn_rs::json_schema::JsonSchema>::json_schema(ctx),
+    )]
+}
+
+/// 動画タグIDの列挙を加える
+///
+/// テストでも実際のIDで生成できるよう, `LOADED_VIDEO_TAG_DATA`でなく定義ファイルを読む
+pub fn with_video_tag_ids(
+    ctx: cmn_rs::json_schema::SchemaContext,
+) -> anyhow::Result<cmn_rs::json_schema::SchemaContext> {
+    use anyhow::Context;
+
+    let path = crate::cfg::video_tag_data_path();
+    let content = std::fs::read_to_string(&path).with_context(|| {
+        format!("Failed to read video tags data from {}", path.display())
+    })?;
+    let video_tags: crate::model::VideoTags = serde_json::from_str(&content)?;
+    Ok(ctx.with_enum(VIDEO_TAG_IDS, video_tags.sorted_ids()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> cmn_rs::json_schema::SchemaContext {
+        with_video_tag_ids(cmn_rs::json_schema::SchemaContext::default()).unwrap()
+    }
+
+    #[test]
+    fn test_committed_schemas_are_up_to_date() {
+        let dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tools/schema");
+        let stale = cmn_rs::json_schema::stale_files(&dir, &files(&ctx())).unwrap();
+        assert!(
+            stale.is_empty(),
+            "schema files are out of date, run `metadata schema`: {stale:?}"
+        );
+    }
+
+    #[test]
+    fn test_data_file_matches_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(&files(&ctx())[0].render()).unwrap();
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("data/tags.json");
+        let content = std::fs::read_to_string(path).unwrap();
+        // 定義ファイルそのものと, モデルで読み込んでシリアライズし直した値の両方を検証する
+        for instance in [
+            serde_json::from_str(&content).unwrap(),
+            cmn_rs::json_schema::round_trip::<crate::model::VideoTags>(&content),
+        ] {
+            assert_eq!(
+                cmn_rs::json_schema::validate(&schema, &instance),
+                Vec::<String>::new()
+            );
+        }
+    }
+
+    #[test]
+    fn test_video_tag_schema_covers_every_field() {
+        let schema =
+            <crate::model::VideoTags as cmn_rs::json_schema::JsonSchema>::json_schema(
+                &ctx(),
+            );
+        let instance = cmn_rs::json_schema::round_trip::<crate::model::VideoTags>(
+            r#"{
+                "3d": {"ja": "3D", "en": "3D"},
+                "3d-debut": {"ja": "3Dお披露目", "en": "3D Debut", "blocked": true, "intId": 4, "parent": "3d"}
+            }"#,
+        );
+        assert_eq!(
+            cmn_rs::json_schema::property_name_mismatches(
+                &schema["patternProperties"]["^[a-z0-9-]+$"],
+                &instance["3d-debut"]
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &instance),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_parent_must_be_defined_tag() {
+        let schema =
+            <crate::model::VideoTag as cmn_rs::json_schema::JsonSchema>::json_schema(
+                &ctx(),
+            );
+        let tag = serde_json::json!({
+            "ja": "3Dお披露目", "en": "3D Debut", "blocked": true, "intId": 4, "parent": "3d"
+        });
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &tag),
+            Vec::<String>::new()
+        );
+
+        let tag =
+            serde_json::json!({ "ja": "a", "en": "a", "parent": "undefined-tag" });
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &tag),
+            vec!["/parent: does not match any of the schemas"]
+        );
+    }
+}
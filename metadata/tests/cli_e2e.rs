@@ -26,6 +26,18 @@ const TAG_SNIPPET_JSON: &str = r#"{
 }
 "#;
 
+const SONG_SNIPPET_JSON: &str = r#"{
+  "SongsSnippet": {
+    "prefix": "song",
+    "body": ["placeholder"],
+    "description": "test snippet"
+  },
+  "keep": {
+    "x": 1
+  }
+}
+"#;
+
 fn write_text_file(path: &std::path::Path, content: &str) {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).unwrap();
@@ -60,6 +72,10 @@ fn test_metadata_artist_minify_command_generates_outputs() {
         .arg("livers.min.json")
         .arg("--min-official-channels-file-name")
         .arg("official_channels.min.json")
+        .arg("--min-official-channels-search-index-file-name")
+        .arg("official_channels_search_index.min.json")
+        .arg("--min-groups-file-name")
+        .arg("groups.min.json")
         .arg("--dataset-build-id")
         .arg(TEST_DATASET_BUILD_ID);
 
@@ -79,9 +95,29 @@ fn test_metadata_artist_minify_command_generates_outputs() {
     assert_eq!(channels["schemaVersion"], 1);
     assert!(channels["data"].is_object());
 
+    let groups = read_json(&out_dir.join("groups.min.json"));
+    assert_eq!(groups["schemaVersion"], 1);
+    assert_eq!(
+        groups["data"]["test-unit"]["members"],
+        serde_json::json!(["yugamin", "riku-tazumi"])
+    );
+    assert_eq!(groups["data"]["test-gen-1"]["kind"], "generation");
+
     let search_index = read_json(&out_dir.join("livers_search_index.min.json"));
     assert_eq!(search_index["schemaVersion"], 1);
     assert!(search_index["data"].is_array());
+    assert!(
+        search_index["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|entry| entry["variant"] == "romaji")
+    );
+
+    let official_search_index =
+        read_json(&out_dir.join("official_channels_search_index.min.json"));
+    assert_eq!(official_search_index["schemaVersion"], 1);
+    assert!(official_search_index["data"].is_array());
 }
 
 #[test]
@@ -143,6 +179,47 @@ fn test_metadata_artist_hash_inputs_prints_sha256() {
     assert!(hash.bytes().all(|byte| byte.is_ascii_hexdigit()));
 }
 
+#[test]
+fn test_metadata_artist_check_ids_writes_lock_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let lock_path = tmp.path().join("int_ids.lock.json");
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("ARTIST_INT_ID_LOCK_PATH", &lock_path)
+        .arg("--quiet")
+        .arg("artist")
+        .arg("check-ids");
+    cmd.assert()
+        .failure()
+        .stdout(predicates::str::contains("not in the lock file"));
+    assert!(!lock_path.exists());
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("ARTIST_INT_ID_LOCK_PATH", &lock_path)
+        .arg("--quiet")
+        .arg("artist")
+        .arg("check-ids")
+        .arg("--write");
+    cmd.assert().success();
+
+    // 定義に無いライバーはID順に0から, 公式チャンネルは定義の値のまま
+    let lock = read_json(&lock_path);
+    assert_eq!(
+        lock["livers"],
+        serde_json::json!({"riku-tazumi": 0, "yudorikku": 1, "yugamin": 2})
+    );
+    assert_eq!(lock["officialChannels"]["test-channel-1"], 950);
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("ARTIST_INT_ID_LOCK_PATH", &lock_path)
+        .arg("--quiet")
+        .arg("artist")
+        .arg("check-ids");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Int IDs are consistent"));
+}
+
 #[test]
 fn test_metadata_tag_snippet_updates_snippet() {
     let tmp = tempfile::tempdir().unwrap();
@@ -178,11 +255,14 @@ fn test_metadata_tag_minify_generates_min() {
         .arg(out_dir.to_string_lossy().to_string())
         .arg("--min-tags-file-name")
         .arg("tags.min.json")
+        .arg("--min-tags-search-index-file-name")
+        .arg("tags_search_index.min.json")
         .arg("--dataset-build-id")
         .arg(TEST_DATASET_BUILD_ID);
 
     cmd.assert().success();
     assert!(out_dir.join("tags.min.json").exists());
+    assert!(out_dir.join("tags_search_index.min.json").exists());
 
     let tags = read_json(&out_dir.join("tags.min.json"));
     assert_eq!(tags["schemaVersion"], 1);
@@ -219,3 +299,340 @@ fn test_metadata_tag_hash_inputs_prints_sha256() {
     assert_eq!(hash.len(), 64);
     assert!(hash.bytes().all(|byte| byte.is_ascii_hexdigit()));
 }
+
+#[test]
+fn test_metadata_song_snippet_and_minify() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out_dir = tmp.path().join("out");
+    let snippet_path = tmp.path().join("songs.code-snippets");
+    write_text_file(&snippet_path, SONG_SNIPPET_JSON);
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.arg("song")
+        .arg("snippet")
+        .arg("--code-snippets-path")
+        .arg(snippet_path.to_string_lossy().to_string());
+    cmd.assert().success();
+
+    let snippet = read_json(&snippet_path);
+    assert!(
+        snippet["SongsSnippet"]["body"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("\"songId\": \"${1|")
+    );
+    assert_eq!(snippet["keep"]["x"], 1);
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.arg("song")
+        .arg("minify")
+        .arg("--output-dir")
+        .arg(out_dir.to_string_lossy().to_string())
+        .arg("--dataset-build-id")
+        .arg(TEST_DATASET_BUILD_ID);
+    cmd.assert().success();
+
+    let songs = read_json(&out_dir.join("songs.min.json"));
+    assert_eq!(songs["schemaVersion"], 1);
+    assert_eq!(songs["datasetBuildId"], TEST_DATASET_BUILD_ID);
+    assert!(songs["data"].as_object().unwrap().contains_key("idol"));
+}
+
+const LIVERS_JSON: &str = r#"{
+  "riku-tazumi": {
+    "ja": "田角陸",
+    "jah": "たずみりく",
+    "en": "Tazumi Riku",
+    "aliases": [],
+    "channelId": "UC1111111111111111111111",
+    "color": "111111",
+    "intId": 0
+  },
+  "yugamin": {
+    "ja": "ゆがみん",
+    "jah": "ゆがみん",
+    "en": "Yugamin",
+    "aliases": [],
+    "channelId": "UC2222222222222222222222",
+    "color": "222222",
+    "intId": 1
+  }
+}
+"#;
+
+const GROUPS_JSON: &str = r#"{
+  "test-unit": {
+    "ja": "テストユニット",
+    "en": "Test Unit",
+    "kind": "unit",
+    "members": ["yugamin", "riku-tazumi"]
+  }
+}
+"#;
+
+const MONTH_FILE_JSON: &str = r#"[
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "videoTags": ["karaoke"],
+    "clips": [
+      {
+        "songTitle": "riku-tazumi",
+        "liverIds": ["riku-tazumi", "yugamin"],
+        "clipTags": ["karaoke"]
+      }
+    ]
+  }
+]
+"#;
+
+#[test]
+fn test_metadata_artist_rename_dry_run_then_apply() {
+    let tmp = tempfile::tempdir().unwrap();
+    let livers_path = tmp.path().join("livers.json");
+    let groups_path = tmp.path().join("groups.json");
+    let snippet_path = tmp.path().join("music.code-snippets");
+    let music_data_dir = tmp.path().join("music/data");
+    let month_path = music_data_dir.join("music/2026/01.json");
+    let input_path = music_data_dir.join("input/new.json");
+    let lock_path = tmp.path().join("int_ids.lock.json");
+    let lock_json =
+        "{\n  \"livers\": {\n    \"riku-tazumi\": 0,\n    \"yugamin\": 2\n  }\n}\n";
+    write_text_file(&livers_path, LIVERS_JSON);
+    write_text_file(&lock_path, lock_json);
+    write_text_file(&groups_path, GROUPS_JSON);
+    write_text_file(&snippet_path, LIVER_SNIPPET_JSON);
+    write_text_file(&month_path, MONTH_FILE_JSON);
+    write_text_file(&input_path, MONTH_FILE_JSON);
+
+    let rename_cmd = |dry_run: bool| {
+        let mut cmd = Command::cargo_bin("metadata").unwrap();
+        cmd.arg("--quiet")
+            .arg("artist")
+            .arg("rename")
+            .arg("riku-tazumi")
+            .arg("tazumi-riku")
+            .arg("--livers-path")
+            .arg(livers_path.to_string_lossy().to_string())
+            .arg("--groups-path")
+            .arg(groups_path.to_string_lossy().to_string())
+            .arg("--music-code-snippets-path")
+            .arg(snippet_path.to_string_lossy().to_string())
+            .arg("--int-id-lock-path")
+            .arg(lock_path.to_string_lossy().to_string())
+            .arg("--music-data-dir")
+            .arg(music_data_dir.to_string_lossy().to_string());
+        if dry_run {
+            cmd.arg("--dry-run");
+        }
+        cmd
+    };
+
+    rename_cmd(true)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "-        \"liverIds\": [\"riku-tazumi\", \"yugamin\"]",
+        ))
+        .stdout(predicates::str::contains(
+            "would change 5 references in 6 files",
+        ));
+    assert_eq!(std::fs::read_to_string(&livers_path).unwrap(), LIVERS_JSON);
+    assert_eq!(
+        std::fs::read_to_string(&month_path).unwrap(),
+        MONTH_FILE_JSON
+    );
+
+    rename_cmd(false).assert().success();
+
+    assert_eq!(
+        std::fs::read_to_string(&livers_path).unwrap(),
+        LIVERS_JSON.replace("\"riku-tazumi\": {", "\"tazumi-riku\": {")
+    );
+    assert_eq!(
+        std::fs::read_to_string(&groups_path).unwrap(),
+        GROUPS_JSON.replace(
+            "[\"yugamin\", \"riku-tazumi\"]",
+            "[\"yugamin\", \"tazumi-riku\"]"
+        )
+    );
+    let expected_month = MONTH_FILE_JSON.replace(
+        "[\"riku-tazumi\", \"yugamin\"]",
+        "[\"tazumi-riku\", \"yugamin\"]",
+    );
+    assert_eq!(
+        std::fs::read_to_string(&month_path).unwrap(),
+        expected_month
+    );
+    assert_eq!(
+        std::fs::read_to_string(&input_path).unwrap(),
+        expected_month
+    );
+    let snippet = std::fs::read_to_string(&snippet_path).unwrap();
+    assert!(snippet.contains("\\\"${1|tazumi-riku,yugamin|}\\\","));
+    // 整数IDはそのまま新IDに移る
+    assert_eq!(
+        read_json(&lock_path)["livers"],
+        serde_json::json!({"tazumi-riku": 0, "yugamin": 2})
+    );
+
+    // 変更後は旧IDが存在しない
+    rename_cmd(false).assert().failure();
+}
+
+#[test]
+fn test_metadata_rename_fails_while_music_root_is_locked() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tags_path = tmp.path().join("tags.json");
+    let snippet_path = tmp.path().join("tags.code-snippets");
+    let music_data_dir = tmp.path().join("music/data");
+    let month_path = music_data_dir.join("music/2026/01.json");
+    let tags_json = r#"{"karaoke": {"ja": "歌枠", "en": "karaoke", "intId": 0}}"#;
+    write_text_file(&tags_path, tags_json);
+    write_text_file(&snippet_path, TAG_SNIPPET_JSON);
+    write_text_file(&month_path, MONTH_FILE_JSON);
+
+    let _lock =
+        musictl::music_file::MusicRootLock::acquire(&music_data_dir.join("music"))
+            .unwrap();
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.arg("tag")
+        .arg("rename")
+        .arg("karaoke")
+        .arg("utawaku")
+        .arg("--tags-path")
+        .arg(&tags_path)
+        .arg("--code-snippets-path")
+        .arg(&snippet_path)
+        .arg("--music-data-dir")
+        .arg(&music_data_dir);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Music root is locked"));
+    assert_eq!(std::fs::read_to_string(&tags_path).unwrap(), tags_json);
+    assert_eq!(
+        std::fs::read_to_string(&month_path).unwrap(),
+        MONTH_FILE_JSON
+    );
+}
+
+#[test]
+fn test_metadata_tag_rename_rejects_existing_id() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tags_path = tmp.path().join("tags.json");
+    let snippet_path = tmp.path().join("tags.code-snippets");
+    let music_data_dir = tmp.path().join("music/data");
+    let tags_json = r#"{
+  "karaoke": { "ja": "歌枠", "en": "karaoke", "intId": 0 },
+  "3d": { "ja": "3D", "en": "3D", "intId": 1 },
+  "karaoke-3d": { "ja": "3D歌枠", "en": "3D karaoke", "intId": 2, "parent": "karaoke" }
+}
+"#;
+    write_text_file(&tags_path, tags_json);
+    write_text_file(&snippet_path, TAG_SNIPPET_JSON);
+    write_text_file(&music_data_dir.join("music/2026/01.json"), MONTH_FILE_JSON);
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.arg("tag")
+        .arg("rename")
+        .arg("karaoke")
+        .arg("3d")
+        .arg("--tags-path")
+        .arg(tags_path.to_string_lossy().to_string())
+        .arg("--code-snippets-path")
+        .arg(snippet_path.to_string_lossy().to_string())
+        .arg("--music-data-dir")
+        .arg(music_data_dir.to_string_lossy().to_string());
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("already defined"));
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.arg("tag")
+        .arg("rename")
+        .arg("karaoke")
+        .arg("utawaku")
+        .arg("--tags-path")
+        .arg(tags_path.to_string_lossy().to_string())
+        .arg("--code-snippets-path")
+        .arg(snippet_path.to_string_lossy().to_string())
+        .arg("--music-data-dir")
+        .arg(music_data_dir.to_string_lossy().to_string());
+    cmd.assert().success();
+
+    let month = read_json(&music_data_dir.join("music/2026/01.json"));
+    assert_eq!(month[0]["videoTags"], serde_json::json!(["utawaku"]));
+    assert_eq!(
+        month[0]["clips"][0]["clipTags"],
+        serde_json::json!(["utawaku"])
+    );
+    let tags = read_json(&tags_path);
+    assert!(tags["utawaku"].is_object());
+    assert_eq!(tags["karaoke-3d"]["parent"], "utawaku");
+}
+
+#[test]
+fn test_metadata_schema_writes_and_checks_schemas() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tags_path = tmp.path().join("tags.json");
+    write_text_file(
+        &tags_path,
+        r#"{"karaoke": {"ja": "歌枠", "en": "karaoke"}, "3d": {"ja": "3D", "en": "3D"}}"#,
+    );
+    let livers_path = tmp.path().join("livers.json");
+    write_text_file(&livers_path, LIVERS_JSON);
+    let out_dir = tmp.path().join("schema");
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("VIDEO_TAG_SET_PATH", &tags_path)
+        .env("LIVER_SET_PATH", &livers_path)
+        .arg("--quiet")
+        .arg("schema")
+        .arg("--output-dir")
+        .arg(&out_dir);
+    cmd.assert().success();
+
+    let video_tags = read_json(&out_dir.join("video_tags.schema.json"));
+    assert_eq!(
+        video_tags["patternProperties"]["^[a-z0-9-]+$"]["properties"]["parent"]["anyOf"]
+            [0]["enum"],
+        serde_json::json!(["3d", "karaoke"])
+    );
+    assert_eq!(
+        read_json(&out_dir.join("livers.schema.json"))["title"],
+        "Livers"
+    );
+    assert!(out_dir.join("official_channels.schema.json").exists());
+    let groups = read_json(&out_dir.join("groups.schema.json"));
+    assert_eq!(
+        groups["patternProperties"]["^[a-z0-9-]+$"]["properties"]["members"]["items"]["enum"],
+        serde_json::json!(["riku-tazumi", "yugamin"])
+    );
+
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("VIDEO_TAG_SET_PATH", &tags_path)
+        .env("LIVER_SET_PATH", &livers_path)
+        .arg("--quiet")
+        .arg("schema")
+        .arg("--output-dir")
+        .arg(&out_dir)
+        .arg("--check");
+    cmd.assert().success();
+
+    write_text_file(&out_dir.join("livers.schema.json"), "{}\n");
+    let mut cmd = Command::cargo_bin("metadata").unwrap();
+    cmd.env("VIDEO_TAG_SET_PATH", &tags_path)
+        .env("LIVER_SET_PATH", &livers_path)
+        .arg("--quiet")
+        .arg("schema")
+        .arg("--output-dir")
+        .arg(&out_dir)
+        .arg("--check");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("1 schema files are out of date"));
+    assert_eq!(
+        std::fs::read_to_string(out_dir.join("livers.schema.json")).unwrap(),
+        "{}\n"
+    );
+}
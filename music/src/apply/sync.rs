@@ -33,12 +33,13 @@ where
         >,
 {
     let mut failed_files: Vec<String> = Vec::new();
+    let mut history_entries = Vec::new();
 
     for music_file in music_lib.iter_files_mut() {
         let path_buf = music_file.get_path().to_path_buf();
         tracing::debug!("Syncing music file: {}", path_buf.display());
         match sync_one_file(music_file, &mut fetch_video_info).await {
-            Ok(()) => {}
+            Ok(entries) => history_entries.extend(entries),
             Err(SyncError::Continue(p, msg)) => {
                 failed_files.push(format!("{}: {}", p.display(), msg));
                 continue;
@@ -49,6 +50,16 @@ where
         }
     }
 
+    // 同期できた月別ファイルをまとめて書き込む. 一部の月だけ更新された状態にはならない
+    crate::music_file::MusicLibraryRepository::save_month_files(&music_lib)?;
+
+    let history = crate::history::HistoryStore::in_music_root(music_lib.get_root_dir());
+    if let Err(e) = history.append(&history_entries) {
+        let msg = format!("Failed to record video status history: {e}");
+        tracing::error!("{msg}");
+        failed_files.push(msg);
+    }
+
     if failed_files.is_empty() {
         tracing::info!("All music files synced successfully.");
         Ok(())
@@ -59,15 +70,17 @@ where
     }
 }
 
-/// 単一の`MusicFile`を同期
+/// 単一の`MusicFile`をAPIの情報で更新する
+///
+/// ファイルには書き込まない. 戻り値は観測した動画の状態変化で, 履歴に追記するもの
 ///
-/// - `Ok(())`: 成功したとき
+/// - `Ok(_)`: 成功したとき
 /// - `SyncError`: 失敗したとき
 #[tracing::instrument(level = tracing::Level::DEBUG, skip(fetch_video_info))]
 async fn sync_one_file<F, Fut>(
     music_file: &mut crate::music_file::MusicFile,
     fetch_video_info: &mut F,
-) -> Result<(), SyncError>
+) -> Result<Vec<crate::history::StatusHistoryEntry>, SyncError>
 where
     F: FnMut(crate::model::VideoIds) -> Fut,
     Fut: std::future::Future<
@@ -95,17 +108,18 @@ where
             SyncError::Continue(path_buf.clone(), msg)
         })?;
 
+    let history_entries = crate::history::StatusHistoryEntry::observe_all(
+        music_file.videos(),
+        &new_videos,
+    );
+
     music_file.replace_videos(new_videos).map_err(|e| {
         let msg = format!("Failed to replace videos in music file: {e}");
         tracing::error!("{msg}");
         SyncError::Continue(path_buf.clone(), msg)
     })?;
 
-    music_file.save().map_err(|e| {
-        let msg = format!("Failed to save music file: {e}");
-        tracing::error!("{msg}");
-        SyncError::Continue(path_buf.clone(), msg)
-    })
+    Ok(history_entries)
 }
 
 #[cfg(test)]
@@ -216,6 +230,50 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_apply_sync_with_fetcher_records_history() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+
+        write_month_file(root, 2024, 1, MONTH_2024_01_JSON);
+        write_month_file(root, 2024, 2, MONTH_2024_02_JSON);
+
+        let fetch = |video_ids: crate::model::VideoIds| async move {
+            let infos = video_ids
+                .into_vec()
+                .into_iter()
+                .map(|id| api_info_for_id(&id))
+                .collect::<Vec<_>>();
+            Ok(crate::model::ApiVideoInfoList::from_vec_ignore_duplicated(
+                infos,
+            ))
+        };
+
+        apply_sync_with_fetcher(build_music_library(root), fetch)
+            .await
+            .unwrap();
+
+        let history = crate::history::HistoryStore::in_music_root(root);
+        let timeline = history
+            .timeline(&crate::model::VideoId::test_id_1())
+            .unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(
+            timeline[0].get_changes(),
+            [crate::history::StatusChange::Title {
+                old: "sync test video 1".to_string(),
+                new: "synced-11111111111".to_string(),
+            }]
+        );
+        assert_eq!(history.load().unwrap().len(), 2);
+
+        // 状態が変わらなければ追記されない
+        apply_sync_with_fetcher(build_music_library(root), fetch)
+            .await
+            .unwrap();
+        assert_eq!(history.load().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_apply_sync_with_fetcher_fatal_network_error() {
         let tmp = tempfile::tempdir().unwrap();
@@ -239,6 +297,50 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_apply_sync_with_fetcher_writes_nothing_on_fatal_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+
+        write_month_file(root, 2024, 1, MONTH_2024_01_JSON);
+        write_month_file(root, 2024, 2, MONTH_2024_02_JSON);
+
+        // 1つ目の月は同期できるが, 2つ目の月でAPIを呼べなくなる
+        let mut calls = 0;
+        let res = apply_sync_with_fetcher(build_music_library(root), |video_ids| {
+            calls += 1;
+            let first = calls == 1;
+            async move {
+                if !first {
+                    return Err(crate::fetcher::YouTubeApiError::NetworkError(
+                        "network down".to_string(),
+                    ));
+                }
+                let infos = video_ids
+                    .into_vec()
+                    .into_iter()
+                    .map(|id| api_info_for_id(&id))
+                    .collect::<Vec<_>>();
+                Ok(crate::model::ApiVideoInfoList::from_vec_ignore_duplicated(
+                    infos,
+                ))
+            }
+        })
+        .await;
+
+        assert!(matches!(res, Err(crate::apply::ApplyError::YouTubeApi(_))));
+        assert_eq!(
+            std::fs::read_to_string(root.join("2024/01.json")).unwrap(),
+            MONTH_2024_01_JSON
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("2024/02.json")).unwrap(),
+            MONTH_2024_02_JSON
+        );
+        let history = crate::history::HistoryStore::in_music_root(root);
+        assert!(history.load().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_apply_sync_with_fetcher_partial_failure() {
         let tmp = tempfile::tempdir().unwrap();
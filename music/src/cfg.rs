@@ -3,6 +3,23 @@ pub const DEFAULT_MIN_OUTPUT_CLIPS_PATH: &str = "public/music/clips.min.json";
 pub const DEFAULT_MIN_OUTPUT_VIDEOS_PATH: &str = "public/music/videos.min.json";
 pub const DEFAULT_MERGE_FILES_INPUT_DIR: &str = "./music/data/input/";
 pub const DEFAULT_MERGED_FILE_OUTPUT_DIR: &str = "./music/data/input/";
+pub const DEFAULT_SCHEMA_OUTPUT_DIR: &str = "tools/schema";
+/// セットリストの取り込みで, 最後の曲の長さが不明なときに仮に使う秒数
+pub const DEFAULT_LAST_CLIP_SECS: u32 = 240;
+/// 楽曲IDの候補とする, 曲名の類似度の下限
+pub const DEFAULT_SONG_MATCH_MIN_SCORE: f64 = 0.8;
+/// `stats`の曲ごとの集計に含める件数
+pub const DEFAULT_STATS_TOP_SONGS: usize = 20;
+/// 楽曲情報のルート直下に置く, 動画の状態履歴のファイル名
+pub const HISTORY_FILE_NAME: &str = "history.jsonl";
+/// 楽曲情報のルート直下に置く, 書き込み中の`musictl`が保持するロックファイル名
+pub const MUSIC_ROOT_LOCK_FILE_NAME: &str = ".musictl.lock";
+/// 楽曲情報のルート直下に置く, 複数の月別ファイルを書き換えている間だけ存在するジャーナルのファイル名
+pub const MUSIC_ROOT_JOURNAL_FILE_NAME: &str = ".musictl.journal";
+/// lintの設定ファイル名. 楽曲情報のルート, 年のディレクトリに置く
+pub const LINT_CONFIG_FILE_NAME: &str = "lint.json";
+/// lintの`short-clip`で許容するクリップの最短の秒数
+pub const DEFAULT_LINT_SHORT_CLIP_MIN_SECS: u32 = 30;
 
 pub fn default_music_root_dir() -> String {
     DEFAULT_MUSIC_ROOT_DIR.to_string()
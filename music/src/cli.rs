@@ -2,6 +2,7 @@ mod file_paths;
 mod shared_args;
 mod tracing_level;
 mod video_ids;
+mod where_clause;
 
 pub(super) mod parser;
 
@@ -9,8 +10,9 @@ pub use file_paths::FilePathsFromCli;
 pub use parser::Cli;
 pub(crate) use parser::Commands;
 pub(crate) use shared_args::{
-    ApiKeyArgs, DatasetBuildIdArgs, DuplicateVideoPolicyArgs, InputFilesArgs,
-    MarkdownArgs, MergeDirectoriesArgs, MinOutputArgs, MusicRootArgs,
+    ApiKeyArgs, ClipFilterArgs, DatasetBuildIdArgs, DuplicateVideoPolicyArgs,
+    InputFilesArgs, MarkdownArgs, MergeDirectoriesArgs, MinOutputArgs, MusicRootArgs,
 };
 pub use tracing_level::TracingLevel;
 pub use video_ids::VideoIdsFromCli;
+pub(crate) use where_clause::WhereClause;
@@ -6,6 +6,10 @@ pub struct Cli {
 
     #[clap(flatten)]
     pub(crate) trace_level: cmn_rs::tracing::CliTraceOps,
+
+    /// Format of errors located in input or month files. Defaults to `github` in GitHub Actions.
+    #[arg(long, global = true, value_enum, value_name = "FORMAT")]
+    pub(crate) diagnostic_format: Option<cmn_rs::source_diagnostic::DiagnosticFormat>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -18,8 +22,20 @@ pub(crate) enum Commands {
     Sync(SyncCommands),
     /// Run build-related commands for public artifacts.
     Build(BuildCommands),
+    /// Export clips as playlists, data dumps or setlists.
+    Export(ExportCommands),
+    /// Edit the clips and videos matching a query in the music library.
+    Edit(EditCommands),
+    /// Check the music library for likely mistakes with configurable rules.
+    Lint(LintCommands),
+    /// Summarize the music library as Markdown or JSON.
+    Stats(StatsCommands),
+    /// Compare two music libraries and report changed videos and clips.
+    Diff(DiffCommands),
     /// Run utility commands that are outside the core music‑library workflows.
     Util(UtilCommands),
+    /// Generate the JSON Schema of input files with the current liver and tag IDs.
+    Schema(SchemaArgs),
 }
 
 // 入力値は形式が正しければ成功とみなす. 例えば指定されたパスが存在しないなら後続の処理でエラーを出す
@@ -130,6 +146,127 @@ pub(crate) struct BuildHashInputsCommands {
     pub(crate) music_root: crate::cli::MusicRootArgs,
 }
 
+// MARK: export
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ExportCommands {
+    /// Output format
+    #[arg(short, long, value_enum)]
+    pub(crate) format: crate::export::ExportFormat,
+    /// Output file. Writes to stdout when omitted (use with `--quiet` to keep logs out)
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) output: Option<std::path::PathBuf>,
+    #[command(flatten)]
+    pub(crate) filter: crate::cli::ClipFilterArgs,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+// MARK: edit
+
+#[derive(Debug, clap::Args)]
+#[command(group(
+    clap::ArgGroup::new("operation")
+        .required(true)
+        .multiple(true)
+        .args(["add_tag", "remove_tag", "set_song_title", "replace_liver"]),
+))]
+pub(crate) struct EditCommands {
+    /// Condition as KEY=VALUE (video, liver, tag, title regex, from, to). Repeat to AND them
+    #[arg(long = "where", value_name = "KEY=VALUE", required = true)]
+    pub(crate) where_clauses: Vec<crate::cli::WhereClause>,
+    /// Add these tags to videos with a matching clip (comma-separated or repeated)
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    pub(crate) add_tag: Vec<String>,
+    /// Remove these tags and their child tags from videos with a matching clip (comma-separated or repeated)
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    pub(crate) remove_tag: Vec<String>,
+    /// Set the song title of matching clips
+    #[arg(long, value_name = "TITLE")]
+    pub(crate) set_song_title: Option<String>,
+    /// Replace a singer of matching clips (repeatable)
+    #[arg(long, value_name = "OLD=NEW")]
+    pub(crate) replace_liver: Vec<crate::operations::LiverReplacement>,
+    /// Set the playback volume (1-100 percent) of matching clips
+    #[arg(long, value_name = "PERCENT", conflicts_with = "unset_volume_percent")]
+    pub(crate) set_volume_percent: Option<crate::model::VolumePercent>,
+    /// Remove the playback volume of matching clips (play at the original volume)
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) unset_volume_percent: bool,
+    /// Print the diff of the month files without writing them
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) dry_run: bool,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+// MARK: lint
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct LintCommands {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = crate::lint::LintFormat::Text)]
+    pub(crate) format: crate::lint::LintFormat,
+    /// Output file. Writes to stdout when omitted (use with `--quiet` to keep logs out)
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) output: Option<std::path::PathBuf>,
+    /// Fail when any warning is found, not only errors
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) deny_warnings: bool,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+// MARK: stats
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct StatsCommands {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = crate::stats::StatsFormat::Markdown)]
+    pub(crate) format: crate::stats::StatsFormat,
+    /// Output file. Writes to stdout when omitted (use with `--quiet` to keep logs out)
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) output: Option<std::path::PathBuf>,
+    /// Number of songs listed in the top songs ranking
+    #[arg(long, value_name = "N", default_value_t = crate::cfg::DEFAULT_STATS_TOP_SONGS)]
+    pub(crate) top: usize,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+// MARK: diff
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct DiffCommands {
+    /// Music root directory before the change
+    #[arg(value_name = "DIR_A")]
+    pub(crate) dir_a: std::path::PathBuf,
+    /// Music root directory after the change
+    #[arg(value_name = "DIR_B")]
+    pub(crate) dir_b: std::path::PathBuf,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = crate::library_diff::DiffFormat::Markdown)]
+    pub(crate) format: crate::library_diff::DiffFormat,
+    /// Output file. Writes to stdout when omitted (use with `--quiet` to keep logs out)
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) output: Option<std::path::PathBuf>,
+    /// Do not compare this field of videos and clips, e.g. `syncedAt` (comma-separated or repeated)
+    #[arg(long, value_name = "FIELD", value_delimiter = ',')]
+    pub(crate) ignore_field: Vec<String>,
+}
+
+// MARK: schema
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct SchemaArgs {
+    /// Directory for the schema file
+    #[arg(long, value_name = "DIR", default_value = crate::cfg::DEFAULT_SCHEMA_OUTPUT_DIR)]
+    pub(crate) output_dir: std::path::PathBuf,
+    /// Fail if the schema file is out of date instead of writing it
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) check: bool,
+}
+
 // MARK: util
 
 #[derive(Debug, clap::Args)]
@@ -144,6 +281,14 @@ pub(crate) enum UtilMode {
     Merge(MergeFilesArgs),
     /// Check for duplicate video IDs in the input
     Find(FindDuplicateIdsArgs),
+    /// Print or compact the video status history recorded by `sync`
+    History(HistoryArgs),
+    /// Convert a timestamp setlist text into an input file
+    ImportSetlist(ImportSetlistArgs),
+    /// Suggest songId for clips without one by fuzzy matching their titles against songs.json
+    SuggestSongIds(SuggestSongIdsArgs),
+    /// Rewrite every month file in the latest schema version
+    Migrate(MigrateArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -164,6 +309,64 @@ pub(crate) struct MergeFilesArgs {
     pub(crate) remove_source_files: bool,
 }
 
+#[derive(Debug, clap::Args)]
+pub(crate) struct HistoryArgs {
+    /// Video ID whose status timeline is printed
+    #[arg(value_name = "VIDEO_ID", required_unless_present = "compact_before")]
+    pub(crate) video_id: Option<crate::model::VideoId>,
+    /// Merge entries recorded before this date (YYYY-MM-DD, UTC) into one entry per video
+    #[arg(long, value_name = "DATE", conflicts_with = "video_id")]
+    pub(crate) compact_before: Option<chrono::NaiveDate>,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ImportSetlistArgs {
+    /// Video ID the setlist belongs to
+    #[arg(long, value_name = "VIDEO_ID")]
+    pub(crate) video_id: crate::model::VideoId,
+    /// Text file containing timestamp lines such as `1:10 title / singer`
+    #[arg(long, value_name = "FILE")]
+    pub(crate) file: std::path::PathBuf,
+    /// Output path of the input file [default: <input dir>/setlist-<VIDEO_ID>.json]
+    #[arg(short, long, value_name = "FILE")]
+    pub(crate) output: Option<std::path::PathBuf>,
+    /// Comma-separated liver IDs used for clips without resolvable singers
+    #[arg(long, value_name = "LIVER_ID", value_delimiter = ',')]
+    pub(crate) liver: Vec<String>,
+    /// Seconds subtracted from the next start time when inferring endTime
+    #[arg(long, value_name = "SECS", default_value_t = 0)]
+    pub(crate) end_gap_secs: u32,
+    /// endTime of the last clip in ISO 8601 (e.g. PT1H2M3S)
+    #[arg(long, value_name = "DURATION")]
+    pub(crate) last_end_time: Option<crate::model::Duration>,
+    /// Assumed length in seconds of the last clip when `--last-end-time` is not given
+    #[arg(long, value_name = "SECS", default_value_t = crate::cfg::DEFAULT_LAST_CLIP_SECS)]
+    pub(crate) default_last_clip_secs: u32,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct SuggestSongIdsArgs {
+    /// Minimum title similarity (0.0-1.0) for a song to be suggested
+    #[arg(long, value_name = "SCORE", default_value_t = crate::cfg::DEFAULT_SONG_MATCH_MIN_SCORE)]
+    pub(crate) min_score: f64,
+    /// Write the suggested songId into the month files
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) apply: bool,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct MigrateArgs {
+    /// Fail if any month file is not in the latest schema version, without writing
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub(crate) check: bool,
+    #[command(flatten)]
+    pub(crate) music_root: crate::cli::MusicRootArgs,
+}
+
 // MARK: impl
 
 impl Cli {
@@ -184,4 +387,9 @@ impl Cli {
     pub fn is_quiet(&self) -> bool {
         self.trace_level.quiet
     }
+
+    pub fn diagnostic_format(&self) -> cmn_rs::source_diagnostic::DiagnosticFormat {
+        self.diagnostic_format
+            .unwrap_or_else(cmn_rs::source_diagnostic::DiagnosticFormat::detect)
+    }
 }
@@ -77,3 +77,42 @@ pub(crate) struct MergeDirectoriesArgs {
     #[arg(short, long, value_name = "DIR", default_value = crate::cfg::DEFAULT_MERGED_FILE_OUTPUT_DIR)]
     pub(crate) output_dir: std::path::PathBuf,
 }
+
+#[derive(Debug, Clone, clap::Args)]
+pub(crate) struct ClipFilterArgs {
+    /// Only clips in these videos (comma-separated or repeated)
+    #[arg(long = "video-id", value_name = "VIDEO_ID", value_delimiter = ',')]
+    pub(crate) video_ids: Vec<crate::model::VideoId>,
+    /// Only clips sung by any of these livers (comma-separated or repeated)
+    #[arg(long = "liver", value_name = "LIVER_ID", value_delimiter = ',')]
+    pub(crate) liver_ids: Vec<String>,
+    /// Only clips with any of these tags on the clip or its video (comma-separated or repeated)
+    #[arg(long = "tag", value_name = "TAG", value_delimiter = ',')]
+    pub(crate) tags: Vec<String>,
+    /// Only videos published on or after this date (YYYY-MM-DD, UTC)
+    #[arg(long = "from", value_name = "DATE")]
+    pub(crate) published_from: Option<chrono::NaiveDate>,
+    /// Only videos published on or before this date (YYYY-MM-DD, UTC)
+    #[arg(long = "to", value_name = "DATE")]
+    pub(crate) published_to: Option<chrono::NaiveDate>,
+}
+
+impl ClipFilterArgs {
+    /// 絞り込み条件に変換する
+    ///
+    /// - Err: 存在しないライバーID, タグが指定されたとき, 期間が逆転しているとき
+    pub(crate) fn try_into_filter(
+        self,
+    ) -> Result<crate::clip_filter::ClipFilter, String> {
+        let filter = crate::clip_filter::ClipFilter {
+            video_ids: self.video_ids,
+            liver_ids: self.liver_ids,
+            tags: self.tags,
+            published_from: self.published_from,
+            published_to: self.published_to,
+            song_title: None,
+        };
+        filter.validate()?;
+        Ok(filter)
+    }
+}
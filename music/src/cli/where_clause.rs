@@ -0,0 +1,171 @@
+/// `--where`で指定する絞り込み条件の1つ
+///
+/// `KEY=VALUE`の形式. `video`, `liver`, `tag`はカンマ区切りで複数指定できる
+#[derive(Debug, Clone)]
+pub(crate) enum WhereClause {
+    /// `video=ID[,ID...]`
+    VideoIds(Vec<crate::model::VideoId>),
+    /// `liver=LIVER_ID[,LIVER_ID...]`
+    LiverIds(Vec<String>),
+    /// `tag=TAG[,TAG...]`
+    Tags(Vec<String>),
+    /// `title=REGEX`
+    SongTitle(regex::Regex),
+    /// `from=YYYY-MM-DD`
+    PublishedFrom(chrono::NaiveDate),
+    /// `to=YYYY-MM-DD`
+    PublishedTo(chrono::NaiveDate),
+}
+
+impl WhereClause {
+    /// 複数の条件をまとめて絞り込み条件に変換する
+    ///
+    /// - 同じキーの`video`, `liver`, `tag`は値を合わせる
+    /// - Err: `title`, `from`, `to`を2回以上指定したとき, 値が不正なとき
+    pub(crate) fn try_into_filter(
+        clauses: Vec<WhereClause>,
+    ) -> Result<crate::clip_filter::ClipFilter, String> {
+        fn set_once<T>(
+            slot: &mut Option<T>,
+            value: T,
+            key: &str,
+        ) -> Result<(), String> {
+            if slot.replace(value).is_some() {
+                Err(format!("`{key}` is specified more than once in --where"))
+            } else {
+                Ok(())
+            }
+        }
+
+        let mut filter = crate::clip_filter::ClipFilter::default();
+        for clause in clauses {
+            match clause {
+                WhereClause::VideoIds(ids) => filter.video_ids.extend(ids),
+                WhereClause::LiverIds(ids) => filter.liver_ids.extend(ids),
+                WhereClause::Tags(tags) => filter.tags.extend(tags),
+                WhereClause::SongTitle(re) => {
+                    set_once(&mut filter.song_title, re, "title")?
+                }
+                WhereClause::PublishedFrom(date) => {
+                    set_once(&mut filter.published_from, date, "from")?
+                }
+                WhereClause::PublishedTo(date) => {
+                    set_once(&mut filter.published_to, date, "to")?
+                }
+            }
+        }
+        filter.validate()?;
+        Ok(filter)
+    }
+}
+
+impl std::str::FromStr for WhereClause {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn list(key: &str, value: &str) -> Result<Vec<String>, String> {
+            let values = value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                Err(format!("`{key}` needs at least one value"))
+            } else {
+                Ok(values)
+            }
+        }
+        fn date(value: &str) -> Result<chrono::NaiveDate, String> {
+            value.trim().parse().map_err(|e| {
+                format!("invalid date `{value}` (expected YYYY-MM-DD): {e}")
+            })
+        }
+
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected KEY=VALUE, got `{s}`"))?;
+
+        let key = key.trim();
+        Ok(match key {
+            "video" => WhereClause::VideoIds(
+                list(key, value)?
+                    .into_iter()
+                    .map(crate::model::VideoId::new)
+                    .collect::<Result<_, _>>()
+                    .map_err(str::to_string)?,
+            ),
+            "liver" => WhereClause::LiverIds(list(key, value)?),
+            "tag" => WhereClause::Tags(list(key, value)?),
+            "title" => WhereClause::SongTitle(
+                regex::Regex::new(value)
+                    .map_err(|e| format!("invalid title regex: {e}"))?,
+            ),
+            "from" => WhereClause::PublishedFrom(date(value)?),
+            "to" => WhereClause::PublishedTo(date(value)?),
+            other => {
+                return Err(format!(
+                    "unknown key `{other}` (expected video, liver, tag, title, from or to)"
+                ));
+            }
+        })
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(clauses: &[&str]) -> Result<crate::clip_filter::ClipFilter, String> {
+        let clauses = clauses
+            .iter()
+            .map(|s| s.parse::<WhereClause>())
+            .collect::<Result<Vec<_>, _>>()?;
+        WhereClause::try_into_filter(clauses)
+    }
+
+    #[test]
+    fn test_where_clause_into_filter() {
+        let filter = parse(&[
+            "video=11111111111",
+            "liver=riku-tazumi,yugamin",
+            "liver=yudorikku",
+            "tag=karaoke",
+            "title=^Test, Song$",
+            "from=2024-01-01",
+            "to=2024-01-31",
+        ])
+        .unwrap();
+        assert_eq!(filter.video_ids, vec![crate::model::VideoId::test_id_1()]);
+        assert_eq!(
+            filter.liver_ids,
+            vec!["riku-tazumi", "yugamin", "yudorikku"]
+        );
+        assert_eq!(filter.tags, vec!["karaoke"]);
+        assert_eq!(filter.song_title.unwrap().as_str(), "^Test, Song$");
+        assert_eq!(
+            filter.published_from,
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+        );
+        assert_eq!(
+            filter.published_to,
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 31)
+        );
+    }
+
+    #[test]
+    fn test_where_clause_invalid() {
+        assert!("liver".parse::<WhereClause>().is_err());
+        assert!("singer=yugamin".parse::<WhereClause>().is_err());
+        assert!("liver=".parse::<WhereClause>().is_err());
+        assert!("title=(".parse::<WhereClause>().is_err());
+        assert!("from=2024/01/01".parse::<WhereClause>().is_err());
+        assert!("video=short".parse::<WhereClause>().is_err());
+
+        assert!(parse(&["liver=unknown-liver"]).is_err());
+        assert!(parse(&["title=a", "title=b"]).is_err());
+        assert!(parse(&["from=2024-02-01", "to=2024-01-01"]).is_err());
+    }
+}
@@ -1,7 +1,13 @@
 mod add;
+mod diff;
+mod edit;
 mod error;
+mod export;
 mod hash_inputs;
+mod lint;
 mod min;
+mod schema;
+mod stats;
 mod sync;
 mod update;
 mod util;
@@ -21,6 +27,12 @@ pub async fn cli_exec_handler(cli: crate::cli::Cli) -> Result<(), CliExecError>
                 hash_inputs::handle_hash_inputs(hash_inputs_cmd)
             }
         },
+        crate::cli::Commands::Export(export_cmd) => export::handle_export(export_cmd),
+        crate::cli::Commands::Edit(edit_cmd) => edit::handle_edit(edit_cmd),
+        crate::cli::Commands::Lint(lint_cmd) => lint::handle_lint(lint_cmd),
+        crate::cli::Commands::Stats(stats_cmd) => stats::handle_stats(stats_cmd),
+        crate::cli::Commands::Diff(diff_cmd) => diff::handle_diff(diff_cmd),
         crate::cli::Commands::Util(util_cmd) => util::handle_util(util_cmd),
+        crate::cli::Commands::Schema(schema_cmd) => schema::handle_schema(schema_cmd),
     }
 }
@@ -11,12 +11,16 @@ pub(super) async fn handle_add(
 async fn handle_apply(
     args: crate::cli::parser::AddApplyArgs,
 ) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let _lock = crate::music_file::MusicLibraryRepository::lock(
+        args.music_root.music_root_dir.as_path(),
+    )?;
     let music_lib = crate::music_file::MusicLibraryRepository::load(
         args.music_root.music_root_dir.as_path(),
     )?;
 
     let input_files = args.input.into_file_paths();
-    let anonymous_videos = crate::validate::try_load_anonymous_videos(&input_files)?;
+    let (anonymous_videos, sources) =
+        crate::validate::try_load_anonymous_videos_with_sources(&input_files)?;
 
     crate::apply::apply_add(
         music_lib,
@@ -25,7 +29,14 @@ async fn handle_apply(
         args.duplicate_video_policy.duplicate_video_policy(),
     )
     .await
-    .map_err(Into::into)
+    .map_err(|e| match e {
+        // 動画の詳細情報と合わせたときのエラーは入力ファイルの位置を示す
+        crate::apply::ApplyError::VerifyVideos(errs) => match sources.locate(&errs) {
+            Some(diagnostics) => diagnostics.into(),
+            None => crate::apply::ApplyError::VerifyVideos(errs).into(),
+        },
+        e => e.into(),
+    })
 }
 
 fn handle_validate(
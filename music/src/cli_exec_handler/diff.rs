@@ -0,0 +1,20 @@
+pub(super) fn handle_diff(
+    cmd: crate::cli::parser::DiffCommands,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let before = crate::music_file::MusicLibraryRepository::load(cmd.dir_a.as_path())?;
+    let after = crate::music_file::MusicLibraryRepository::load(cmd.dir_b.as_path())?;
+    let diff = crate::library_diff::diff_libraries(&before, &after, &cmd.ignore_field)?;
+    let content = crate::library_diff::render(cmd.format, &diff)?;
+
+    match cmd.output {
+        Some(path) => {
+            std::fs::write(&path, content)?;
+            tracing::info!("Wrote library diff to {}", path.display());
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().lock().write_all(content.as_bytes())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+pub(super) fn handle_edit(
+    cmd: crate::cli::parser::EditCommands,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    use crate::cli_exec_handler::CliExecError;
+
+    let filter = crate::cli::WhereClause::try_into_filter(cmd.where_clauses)
+        .map_err(CliExecError::Message)?;
+    let edits = crate::operations::VideoEdits {
+        add_tags: cmd
+            .add_tag
+            .iter()
+            .map(|tag| tagctl::model::VideoTagId::new(tag.as_str()))
+            .collect::<Result<_, _>>()
+            .map_err(CliExecError::Message)?,
+        remove_tags: cmd.remove_tag,
+        set_song_title: cmd.set_song_title,
+        replace_livers: cmd.replace_liver,
+        set_volume_percent: cmd.set_volume_percent,
+        unset_volume_percent: cmd.unset_volume_percent,
+    };
+    if let Some(title) = &edits.set_song_title
+        && title.trim().is_empty()
+    {
+        return Err(CliExecError::Message(
+            "--set-song-title must not be empty".to_string(),
+        ));
+    }
+
+    // dry-runは書き込まないためロックしない
+    let _lock = (!cmd.dry_run)
+        .then(|| {
+            crate::music_file::MusicLibraryRepository::lock(
+                cmd.music_root.music_root_dir.as_path(),
+            )
+        })
+        .transpose()?;
+    let mut library = crate::music_file::MusicLibraryRepository::load(
+        cmd.music_root.music_root_dir.as_path(),
+    )?;
+    let report = crate::operations::edit_videos(&mut library, &filter, &edits)?;
+
+    if cmd.dry_run {
+        print!("{}", report.diff());
+        println!(
+            "Dry run: {} clips in {} videos would be edited ({} files).",
+            report.edited_clips,
+            report.edited_videos,
+            report.edited_files.len()
+        );
+        return Ok(());
+    }
+
+    if !report.edited_files.is_empty() {
+        crate::music_file::MusicLibraryRepository::save_month_files(&library)?;
+    }
+    println!(
+        "Edited {} clips in {} videos ({} files).",
+        report.edited_clips,
+        report.edited_videos,
+        report.edited_files.len()
+    );
+    Ok(())
+}
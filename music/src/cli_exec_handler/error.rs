@@ -7,11 +7,36 @@ pub enum CliExecError {
     #[error(transparent)]
     AnonymousVideoValidation(#[from] crate::validate::AnonymousVideoValidateErrors),
     #[error(transparent)]
+    History(#[from] crate::history::HistoryError),
+    #[error(transparent)]
+    Lint(#[from] crate::lint::LintError),
+    #[error(transparent)]
     Operation(#[from] crate::operations::OperationError),
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Diagnostics(#[from] cmn_rs::source_diagnostic::SourceDiagnostics),
     #[error("{0}")]
     Message(String),
 }
+
+impl CliExecError {
+    /// ファイル上の位置を特定できたエラー. 該当箇所の抜粋やGitHub Actionsの注釈に使う
+    pub fn diagnostics(&self) -> cmn_rs::source_diagnostic::SourceDiagnostics {
+        let diagnostics = match self {
+            Self::Diagnostics(diagnostics) => diagnostics.0.clone(),
+            Self::AnonymousVideoValidation(errs) => errs.diagnostics().0,
+            Self::MusicFile(errs)
+            | Self::Apply(crate::apply::ApplyError::MusicFiles(errs)) => {
+                errs.diagnostics()
+            }
+            Self::Apply(crate::apply::ApplyError::MusicFile(err)) => {
+                err.diagnostic().into_iter().cloned().collect()
+            }
+            _ => Vec::new(),
+        };
+        cmn_rs::source_diagnostic::SourceDiagnostics(diagnostics)
+    }
+}
@@ -0,0 +1,30 @@
+pub(super) fn handle_export(
+    cmd: crate::cli::parser::ExportCommands,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let filter = cmd
+        .filter
+        .try_into_filter()
+        .map_err(crate::cli_exec_handler::CliExecError::Message)?;
+
+    let videos = crate::music_file::MusicLibraryRepository::load(
+        cmd.music_root.music_root_dir.as_path(),
+    )?
+    .into_videos()
+    .map_err(|error| {
+        crate::cli_exec_handler::CliExecError::MusicFile(error.into_errors())
+    })?;
+
+    let content = crate::export::render(cmd.format, &videos, &filter)?;
+
+    match cmd.output {
+        Some(path) => {
+            std::fs::write(&path, content)?;
+            tracing::info!("Exported clips to {}", path.display());
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().lock().write_all(content.as_bytes())?;
+        }
+    }
+    Ok(())
+}
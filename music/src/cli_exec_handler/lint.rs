@@ -0,0 +1,28 @@
+pub(super) fn handle_lint(
+    cmd: crate::cli::parser::LintCommands,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let library = crate::music_file::MusicLibraryRepository::load(
+        cmd.music_root.music_root_dir.as_path(),
+    )?;
+    let diagnostics = crate::lint::lint_library(&library)?;
+    let content = crate::lint::render(cmd.format, &diagnostics)?;
+
+    match cmd.output {
+        Some(path) => {
+            std::fs::write(&path, content)?;
+            tracing::info!("Wrote lint results to {}", path.display());
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().lock().write_all(content.as_bytes())?;
+        }
+    }
+
+    let (errors, warnings) = crate::lint::count(&diagnostics);
+    if errors > 0 || (cmd.deny_warnings && warnings > 0) {
+        return Err(crate::cli_exec_handler::CliExecError::Message(format!(
+            "lint found {errors} errors and {warnings} warnings"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+pub(super) fn handle_schema(
+    cmd: crate::cli::parser::SchemaArgs,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    use crate::cli_exec_handler::CliExecError;
+
+    let ctx =
+        crate::schema::context().map_err(|e| CliExecError::Message(e.to_string()))?;
+    let paths = cmn_rs::json_schema::write_or_check(
+        &cmd.output_dir,
+        &crate::schema::files(&ctx),
+        cmd.check,
+    )
+    .map_err(CliExecError::Message)?;
+    let verb = if cmd.check { "Up to date" } else { "Wrote" };
+    for path in paths {
+        println!("{verb}: {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+pub(super) fn handle_stats(
+    cmd: crate::cli::parser::StatsCommands,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let library = crate::music_file::MusicLibraryRepository::load(
+        cmd.music_root.music_root_dir.as_path(),
+    )?;
+    let stats = crate::stats::compute(&library, cmd.top);
+    let content = crate::stats::render(cmd.format, &stats)?;
+
+    match cmd.output {
+        Some(path) => {
+            std::fs::write(&path, content)?;
+            tracing::info!("Wrote library statistics to {}", path.display());
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().lock().write_all(content.as_bytes())?;
+        }
+    }
+    Ok(())
+}
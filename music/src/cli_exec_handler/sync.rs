@@ -1,6 +1,9 @@
 pub(super) async fn handle_sync(
     cmd: crate::cli::parser::SyncCommands,
 ) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let _lock = crate::music_file::MusicLibraryRepository::lock(
+        cmd.music_root.music_root_dir.as_path(),
+    )?;
     let music_lib = crate::music_file::MusicLibraryRepository::load(
         cmd.music_root.music_root_dir.as_path(),
     )?;
@@ -11,6 +11,9 @@ pub(super) fn handle_update(
 fn handle_apply(
     args: crate::cli::parser::UpdateApplyArgs,
 ) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let _lock = crate::music_file::MusicLibraryRepository::lock(
+        args.music_root.music_root_dir.as_path(),
+    )?;
     let music_lib = crate::music_file::MusicLibraryRepository::load(
         args.music_root.music_root_dir.as_path(),
     )?;
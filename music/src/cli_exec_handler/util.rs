@@ -5,6 +5,10 @@ pub(super) fn handle_util(
     match cmd.mode {
         UtilMode::Find(args) => handle_find(args),
         UtilMode::Merge(args) => handle_merge(args),
+        UtilMode::History(args) => handle_history(args),
+        UtilMode::ImportSetlist(args) => handle_import_setlist(args),
+        UtilMode::SuggestSongIds(args) => handle_suggest_song_ids(args),
+        UtilMode::Migrate(args) => handle_migrate(args),
     }
 }
 
@@ -37,3 +41,175 @@ fn handle_merge(
     println!("Merged file written to {}", result.output_file.display());
     Ok(())
 }
+
+fn handle_history(
+    args: crate::cli::parser::HistoryArgs,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let store = crate::history::HistoryStore::in_music_root(
+        args.music_root.music_root_dir.as_path(),
+    );
+
+    if let Some(date) = args.compact_before {
+        let _lock = crate::music_file::MusicLibraryRepository::lock(
+            args.music_root.music_root_dir.as_path(),
+        )?;
+        let before = date.and_time(chrono::NaiveTime::MIN).and_utc();
+        let summary = store.compact(before)?;
+        println!(
+            "Compacted {} history entries into {} ({})",
+            summary.before,
+            summary.after,
+            store.get_path().display()
+        );
+        return Ok(());
+    }
+
+    // clapで`compact_before`がないときは必須にしている
+    let Some(video_id) = args.video_id else {
+        return Err(crate::cli_exec_handler::CliExecError::Message(
+            "video id is required".to_string(),
+        ));
+    };
+
+    let timeline = store.timeline(&video_id)?;
+    if timeline.is_empty() {
+        println!("No status history recorded for {video_id}.");
+        return Ok(());
+    }
+
+    println!("Status history for {video_id}:");
+    for entry in timeline {
+        let recorded_at = entry
+            .get_recorded_at()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        for change in entry.get_changes() {
+            println!("{recorded_at}  {change}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_import_setlist(
+    args: crate::cli::parser::ImportSetlistArgs,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let text = std::fs::read_to_string(&args.file)?;
+    let options = crate::operations::ImportSetlistOptions {
+        end_gap_secs: args.end_gap_secs,
+        last_end_time: args.last_end_time,
+        default_last_clip_secs: args.default_last_clip_secs,
+        default_liver_ids: args.liver,
+    };
+    let result = crate::operations::import_setlist(&args.video_id, &text, &options)?;
+
+    let output_file = args.output.unwrap_or_else(|| {
+        std::path::Path::new(crate::cfg::DEFAULT_MERGE_FILES_INPUT_DIR)
+            .join(format!("setlist-{}.json", args.video_id))
+    });
+    crate::operations::write_setlist_input_file(&result.videos, &output_file)?;
+    println!("Input file written to {}", output_file.display());
+
+    if result.warnings.is_empty() {
+        println!("No warnings.");
+    } else {
+        println!("Warnings ({}):", result.warnings.len());
+        for warning in &result.warnings {
+            println!("- {warning}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_suggest_song_ids(
+    args: crate::cli::parser::SuggestSongIdsArgs,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    if !(0.0..=1.0).contains(&args.min_score) {
+        return Err(crate::cli_exec_handler::CliExecError::Message(format!(
+            "--min-score must be between 0.0 and 1.0, got {}",
+            args.min_score
+        )));
+    }
+
+    let _lock = args
+        .apply
+        .then(|| {
+            crate::music_file::MusicLibraryRepository::lock(
+                args.music_root.music_root_dir.as_path(),
+            )
+        })
+        .transpose()?;
+    let mut library = crate::music_file::MusicLibraryRepository::load(
+        args.music_root.music_root_dir.as_path(),
+    )?;
+    let suggestions = crate::operations::suggest_song_ids(&library, args.min_score);
+
+    for suggestion in &suggestions {
+        println!(
+            "{} {} {:?} -> {} ({:.2})",
+            suggestion.video_id,
+            suggestion.uuid,
+            suggestion.song_title,
+            suggestion.song_id,
+            suggestion.score
+        );
+    }
+
+    if !args.apply {
+        println!(
+            "{} clips have a songId suggestion. Re-run with --apply to write them.",
+            suggestions.len()
+        );
+        return Ok(());
+    }
+
+    let applied =
+        crate::operations::apply_song_id_suggestions(&mut library, &suggestions)?;
+    if applied > 0 {
+        crate::music_file::MusicLibraryRepository::save_month_files(&library)?;
+    }
+    println!("Set songId on {applied} clips.");
+    Ok(())
+}
+
+fn handle_migrate(
+    args: crate::cli::parser::MigrateArgs,
+) -> Result<(), crate::cli_exec_handler::CliExecError> {
+    let latest = crate::music_file::CURRENT_SCHEMA_VERSION;
+    let _lock = (!args.check)
+        .then(|| {
+            crate::music_file::MusicLibraryRepository::lock(
+                args.music_root.music_root_dir.as_path(),
+            )
+        })
+        .transpose()?;
+    let library = crate::music_file::MusicLibraryRepository::load(
+        args.music_root.music_root_dir.as_path(),
+    )?;
+
+    let mut outdated = library
+        .iter_files()
+        .filter(|file| file.get_schema_version() < latest)
+        .map(|file| (file.get_path().to_path_buf(), file.get_schema_version()))
+        .collect::<Vec<_>>();
+    outdated.sort();
+    for (path, version) in &outdated {
+        println!("{}: schemaVersion {version} -> {latest}", path.display());
+    }
+
+    if args.check {
+        if outdated.is_empty() {
+            println!("All month files are in schemaVersion {latest}.");
+            return Ok(());
+        }
+        return Err(crate::cli_exec_handler::CliExecError::Message(format!(
+            "{} month files are older than schemaVersion {latest}. Run `musictl util migrate`.",
+            outdated.len()
+        )));
+    }
+
+    crate::music_file::MusicLibraryRepository::save_month_files(&library)?;
+    println!(
+        "Migrated {} month files to schemaVersion {latest}.",
+        outdated.len()
+    );
+    Ok(())
+}
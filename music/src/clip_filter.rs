@@ -0,0 +1,248 @@
+/// クリップの絞り込み条件
+///
+/// - 条件ごとに, 指定した値のいずれかに一致すれば満たす
+/// - 全ての条件を満たすクリップを対象とする
+/// - 何も指定しないときは全てのクリップが対象
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClipFilter {
+    /// 動画ID
+    pub(crate) video_ids: Vec<crate::model::VideoId>,
+    /// クリップの歌唱者のライバーID
+    pub(crate) liver_ids: Vec<String>,
+    /// タグ. 動画のタグとクリップのタグのいずれかに一致すれば満たす
+    pub(crate) tags: Vec<String>,
+    /// 動画の公開日(UTC)の下限. この日を含む
+    pub(crate) published_from: Option<chrono::NaiveDate>,
+    /// 動画の公開日(UTC)の上限. この日を含む
+    pub(crate) published_to: Option<chrono::NaiveDate>,
+    /// 曲名に一致する正規表現
+    pub(crate) song_title: Option<regex::Regex>,
+}
+
+impl ClipFilter {
+    /// 条件に指定した値を検証する
+    ///
+    /// - Err: 存在しないライバーID, タグが指定されたとき, 期間が逆転しているとき
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if !self.liver_ids.is_empty() {
+            artistctl::model::LiverIds::new(
+                self.liver_ids.iter().map(String::as_str).collect(),
+            )?;
+        }
+        for tag in &self.tags {
+            tagctl::model::VideoTagId::new(tag.as_str())?;
+        }
+        if let (Some(from), Some(to)) = (self.published_from, self.published_to)
+            && from > to
+        {
+            return Err(format!("from date ({from}) is after to date ({to})"));
+        }
+        Ok(())
+    }
+
+    /// 動画に関する条件を満たすか
+    pub(crate) fn matches_video(&self, video: &crate::model::VerifiedVideo) -> bool {
+        let published_on = video.get_published_at().get_date();
+
+        (self.video_ids.is_empty() || self.video_ids.contains(video.get_video_id()))
+            && self.published_from.is_none_or(|from| from <= published_on)
+            && self.published_to.is_none_or(|to| published_on <= to)
+    }
+
+    /// クリップに関する条件を満たすか
+    ///
+    /// - タグは`video`のタグと`clip`のタグを合わせて判定する
+    pub(crate) fn matches_clip(
+        &self,
+        video: &crate::model::VerifiedVideo,
+        clip: &crate::model::VerifiedClip,
+    ) -> bool {
+        (self.tags.is_empty()
+            || video
+                .get_video_tags()
+                .to_vec()
+                .into_iter()
+                .chain(clip.get_clip_tags().to_vec())
+                .any(|tag| self.tags.iter().any(|want| want == tag)))
+            && (self.liver_ids.is_empty()
+                || clip
+                    .get_liver_ids()
+                    .to_vec()
+                    .iter()
+                    .any(|liver_id| self.liver_ids.iter().any(|want| want == liver_id)))
+            && self
+                .song_title
+                .as_ref()
+                .is_none_or(|re| re.is_match(clip.get_song_title()))
+    }
+
+    /// 条件を満たす(動画, クリップ)を公開日時, 開始時間の順で返す
+    pub(crate) fn select<'a>(
+        &self,
+        videos: &'a crate::model::VerifiedVideos,
+    ) -> Vec<(
+        &'a crate::model::VerifiedVideo,
+        &'a crate::model::VerifiedClip,
+    )> {
+        let mut selected_videos = videos
+            .iter()
+            .filter(|video| self.matches_video(video))
+            .collect::<Vec<_>>();
+        selected_videos.sort_by(|a, b| {
+            a.get_published_at()
+                .cmp(b.get_published_at())
+                .then_with(|| a.get_video_id().cmp(b.get_video_id()))
+        });
+
+        selected_videos
+            .into_iter()
+            .flat_map(|video| {
+                video
+                    .clips()
+                    .filter(|clip| self.matches_clip(video, clip))
+                    .map(move |clip| (video, clip))
+            })
+            .collect()
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn videos() -> crate::model::VerifiedVideos {
+        crate::model::VerifiedVideos::try_from_vec(vec![
+            crate::model::VerifiedVideo::self_a(),
+            crate::model::VerifiedVideo::self_b(),
+        ])
+        .unwrap()
+    }
+
+    fn selected_titles(filter: &ClipFilter) -> Vec<String> {
+        let videos = videos();
+        filter
+            .select(&videos)
+            .into_iter()
+            .map(|(_, clip)| clip.get_song_title().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_select_without_conditions_returns_all_in_order() {
+        assert_eq!(
+            selected_titles(&ClipFilter::default()),
+            vec![
+                "Test Song A1",
+                "Test Song A2",
+                "Test Song A3",
+                "Test Song B1",
+                "Test Song B2",
+                "Test Song B3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_by_video_and_liver() {
+        let filter = ClipFilter {
+            video_ids: vec![crate::model::VideoId::test_id_2()],
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_titles(&filter),
+            vec!["Test Song B1", "Test Song B2", "Test Song B3"]
+        );
+
+        let filter = ClipFilter {
+            liver_ids: vec!["yugamin".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_titles(&filter),
+            vec!["Test Song A2", "Test Song B2"]
+        );
+    }
+
+    #[test]
+    fn test_select_by_tag_and_date() {
+        let video_a = crate::model::VerifiedVideo::self_a();
+        let tag = video_a.get_video_tags().to_vec()[0].to_string();
+        let filter = ClipFilter {
+            tags: vec![tag],
+            ..Default::default()
+        };
+        assert!(selected_titles(&filter).contains(&"Test Song A1".to_string()));
+
+        let published_on = video_a.get_published_at().get_date();
+        let filter = ClipFilter {
+            published_from: Some(published_on),
+            published_to: Some(published_on),
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_titles(&filter),
+            vec!["Test Song A1", "Test Song A2", "Test Song A3"]
+        );
+
+        let filter = ClipFilter {
+            published_to: published_on.pred_opt(),
+            ..Default::default()
+        };
+        assert!(selected_titles(&filter).is_empty());
+    }
+
+    #[test]
+    fn test_select_by_clip_tag() {
+        // `acoustic`はクリップのタグにのみ含まれる
+        let videos = crate::model::VerifiedVideos::try_from_vec(vec![
+            crate::model::VerifiedVideo::self_a_with_clip_tags(
+                crate::model::VideoTagIds::new(vec!["acoustic"]).unwrap(),
+            ),
+            crate::model::VerifiedVideo::self_b(),
+        ])
+        .unwrap();
+        let filter = ClipFilter {
+            tags: vec!["acoustic".to_string()],
+            ..Default::default()
+        };
+        let titles = filter
+            .select(&videos)
+            .into_iter()
+            .map(|(_, clip)| clip.get_song_title().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Test Song A1"]);
+    }
+
+    #[test]
+    fn test_select_by_song_title() {
+        let filter = ClipFilter {
+            song_title: Some(regex::Regex::new("^Test Song [AB]2$").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_titles(&filter),
+            vec!["Test Song A2", "Test Song B2"]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ClipFilter::default().validate().is_ok());
+
+        let filter = ClipFilter {
+            liver_ids: vec!["unknown-liver".to_string()],
+            ..Default::default()
+        };
+        assert!(filter.validate().is_err());
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let filter = ClipFilter {
+            published_from: date.succ_opt(),
+            published_to: Some(date),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_err());
+    }
+}
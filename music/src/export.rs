@@ -0,0 +1,90 @@
+mod playlist;
+mod row;
+mod setlist;
+mod table;
+
+pub(crate) use row::ExportRow;
+
+/// エクスポートの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    /// M3U playlist of YouTube URLs with start/end
+    M3u,
+    /// XSPF playlist of YouTube URLs with start/end
+    Xspf,
+    /// CSV with one clip per row
+    Csv,
+    /// JSON Lines with one clip per line
+    Jsonl,
+    /// Plain-text setlist per video (`00:01:10 title`)
+    Setlist,
+}
+
+/// 楽曲情報を指定した形式の文字列に変換する
+///
+/// - `filter`を満たすクリップのみを出力する
+/// - 出力順は動画の公開日時, クリップの開始時間の順
+pub(crate) fn render(
+    format: ExportFormat,
+    videos: &crate::model::VerifiedVideos,
+    filter: &crate::clip_filter::ClipFilter,
+) -> Result<String, serde_json::Error> {
+    let rows = filter
+        .select(videos)
+        .into_iter()
+        .map(|(video, clip)| ExportRow::new(video, clip))
+        .collect::<Vec<_>>();
+
+    Ok(match format {
+        ExportFormat::M3u => playlist::to_m3u(&rows),
+        ExportFormat::Xspf => playlist::to_xspf(&rows),
+        ExportFormat::Csv => table::to_csv(&rows),
+        ExportFormat::Jsonl => table::to_jsonl(&rows)?,
+        ExportFormat::Setlist => setlist::to_setlist(&rows),
+    })
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn videos() -> crate::model::VerifiedVideos {
+        crate::model::VerifiedVideos::try_from_vec(vec![
+            crate::model::VerifiedVideo::self_a(),
+            crate::model::VerifiedVideo::self_b(),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_applies_filter() {
+        let filter = crate::clip_filter::ClipFilter {
+            liver_ids: vec!["yugamin".to_string()],
+            ..Default::default()
+        };
+        let jsonl = render(ExportFormat::Jsonl, &videos(), &filter).unwrap();
+        let titles = jsonl
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["songTitle"].as_str().unwrap().to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Test Song A2", "Test Song B2"]);
+    }
+
+    #[test]
+    fn test_render_empty_selection() {
+        let filter = crate::clip_filter::ClipFilter {
+            video_ids: vec![crate::model::VideoId::test_id_5()],
+            ..Default::default()
+        };
+        assert_eq!(render(ExportFormat::Jsonl, &videos(), &filter).unwrap(), "");
+        assert_eq!(
+            render(ExportFormat::M3u, &videos(), &filter).unwrap(),
+            "#EXTM3U\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+/// M3U(拡張形式)のプレイリストに変換する
+///
+/// - 再生位置はURLのクエリに加えて, VLCの`#EXTVLCOPT`でも指定する
+pub(super) fn to_m3u(rows: &[super::ExportRow]) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for row in rows {
+        out.push_str(&format!(
+            "#EXTINF:{},{} - {}\n",
+            row.duration_secs(),
+            single_line(&row.artists_display()),
+            single_line(&row.song_title)
+        ));
+        out.push_str(&format!("#EXTVLCOPT:start-time={}\n", row.start_secs));
+        out.push_str(&format!("#EXTVLCOPT:stop-time={}\n", row.end_secs));
+        out.push_str(&row.url);
+        out.push('\n');
+    }
+    out
+}
+
+/// XSPFのプレイリストに変換する
+pub(super) fn to_xspf(rows: &[super::ExportRow]) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n",
+        "  <trackList>\n",
+    ));
+    for row in rows {
+        out.push_str("    <track>\n");
+        out.push_str(&format!(
+            "      <location>{}</location>\n",
+            escape_xml(&row.url)
+        ));
+        out.push_str(&format!(
+            "      <title>{}</title>\n",
+            escape_xml(&row.song_title)
+        ));
+        out.push_str(&format!(
+            "      <creator>{}</creator>\n",
+            escape_xml(&row.artists_display())
+        ));
+        out.push_str(&format!(
+            "      <album>{}</album>\n",
+            escape_xml(&row.video_title)
+        ));
+        out.push_str(&format!(
+            "      <duration>{}</duration>\n",
+            u64::from(row.duration_secs()) * 1000
+        ));
+        out.push_str("    </track>\n");
+    }
+    out.push_str("  </trackList>\n</playlist>\n");
+    out
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 改行を空白に置き換える. M3Uは1行1項目のため
+fn single_line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_m3u() {
+        let m3u = to_m3u(&[super::super::ExportRow::self_a_1()]);
+        assert_eq!(
+            m3u,
+            "#EXTM3U\n\
+             #EXTINF:5,田角陸, Apple Mike - Test Song A1\n\
+             #EXTVLCOPT:start-time=5\n\
+             #EXTVLCOPT:stop-time=10\n\
+             https://www.youtube.com/embed/11111111111?start=5&end=10\n"
+        );
+    }
+
+    #[test]
+    fn test_to_xspf_escapes() {
+        let mut row = super::super::ExportRow::self_a_1();
+        row.song_title = "Rock & <Roll>".to_string();
+        let xspf = to_xspf(&[row]);
+        assert!(xspf.contains("<title>Rock &amp; &lt;Roll&gt;</title>"));
+        assert!(xspf.contains(
+            "<location>https://www.youtube.com/embed/11111111111?start=5&amp;end=10</location>"
+        ));
+        assert!(xspf.contains("<duration>5000</duration>"));
+        assert!(xspf.ends_with("</playlist>\n"));
+    }
+}
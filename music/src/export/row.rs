@@ -0,0 +1,111 @@
+/// エクスポートする1クリップ分の情報
+///
+/// クリップに動画情報とライバー名を結合したもの
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExportRow {
+    pub(crate) video_id: String,
+    pub(crate) video_title: String,
+    pub(crate) published_at: String,
+    pub(crate) video_tags: Vec<String>,
+    pub(crate) uuid: String,
+    pub(crate) song_title: String,
+    pub(crate) liver_ids: Vec<String>,
+    /// ライバーの日本語名. `liver_ids`と同じ順
+    pub(crate) liver_names: Vec<String>,
+    pub(crate) external_artists: Vec<String>,
+    pub(crate) start_secs: u32,
+    pub(crate) end_secs: u32,
+    /// 開始, 終了位置付きの動画URL
+    ///
+    /// `watch?v=`のURLは`end`を無視するので, 範囲が効く埋め込みプレイヤーのURLにする
+    pub(crate) url: String,
+}
+
+impl ExportRow {
+    pub(crate) fn new(
+        video: &crate::model::VerifiedVideo,
+        clip: &crate::model::VerifiedClip,
+    ) -> Self {
+        let video_id = video.get_video_id().to_string();
+        let start_secs = clip.get_start_time().as_secs();
+        let end_secs = clip.get_end_time().as_secs();
+        let url = format!(
+            "https://www.youtube.com/embed/{video_id}?start={start_secs}&end={end_secs}"
+        );
+
+        Self {
+            video_title: video.get_title().to_string(),
+            published_at: video.get_published_at().to_string(),
+            video_tags: to_strings(video.get_video_tags().to_vec()),
+            uuid: clip.uuid_string(),
+            song_title: clip.get_song_title().to_string(),
+            liver_ids: to_strings(clip.get_liver_ids().to_vec()),
+            liver_names: clip.get_liver_ids().get_artists_ja_name(),
+            external_artists: clip
+                .get_external_artists_name()
+                .map(|names| to_strings(names.to_vec()))
+                .unwrap_or_default(),
+            start_secs,
+            end_secs,
+            url,
+            video_id,
+        }
+    }
+
+    /// 歌唱者の表示名. ライバー, 外部アーティストの順に`, `で結合
+    pub(crate) fn artists_display(&self) -> String {
+        self.liver_names
+            .iter()
+            .chain(self.external_artists.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub(crate) fn duration_secs(&self) -> u32 {
+        self.end_secs.saturating_sub(self.start_secs)
+    }
+}
+
+fn to_strings(values: Vec<&str>) -> Vec<String> {
+    values.into_iter().map(str::to_string).collect()
+}
+
+// MARK: For Tests
+
+#[cfg(test)]
+impl ExportRow {
+    /// `VerifiedVideo::self_a`の1つ目のクリップ
+    pub(crate) fn self_a_1() -> Self {
+        Self::new(
+            &crate::model::VerifiedVideo::self_a(),
+            &crate::model::VerifiedClip::self_a_1(),
+        )
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_row_new() {
+        let row = ExportRow::self_a_1();
+        assert_eq!(row.video_id, "11111111111");
+        assert_eq!(row.song_title, "Test Song A1");
+        assert_eq!(row.liver_ids, vec!["riku-tazumi"]);
+        assert_eq!(row.liver_names, vec!["田角陸"]);
+        assert_eq!(row.external_artists, vec!["Apple Mike"]);
+        assert_eq!(row.start_secs, 5);
+        assert_eq!(row.end_secs, 10);
+        assert_eq!(row.duration_secs(), 5);
+        assert_eq!(
+            row.url,
+            "https://www.youtube.com/embed/11111111111?start=5&end=10"
+        );
+        assert_eq!(row.artists_display(), "田角陸, Apple Mike");
+    }
+}
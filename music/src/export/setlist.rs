@@ -0,0 +1,79 @@
+/// 動画ごとのセットリスト(テキスト)に変換する
+///
+/// - 動画ごとに`# タイトル (URL)`の見出しを付け, 空行で区切る
+/// - 各行は`HH:MM:SS 曲名`
+///
+/// `rows`は動画ごとにまとまっていること
+pub(super) fn to_setlist(rows: &[super::ExportRow]) -> String {
+    let mut out = String::new();
+    let mut current_video: Option<&str> = None;
+
+    for row in rows {
+        if current_video != Some(row.video_id.as_str()) {
+            if current_video.is_some() {
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "# {} (https://youtu.be/{})\n",
+                row.video_title, row.video_id
+            ));
+            current_video = Some(row.video_id.as_str());
+        }
+        out.push_str(&format!(
+            "{} {}\n",
+            format_hms(row.start_secs),
+            row.song_title
+        ));
+    }
+    out
+}
+
+fn format_hms(secs: u32) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_hms() {
+        assert_eq!(format_hms(0), "00:00:00");
+        assert_eq!(format_hms(70), "00:01:10");
+        assert_eq!(format_hms(3723), "01:02:03");
+    }
+
+    #[test]
+    fn test_to_setlist_groups_by_video() {
+        let video_a = crate::model::VerifiedVideo::self_a();
+        let video_b = crate::model::VerifiedVideo::self_b();
+        let rows = video_a
+            .clips()
+            .map(|clip| super::super::ExportRow::new(&video_a, clip))
+            .chain(
+                video_b
+                    .clips()
+                    .take(1)
+                    .map(|clip| super::super::ExportRow::new(&video_b, clip)),
+            )
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            to_setlist(&rows),
+            "# Test Video A (https://youtu.be/11111111111)\n\
+             00:00:05 Test Song A1\n\
+             00:00:15 Test Song A2\n\
+             00:00:25 Test Song A3\n\
+             \n\
+             # Test Video B (https://youtu.be/22222222222)\n\
+             00:00:07 Test Song B1\n"
+        );
+    }
+}
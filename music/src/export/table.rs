@@ -0,0 +1,107 @@
+const CSV_HEADER: [&str; 12] = [
+    "video_id",
+    "video_title",
+    "published_at",
+    "video_tags",
+    "uuid",
+    "song_title",
+    "liver_ids",
+    "liver_names",
+    "external_artists",
+    "start_secs",
+    "end_secs",
+    "url",
+];
+
+/// 複数の値を1セルに入れるときの区切り
+const CSV_LIST_SEPARATOR: &str = "; ";
+
+/// CSV(RFC 4180)に変換する
+///
+/// - 1行目はヘッダ
+/// - 改行は`\r\n`
+pub(super) fn to_csv(rows: &[super::ExportRow]) -> String {
+    let mut out = String::new();
+    push_csv_record(&mut out, CSV_HEADER.iter().map(|s| s.to_string()));
+    for row in rows {
+        push_csv_record(
+            &mut out,
+            [
+                row.video_id.clone(),
+                row.video_title.clone(),
+                row.published_at.clone(),
+                row.video_tags.join(CSV_LIST_SEPARATOR),
+                row.uuid.clone(),
+                row.song_title.clone(),
+                row.liver_ids.join(CSV_LIST_SEPARATOR),
+                row.liver_names.join(CSV_LIST_SEPARATOR),
+                row.external_artists.join(CSV_LIST_SEPARATOR),
+                row.start_secs.to_string(),
+                row.end_secs.to_string(),
+                row.url.clone(),
+            ],
+        );
+    }
+    out
+}
+
+/// JSON Linesに変換する
+pub(super) fn to_jsonl(rows: &[super::ExportRow]) -> Result<String, serde_json::Error> {
+    let mut out = String::new();
+    for row in rows {
+        out.push_str(&serde_json::to_string(row)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn push_csv_record(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    let record = fields
+        .into_iter()
+        .map(|field| escape_csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push_str(&record);
+    out.push_str("\r\n");
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv() {
+        let mut row = super::super::ExportRow::self_a_1();
+        row.song_title = "Say \"Hello\", World".to_string();
+        let csv = to_csv(&[row]);
+        let mut lines = csv.split("\r\n");
+        assert_eq!(lines.next().unwrap(), CSV_HEADER.join(","));
+        let record = lines.next().unwrap();
+        assert!(record.starts_with("11111111111,"));
+        assert!(record.contains(",\"Say \"\"Hello\"\", World\","));
+        assert!(record.contains(",riku-tazumi,田角陸,Apple Mike,5,10,"));
+        assert_eq!(lines.next(), Some(""));
+    }
+
+    #[test]
+    fn test_to_jsonl() {
+        let row = super::super::ExportRow::self_a_1();
+        let jsonl = to_jsonl(&[row.clone(), row]).unwrap();
+        let lines = jsonl.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["videoId"], "11111111111");
+        assert_eq!(value["liverNames"], serde_json::json!(["田角陸"]));
+        assert_eq!(value["startSecs"], 5);
+    }
+}
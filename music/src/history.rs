@@ -0,0 +1,10 @@
+mod entry;
+mod error;
+mod store;
+
+pub(crate) use entry::StatusHistoryEntry;
+pub use error::HistoryError;
+pub(crate) use store::HistoryStore;
+
+#[cfg(test)]
+pub(crate) use entry::StatusChange;
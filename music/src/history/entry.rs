@@ -0,0 +1,382 @@
+/// 動画の状態変化の記録
+///
+/// 履歴ファイル(JSONL)の1行に対応
+///
+/// 以下を保証
+/// - `changes` は空でないこと
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub(crate) struct StatusHistoryEntry {
+    /// 動画ID
+    video_id: crate::model::VideoId,
+    /// 変化を観測した日時. 同期時の`syncedAt`
+    #[serde(with = "crate::util::datetime_serde")]
+    recorded_at: chrono::DateTime<chrono::Utc>,
+    /// 観測した変化
+    changes: Vec<StatusChange>,
+}
+
+/// 動画の状態のうち, 変化を追跡するフィールド
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "field", rename_all = "camelCase")]
+pub(crate) enum StatusChange {
+    /// 動画のタイトル
+    Title { old: String, new: String },
+    /// 動画のプライバシー設定
+    PrivacyStatus {
+        old: crate::model::PrivacyStatus,
+        new: crate::model::PrivacyStatus,
+    },
+    /// 動画が埋め込み可能かどうか
+    Embeddable { old: bool, new: bool },
+}
+
+impl StatusHistoryEntry {
+    pub(crate) fn get_video_id(&self) -> &crate::model::VideoId {
+        &self.video_id
+    }
+    pub(crate) fn get_recorded_at(&self) -> &chrono::DateTime<chrono::Utc> {
+        &self.recorded_at
+    }
+    pub(crate) fn get_changes(&self) -> &[StatusChange] {
+        &self.changes
+    }
+
+    /// 同期前後の動画情報を比較して変化を記録する
+    ///
+    /// - 変化が無いときは`None`
+    /// - 観測日時は同期後の`syncedAt`
+    pub(crate) fn observe(
+        old: &crate::model::VerifiedVideo,
+        new: &crate::model::VerifiedVideo,
+    ) -> Option<Self> {
+        let mut changes = Vec::new();
+
+        if old.get_title() != new.get_title() {
+            changes.push(StatusChange::Title {
+                old: old.get_title().to_string(),
+                new: new.get_title().to_string(),
+            });
+        }
+        if old.get_privacy_status() != new.get_privacy_status() {
+            changes.push(StatusChange::PrivacyStatus {
+                old: *old.get_privacy_status(),
+                new: *new.get_privacy_status(),
+            });
+        }
+        if old.is_embeddable() != new.is_embeddable() {
+            changes.push(StatusChange::Embeddable {
+                old: old.is_embeddable(),
+                new: new.is_embeddable(),
+            });
+        }
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(Self {
+                video_id: new.get_video_id().clone(),
+                recorded_at: *new.get_synced_at(),
+                changes,
+            })
+        }
+    }
+
+    /// 同期前後の動画群を比較し, 変化のあった動画の記録を返す
+    ///
+    /// - 同期前にのみ, または同期後にのみ存在する動画は無視する
+    /// - 戻り値は動画IDの順
+    pub(crate) fn observe_all(
+        old: &crate::model::VerifiedVideos,
+        new: &crate::model::VerifiedVideos,
+    ) -> Vec<Self> {
+        let mut entries = old
+            .iter()
+            .filter_map(|old_video| {
+                new.get(old_video.get_video_id())
+                    .and_then(|new_video| Self::observe(old_video, new_video))
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.video_id.cmp(&b.video_id));
+        entries
+    }
+
+    /// 同一動画の複数の記録を1つにまとめる
+    ///
+    /// - 各フィールドは最初の`old`と最後の`new`をとる
+    /// - 結果として変化が打ち消されたフィールドは除く
+    /// - 観測日時は最後の記録のもの
+    /// - 全てのフィールドが打ち消されたときは`None`
+    ///
+    /// `entries`は観測日時順で, 全て同じ動画IDであること
+    pub(super) fn merge(entries: Vec<Self>) -> Option<Self> {
+        let last = entries.last()?;
+        let video_id = last.video_id.clone();
+        let recorded_at = last.recorded_at;
+        debug_assert!(entries.iter().all(|entry| entry.video_id == video_id));
+
+        let mut title: Option<(String, String)> = None;
+        let mut privacy_status: Option<(
+            crate::model::PrivacyStatus,
+            crate::model::PrivacyStatus,
+        )> = None;
+        let mut embeddable: Option<(bool, bool)> = None;
+
+        for change in entries.into_iter().flat_map(|entry| entry.changes) {
+            match change {
+                StatusChange::Title { old, new } => {
+                    title = Some((title.map_or(old, |(first, _)| first), new));
+                }
+                StatusChange::PrivacyStatus { old, new } => {
+                    privacy_status =
+                        Some((privacy_status.map_or(old, |(first, _)| first), new));
+                }
+                StatusChange::Embeddable { old, new } => {
+                    embeddable =
+                        Some((embeddable.map_or(old, |(first, _)| first), new));
+                }
+            }
+        }
+
+        let changes = [
+            title
+                .filter(|(old, new)| old != new)
+                .map(|(old, new)| StatusChange::Title { old, new }),
+            privacy_status
+                .filter(|(old, new)| old != new)
+                .map(|(old, new)| StatusChange::PrivacyStatus { old, new }),
+            embeddable
+                .filter(|(old, new)| old != new)
+                .map(|(old, new)| StatusChange::Embeddable { old, new }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(Self {
+                video_id,
+                recorded_at,
+                changes,
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for StatusChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusChange::Title { old, new } => {
+                write!(f, "title: {old:?} -> {new:?}")
+            }
+            StatusChange::PrivacyStatus { old, new } => write!(
+                f,
+                "privacyStatus: {} -> {}",
+                privacy_status_str(old),
+                privacy_status_str(new)
+            ),
+            StatusChange::Embeddable { old, new } => {
+                write!(f, "embeddable: {old} -> {new}")
+            }
+        }
+    }
+}
+
+fn privacy_status_str(status: &crate::model::PrivacyStatus) -> &'static str {
+    match status {
+        crate::model::PrivacyStatus::Public => "public",
+        crate::model::PrivacyStatus::Unlisted => "unlisted",
+        crate::model::PrivacyStatus::Private => "private",
+    }
+}
+
+// MARK: For Tests
+
+#[cfg(test)]
+impl StatusHistoryEntry {
+    pub(crate) fn new_for_test(
+        video_id: crate::model::VideoId,
+        recorded_at: chrono::DateTime<chrono::Utc>,
+        changes: Vec<StatusChange>,
+    ) -> Self {
+        Self {
+            video_id,
+            recorded_at,
+            changes,
+        }
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(day: u32) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+        chrono::Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()
+    }
+
+    fn synced_video(
+        api_info: crate::model::ApiVideoInfo,
+    ) -> crate::model::VerifiedVideo {
+        let video = crate::model::VerifiedVideo::self_a();
+        let videos = crate::model::VerifiedVideos::try_from_vec(vec![video]).unwrap();
+        let api_list =
+            crate::model::ApiVideoInfoList::from_vec_ignore_duplicated(vec![api_info]);
+        videos
+            .with_new_api_info_list(api_list)
+            .unwrap()
+            .into_sorted_vec()
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_observe_no_change() {
+        let old = crate::model::VerifiedVideo::self_a();
+        let new =
+            synced_video(crate::model::ApiVideoInfo::self_a().update_synced_at(utc(2)));
+        assert_eq!(StatusHistoryEntry::observe(&old, &new), None);
+    }
+
+    #[test]
+    fn test_observe_detects_changes() {
+        let old = crate::model::VerifiedVideo::self_a();
+        let api_info = crate::model::ApiVideoInfo::self_a()
+            .update_synced_at(utc(2))
+            .set_title("Renamed")
+            .set_privacy_status(crate::model::PrivacyStatus::Unlisted)
+            .set_embeddable(false);
+        let new = synced_video(api_info);
+
+        let entry = StatusHistoryEntry::observe(&old, &new).unwrap();
+        assert_eq!(entry.video_id, crate::model::VideoId::test_id_1());
+        assert_eq!(entry.recorded_at, utc(2));
+        assert_eq!(
+            entry.changes,
+            vec![
+                StatusChange::Title {
+                    old: old.get_title().to_string(),
+                    new: "Renamed".to_string(),
+                },
+                StatusChange::PrivacyStatus {
+                    old: crate::model::PrivacyStatus::Public,
+                    new: crate::model::PrivacyStatus::Unlisted,
+                },
+                StatusChange::Embeddable {
+                    old: true,
+                    new: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_serde_format() {
+        let entry = StatusHistoryEntry::new_for_test(
+            crate::model::VideoId::test_id_1(),
+            utc(1),
+            vec![StatusChange::PrivacyStatus {
+                old: crate::model::PrivacyStatus::Public,
+                new: crate::model::PrivacyStatus::Private,
+            }],
+        );
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            json,
+            r#"{"videoId":"11111111111","recordedAt":"2025-01-01T00:00:00Z","changes":[{"field":"privacyStatus","old":"public","new":"private"}]}"#
+        );
+        let back: StatusHistoryEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, entry);
+    }
+
+    #[test]
+    fn test_merge_keeps_first_old_and_last_new() {
+        let id = crate::model::VideoId::test_id_1();
+        let entries = vec![
+            StatusHistoryEntry::new_for_test(
+                id.clone(),
+                utc(1),
+                vec![
+                    StatusChange::Title {
+                        old: "a".to_string(),
+                        new: "b".to_string(),
+                    },
+                    StatusChange::Embeddable {
+                        old: true,
+                        new: false,
+                    },
+                ],
+            ),
+            StatusHistoryEntry::new_for_test(
+                id.clone(),
+                utc(3),
+                vec![
+                    StatusChange::Title {
+                        old: "b".to_string(),
+                        new: "c".to_string(),
+                    },
+                    StatusChange::Embeddable {
+                        old: false,
+                        new: true,
+                    },
+                ],
+            ),
+        ];
+
+        let merged = StatusHistoryEntry::merge(entries).unwrap();
+        assert_eq!(merged.recorded_at, utc(3));
+        // embeddable は元に戻ったので除かれる
+        assert_eq!(
+            merged.changes,
+            vec![StatusChange::Title {
+                old: "a".to_string(),
+                new: "c".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_cancelled_out() {
+        let id = crate::model::VideoId::test_id_1();
+        let entries = vec![
+            StatusHistoryEntry::new_for_test(
+                id.clone(),
+                utc(1),
+                vec![StatusChange::Embeddable {
+                    old: true,
+                    new: false,
+                }],
+            ),
+            StatusHistoryEntry::new_for_test(
+                id,
+                utc(2),
+                vec![StatusChange::Embeddable {
+                    old: false,
+                    new: true,
+                }],
+            ),
+        ];
+        assert_eq!(StatusHistoryEntry::merge(entries), None);
+        assert_eq!(StatusHistoryEntry::merge(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_status_change_display() {
+        let change = StatusChange::PrivacyStatus {
+            old: crate::model::PrivacyStatus::Public,
+            new: crate::model::PrivacyStatus::Unlisted,
+        };
+        assert_eq!(change.to_string(), "privacyStatus: public -> unlisted");
+        let change = StatusChange::Title {
+            old: "a".to_string(),
+            new: "b".to_string(),
+        };
+        assert_eq!(change.to_string(), r#"title: "a" -> "b""#);
+    }
+}
@@ -0,0 +1,21 @@
+/// 動画の状態履歴ファイルに関するエラー
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum HistoryError {
+    /// ファイルの読み書きに失敗
+    #[error("Failed to access history file {path} when {when}: {msg}")]
+    Io {
+        path: std::path::PathBuf,
+        msg: String,
+        when: String,
+    },
+    /// 履歴ファイルの行が不正
+    #[error("Invalid history entry at {path}:{line}: {msg}")]
+    InvalidEntry {
+        path: std::path::PathBuf,
+        line: usize,
+        msg: String,
+    },
+    /// 履歴のシリアライズに失敗
+    #[error("Failed to serialize history entry: {msg}")]
+    Serialize { msg: String },
+}
@@ -0,0 +1,325 @@
+/// 動画の状態履歴を保存するJSONLファイル
+///
+/// - 楽曲情報のルート直下に置く
+/// - 追記のみで, 圧縮時を除いて既存の行は書き換えない
+#[derive(Debug, Clone)]
+pub(crate) struct HistoryStore {
+    path: std::path::PathBuf,
+}
+
+/// 履歴の圧縮結果
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CompactSummary {
+    /// 圧縮前の記録数
+    pub(crate) before: usize,
+    /// 圧縮後の記録数
+    pub(crate) after: usize,
+}
+
+impl HistoryStore {
+    /// 楽曲情報のルートに対応する履歴ファイル
+    pub(crate) fn in_music_root(music_root: &std::path::Path) -> Self {
+        Self {
+            path: music_root.join(crate::cfg::HISTORY_FILE_NAME),
+        }
+    }
+
+    pub(crate) fn get_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// 記録を末尾に追記する
+    ///
+    /// - `entries`が空のときはファイルに触れない
+    pub(crate) fn append(
+        &self,
+        entries: &[super::StatusHistoryEntry],
+    ) -> Result<(), super::HistoryError> {
+        use std::io::Write;
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&Self::to_line(entry)?);
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_err(&e, "opening to append"))?;
+        file.write_all(lines.as_bytes())
+            .map_err(|e| self.io_err(&e, "appending entries"))?;
+
+        tracing::debug!(
+            "Appended {} status history entries to {}",
+            entries.len(),
+            self.path.display()
+        );
+        Ok(())
+    }
+
+    /// 全ての記録を読み込む
+    ///
+    /// - ファイルが存在しないときは空
+    /// - 空行は無視する
+    pub(crate) fn load(
+        &self,
+    ) -> Result<Vec<super::StatusHistoryEntry>, super::HistoryError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(self.io_err(&e, "reading")),
+        };
+
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    super::HistoryError::InvalidEntry {
+                        path: self.path.clone(),
+                        line: index + 1,
+                        msg: e.to_string(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// 指定した動画の記録を観測日時順に返す
+    pub(crate) fn timeline(
+        &self,
+        video_id: &crate::model::VideoId,
+    ) -> Result<Vec<super::StatusHistoryEntry>, super::HistoryError> {
+        let mut entries = self
+            .load()?
+            .into_iter()
+            .filter(|entry| entry.get_video_id() == video_id)
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| *entry.get_recorded_at());
+        Ok(entries)
+    }
+
+    /// `before`より前の記録を動画ごとに1つにまとめる
+    ///
+    /// - `before`以降の記録はそのまま残す
+    /// - ファイルは一時ファイルに書き出してから置き換える
+    pub(crate) fn compact(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<CompactSummary, super::HistoryError> {
+        use std::collections::BTreeMap;
+
+        let entries = self.load()?;
+        let before_len = entries.len();
+
+        let mut old_entries: BTreeMap<
+            crate::model::VideoId,
+            Vec<super::StatusHistoryEntry>,
+        > = BTreeMap::new();
+        let mut kept = Vec::new();
+        for entry in entries {
+            if *entry.get_recorded_at() < before {
+                old_entries
+                    .entry(entry.get_video_id().clone())
+                    .or_default()
+                    .push(entry);
+            } else {
+                kept.push(entry);
+            }
+        }
+
+        let mut compacted = old_entries
+            .into_values()
+            .filter_map(|mut entries| {
+                entries.sort_by_key(|entry| *entry.get_recorded_at());
+                super::StatusHistoryEntry::merge(entries)
+            })
+            .collect::<Vec<_>>();
+        compacted.extend(kept);
+        // 安定ソートなので同時刻の記録は元の順序を保つ
+        compacted.sort_by_key(|entry| *entry.get_recorded_at());
+
+        self.rewrite(&compacted)?;
+
+        Ok(CompactSummary {
+            before: before_len,
+            after: compacted.len(),
+        })
+    }
+
+    fn rewrite(
+        &self,
+        entries: &[super::StatusHistoryEntry],
+    ) -> Result<(), super::HistoryError> {
+        use std::io::Write;
+
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&Self::to_line(entry)?);
+        }
+
+        let dir = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)
+            .map_err(|e| self.io_err(&e, "creating temporary file"))?;
+        tmp.write_all(lines.as_bytes())
+            .map_err(|e| self.io_err(&e, "writing temporary file"))?;
+        tmp.persist(&self.path)
+            .map_err(|e| self.io_err(&e.error, "replacing with compacted file"))?;
+        Ok(())
+    }
+
+    fn to_line(
+        entry: &super::StatusHistoryEntry,
+    ) -> Result<String, super::HistoryError> {
+        serde_json::to_string(entry)
+            .map(|json| json + "\n")
+            .map_err(|e| super::HistoryError::Serialize { msg: e.to_string() })
+    }
+
+    fn io_err(&self, e: &std::io::Error, when: &str) -> super::HistoryError {
+        super::HistoryError::Io {
+            path: self.path.clone(),
+            msg: e.to_string(),
+            when: when.to_string(),
+        }
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(day: u32) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+        chrono::Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()
+    }
+
+    fn title_entry(
+        id: crate::model::VideoId,
+        day: u32,
+        old: &str,
+        new: &str,
+    ) -> super::super::StatusHistoryEntry {
+        super::super::StatusHistoryEntry::new_for_test(
+            id,
+            utc(day),
+            vec![super::super::StatusChange::Title {
+                old: old.to_string(),
+                new: new.to_string(),
+            }],
+        )
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = HistoryStore::in_music_root(tmp.path());
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_append_and_timeline() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = HistoryStore::in_music_root(tmp.path());
+        let id_1 = crate::model::VideoId::test_id_1();
+        let id_2 = crate::model::VideoId::test_id_2();
+
+        store
+            .append(&[title_entry(id_1.clone(), 3, "b", "c")])
+            .unwrap();
+        store
+            .append(&[
+                title_entry(id_2.clone(), 2, "x", "y"),
+                title_entry(id_1.clone(), 1, "a", "b"),
+            ])
+            .unwrap();
+        store.append(&[]).unwrap();
+
+        assert_eq!(store.load().unwrap().len(), 3);
+
+        let timeline = store.timeline(&id_1).unwrap();
+        assert_eq!(
+            timeline,
+            vec![
+                title_entry(id_1.clone(), 1, "a", "b"),
+                title_entry(id_1, 3, "b", "c"),
+            ]
+        );
+        assert!(
+            store
+                .timeline(&crate::model::VideoId::test_id_3())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_line() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = HistoryStore::in_music_root(tmp.path());
+        store
+            .append(&[title_entry(crate::model::VideoId::test_id_1(), 1, "a", "b")])
+            .unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(store.get_path())
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"\n{oops}\n"))
+            .unwrap();
+
+        let err = store.load().unwrap_err();
+        assert!(matches!(
+            err,
+            super::super::HistoryError::InvalidEntry { line: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_compact_merges_old_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = HistoryStore::in_music_root(tmp.path());
+        let id_1 = crate::model::VideoId::test_id_1();
+        let id_2 = crate::model::VideoId::test_id_2();
+
+        store
+            .append(&[
+                title_entry(id_1.clone(), 1, "a", "b"),
+                title_entry(id_2.clone(), 2, "x", "y"),
+                title_entry(id_1.clone(), 3, "b", "c"),
+                title_entry(id_2.clone(), 4, "y", "x"),
+                title_entry(id_1.clone(), 10, "c", "d"),
+            ])
+            .unwrap();
+
+        let summary = store.compact(utc(5)).unwrap();
+        assert_eq!(
+            summary,
+            CompactSummary {
+                before: 5,
+                after: 2
+            }
+        );
+
+        // id_2 は変化が打ち消されたので消える
+        assert_eq!(
+            store.load().unwrap(),
+            vec![
+                title_entry(id_1.clone(), 3, "a", "c"),
+                title_entry(id_1, 10, "c", "d"),
+            ]
+        );
+    }
+}
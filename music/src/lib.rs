@@ -2,12 +2,19 @@ pub mod apply;
 pub mod cfg;
 pub mod cli;
 pub mod cli_exec_handler;
+pub mod clip_filter;
+pub mod export;
 pub mod fetcher;
 pub mod hash_inputs;
+pub mod history;
+pub mod library_diff;
+pub mod lint;
 pub mod min_output;
 pub mod model;
 pub mod music_file;
 pub mod operations;
 pub mod report;
+pub mod schema;
+pub mod stats;
 pub mod util;
 pub mod validate;
@@ -0,0 +1,413 @@
+mod markdown;
+
+/// 差分の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum DiffFormat {
+    /// Markdown suitable for a pull request comment
+    Markdown,
+    /// JSON object with every change
+    Json,
+}
+
+/// 2つの楽曲情報の差分
+///
+/// - 動画は動画IDで, クリップは同じ動画内のUUIDで対応付ける
+/// - ファイル内の並び順, 書式の違いは差分にならない
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryDiff {
+    pub(crate) added_videos: Vec<VideoSummary>,
+    pub(crate) removed_videos: Vec<VideoSummary>,
+    pub(crate) modified_videos: Vec<VideoDiff>,
+}
+
+/// 追加, 削除された動画
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VideoSummary {
+    pub(crate) video_id: crate::model::VideoId,
+    pub(crate) title: String,
+    pub(crate) clip_count: usize,
+}
+
+/// 両方に存在し, 内容が異なる動画
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VideoDiff {
+    pub(crate) video_id: crate::model::VideoId,
+    /// 変更後のタイトル
+    pub(crate) title: String,
+    /// クリップ以外のフィールドの変更
+    pub(crate) changes: Vec<FieldChange>,
+    pub(crate) added_clips: Vec<ClipSummary>,
+    pub(crate) removed_clips: Vec<ClipSummary>,
+    pub(crate) modified_clips: Vec<ClipDiff>,
+}
+
+/// 追加, 削除されたクリップ
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClipSummary {
+    pub(crate) uuid: crate::model::UuidVer4,
+    pub(crate) song_title: String,
+    pub(crate) start_time: crate::model::Duration,
+    pub(crate) end_time: crate::model::Duration,
+}
+
+/// 両方に存在し, 内容が異なるクリップ
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClipDiff {
+    pub(crate) uuid: crate::model::UuidVer4,
+    /// 変更後の曲名
+    pub(crate) song_title: String,
+    pub(crate) changes: Vec<FieldChange>,
+}
+
+/// 月別ファイルでのフィールド名と, その変更前後の値. 存在しないときは`null`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FieldChange {
+    pub(crate) field: String,
+    pub(crate) old: serde_json::Value,
+    pub(crate) new: serde_json::Value,
+}
+
+/// 差分の件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct DiffCounts {
+    pub(crate) added_videos: usize,
+    pub(crate) removed_videos: usize,
+    pub(crate) modified_videos: usize,
+    /// 追加された動画のクリップを含む
+    pub(crate) added_clips: usize,
+    /// 削除された動画のクリップを含む
+    pub(crate) removed_clips: usize,
+    pub(crate) modified_clips: usize,
+}
+
+impl LibraryDiff {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.added_videos.is_empty()
+            && self.removed_videos.is_empty()
+            && self.modified_videos.is_empty()
+    }
+
+    pub(crate) fn counts(&self) -> DiffCounts {
+        let clips_in = |videos: &[VideoSummary]| {
+            videos.iter().map(|video| video.clip_count).sum::<usize>()
+        };
+        DiffCounts {
+            added_videos: self.added_videos.len(),
+            removed_videos: self.removed_videos.len(),
+            modified_videos: self.modified_videos.len(),
+            added_clips: clips_in(&self.added_videos)
+                + self
+                    .modified_videos
+                    .iter()
+                    .map(|video| video.added_clips.len())
+                    .sum::<usize>(),
+            removed_clips: clips_in(&self.removed_videos)
+                + self
+                    .modified_videos
+                    .iter()
+                    .map(|video| video.removed_clips.len())
+                    .sum::<usize>(),
+            modified_clips: self
+                .modified_videos
+                .iter()
+                .map(|video| video.modified_clips.len())
+                .sum(),
+        }
+    }
+}
+
+impl VideoSummary {
+    fn new(video: &crate::model::VerifiedVideo) -> Self {
+        Self {
+            video_id: video.get_video_id().clone(),
+            title: video.get_title().to_string(),
+            clip_count: video.clips().count(),
+        }
+    }
+}
+
+impl ClipSummary {
+    fn new(clip: &crate::model::VerifiedClip) -> Self {
+        Self {
+            uuid: clip.get_uuid().clone(),
+            song_title: clip.get_song_title().to_string(),
+            start_time: clip.get_start_time().clone(),
+            end_time: clip.get_end_time().clone(),
+        }
+    }
+}
+
+/// `before`から`after`への差分を求める
+///
+/// - `ignore_fields`: 比較しない月別ファイルのフィールド名. 動画, クリップの両方に適用する
+/// - 動画は変更後(削除されたものは変更前)の公開日時の順, クリップは開始時間の順
+pub(crate) fn diff_libraries(
+    before: &crate::music_file::MusicLibrary,
+    after: &crate::music_file::MusicLibrary,
+    ignore_fields: &[String],
+) -> Result<LibraryDiff, serde_json::Error> {
+    let before = videos_by_id(before);
+    let after = videos_by_id(after);
+    let mut diff = LibraryDiff::default();
+
+    for (video_id, old) in &before {
+        match after.get(video_id) {
+            None => diff.removed_videos.push(VideoSummary::new(old)),
+            Some(new) => {
+                if let Some(video_diff) = diff_video(old, new, ignore_fields)? {
+                    diff.modified_videos.push(video_diff);
+                }
+            }
+        }
+    }
+    for (video_id, new) in &after {
+        if !before.contains_key(video_id) {
+            diff.added_videos.push(VideoSummary::new(new));
+        }
+    }
+
+    let published_at = |video_id: &crate::model::VideoId| {
+        after
+            .get(video_id)
+            .or_else(|| before.get(video_id))
+            .map(|video| video.get_published_at().as_secs())
+    };
+    diff.added_videos
+        .sort_by_key(|video| (published_at(&video.video_id), video.video_id.clone()));
+    diff.removed_videos
+        .sort_by_key(|video| (published_at(&video.video_id), video.video_id.clone()));
+    diff.modified_videos
+        .sort_by_key(|video| (published_at(&video.video_id), video.video_id.clone()));
+
+    Ok(diff)
+}
+
+fn videos_by_id(
+    library: &crate::music_file::MusicLibrary,
+) -> std::collections::BTreeMap<&crate::model::VideoId, &crate::model::VerifiedVideo> {
+    library
+        .iter_files()
+        .flat_map(|file| file.videos().iter())
+        .map(|video| (video.get_video_id(), video))
+        .collect()
+}
+
+/// 内容が同じときは`None`
+fn diff_video(
+    old: &crate::model::VerifiedVideo,
+    new: &crate::model::VerifiedVideo,
+    ignore_fields: &[String],
+) -> Result<Option<VideoDiff>, serde_json::Error> {
+    let mut ignore_video_fields = ignore_fields.to_vec();
+    ignore_video_fields.push("clips".to_string());
+    let changes = diff_fields(
+        &serde_json::to_value(old)?,
+        &serde_json::to_value(new)?,
+        &ignore_video_fields,
+    );
+
+    let old_clips = old
+        .clips()
+        .map(|clip| (clip.get_uuid(), clip))
+        .collect::<std::collections::HashMap<_, _>>();
+    let new_clips = new
+        .clips()
+        .map(|clip| (clip.get_uuid(), clip))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let mut added_clips = Vec::new();
+    let mut modified_clips = Vec::new();
+    // `clips()`は開始時間の順
+    for clip in new.clips() {
+        match old_clips.get(clip.get_uuid()) {
+            None => added_clips.push(ClipSummary::new(clip)),
+            Some(old_clip) => {
+                let changes = diff_fields(
+                    &serde_json::to_value(old_clip)?,
+                    &serde_json::to_value(clip)?,
+                    ignore_fields,
+                );
+                if !changes.is_empty() {
+                    modified_clips.push(ClipDiff {
+                        uuid: clip.get_uuid().clone(),
+                        song_title: clip.get_song_title().to_string(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    let removed_clips = old
+        .clips()
+        .filter(|clip| !new_clips.contains_key(clip.get_uuid()))
+        .map(ClipSummary::new)
+        .collect::<Vec<_>>();
+
+    if changes.is_empty()
+        && added_clips.is_empty()
+        && removed_clips.is_empty()
+        && modified_clips.is_empty()
+    {
+        return Ok(None);
+    }
+    Ok(Some(VideoDiff {
+        video_id: new.get_video_id().clone(),
+        title: new.get_title().to_string(),
+        changes,
+        added_clips,
+        removed_clips,
+        modified_clips,
+    }))
+}
+
+/// JSONオブジェクトのフィールドごとの差分. フィールド名の順
+fn diff_fields(
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    ignore_fields: &[String],
+) -> Vec<FieldChange> {
+    let empty = serde_json::Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+
+    old.keys()
+        .chain(new.keys())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .filter(|field| !ignore_fields.contains(field))
+        .filter_map(|field| {
+            let old = old.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            let new = new.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                old,
+                new,
+            })
+        })
+        .collect()
+}
+
+/// 差分を指定した形式の文字列に変換する
+pub(crate) fn render(
+    format: DiffFormat,
+    diff: &LibraryDiff,
+) -> Result<String, serde_json::Error> {
+    Ok(match format {
+        DiffFormat::Markdown => markdown::to_markdown(diff),
+        DiffFormat::Json => serde_json::to_string_pretty(diff)? + "\n",
+    })
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_libraries_same() {
+        let (_a, before) = crate::music_file::MusicLibrary::with_videos(vec![
+            crate::model::VerifiedVideo::self_a(),
+            crate::model::VerifiedVideo::self_b(),
+        ]);
+        let (_b, after) = crate::music_file::MusicLibrary::with_videos(vec![
+            crate::model::VerifiedVideo::self_b(),
+            crate::model::VerifiedVideo::self_a(),
+        ]);
+        let diff = diff_libraries(&before, &after, &[]).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.counts(), DiffCounts::default());
+    }
+
+    #[test]
+    fn test_diff_libraries_added_removed_modified() {
+        let (_a, before) = crate::music_file::MusicLibrary::with_videos(vec![
+            crate::model::VerifiedVideo::self_a(),
+        ]);
+
+        let (edited, _) = crate::model::VerifiedVideo::self_a()
+            .try_edit(|video_tags, clips| {
+                video_tags.insert(tagctl::model::VideoTagId::new("acoustic").unwrap());
+                clips[0].set_song_title("Retitled");
+            })
+            .unwrap();
+        let (_b, after) = crate::music_file::MusicLibrary::with_videos(vec![
+            edited,
+            crate::model::VerifiedVideo::self_b(),
+        ]);
+
+        let diff = diff_libraries(&before, &after, &[]).unwrap();
+        assert_eq!(diff.added_videos.len(), 1);
+        assert_eq!(diff.added_videos[0].clip_count, 3);
+        assert!(diff.removed_videos.is_empty());
+        assert_eq!(diff.modified_videos.len(), 1);
+
+        let video = &diff.modified_videos[0];
+        assert_eq!(
+            video.changes,
+            vec![FieldChange {
+                field: "videoTags".to_string(),
+                old: serde_json::json!(["karaoke"]),
+                new: serde_json::json!(["acoustic", "karaoke"]),
+            }]
+        );
+        assert_eq!(video.modified_clips.len(), 1);
+        assert_eq!(
+            video.modified_clips[0].changes,
+            vec![FieldChange {
+                field: "songTitle".to_string(),
+                old: serde_json::json!("Test Song A1"),
+                new: serde_json::json!("Retitled"),
+            }]
+        );
+        assert_eq!(
+            diff.counts(),
+            DiffCounts {
+                added_videos: 1,
+                modified_videos: 1,
+                added_clips: 3,
+                modified_clips: 1,
+                ..Default::default()
+            }
+        );
+
+        // 逆方向
+        let diff = diff_libraries(&after, &before, &[]).unwrap();
+        assert_eq!(diff.removed_videos.len(), 1);
+        assert_eq!(diff.counts().removed_clips, 3);
+    }
+
+    #[test]
+    fn test_diff_libraries_clips_and_ignore_fields() {
+        let (_a, before) = crate::music_file::MusicLibrary::with_videos(vec![
+            crate::model::VerifiedVideo::self_a(),
+        ]);
+
+        let (edited, _) = crate::model::VerifiedVideo::self_a()
+            .try_edit(|video_tags, clips| {
+                video_tags.insert(tagctl::model::VideoTagId::new("acoustic").unwrap());
+                clips[1].set_liver_ids(artistctl::model::LiverIds::self_1());
+            })
+            .unwrap();
+        let (_b, after) = crate::music_file::MusicLibrary::with_videos(vec![edited]);
+
+        let diff = diff_libraries(&before, &after, &["videoTags".to_string()]).unwrap();
+        let video = &diff.modified_videos[0];
+        assert!(video.changes.is_empty());
+        assert_eq!(video.modified_clips[0].changes[0].field, "liverIds");
+
+        let diff = diff_libraries(
+            &before,
+            &after,
+            &["videoTags".to_string(), "liverIds".to_string()],
+        )
+        .unwrap();
+        assert!(diff.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+/// PRのコメントに貼るためのMarkdown
+pub(super) fn to_markdown(diff: &super::LibraryDiff) -> String {
+    let mut md = String::from("## Music library diff\n\n");
+    if diff.is_empty() {
+        md.push_str("No changes.\n");
+        return md;
+    }
+
+    let counts = diff.counts();
+    md.push_str("| | Videos | Clips |\n|:---|---:|---:|\n");
+    md.push_str(&format!(
+        "| Added | {} | {} |\n",
+        counts.added_videos, counts.added_clips
+    ));
+    md.push_str(&format!(
+        "| Removed | {} | {} |\n",
+        counts.removed_videos, counts.removed_clips
+    ));
+    md.push_str(&format!(
+        "| Modified | {} | {} |\n",
+        counts.modified_videos, counts.modified_clips
+    ));
+
+    if !diff.added_videos.is_empty() {
+        md.push_str("\n### Added videos\n\n");
+        for video in &diff.added_videos {
+            md.push_str(&video_summary_line(video));
+        }
+    }
+    if !diff.removed_videos.is_empty() {
+        md.push_str("\n### Removed videos\n\n");
+        for video in &diff.removed_videos {
+            md.push_str(&video_summary_line(video));
+        }
+    }
+    if !diff.modified_videos.is_empty() {
+        md.push_str("\n### Modified videos\n");
+        for video in &diff.modified_videos {
+            md.push_str(&video_diff_section(video));
+        }
+    }
+
+    md
+}
+
+/// `- [videoId](URL) タイトル (3 clips)`
+fn video_summary_line(video: &super::VideoSummary) -> String {
+    format!(
+        "- {} {} ({} clips)\n",
+        video_link(&video.video_id),
+        video.title,
+        video.clip_count
+    )
+}
+
+fn video_diff_section(video: &super::VideoDiff) -> String {
+    let mut md = format!("\n#### {} {}\n\n", video_link(&video.video_id), video.title);
+
+    if !video.changes.is_empty() {
+        md.push_str("| Field | Before | After |\n|:---|:---|:---|\n");
+        for change in &video.changes {
+            md.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                change.field,
+                value_cell(&change.old),
+                value_cell(&change.new)
+            ));
+        }
+        md.push('\n');
+    }
+
+    for clip in &video.added_clips {
+        md.push_str(&format!("- Added {}\n", clip_summary(clip)));
+    }
+    for clip in &video.removed_clips {
+        md.push_str(&format!("- Removed {}\n", clip_summary(clip)));
+    }
+    if !video.added_clips.is_empty() || !video.removed_clips.is_empty() {
+        md.push('\n');
+    }
+
+    if !video.modified_clips.is_empty() {
+        md.push_str("| Clip | Field | Before | After |\n|:---|:---|:---|:---|\n");
+        for clip in &video.modified_clips {
+            for change in &clip.changes {
+                md.push_str(&format!(
+                    "| {} | `{}` | {} | {} |\n",
+                    escape_cell(&clip.song_title),
+                    change.field,
+                    value_cell(&change.old),
+                    value_cell(&change.new)
+                ));
+            }
+        }
+    }
+
+    md
+}
+
+/// `"曲名" 1:02-3:04 (`uuid`)`
+fn clip_summary(clip: &super::ClipSummary) -> String {
+    format!(
+        "\"{}\" {}-{} (`{}`)",
+        clip.song_title,
+        clip.start_time.to_short_str(),
+        clip.end_time.to_short_str(),
+        clip.uuid
+    )
+}
+
+fn video_link(video_id: &crate::model::VideoId) -> String {
+    format!("[{video_id}](https://youtu.be/{video_id})")
+}
+
+/// JSONの値をコードとして表示する. 存在しない値は`-`
+fn value_cell(value: &serde_json::Value) -> String {
+    if value.is_null() {
+        return "-".to_string();
+    }
+    format!("`{}`", escape_cell(&value.to_string()))
+}
+
+/// 表のセルを壊さないように`|`をエスケープする
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown_empty() {
+        assert_eq!(
+            to_markdown(&crate::library_diff::LibraryDiff::default()),
+            "## Music library diff\n\nNo changes.\n"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let clip = crate::library_diff::ClipSummary {
+            uuid: crate::model::UuidVer4::self_partly_rand(0xa1),
+            song_title: "Song|A".to_string(),
+            start_time: crate::model::Duration::from_secs_u16(62),
+            end_time: crate::model::Duration::from_secs_u16(184),
+        };
+        let diff = crate::library_diff::LibraryDiff {
+            added_videos: vec![crate::library_diff::VideoSummary {
+                video_id: crate::model::VideoId::test_id_2(),
+                title: "New Video".to_string(),
+                clip_count: 3,
+            }],
+            removed_videos: vec![],
+            modified_videos: vec![crate::library_diff::VideoDiff {
+                video_id: crate::model::VideoId::test_id_1(),
+                title: "Edited Video".to_string(),
+                changes: vec![crate::library_diff::FieldChange {
+                    field: "uploaderName".to_string(),
+                    old: serde_json::json!("Test Channel 1"),
+                    new: serde_json::Value::Null,
+                }],
+                added_clips: vec![clip.clone()],
+                removed_clips: vec![],
+                modified_clips: vec![crate::library_diff::ClipDiff {
+                    uuid: clip.uuid.clone(),
+                    song_title: clip.song_title.clone(),
+                    changes: vec![crate::library_diff::FieldChange {
+                        field: "liverIds".to_string(),
+                        old: serde_json::json!(["riku-tazumi"]),
+                        new: serde_json::json!(["riku-tazumi", "yugamin"]),
+                    }],
+                }],
+            }],
+        };
+        let md = to_markdown(&diff);
+
+        assert!(md.contains("| Added | 1 | 4 |\n"));
+        assert!(md.contains("| Modified | 1 | 1 |\n"));
+        assert!(md.contains(
+            "### Added videos\n\n- [22222222222](https://youtu.be/22222222222) New Video (3 clips)\n"
+        ));
+        assert!(!md.contains("### Removed videos"));
+        assert!(md.contains("| `uploaderName` | `\"Test Channel 1\"` | - |\n"));
+        assert!(md.contains("- Added \"Song|A\" 1:02-3:04 (`"));
+        assert!(md.contains(
+            "| Song\\|A | `liverIds` | `[\"riku-tazumi\"]` | `[\"riku-tazumi\",\"yugamin\"]` |\n"
+        ));
+    }
+}
@@ -0,0 +1,237 @@
+mod config;
+mod error;
+mod output;
+mod rules;
+
+pub(crate) use config::{LintConfig, LintConfigResolver};
+pub use error::LintError;
+pub(crate) use output::{LintFormat, count, render};
+
+/// lintのルール
+///
+/// - 設定ファイルではkebab-caseの名前で指定する
+/// - 楽曲情報として不正ではないが, 誤りの可能性が高いものを検出する
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum LintRule {
+    /// 同じ動画内でクリップの時間が重なっている, 境界が接している
+    ///
+    /// 重なりは月別ファイルの読み込み時にエラーになるため, 主に接しているものを検出する
+    OverlappingClips,
+    /// クリップが指定した秒数より短い
+    ShortClip,
+    /// 同じ動画内で同じ曲が複数回歌われている
+    DuplicateSong,
+    /// `songTitle`に全角空白, 前後の空白が含まれる
+    SongTitleWhitespace,
+    /// `externalArtistsName`に箱内のライバーの名前が含まれる
+    ExternalArtistIsLiver,
+    /// ライバー本人のチャンネル(サブチャンネルを含む)の動画に`uploaderName`が設定されている
+    UploaderNameOnLiverChannel,
+}
+
+/// 検出した問題の重要度. 設定ファイルでは`off`でルールを無効にする
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+/// lintで検出した問題
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LintDiagnostic {
+    pub(crate) rule: LintRule,
+    pub(crate) severity: Severity,
+    /// 月別ファイルのパス. 区切り文字は`/`
+    pub(crate) path: String,
+    pub(crate) video_id: crate::model::VideoId,
+    /// 動画全体に対する問題のときは`None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) uuid: Option<crate::model::UuidVer4>,
+    pub(crate) message: String,
+}
+
+impl LintRule {
+    pub(crate) const ALL: [Self; 6] = [
+        Self::OverlappingClips,
+        Self::ShortClip,
+        Self::DuplicateSong,
+        Self::SongTitleWhitespace,
+        Self::ExternalArtistIsLiver,
+        Self::UploaderNameOnLiverChannel,
+    ];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::OverlappingClips => "overlapping-clips",
+            Self::ShortClip => "short-clip",
+            Self::DuplicateSong => "duplicate-song",
+            Self::SongTitleWhitespace => "song-title-whitespace",
+            Self::ExternalArtistIsLiver => "external-artist-is-liver",
+            Self::UploaderNameOnLiverChannel => "uploader-name-on-liver-channel",
+        }
+    }
+
+    /// SARIFのルール定義に載せる説明
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::OverlappingClips => {
+                "Clips in the same video overlap or share a boundary"
+            }
+            Self::ShortClip => "Clip is shorter than the configured minimum length",
+            Self::DuplicateSong => "The same song appears twice in one video",
+            Self::SongTitleWhitespace => {
+                "songTitle contains a full-width space or leading/trailing whitespace"
+            }
+            Self::ExternalArtistIsLiver => {
+                "externalArtistsName contains the name of a known liver"
+            }
+            Self::UploaderNameOnLiverChannel => {
+                "uploaderName is set on a video from a liver's own channel"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for LintRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Severity {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// 楽曲情報の全ての月別ファイルをlintする
+///
+/// - 月別ファイルごとに, 楽曲情報のルートからそのファイルのディレクトリまでの設定ファイルを適用する
+/// - 出力順はファイルのパス, 動画の公開日時の順. 動画ごとに動画全体の問題, クリップの開始時間の順
+///
+/// # Errors
+/// - 設定ファイルが読み込めないとき, 内容が不正なとき
+pub(crate) fn lint_library(
+    library: &crate::music_file::MusicLibrary,
+) -> Result<Vec<LintDiagnostic>, LintError> {
+    let mut resolver = LintConfigResolver::new(library.get_root_dir());
+    let context = rules::LintContext::from_loaded_livers();
+
+    let mut files = library.iter_files().collect::<Vec<_>>();
+    files.sort_by(|a, b| a.get_path().cmp(b.get_path()));
+
+    let mut diagnostics = Vec::new();
+    for file in files {
+        let config = resolver.resolve(file.get_path())?;
+        let path = file.get_path().to_string_lossy().replace('\\', "/");
+
+        let mut videos = file.videos().iter().collect::<Vec<_>>();
+        videos.sort_by(|a, b| {
+            a.get_published_at()
+                .cmp(b.get_published_at())
+                .then_with(|| a.get_video_id().cmp(b.get_video_id()))
+        });
+        for video in videos {
+            diagnostics.extend(rules::check_video(video, &config, &context, &path));
+        }
+    }
+    Ok(diagnostics)
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_rule_names_match_serde() {
+        for rule in LintRule::ALL {
+            assert_eq!(
+                serde_json::to_value(rule).unwrap(),
+                serde_json::Value::String(rule.as_str().to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_lint_library_applies_directory_config() {
+        let (root, library) = crate::music_file::MusicLibrary::with_videos(vec![
+            crate::model::VerifiedVideo::self_a(),
+        ]);
+        let month_dir = library
+            .iter_files()
+            .next()
+            .unwrap()
+            .get_path()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        // 既定: 5秒のクリップ3つ, ライバーのチャンネルに`uploaderName`
+        let rules = |diagnostics: &[LintDiagnostic]| {
+            diagnostics
+                .iter()
+                .map(|d| (d.rule, d.severity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rules(&lint_library(&library).unwrap()),
+            vec![
+                (LintRule::UploaderNameOnLiverChannel, Severity::Warning),
+                (LintRule::ShortClip, Severity::Warning),
+                (LintRule::ShortClip, Severity::Warning),
+                (LintRule::ShortClip, Severity::Warning),
+            ]
+        );
+
+        std::fs::write(
+            root.path().join(crate::cfg::LINT_CONFIG_FILE_NAME),
+            r#"{"rules": {"uploader-name-on-liver-channel": "error"}, "shortClipMinSecs": 5}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rules(&lint_library(&library).unwrap()),
+            vec![(LintRule::UploaderNameOnLiverChannel, Severity::Error)]
+        );
+
+        // 年のディレクトリの設定がルートの設定より優先される
+        std::fs::create_dir_all(&month_dir).unwrap();
+        std::fs::write(
+            month_dir.join(crate::cfg::LINT_CONFIG_FILE_NAME),
+            r#"{"rules": {"uploader-name-on-liver-channel": "off"}}"#,
+        )
+        .unwrap();
+        assert!(lint_library(&library).unwrap().is_empty());
+    }
+}
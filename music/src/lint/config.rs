@@ -0,0 +1,206 @@
+/// 1つのディレクトリに置く設定ファイル(`lint.json`)の内容
+///
+/// 指定しなかった項目は上位のディレクトリの設定を引き継ぐ
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct RawLintConfig {
+    /// ルールごとの重要度. `off`で無効
+    #[serde(default)]
+    rules: std::collections::BTreeMap<super::LintRule, super::Severity>,
+    /// `short-clip`で許容するクリップの最短の秒数
+    short_clip_min_secs: Option<u32>,
+}
+
+/// 月別ファイルに適用するlintの設定
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LintConfig {
+    severities: std::collections::BTreeMap<super::LintRule, super::Severity>,
+    short_clip_min_secs: u32,
+}
+
+/// 月別ファイルごとに, 楽曲情報のルートからそのファイルのディレクトリまでの設定ファイルを重ねる
+///
+/// - 下位のディレクトリの設定が優先される
+/// - 読み込んだ設定ファイルはディレクトリごとに保持する
+#[derive(Debug)]
+pub(crate) struct LintConfigResolver {
+    root: std::path::PathBuf,
+    loaded: std::collections::HashMap<std::path::PathBuf, Option<RawLintConfig>>,
+}
+
+impl Default for LintConfig {
+    /// 全てのルールが`warning`
+    fn default() -> Self {
+        Self {
+            severities: super::LintRule::ALL
+                .into_iter()
+                .map(|rule| (rule, super::Severity::Warning))
+                .collect(),
+            short_clip_min_secs: crate::cfg::DEFAULT_LINT_SHORT_CLIP_MIN_SECS,
+        }
+    }
+}
+
+impl LintConfig {
+    pub(crate) fn severity(&self, rule: super::LintRule) -> super::Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or(super::Severity::Warning)
+    }
+
+    pub(crate) fn get_short_clip_min_secs(&self) -> u32 {
+        self.short_clip_min_secs
+    }
+
+    fn merge(&mut self, raw: &RawLintConfig) {
+        self.severities
+            .extend(raw.rules.iter().map(|(rule, severity)| (*rule, *severity)));
+        if let Some(secs) = raw.short_clip_min_secs {
+            self.short_clip_min_secs = secs;
+        }
+    }
+}
+
+impl LintConfigResolver {
+    pub(crate) fn new(root: &std::path::Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            loaded: std::collections::HashMap::new(),
+        }
+    }
+
+    /// `file`に適用する設定
+    ///
+    /// - `file`が楽曲情報のルートの外にあるときはルートの設定のみ
+    ///
+    /// # Errors
+    /// - 設定ファイルが読み込めないとき, 内容が不正なとき
+    pub(crate) fn resolve(
+        &mut self,
+        file: &std::path::Path,
+    ) -> Result<LintConfig, super::LintError> {
+        let mut dirs = vec![self.root.clone()];
+        if let Some(rel) = file
+            .parent()
+            .and_then(|parent| parent.strip_prefix(&self.root).ok())
+        {
+            for component in rel.components() {
+                let dir = dirs.last().expect("root is pushed first").join(component);
+                dirs.push(dir);
+            }
+        }
+
+        let mut config = LintConfig::default();
+        for dir in dirs {
+            if let Some(raw) = self.load(dir)? {
+                config.merge(raw);
+            }
+        }
+        Ok(config)
+    }
+
+    fn load(
+        &mut self,
+        dir: std::path::PathBuf,
+    ) -> Result<Option<&RawLintConfig>, super::LintError> {
+        use std::collections::hash_map::Entry;
+
+        let raw = match self.loaded.entry(dir) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = entry.key().join(crate::cfg::LINT_CONFIG_FILE_NAME);
+                let raw = match std::fs::read_to_string(&path) {
+                    Ok(content) => {
+                        Some(serde_json::from_str(&content).map_err(|e| {
+                            super::LintError::InvalidConfig {
+                                path: path.clone(),
+                                msg: e.to_string(),
+                            }
+                        })?)
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => {
+                        return Err(super::LintError::ConfigRead {
+                            path,
+                            msg: e.to_string(),
+                        });
+                    }
+                };
+                entry.insert(raw)
+            }
+        };
+        Ok(raw.as_ref())
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_config_merge() {
+        let mut config = LintConfig::default();
+        assert_eq!(
+            config.severity(crate::lint::LintRule::ShortClip),
+            crate::lint::Severity::Warning
+        );
+
+        let raw: RawLintConfig = serde_json::from_str(
+            r#"{"rules": {"short-clip": "off", "duplicate-song": "error"}, "shortClipMinSecs": 10}"#,
+        )
+        .unwrap();
+        config.merge(&raw);
+        assert_eq!(
+            config.severity(crate::lint::LintRule::ShortClip),
+            crate::lint::Severity::Off
+        );
+        assert_eq!(
+            config.severity(crate::lint::LintRule::DuplicateSong),
+            crate::lint::Severity::Error
+        );
+        assert_eq!(config.get_short_clip_min_secs(), 10);
+
+        // 指定しなかった項目は引き継ぐ
+        config.merge(
+            &serde_json::from_str(r#"{"rules": {"short-clip": "warning"}}"#).unwrap(),
+        );
+        assert_eq!(
+            config.severity(crate::lint::LintRule::ShortClip),
+            crate::lint::Severity::Warning
+        );
+        assert_eq!(config.get_short_clip_min_secs(), 10);
+    }
+
+    #[test]
+    fn test_raw_lint_config_rejects_unknown() {
+        assert!(
+            serde_json::from_str::<RawLintConfig>(
+                r#"{"rules": {"no-such-rule": "off"}}"#
+            )
+            .is_err()
+        );
+        assert!(
+            serde_json::from_str::<RawLintConfig>(
+                r#"{"rules": {"short-clip": "info"}}"#
+            )
+            .is_err()
+        );
+        assert!(serde_json::from_str::<RawLintConfig>(r#"{"minSecs": 10}"#).is_err());
+    }
+
+    #[test]
+    fn test_lint_config_resolver_reports_invalid_config() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join(crate::cfg::LINT_CONFIG_FILE_NAME), "{")
+            .unwrap();
+        let mut resolver = LintConfigResolver::new(root.path());
+        assert!(matches!(
+            resolver.resolve(&root.path().join("2025/01.json")),
+            Err(crate::lint::LintError::InvalidConfig { .. })
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+/// lintの設定ファイルに関するエラー
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum LintError {
+    /// 設定ファイルの読み込みに失敗
+    #[error("Failed to read lint config {path}: {msg}")]
+    ConfigRead {
+        path: std::path::PathBuf,
+        msg: String,
+    },
+    /// 設定ファイルの内容が不正
+    #[error("Invalid lint config {path}: {msg}")]
+    InvalidConfig {
+        path: std::path::PathBuf,
+        msg: String,
+    },
+}
@@ -0,0 +1,204 @@
+/// lintの結果の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LintFormat {
+    /// One line per problem followed by a summary
+    Text,
+    /// JSON object with the problems and their counts
+    Json,
+    /// SARIF 2.1.0 for code scanning tools
+    Sarif,
+}
+
+/// 重要度ごとの件数. `(error, warning)`
+pub(crate) fn count(diagnostics: &[super::LintDiagnostic]) -> (usize, usize) {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == super::Severity::Error)
+        .count();
+    (errors, diagnostics.len() - errors)
+}
+
+/// lintの結果を指定した形式の文字列に変換する
+pub(crate) fn render(
+    format: LintFormat,
+    diagnostics: &[super::LintDiagnostic],
+) -> Result<String, serde_json::Error> {
+    Ok(match format {
+        LintFormat::Text => to_text(diagnostics),
+        LintFormat::Json => to_json(diagnostics)?,
+        LintFormat::Sarif => to_sarif(diagnostics)?,
+    })
+}
+
+/// `path: severity[rule] videoId uuid: message`
+fn to_text(diagnostics: &[super::LintDiagnostic]) -> String {
+    let mut text = String::new();
+    for d in diagnostics {
+        let target = match &d.uuid {
+            Some(uuid) => format!("{} {uuid}", d.video_id),
+            None => d.video_id.to_string(),
+        };
+        text.push_str(&format!(
+            "{}: {}[{}] {target}: {}\n",
+            d.path,
+            d.severity.as_str(),
+            d.rule,
+            d.message
+        ));
+    }
+    let (errors, warnings) = count(diagnostics);
+    text.push_str(&format!("{errors} errors, {warnings} warnings\n"));
+    text
+}
+
+fn to_json(diagnostics: &[super::LintDiagnostic]) -> Result<String, serde_json::Error> {
+    let (errors, warnings) = count(diagnostics);
+    let value = serde_json::json!({
+        "errorCount": errors,
+        "warningCount": warnings,
+        "diagnostics": diagnostics,
+    });
+    Ok(serde_json::to_string_pretty(&value)? + "\n")
+}
+
+/// 位置は月別ファイル単位. 動画ID, UUIDは論理的な位置として載せる
+fn to_sarif(
+    diagnostics: &[super::LintDiagnostic],
+) -> Result<String, serde_json::Error> {
+    let rules = super::LintRule::ALL
+        .iter()
+        .map(|rule| {
+            serde_json::json!({
+                "id": rule.as_str(),
+                "shortDescription": { "text": rule.description() },
+                "defaultConfiguration": { "level": super::Severity::Warning.as_str() },
+            })
+        })
+        .collect::<Vec<_>>();
+    let results = diagnostics
+        .iter()
+        .map(|d| {
+            let rule_index = super::LintRule::ALL
+                .iter()
+                .position(|rule| *rule == d.rule)
+                .expect("ALL contains every rule");
+            let name = match &d.uuid {
+                Some(uuid) => format!("{}/{uuid}", d.video_id),
+                None => d.video_id.to_string(),
+            };
+            serde_json::json!({
+                "ruleId": d.rule.as_str(),
+                "ruleIndex": rule_index,
+                "level": d.severity.as_str(),
+                "message": { "text": d.message },
+                "locations": [{
+                    "physicalLocation": { "artifactLocation": { "uri": d.path } },
+                    "logicalLocations": [{
+                        "fullyQualifiedName": name,
+                        "kind": "object",
+                    }],
+                }],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let value = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "musictl",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    Ok(serde_json::to_string_pretty(&value)? + "\n")
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostics() -> Vec<crate::lint::LintDiagnostic> {
+        vec![
+            crate::lint::LintDiagnostic {
+                rule: crate::lint::LintRule::UploaderNameOnLiverChannel,
+                severity: crate::lint::Severity::Error,
+                path: "music/2024/01.json".to_string(),
+                video_id: crate::model::VideoId::test_id_1(),
+                uuid: None,
+                message: "uploaderName is set".to_string(),
+            },
+            crate::lint::LintDiagnostic {
+                rule: crate::lint::LintRule::ShortClip,
+                severity: crate::lint::Severity::Warning,
+                path: "music/2024/01.json".to_string(),
+                video_id: crate::model::VideoId::test_id_1(),
+                uuid: Some(crate::model::UuidVer4::self_partly_rand(0xa1)),
+                message: "clip is 5s long, shorter than 30s".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_text() {
+        let text = render(LintFormat::Text, &diagnostics()).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "music/2024/01.json: error[uploader-name-on-liver-channel] 11111111111: uploaderName is set"
+        );
+        assert!(
+            lines[1]
+                .starts_with("music/2024/01.json: warning[short-clip] 11111111111 ")
+        );
+        assert_eq!(lines[2], "1 errors, 1 warnings");
+
+        assert_eq!(
+            render(LintFormat::Text, &[]).unwrap(),
+            "0 errors, 0 warnings\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = render(LintFormat::Json, &diagnostics()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["errorCount"], 1);
+        assert_eq!(value["warningCount"], 1);
+        assert_eq!(
+            value["diagnostics"][0]["rule"],
+            "uploader-name-on-liver-channel"
+        );
+        assert_eq!(value["diagnostics"][0]["videoId"], "11111111111");
+        assert!(value["diagnostics"][0].get("uuid").is_none());
+        assert_eq!(value["diagnostics"][1]["severity"], "warning");
+    }
+
+    #[test]
+    fn test_render_sarif() {
+        let sarif = render(LintFormat::Sarif, &diagnostics()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        assert_eq!(value["version"], "2.1.0");
+
+        let run = &value["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"].as_array().unwrap().len(),
+            crate::lint::LintRule::ALL.len()
+        );
+        let result = &run["results"][1];
+        assert_eq!(result["ruleId"], "short-clip");
+        assert_eq!(result["ruleIndex"], 1);
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "music/2024/01.json"
+        );
+    }
+}
@@ -0,0 +1,306 @@
+/// ルールの検査に使うライバーの情報
+#[derive(Debug, Clone, Default)]
+pub(super) struct LintContext {
+    /// 正規化したライバーの名前 -> その名前を持つライバーID. ID順
+    liver_names: std::collections::HashMap<String, std::collections::BTreeSet<String>>,
+    /// チャンネルID(サブチャンネルを含む) -> ライバーID
+    liver_channels: std::collections::HashMap<String, String>,
+}
+
+impl LintContext {
+    pub(super) fn from_loaded_livers() -> Self {
+        Self::from_livers(artistctl::model::LOADED_LIVER_DATA.clone())
+    }
+
+    fn from_livers(livers: artistctl::model::Livers) -> Self {
+        let mut context = Self::default();
+        for (id, liver) in livers {
+            let id = id.as_str().to_string();
+            for channel_id in liver.channel_ids() {
+                context
+                    .liver_channels
+                    .insert(channel_id.to_string(), id.clone());
+            }
+            let inner = liver.into_inner();
+            for name in [inner.ja, inner.jah, inner.en]
+                .into_iter()
+                .chain(inner.aliases)
+            {
+                context
+                    .liver_names
+                    .entry(cmn_rs::text::match_key(&name))
+                    .or_default()
+                    .insert(id.clone());
+            }
+        }
+        context
+    }
+}
+
+/// 動画とそのクリップを設定で有効なルールで検査する
+pub(super) fn check_video(
+    video: &crate::model::VerifiedVideo,
+    config: &super::LintConfig,
+    context: &LintContext,
+    path: &str,
+) -> Vec<super::LintDiagnostic> {
+    let mut found: Vec<(super::LintRule, Option<&crate::model::UuidVer4>, String)> =
+        Vec::new();
+
+    if let Some(name) = video.get_uploader_name()
+        && let Some(liver_id) = context
+            .liver_channels
+            .get(&video.get_channel_id().to_string())
+    {
+        found.push((
+            super::LintRule::UploaderNameOnLiverChannel,
+            None,
+            format!("uploaderName \"{name}\" is set on the channel of `{liver_id}`"),
+        ));
+    }
+
+    let mut clips = video.clips().collect::<Vec<_>>();
+    clips.sort_by_key(|clip| clip.get_start_time().as_secs());
+
+    let mut latest_end: Option<&crate::model::VerifiedClip> = None;
+    let mut seen_titles = std::collections::HashSet::new();
+    let mut seen_song_ids = std::collections::HashSet::new();
+    for clip in clips {
+        let uuid = Some(clip.get_uuid());
+        let title = clip.get_song_title();
+        let start = clip.get_start_time().as_secs();
+        let end = clip.get_end_time().as_secs();
+
+        // 重なりは読み込み時に弾かれるため, 実際には境界が接しているクリップを検出する
+        if let Some(prev) = latest_end
+            && start <= prev.get_end_time().as_secs()
+        {
+            let relation = if start < prev.get_end_time().as_secs() {
+                "overlaps"
+            } else {
+                "starts exactly at the end of"
+            };
+            found.push((
+                super::LintRule::OverlappingClips,
+                uuid,
+                format!(
+                    "clip starting at {} {relation} \"{}\" ending at {}",
+                    clip.get_start_time().to_short_str(),
+                    prev.get_song_title(),
+                    prev.get_end_time().to_short_str()
+                ),
+            ));
+        }
+        if latest_end.is_none_or(|prev| prev.get_end_time().as_secs() < end) {
+            latest_end = Some(clip);
+        }
+
+        let min_secs = config.get_short_clip_min_secs();
+        if end - start < min_secs {
+            found.push((
+                super::LintRule::ShortClip,
+                uuid,
+                format!("clip is {}s long, shorter than {min_secs}s", end - start),
+            ));
+        }
+
+        let new_title = seen_titles.insert(cmn_rs::text::match_key(title));
+        let new_song_id = clip
+            .get_song_id()
+            .is_none_or(|song_id| seen_song_ids.insert(song_id.as_str()));
+        if !new_title || !new_song_id {
+            found.push((
+                super::LintRule::DuplicateSong,
+                uuid,
+                format!("\"{title}\" is sung more than once in this video"),
+            ));
+        }
+
+        if title.contains('\u{3000}') {
+            found.push((
+                super::LintRule::SongTitleWhitespace,
+                uuid,
+                format!("songTitle \"{title}\" contains a full-width space"),
+            ));
+        } else if title.trim() != title {
+            found.push((
+                super::LintRule::SongTitleWhitespace,
+                uuid,
+                format!("songTitle \"{title}\" has leading or trailing whitespace"),
+            ));
+        }
+
+        for name in clip
+            .get_external_artists_name()
+            .map(|names| names.to_vec())
+            .unwrap_or_default()
+        {
+            if let Some(liver_ids) =
+                context.liver_names.get(&cmn_rs::text::match_key(name))
+            {
+                // 複数のライバーが同じ名前を持つときは全て挙げる
+                let livers = liver_ids
+                    .iter()
+                    .map(|id| format!("`{id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let message = if liver_ids.len() == 1 {
+                    format!(
+                        "externalArtistsName \"{name}\" is the liver {livers}; use liverIds instead"
+                    )
+                } else {
+                    format!(
+                        "externalArtistsName \"{name}\" is ambiguous between the livers {livers}; use liverIds instead"
+                    )
+                };
+                found.push((super::LintRule::ExternalArtistIsLiver, uuid, message));
+            }
+        }
+    }
+
+    found
+        .into_iter()
+        .filter_map(|(rule, uuid, message)| {
+            let severity = config.severity(rule);
+            (severity != super::Severity::Off).then(|| super::LintDiagnostic {
+                rule,
+                severity,
+                path: path.to_string(),
+                video_id: video.get_video_id().clone(),
+                uuid: uuid.cloned(),
+                message,
+            })
+        })
+        .collect()
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIDEO_JSON: &str = r#"{
+        "videoId": "11111111111",
+        "title": "Test Video",
+        "channelId": "UC2222222222222222222222",
+        "uploaderName": "ゆがみんのチャンネル",
+        "publishedAt": "2024-01-01T00:00:00Z",
+        "syncedAt": "2024-01-02T00:00:00Z",
+        "duration": "PT10M",
+        "privacyStatus": "public",
+        "embeddable": true,
+        "clips": [
+            {
+                "songTitle": "アイドル",
+                "songId": "idol",
+                "liverIds": ["riku-tazumi"],
+                "startTime": "PT1M",
+                "endTime": "PT2M",
+                "uuid": "11786ebd-4b42-428b-81f8-ecf791887321"
+            },
+            {
+                "songTitle": "ふわふわ時間　",
+                "liverIds": ["riku-tazumi"],
+                "externalArtistsName": ["Yugamin", "Apple Mike"],
+                "startTime": "PT2M",
+                "endTime": "PT2M10S",
+                "uuid": "11786ebd-4b42-428b-81f8-ecf791887322"
+            },
+            {
+                "songTitle": "Idol (cover) ",
+                "songId": "idol",
+                "liverIds": ["yugamin"],
+                "startTime": "PT5M",
+                "endTime": "PT6M",
+                "uuid": "11786ebd-4b42-428b-81f8-ecf791887323"
+            }
+        ]
+    }"#;
+
+    fn check(config: &crate::lint::LintConfig) -> Vec<(crate::lint::LintRule, String)> {
+        let video: crate::model::VerifiedVideo =
+            serde_json::from_str(VIDEO_JSON).unwrap();
+        check_video(
+            &video,
+            config,
+            &LintContext::from_loaded_livers(),
+            "2024/01.json",
+        )
+        .into_iter()
+        .map(|d| {
+            (
+                d.rule,
+                d.uuid.map(|uuid| uuid.to_string()).unwrap_or_default(),
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_check_video_all_rules() {
+        use crate::lint::LintRule;
+
+        let uuid = |n: u8| format!("11786ebd-4b42-428b-81f8-ecf79188732{n}");
+        assert_eq!(
+            check(&crate::lint::LintConfig::default()),
+            vec![
+                (LintRule::UploaderNameOnLiverChannel, String::new()),
+                (LintRule::OverlappingClips, uuid(2)),
+                (LintRule::ShortClip, uuid(2)),
+                (LintRule::SongTitleWhitespace, uuid(2)),
+                (LintRule::ExternalArtistIsLiver, uuid(2)),
+                (LintRule::DuplicateSong, uuid(3)),
+                (LintRule::SongTitleWhitespace, uuid(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_video_uploader_name_on_sub_channel() {
+        let livers = artistctl::model::Livers::self_with_sub_channel();
+        let video: crate::model::VerifiedVideo =
+            serde_json::from_str(VIDEO_JSON).unwrap();
+
+        let messages = check_video(
+            &video,
+            &crate::lint::LintConfig::default(),
+            &LintContext::from_livers(livers),
+            "2024/01.json",
+        )
+        .into_iter()
+        .filter(|d| d.rule == crate::lint::LintRule::UploaderNameOnLiverChannel)
+        .map(|d| d.message)
+        .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "uploaderName \"ゆがみんのチャンネル\" is set on the channel of `riku-tazumi`"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_video_external_artist_shared_by_livers() {
+        let livers = artistctl::model::Livers::self_with_shared_name();
+        let video: crate::model::VerifiedVideo =
+            serde_json::from_str(VIDEO_JSON).unwrap();
+
+        let messages = check_video(
+            &video,
+            &crate::lint::LintConfig::default(),
+            &LintContext::from_livers(livers),
+            "2024/01.json",
+        )
+        .into_iter()
+        .filter(|d| d.rule == crate::lint::LintRule::ExternalArtistIsLiver)
+        .map(|d| d.message)
+        .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "externalArtistsName \"Yugamin\" is ambiguous between the livers `riku-tazumi`, `yugamin`; use liverIds instead"
+            ]
+        );
+    }
+}
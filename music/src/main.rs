@@ -5,8 +5,14 @@ async fn main() {
     let cli = musictl::cli::Cli::parse();
     let _tracing_guard = enable_tracing_log(&cli);
     tracing::debug!("Command line arguments: {:?}", cli);
+    let diagnostic_format = cli.diagnostic_format();
 
     if let Err(e) = musictl::cli_exec_handler::cli_exec_handler(cli).await {
+        // 位置が分かるエラーは, 該当箇所の抜粋(GitHub Actionsでは注釈)を標準エラー出力に出す
+        let diagnostics = e.diagnostics();
+        if !diagnostics.0.is_empty() {
+            eprintln!("{}", diagnostics.render(diagnostic_format));
+        }
         tracing::error!("Command failed: {e}");
         std::process::exit(1);
     }
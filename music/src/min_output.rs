@@ -2,6 +2,19 @@ mod clips;
 mod minified_json;
 mod videos;
 
+/// 音楽データを読み込み, 最小化したクリップ, 動画のファイルを書き出す
+pub fn minify(
+    music_root: &std::path::Path,
+    min_clips_path: &std::path::Path,
+    min_videos_path: &std::path::Path,
+    dataset_build_id: cmn_rs::min_json::DatasetBuildId,
+) -> Result<(), crate::music_file::MusicFileErrors> {
+    let library = crate::music_file::MusicLibraryRepository::load(music_root)?;
+
+    write_minified(&library, min_clips_path, min_videos_path, dataset_build_id)
+        .map_err(|error| error.into_errors())
+}
+
 pub(crate) fn write_minified(
     library: &crate::music_file::MusicLibrary,
     min_clips_path: &std::path::Path,
@@ -5,11 +5,14 @@ pub(super) struct FlatClips<'a>(
 
 struct FlatClipValue<'a> {
     song_title: &'a str,
+    song_id: Option<&'a songctl::model::SongId>,
     liver_ids: &'a artistctl::model::LiverIds,
     external_artists_name: Option<&'a artistctl::model::ExternalArtistsName>,
     clipped_video_id: Option<&'a crate::model::VideoId>,
     start_time: &'a crate::model::Duration,
     end_time: &'a crate::model::Duration,
+    volume_percent: Option<&'a crate::model::VolumePercent>,
+    clip_tags: &'a crate::model::VideoTagIds,
     video_id: &'a crate::model::VideoId,
 }
 
@@ -22,6 +25,8 @@ impl serde::Serialize for FlatClipValue<'_> {
         #[serde(rename_all = "camelCase")]
         struct RawFlatClipValue<'a> {
             song_title: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            song_id: Option<&'a songctl::model::SongId>,
             liver_ids: &'a artistctl::model::LiverIds,
             #[serde(skip_serializing_if = "Option::is_none")]
             external_artists_name: Option<&'a artistctl::model::ExternalArtistsName>,
@@ -29,16 +34,23 @@ impl serde::Serialize for FlatClipValue<'_> {
             clipped_video_id: Option<&'a crate::model::VideoId>,
             start_time_secs: u32,
             end_time_secs: u32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            volume_percent: Option<u8>,
+            #[serde(skip_serializing_if = "crate::model::VideoTagIds::is_empty")]
+            clip_tags: &'a crate::model::VideoTagIds,
             video_id: &'a crate::model::VideoId,
         }
 
         RawFlatClipValue {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time_secs: self.start_time.as_secs(),
             end_time_secs: self.end_time.as_secs(),
+            volume_percent: self.volume_percent.map(|v| v.get()),
+            clip_tags: self.clip_tags,
             video_id: self.video_id,
         }
         .serialize(serializer)
@@ -57,11 +69,14 @@ impl<'a> FlatClips<'a> {
                         clip.get_uuid(),
                         FlatClipValue {
                             song_title: clip.get_song_title(),
+                            song_id: clip.get_song_id(),
                             liver_ids: clip.get_liver_ids(),
                             external_artists_name: clip.get_external_artists_name(),
                             clipped_video_id: clip.get_clipped_video_id(),
                             start_time: clip.get_start_time(),
                             end_time: clip.get_end_time(),
+                            volume_percent: clip.get_volume_percent(),
+                            clip_tags: clip.get_clip_tags(),
                             video_id,
                         },
                     );
@@ -72,3 +87,44 @@ impl<'a> FlatClips<'a> {
         Self(flat_clips)
     }
 }
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_clip_value_serialize_optional_fields() {
+        let song_title = "Test Song";
+        let liver_ids = artistctl::model::LiverIds::self_1();
+        let start_time = crate::model::Duration::from_secs_u16(5);
+        let end_time = crate::model::Duration::from_secs_u16(10);
+        let video_id = crate::model::VideoId::test_id_1();
+        let volume_percent = crate::model::VolumePercent::new(80).unwrap();
+        let clip_tags = crate::model::VideoTagIds::new(vec!["acoustic"]).unwrap();
+        let no_clip_tags = crate::model::VideoTagIds::default();
+        let value = |volume_percent, clip_tags| FlatClipValue {
+            song_title,
+            song_id: None,
+            liver_ids: &liver_ids,
+            external_artists_name: None,
+            clipped_video_id: None,
+            start_time: &start_time,
+            end_time: &end_time,
+            volume_percent,
+            clip_tags,
+            video_id: &video_id,
+        };
+
+        let json =
+            serde_json::to_value(value(Some(&volume_percent), &clip_tags)).unwrap();
+        assert_eq!(json["volumePercent"], serde_json::json!(80));
+        assert_eq!(json["clipTags"], serde_json::json!(["acoustic"]));
+
+        // 指定が無いときは出力しない
+        let json = serde_json::to_value(value(None, &no_clip_tags)).unwrap();
+        assert!(json.get("volumePercent").is_none());
+        assert!(json.get("clipTags").is_none());
+    }
+}
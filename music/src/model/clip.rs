@@ -11,7 +11,7 @@ pub(crate) use verified::VerifiedClipError;
 ///
 /// - Ok: `start_time` < `end_time`のとき
 /// - Error: `start_time` >= `end_time`のとき
-fn validate_start_end_times(
+pub(crate) fn validate_start_end_times(
     start_time: &crate::model::Duration,
     end_time: &crate::model::Duration,
 ) -> Result<(), String> {
@@ -22,3 +22,22 @@ fn validate_start_end_times(
     }
     Ok(())
 }
+
+/// `volume_percent`を指定できるクリップかの検証
+///
+/// - Ok: `volume_percent`が無い, または`clipped_video_id`が無いとき
+/// - Error: 切り抜いた動画があるクリップに`volume_percent`があるとき
+///   - 切り抜いた動画は個別に音量が調整されているため
+fn validate_volume_percent(
+    volume_percent: Option<&crate::model::VolumePercent>,
+    clipped_video_id: Option<&crate::model::VideoId>,
+) -> Result<(), String> {
+    if let (Some(volume_percent), Some(clipped_video_id)) =
+        (volume_percent, clipped_video_id)
+    {
+        return Err(format!(
+            "volumePercent({volume_percent}) cannot be set on a clip with clippedVideoId({clipped_video_id})",
+        ));
+    }
+    Ok(())
+}
@@ -8,6 +8,9 @@
 pub(crate) struct AnonymousClip {
     /// 曲名
     song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    #[serde(skip_serializing_if = "Option::is_none")]
+    song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -18,12 +21,20 @@ pub(crate) struct AnonymousClip {
     start_time: crate::model::Duration,
     /// 曲が終わる時間
     end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    #[serde(skip_serializing_if = "crate::model::VideoTagIds::is_empty")]
+    clip_tags: crate::model::VideoTagIds,
 }
 
 #[cfg(test)]
 struct AnonymousClipInitializer {
     /// 曲名
     song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -34,29 +45,42 @@ struct AnonymousClipInitializer {
     start_time: crate::model::Duration,
     /// 曲が終わる時間
     end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    clip_tags: crate::model::VideoTagIds,
 }
 
 #[cfg(test)]
 impl AnonymousClipInitializer {
     /// `AnonymousClip`を作成
     ///
-    /// - Error: `start_time` >= `end_time`のとき
-    ///   - e.g. `start_time`: 5秒, `end_time`: 3秒
+    /// - Error:
+    ///   - `start_time` >= `end_time`のとき
+    ///     - e.g. `start_time`: 5秒, `end_time`: 3秒
+    ///   - 切り抜いた動画があるクリップに`volume_percent`があるとき
     fn init(self) -> Result<AnonymousClip, String> {
         super::validate_start_end_times(&self.start_time, &self.end_time)?;
+        super::validate_volume_percent(
+            self.volume_percent.as_ref(),
+            self.clipped_video_id.as_ref(),
+        )?;
 
         Ok(AnonymousClip {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
         })
     }
 }
 
-// デシリアライズ時に `start_time` < `end_time` のバリデーションを行うためのカスタムデシリアライザ
+// デシリアライズ時に `start_time` < `end_time` などのバリデーションを行うためのカスタムデシリアライザ
 impl<'de> serde::Deserialize<'de> for AnonymousClip {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -67,11 +91,15 @@ impl<'de> serde::Deserialize<'de> for AnonymousClip {
         #[serde(deny_unknown_fields)]
         struct RawAnonymousClip {
             song_title: String,
+            song_id: Option<songctl::model::SongId>,
             liver_ids: artistctl::model::LiverIds,
             external_artists_name: Option<artistctl::model::ExternalArtistsName>,
             clipped_video_id: Option<crate::model::VideoId>,
             start_time: crate::model::Duration,
             end_time: crate::model::Duration,
+            volume_percent: Option<crate::model::VolumePercent>,
+            #[serde(default)]
+            clip_tags: crate::model::VideoTagIds,
         }
 
         let raw: RawAnonymousClip = serde::Deserialize::deserialize(deserializer)
@@ -79,18 +107,49 @@ impl<'de> serde::Deserialize<'de> for AnonymousClip {
 
         super::validate_start_end_times(&raw.start_time, &raw.end_time)
             .map_err(serde::de::Error::custom)?;
+        super::validate_volume_percent(
+            raw.volume_percent.as_ref(),
+            raw.clipped_video_id.as_ref(),
+        )
+        .map_err(serde::de::Error::custom)?;
 
         Ok(AnonymousClip {
             song_title: raw.song_title,
+            song_id: raw.song_id,
             liver_ids: raw.liver_ids,
             external_artists_name: raw.external_artists_name,
             clipped_video_id: raw.clipped_video_id,
             start_time: raw.start_time,
             end_time: raw.end_time,
+            volume_percent: raw.volume_percent,
+            clip_tags: raw.clip_tags,
         })
     }
 }
 
+/// 入力ファイルのクリップ. `RawAnonymousClip`の形
+impl cmn_rs::json_schema::JsonSchema for AnonymousClip {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("songTitle", String::json_schema(ctx))
+            .nullable("songId", songctl::model::SongId::json_schema(ctx))
+            .required("liverIds", artistctl::model::LiverIds::json_schema(ctx))
+            .nullable(
+                "externalArtistsName",
+                artistctl::model::ExternalArtistsName::json_schema(ctx),
+            )
+            .nullable("clippedVideoId", crate::model::VideoId::json_schema(ctx))
+            .required("startTime", crate::model::Duration::json_schema(ctx))
+            .required("endTime", crate::model::Duration::json_schema(ctx))
+            .nullable(
+                "volumePercent",
+                crate::model::VolumePercent::json_schema(ctx),
+            )
+            .nullable("clipTags", crate::model::VideoTagIds::json_schema(ctx))
+            .build()
+    }
+}
+
 impl AnonymousClip {
     pub(crate) fn get_liver_ids(&self) -> &artistctl::model::LiverIds {
         &self.liver_ids
@@ -121,11 +180,14 @@ impl AnonymousClip {
         let uuid = crate::model::UuidVer4::generate();
         super::verified::VerifiedClipInitializer {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
             uuid,
         }
         .init(video_duration)
@@ -138,11 +200,14 @@ impl AnonymousClip {
     pub(crate) fn self_a_1() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song A1".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(5),
             end_time: crate::model::Duration::from_secs_u16(10),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -150,11 +215,14 @@ impl AnonymousClip {
     pub(crate) fn self_a_2() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song A2".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_2(),
             external_artists_name: None,
             clipped_video_id: Some(crate::model::VideoId::test_id_3()),
             start_time: crate::model::Duration::from_secs_u16(15),
             end_time: crate::model::Duration::from_secs_u16(20),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -162,11 +230,14 @@ impl AnonymousClip {
     pub(crate) fn self_a_3() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song A3".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_3(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_2()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(25),
             end_time: crate::model::Duration::from_secs_u16(30),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -174,11 +245,14 @@ impl AnonymousClip {
     pub(crate) fn self_b_1() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song B1".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_3()),
             clipped_video_id: Some(crate::model::VideoId::test_id_4()),
             start_time: crate::model::Duration::from_secs_u16(7),
             end_time: crate::model::Duration::from_secs_u16(17),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -186,11 +260,14 @@ impl AnonymousClip {
     pub(crate) fn self_b_2() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song B2".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_2(),
             external_artists_name: None,
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(27),
             end_time: crate::model::Duration::from_secs_u16(37),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -198,11 +275,14 @@ impl AnonymousClip {
     pub(crate) fn self_b_3() -> Self {
         AnonymousClipInitializer {
             song_title: "Test Song B3".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_4(),
             external_artists_name: None,
             clipped_video_id: Some(crate::model::VideoId::test_id_5()),
             start_time: crate::model::Duration::from_secs_u16(47),
             end_time: crate::model::Duration::from_secs_u16(57),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         }
         .init()
         .unwrap()
@@ -316,7 +396,7 @@ mod tests {
             "liverIds": ["riku-tazumi"],
             "startTime": "PT5S",
             "endTime": "PT10S",
-            "clipTags": ["legacy-tag"]
+            "clipNote": "legacy note"
         }"#;
         let result: Result<AnonymousClip, _> = serde_json::from_str(invalid);
         assert!(result.is_err());
@@ -326,25 +406,110 @@ mod tests {
     fn test_anonymous_clip_initializer_init() {
         let valid_initializer = AnonymousClipInitializer {
             song_title: "Test Song 3".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(15),
             end_time: crate::model::Duration::from_secs_u16(20),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         };
         let result = valid_initializer.init();
         assert!(result.is_ok());
 
         let invalid_initializer = AnonymousClipInitializer {
             song_title: "Test Song 4".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(25),
             // start >= end
             end_time: crate::model::Duration::from_secs_u16(20),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
         };
         let result = invalid_initializer.init();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_anonymous_clip_deserialize_volume_percent() {
+        let clip: AnonymousClip = serde_json::from_str(
+            r#"
+        {
+            "songTitle": "Test Song 1",
+            "liverIds": ["riku-tazumi"],
+            "startTime": "PT5S",
+            "endTime": "PT10S",
+            "volumePercent": 80
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            clip.volume_percent,
+            Some(crate::model::VolumePercent::new(80).unwrap())
+        );
+
+        // 範囲外
+        let result: Result<AnonymousClip, _> = serde_json::from_str(
+            r#"
+        {
+            "songTitle": "Test Song 1",
+            "liverIds": ["riku-tazumi"],
+            "startTime": "PT5S",
+            "endTime": "PT10S",
+            "volumePercent": 0
+        }"#,
+        );
+        assert!(result.is_err());
+
+        // 切り抜いた動画があるクリップには指定できない
+        let result: Result<AnonymousClip, _> = serde_json::from_str(
+            r#"
+        {
+            "songTitle": "Test Song 1",
+            "liverIds": ["riku-tazumi"],
+            "clippedVideoId": "33333333333",
+            "startTime": "PT5S",
+            "endTime": "PT10S",
+            "volumePercent": 80
+        }"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_anonymous_clip_deserialize_clip_tags() {
+        let clip: AnonymousClip = serde_json::from_str(
+            r#"
+        {
+            "songTitle": "Test Song 1",
+            "liverIds": ["riku-tazumi"],
+            "startTime": "PT5S",
+            "endTime": "PT10S",
+            "clipTags": ["karaoke", "acoustic", "karaoke"]
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(clip.clip_tags.to_vec(), vec!["acoustic", "karaoke"]);
+        assert_eq!(
+            serde_json::to_value(&clip).unwrap()["clipTags"],
+            serde_json::json!(["acoustic", "karaoke"])
+        );
+
+        // 未定義のタグ
+        let result: Result<AnonymousClip, _> = serde_json::from_str(
+            r#"
+        {
+            "songTitle": "Test Song 1",
+            "liverIds": ["riku-tazumi"],
+            "startTime": "PT5S",
+            "endTime": "PT10S",
+            "clipTags": ["legacy-tag"]
+        }"#,
+        );
+        assert!(result.is_err());
+    }
 }
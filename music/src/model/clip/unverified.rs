@@ -8,6 +8,9 @@
 pub(crate) struct UnverifiedClip {
     /// 曲名
     song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    #[serde(skip_serializing_if = "Option::is_none")]
+    song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -18,6 +21,12 @@ pub(crate) struct UnverifiedClip {
     start_time: crate::model::Duration,
     /// 曲が終わる時間
     end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    #[serde(skip_serializing_if = "crate::model::VideoTagIds::is_empty")]
+    clip_tags: crate::model::VideoTagIds,
     /// uuid
     uuid: crate::model::UuidVer4,
 }
@@ -35,11 +44,16 @@ pub(crate) enum UnverifiedClipError {
         start_time: crate::model::Duration,
         end_time: crate::model::Duration,
     },
+    /// 切り抜いた動画があるクリップに`volume_percent`があるとき
+    #[error("(@song_title: {song_title}), {msg}")]
+    VolumePercentOnClippedVideo { song_title: String, msg: String },
 }
 
 struct UnverifiedClipInitializer {
     /// 曲名
     song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -50,6 +64,10 @@ struct UnverifiedClipInitializer {
     start_time: crate::model::Duration,
     /// 曲が終わる時間
     end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    clip_tags: crate::model::VideoTagIds,
     /// uuid
     uuid: crate::model::UuidVer4,
 }
@@ -57,22 +75,29 @@ struct UnverifiedClipInitializer {
 impl UnverifiedClipInitializer {
     /// `UnverifiedClip`を作成
     ///
-    /// - Error: `start_time` >= `end_time`のとき
-    ///   - e.g. `start_time`: 5秒, `end_time`: 3秒
+    /// - Error:
+    ///   - `start_time` >= `end_time`のとき
+    ///     - e.g. `start_time`: 5秒, `end_time`: 3秒
+    ///   - 切り抜いた動画があるクリップに`volume_percent`があるとき
     fn init(self) -> Result<UnverifiedClip, UnverifiedClipError> {
         UnverifiedClip::validate_consistency(
             &self.song_title,
             &self.start_time,
             &self.end_time,
+            self.volume_percent.as_ref(),
+            self.clipped_video_id.as_ref(),
         )?;
 
         Ok(UnverifiedClip {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
             uuid: self.uuid,
         })
     }
@@ -89,11 +114,15 @@ impl<'de> serde::Deserialize<'de> for UnverifiedClip {
         #[serde(deny_unknown_fields)]
         struct RawUnverifiedClip {
             song_title: String,
+            song_id: Option<songctl::model::SongId>,
             liver_ids: artistctl::model::LiverIds,
             external_artists_name: Option<artistctl::model::ExternalArtistsName>,
             clipped_video_id: Option<crate::model::VideoId>,
             start_time: crate::model::Duration,
             end_time: crate::model::Duration,
+            volume_percent: Option<crate::model::VolumePercent>,
+            #[serde(default)]
+            clip_tags: crate::model::VideoTagIds,
             uuid: crate::model::UuidVer4,
         }
 
@@ -105,22 +134,82 @@ impl<'de> serde::Deserialize<'de> for UnverifiedClip {
             &raw.song_title,
             &raw.start_time,
             &raw.end_time,
+            raw.volume_percent.as_ref(),
+            raw.clipped_video_id.as_ref(),
         )
         .map_err(serde::de::Error::custom)?;
 
         Ok(UnverifiedClip {
             song_title: raw.song_title,
+            song_id: raw.song_id,
             liver_ids: raw.liver_ids,
             external_artists_name: raw.external_artists_name,
             clipped_video_id: raw.clipped_video_id,
             start_time: raw.start_time,
             end_time: raw.end_time,
+            volume_percent: raw.volume_percent,
+            clip_tags: raw.clip_tags,
             uuid: raw.uuid,
         })
     }
 }
 
 impl UnverifiedClip {
+    pub(crate) fn get_liver_ids(&self) -> &artistctl::model::LiverIds {
+        &self.liver_ids
+    }
+    pub(crate) fn get_uuid(&self) -> &crate::model::UuidVer4 {
+        &self.uuid
+    }
+
+    /// 曲名を変更する. 変更があったとき`true`
+    pub(crate) fn set_song_title(&mut self, song_title: &str) -> bool {
+        if self.song_title == song_title {
+            return false;
+        }
+        self.song_title = song_title.to_string();
+        true
+    }
+
+    pub(crate) fn get_song_id(&self) -> Option<&songctl::model::SongId> {
+        self.song_id.as_ref()
+    }
+
+    /// 楽曲IDを設定する. 変更があったとき`true`
+    pub(crate) fn set_song_id(&mut self, song_id: songctl::model::SongId) -> bool {
+        if self.song_id.as_ref() == Some(&song_id) {
+            return false;
+        }
+        self.song_id = Some(song_id);
+        true
+    }
+
+    /// 内部アーティストの一覧を変更する. 変更があったとき`true`
+    pub(crate) fn set_liver_ids(
+        &mut self,
+        liver_ids: artistctl::model::LiverIds,
+    ) -> bool {
+        if self.liver_ids == liver_ids {
+            return false;
+        }
+        self.liver_ids = liver_ids;
+        true
+    }
+
+    /// 再生音量を変更する. `None`で元の音量に戻す. 変更があったとき`true`
+    ///
+    /// 切り抜いた動画があるクリップかの検証は`VerifiedClip`への変換時に行う
+    pub(crate) fn set_volume_percent(
+        &mut self,
+        volume_percent: Option<crate::model::VolumePercent>,
+    ) -> bool {
+        if self.volume_percent == volume_percent {
+            return false;
+        }
+        self.volume_percent = volume_percent;
+        true
+    }
+
     /// `VerifiedClip`から`UnverifiedClip`に変換
     pub(crate) fn from_verified_clip(
         verified_clip: crate::model::VerifiedClip,
@@ -129,11 +218,14 @@ impl UnverifiedClip {
         // VerifiedClipの方が制約が強いため必ずUnverifiedClipに変換できる
         UnverifiedClipInitializer {
             song_title: inner.song_title,
+            song_id: inner.song_id,
             liver_ids: inner.liver_ids,
             external_artists_name: inner.external_artists_name,
             clipped_video_id: inner.clipped_video_id,
             start_time: inner.start_time,
             end_time: inner.end_time,
+            volume_percent: inner.volume_percent,
+            clip_tags: inner.clip_tags,
             uuid: inner.uuid,
         }
         .init()
@@ -151,11 +243,14 @@ impl UnverifiedClip {
     ) -> Result<super::VerifiedClip, super::VerifiedClipError> {
         super::verified::VerifiedClipInitializer {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
             uuid: self.uuid,
         }
         .init(video_duration)
@@ -165,10 +260,13 @@ impl UnverifiedClip {
     ///
     /// Error:
     /// - `start_time` >= `end_time`のとき
+    /// - 切り抜いた動画があるクリップに`volume_percent`があるとき
     fn validate_consistency(
         song_title: &str,
         start_time: &crate::model::Duration,
         end_time: &crate::model::Duration,
+        volume_percent: Option<&crate::model::VolumePercent>,
+        clipped_video_id: Option<&crate::model::VideoId>,
     ) -> Result<(), UnverifiedClipError> {
         // `start_time` >= `end_time`の検証
         super::validate_start_end_times(start_time, end_time).map_err(|_| {
@@ -178,8 +276,13 @@ impl UnverifiedClip {
                 end_time: end_time.clone(),
             }
         })?;
-
-        Ok(())
+        // 切り抜いた動画と`volume_percent`の検証
+        super::validate_volume_percent(volume_percent, clipped_video_id).map_err(
+            |msg| UnverifiedClipError::VolumePercentOnClippedVideo {
+                song_title: song_title.to_string(),
+                msg,
+            },
+        )
     }
 }
 
@@ -253,22 +356,28 @@ mod tests {
         // 正常
         let initializer = UnverifiedClipInitializer {
             song_title: "Test Song 1".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: dur_12h_12m_12s(),
             end_time: dur_12h_12m_12s_plus(8),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_1(),
         };
         let _clip = initializer.init().expect("Failed to create UnverifiedClip");
         // 異常, start_time < end_time でない
         let initializer = UnverifiedClipInitializer {
             song_title: "Test Song 2".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: dur_12h_12m_12s(),
             end_time: dur_12h_12m_12s_plus(-5),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_1(),
         };
         let result = initializer.init();
@@ -276,5 +385,24 @@ mod tests {
             result,
             Err(UnverifiedClipError::InvalidClipTimeRange { .. })
         ));
+
+        // 異常, 切り抜いた動画があるクリップに`volume_percent`がある
+        let initializer = UnverifiedClipInitializer {
+            song_title: "Test Song 3".to_string(),
+            song_id: None,
+            liver_ids: artistctl::model::LiverIds::self_1(),
+            external_artists_name: None,
+            clipped_video_id: Some(crate::model::VideoId::test_id_3()),
+            start_time: dur_12h_12m_12s(),
+            end_time: dur_12h_12m_12s_plus(8),
+            volume_percent: Some(crate::model::VolumePercent::new(50).unwrap()),
+            clip_tags: crate::model::VideoTagIds::default(),
+            uuid: crate::model::UuidVer4::self_1(),
+        };
+        let result = initializer.init();
+        assert!(matches!(
+            result,
+            Err(UnverifiedClipError::VolumePercentOnClippedVideo { .. })
+        ));
     }
 }
@@ -10,6 +10,9 @@
 pub struct VerifiedClip {
     /// 曲名
     song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    #[serde(skip_serializing_if = "Option::is_none")]
+    song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -22,6 +25,12 @@ pub struct VerifiedClip {
     start_time: crate::model::Duration,
     /// 曲が終わる時間
     end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    #[serde(skip_serializing_if = "crate::model::VideoTagIds::is_empty")]
+    clip_tags: crate::model::VideoTagIds,
     /// uuid
     uuid: crate::model::UuidVer4,
 }
@@ -50,11 +59,27 @@ pub(crate) enum VerifiedClipError {
         end_time: crate::model::Duration,
         video_duration: crate::model::Duration,
     },
+    /// 切り抜いた動画があるクリップに`volume_percent`があるとき
+    #[error("(@song title: {song_title}), {msg}")]
+    VolumePercentOnClippedVideo { song_title: String, msg: String },
+}
+
+impl VerifiedClipError {
+    /// 入力ファイルで原因となる値のキー
+    pub(crate) fn source_key(&self) -> &'static str {
+        match self {
+            Self::InvalidClipTimeRange { .. } => "startTime",
+            Self::TimeExceedsVideoDuration { .. } => "endTime",
+            Self::VolumePercentOnClippedVideo { .. } => "volumePercent",
+        }
+    }
 }
 
 pub(super) struct VerifiedClipInner {
     /// 曲名
     pub(super) song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    pub(super) song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     pub(super) liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -65,6 +90,10 @@ pub(super) struct VerifiedClipInner {
     pub(super) start_time: crate::model::Duration,
     /// 曲が終わる時間
     pub(super) end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    pub(super) volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    pub(super) clip_tags: crate::model::VideoTagIds,
     /// uuid
     pub(super) uuid: crate::model::UuidVer4,
 }
@@ -72,6 +101,8 @@ pub(super) struct VerifiedClipInner {
 pub(super) struct VerifiedClipInitializer {
     /// 曲名
     pub(super) song_title: String,
+    /// 楽曲ID. `songs.json`に定義されたもの
+    pub(super) song_id: Option<songctl::model::SongId>,
     /// 内部アーティストの一覧
     pub(super) liver_ids: artistctl::model::LiverIds,
     /// 外部アーティストの一覧
@@ -82,6 +113,10 @@ pub(super) struct VerifiedClipInitializer {
     pub(super) start_time: crate::model::Duration,
     /// 曲が終わる時間
     pub(super) end_time: crate::model::Duration,
+    /// 再生音量(%). 指定が無いときは元の音量
+    pub(super) volume_percent: Option<crate::model::VolumePercent>,
+    /// クリップのタグ
+    pub(super) clip_tags: crate::model::VideoTagIds,
     /// uuid
     pub(super) uuid: crate::model::UuidVer4,
 }
@@ -92,6 +127,7 @@ impl VerifiedClipInitializer {
     /// - Error:
     ///   - `start_time` >= `end_time`のとき
     ///   - `start_time`or `end_time`の時間が, 与えられた動画情報にある動画の長さより長いとき
+    ///   - 切り抜いた動画があるクリップに`volume_percent`があるとき
     pub(super) fn init(
         self,
         video_duration: &crate::model::Duration,
@@ -100,11 +136,14 @@ impl VerifiedClipInitializer {
 
         Ok(VerifiedClip {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
             uuid: self.uuid,
         })
     }
@@ -122,7 +161,14 @@ impl VerifiedClipInitializer {
             },
         )?;
         self.validate_video_duration(video_duration)?;
-        Ok(())
+        super::validate_volume_percent(
+            self.volume_percent.as_ref(),
+            self.clipped_video_id.as_ref(),
+        )
+        .map_err(|msg| VerifiedClipError::VolumePercentOnClippedVideo {
+            song_title: self.song_title.clone(),
+            msg,
+        })
     }
 
     /// 動画の長さを超えていないか検証
@@ -149,6 +195,9 @@ impl VerifiedClip {
     pub(crate) fn get_song_title(&self) -> &str {
         &self.song_title
     }
+    pub(crate) fn get_song_id(&self) -> Option<&songctl::model::SongId> {
+        self.song_id.as_ref()
+    }
     pub(crate) fn get_liver_ids(&self) -> &artistctl::model::LiverIds {
         &self.liver_ids
     }
@@ -166,6 +215,12 @@ impl VerifiedClip {
     pub(crate) fn get_end_time(&self) -> &crate::model::Duration {
         &self.end_time
     }
+    pub(crate) fn get_volume_percent(&self) -> Option<&crate::model::VolumePercent> {
+        self.volume_percent.as_ref()
+    }
+    pub(crate) fn get_clip_tags(&self) -> &crate::model::VideoTagIds {
+        &self.clip_tags
+    }
     pub(crate) fn get_uuid(&self) -> &crate::model::UuidVer4 {
         &self.uuid
     }
@@ -178,14 +233,21 @@ impl VerifiedClip {
         self.liver_ids.to_vec()
     }
 
+    pub fn clip_tag_ids(&self) -> Vec<&str> {
+        self.clip_tags.to_vec()
+    }
+
     pub(super) fn into_inner(self) -> VerifiedClipInner {
         VerifiedClipInner {
             song_title: self.song_title,
+            song_id: self.song_id,
             liver_ids: self.liver_ids,
             external_artists_name: self.external_artists_name,
             clipped_video_id: self.clipped_video_id,
             start_time: self.start_time,
             end_time: self.end_time,
+            volume_percent: self.volume_percent,
+            clip_tags: self.clip_tags,
             uuid: self.uuid,
         }
     }
@@ -205,11 +267,14 @@ impl VerifiedClip {
     pub(crate) fn self_a_1() -> Self {
         let ini_1 = VerifiedClipInitializer {
             song_title: "Test Song A1".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_1()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(5),
             end_time: crate::model::Duration::from_secs_u16(10),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xa1),
         };
         Self::self_a_initialize(ini_1)
@@ -217,11 +282,14 @@ impl VerifiedClip {
     pub(crate) fn self_a_2() -> Self {
         let ini_2 = VerifiedClipInitializer {
             song_title: "Test Song A2".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_2(),
             external_artists_name: None,
             clipped_video_id: Some(crate::model::VideoId::test_id_3()),
             start_time: crate::model::Duration::from_secs_u16(15),
             end_time: crate::model::Duration::from_secs_u16(20),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xa2),
         };
         Self::self_a_initialize(ini_2)
@@ -229,11 +297,14 @@ impl VerifiedClip {
     pub(crate) fn self_a_3() -> Self {
         let ini_3 = VerifiedClipInitializer {
             song_title: "Test Song A3".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_3(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_2()),
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(25),
             end_time: crate::model::Duration::from_secs_u16(30),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xa3),
         };
         Self::self_a_initialize(ini_3)
@@ -247,11 +318,14 @@ impl VerifiedClip {
     pub(crate) fn self_b_1() -> Self {
         let ini_b1 = VerifiedClipInitializer {
             song_title: "Test Song B1".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: Some(artistctl::model::ExternalArtistsName::self_3()),
             clipped_video_id: Some(crate::model::VideoId::test_id_4()),
             start_time: crate::model::Duration::from_secs_u16(7),
             end_time: crate::model::Duration::from_secs_u16(17),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xb1),
         };
         Self::self_b_initialize(ini_b1)
@@ -259,11 +333,14 @@ impl VerifiedClip {
     pub(crate) fn self_b_2() -> Self {
         let ini_b2 = VerifiedClipInitializer {
             song_title: "Test Song B2".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_2(),
             external_artists_name: None,
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(27),
             end_time: crate::model::Duration::from_secs_u16(37),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xb2),
         };
         Self::self_b_initialize(ini_b2)
@@ -271,11 +348,14 @@ impl VerifiedClip {
     pub(crate) fn self_b_3() -> Self {
         let ini_b3 = VerifiedClipInitializer {
             song_title: "Test Song B3".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: None,
             clipped_video_id: Some(crate::model::VideoId::test_id_5()),
             start_time: crate::model::Duration::from_secs_u16(47),
             end_time: crate::model::Duration::from_secs_u16(57),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_partly_rand(0xb3),
         };
         Self::self_b_initialize(ini_b3)
@@ -285,6 +365,11 @@ impl VerifiedClip {
     pub(crate) fn set_end_time(&mut self, end_time: crate::model::Duration) {
         self.end_time = end_time;
     }
+
+    /// Only for tests
+    pub(crate) fn set_clip_tags(&mut self, clip_tags: crate::model::VideoTagIds) {
+        self.clip_tags = clip_tags;
+    }
 }
 
 // MARK: Tests
@@ -356,11 +441,14 @@ mod tests {
         // 正常
         let verified_initializer = VerifiedClipInitializer {
             song_title: "Test Song".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: None,
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(30),
             end_time: crate::model::Duration::from_secs_u16(40),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_4(),
         };
         verified_initializer
@@ -370,15 +458,50 @@ mod tests {
         // 異常, `start_time`か`end_time`動画の長さを超えている
         let verified_initializer = VerifiedClipInitializer {
             song_title: "Test Song".to_string(),
+            song_id: None,
             liver_ids: artistctl::model::LiverIds::self_1(),
             external_artists_name: None,
             clipped_video_id: None,
             start_time: crate::model::Duration::from_secs_u16(30),
             end_time: crate::model::Duration::from_secs_u16(50),
+            volume_percent: None,
+            clip_tags: crate::model::VideoTagIds::default(),
             uuid: crate::model::UuidVer4::self_4(),
         };
         let result = verified_initializer
             .validate_video_duration(&crate::model::Duration::from_secs_u16(40));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_verified_clip_volume_percent() {
+        let initializer = |clipped_video_id| VerifiedClipInitializer {
+            song_title: "Test Song".to_string(),
+            song_id: None,
+            liver_ids: artistctl::model::LiverIds::self_1(),
+            external_artists_name: None,
+            clipped_video_id,
+            start_time: crate::model::Duration::from_secs_u16(30),
+            end_time: crate::model::Duration::from_secs_u16(40),
+            volume_percent: Some(crate::model::VolumePercent::new(70).unwrap()),
+            clip_tags: crate::model::VideoTagIds::default(),
+            uuid: crate::model::UuidVer4::self_4(),
+        };
+        let video_duration = crate::model::Duration::from_secs_u16(60);
+
+        let clip = initializer(None).init(&video_duration).unwrap();
+        assert_eq!(
+            clip.get_volume_percent(),
+            Some(&crate::model::VolumePercent::new(70).unwrap())
+        );
+        assert_eq!(serde_json::to_value(&clip).unwrap()["volumePercent"], 70);
+
+        // 異常, 切り抜いた動画があるクリップに指定
+        let result =
+            initializer(Some(crate::model::VideoId::test_id_3())).init(&video_duration);
+        assert!(matches!(
+            result,
+            Err(VerifiedClipError::VolumePercentOnClippedVideo { .. })
+        ));
+    }
 }
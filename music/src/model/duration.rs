@@ -111,6 +111,15 @@ impl std::str::FromStr for Duration {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for Duration {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({
+            "type": "string",
+            "pattern": "^PT([0-9]+H)?([0-9]+M)?([0-9]+S)?$",
+        })
+    }
+}
+
 /// DurationをISO 8601形式の文字列に変換する
 ///
 /// 0h, 0m, 0sのフィールドがある場合は省略される
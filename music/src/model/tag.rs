@@ -4,11 +4,16 @@
 /// - 要素は `tagctl::model::VideoTagId` として妥当であること
 /// - 要素は `VideoTagId` の順序でソートされていること
 /// - 重複がないこと
+/// - 子タグを持つとき, その祖先タグも持つこと. deserialize, `insert` 時に補い, `remove` 時は子孫タグも取り除く
 /// - deserialize 時に `null` は空配列とみなすこと
 #[derive(Debug, serde::Serialize, Clone, PartialEq, Eq, Default)]
 pub(crate) struct VideoTagIds(Vec<tagctl::model::VideoTagId>);
 
 impl VideoTagIds {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub(crate) fn to_vec(&self) -> Vec<&str> {
         self.0
             .iter()
@@ -16,10 +21,46 @@ impl VideoTagIds {
             .collect()
     }
 
+    /// タグを追加する. 祖先タグも合わせて追加する. 追加したとき`true`
+    pub(crate) fn insert(&mut self, tag_id: tagctl::model::VideoTagId) -> bool {
+        if self.0.contains(&tag_id) {
+            return false;
+        }
+        self.0.push(tag_id);
+        Self::complete_ancestors(&mut self.0);
+        true
+    }
+
+    /// タグを取り除く. 子孫タグも合わせて取り除く. 取り除いたとき`true`
+    pub(crate) fn remove(&mut self, tag_id: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|id| {
+            id.as_str() != tag_id
+                && !tagctl::model::LOADED_VIDEO_TAG_DATA
+                    .ancestors_of(id.as_str())
+                    .iter()
+                    .any(|ancestor| ancestor.as_str() == tag_id)
+        });
+        self.0.len() != len
+    }
+
     fn sort_dedup_tag_ids(tag_ids: &mut Vec<tagctl::model::VideoTagId>) {
         tag_ids.sort();
         tag_ids.dedup();
     }
+
+    /// 祖先タグを補ってからソート, 重複除去する
+    fn complete_ancestors(tag_ids: &mut Vec<tagctl::model::VideoTagId>) {
+        let ancestors = tag_ids
+            .iter()
+            .flat_map(|id| {
+                tagctl::model::LOADED_VIDEO_TAG_DATA.ancestors_of(id.as_str())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        tag_ids.extend(ancestors);
+        Self::sort_dedup_tag_ids(tag_ids);
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for VideoTagIds {
@@ -30,18 +71,20 @@ impl<'de> serde::Deserialize<'de> for VideoTagIds {
         let mut tag_ids =
             Option::<Vec<tagctl::model::VideoTagId>>::deserialize(deserializer)?
                 .unwrap_or_default();
-        Self::sort_dedup_tag_ids(&mut tag_ids);
+        Self::complete_ancestors(&mut tag_ids);
         Ok(VideoTagIds(tag_ids))
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for VideoTagIds {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        Vec::<tagctl::model::VideoTagId>::json_schema(ctx)
+    }
+}
+
 #[cfg(any(test, feature = "test-helpers"))]
 #[allow(dead_code)]
 impl VideoTagIds {
-    pub(crate) fn is_empty(&self) -> bool {
-        self.0.is_empty()
-    }
-
     pub(crate) fn new(tag_ids: Vec<&str>) -> Result<Self, String> {
         let mut tag_ids = tag_ids
             .into_iter()
@@ -96,6 +139,46 @@ mod tests {
         assert_eq!(video_tags.to_vec(), vec!["acoustic", "karaoke"]);
     }
 
+    #[test]
+    fn test_video_tag_ids_insert_remove() {
+        let mut video_tags = VideoTagIds::self_1();
+        assert!(video_tags.insert(tagctl::model::VideoTagId::new("acoustic").unwrap()));
+        assert!(!video_tags.insert(tagctl::model::VideoTagId::new("karaoke").unwrap()));
+        assert_eq!(video_tags.to_vec(), vec!["acoustic", "karaoke"]);
+
+        assert!(video_tags.remove("karaoke"));
+        assert!(!video_tags.remove("3d"));
+        assert_eq!(video_tags.to_vec(), vec!["acoustic"]);
+    }
+
+    #[test]
+    fn test_video_tag_ids_insert_adds_ancestors() {
+        let mut video_tags = VideoTagIds::self_1();
+        assert!(video_tags.insert(tagctl::model::VideoTagId::new("3d-debut").unwrap()));
+        assert_eq!(video_tags.to_vec(), vec!["3d", "3d-debut", "karaoke"]);
+    }
+
+    #[test]
+    fn test_video_tag_ids_remove_parent_removes_descendants() {
+        let mut video_tags: VideoTagIds =
+            serde_json::from_str(r#"["3d-debut", "karaoke"]"#).unwrap();
+
+        // 子タグのみ取り除くときは親タグを残す
+        assert!(video_tags.remove("3d-debut"));
+        assert_eq!(video_tags.to_vec(), vec!["3d", "karaoke"]);
+
+        assert!(video_tags.insert(tagctl::model::VideoTagId::new("3d-debut").unwrap()));
+        assert!(video_tags.remove("3d"));
+        assert_eq!(video_tags.to_vec(), vec!["karaoke"]);
+    }
+
+    #[test]
+    fn test_tags_deserialize_adds_ancestors() {
+        let de: VideoTagIds =
+            serde_json::from_str(r#"["3d-debut", "karaoke"]"#).unwrap();
+        assert_eq!(de.to_vec(), vec!["3d", "3d-debut", "karaoke"]);
+    }
+
     #[test]
     fn test_tags_new_invalid() {
         let video_tags = VideoTagIds::new(vec!["invalid-tag"]);
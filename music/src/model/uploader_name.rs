@@ -26,6 +26,12 @@ impl UploaderName {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for UploaderName {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "minLength": 1 })
+    }
+}
+
 impl std::fmt::Display for UploaderName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -163,6 +163,20 @@ impl AnonymousVideo {
     }
 }
 
+/// 入力ファイルの動画. `RawAnonymousVideo`の形
+impl cmn_rs::json_schema::JsonSchema for AnonymousVideo {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        let mut clips = Vec::<crate::model::AnonymousClip>::json_schema(ctx);
+        clips["minItems"] = 1.into();
+        cmn_rs::json_schema::ObjectSchema::new()
+            .required("videoId", crate::model::VideoId::json_schema(ctx))
+            .nullable("uploaderName", crate::model::UploaderName::json_schema(ctx))
+            .nullable("videoTags", crate::model::VideoTagIds::json_schema(ctx))
+            .required("clips", clips)
+            .build()
+    }
+}
+
 // MARK: Videos impl
 
 // `Self`の存在条件を検証するためのカスタムデシリアライザ
@@ -180,6 +194,13 @@ impl<'de> serde::Deserialize<'de> for AnonymousVideos {
     }
 }
 
+/// 入力ファイル全体. 動画の配列
+impl cmn_rs::json_schema::JsonSchema for AnonymousVideos {
+    fn json_schema(ctx: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        Vec::<AnonymousVideo>::json_schema(ctx)
+    }
+}
+
 impl serde::Serialize for AnonymousVideos {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
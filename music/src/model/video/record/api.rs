@@ -158,6 +158,27 @@ impl ApiVideoInfo {
     pub(crate) fn set_duration(self, duration: crate::model::Duration) -> Self {
         Self { duration, ..self }
     }
+
+    pub(crate) fn set_title(self, title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..self
+        }
+    }
+
+    pub(crate) fn set_privacy_status(
+        self,
+        privacy_status: crate::model::PrivacyStatus,
+    ) -> Self {
+        Self {
+            privacy_status,
+            ..self
+        }
+    }
+
+    pub(crate) fn set_embeddable(self, embeddable: bool) -> Self {
+        Self { embeddable, ..self }
+    }
 }
 
 // MARK: Tests
@@ -121,6 +121,10 @@ impl VideoRecord {
         &self.api
     }
 
+    pub(crate) fn video_tags_mut(&mut self) -> &mut crate::model::VideoTagIds {
+        &mut self.local.video_tags
+    }
+
     /// 新しい`VideoRecord`を作成
     ///
     /// Err: `local`と`api`の動画idが一致しない場合
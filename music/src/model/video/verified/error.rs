@@ -3,11 +3,15 @@
 pub(crate) enum VerifiedVideoError {
     /// クリップの情報が不正
     #[error(
-        "invalid clips found ({count}):\n\t{msgs}",
-        count = .0.len(),
-        msgs = .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n\t"),
+        "invalid clips found in video ID {id} ({count}):\n\t{msgs}",
+        count = .errs.len(),
+        msgs = .errs.iter().map(|(_, e)| e.to_string()).collect::<Vec<_>>().join("\n\t"),
     )]
-    InvalidClip(Vec<crate::model::VerifiedClipError>),
+    InvalidClip {
+        id: crate::model::VideoId,
+        /// (クリップの開始時間順の添字, エラー)
+        errs: Vec<(usize, crate::model::VerifiedClipError)>,
+    },
     /// 動画IDが一致しない
     #[error("video_id mismatch: expected {local}, got {fetched}")]
     VideoIdMismatch {
@@ -19,6 +23,8 @@ pub(crate) enum VerifiedVideoError {
     ClipsOverlap {
         id: crate::model::VideoId,
         clips_title: Vec<String>,
+        /// 重複したクリップの開始時間順の添字. `clips_title`と同じ順
+        clip_indices: Vec<usize>,
     },
     /// クリップが存在しない
     #[error("no clips found for video ID {0}")]
@@ -56,6 +62,12 @@ impl From<Vec<VerifiedVideoError>> for VerifiedVideoErrors {
     }
 }
 
+impl VerifiedVideoErrors {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &VerifiedVideoError> {
+        self.errs.iter()
+    }
+}
+
 impl From<VerifiedVideoErrors> for Vec<VerifiedVideoError> {
     fn from(value: VerifiedVideoErrors) -> Self {
         value.errs
@@ -56,6 +56,7 @@ impl<'de> serde::Deserialize<'de> for VerifiedVideo {
         // ここでrecordの情報を基にVerifiedClipを作成
         let verified_clips = Self::verify_clips_from_unverified(
             raw.clips,
+            record.get_video_id(),
             record.get_api().get_duration(),
         )
         .map_err(serde::de::Error::custom)?;
@@ -115,7 +116,7 @@ impl VerifiedVideo {
     pub(crate) fn get_video_tags(&self) -> &crate::model::VideoTagIds {
         self.record.get_local().get_video_tags()
     }
-    pub(crate) fn get_title(&self) -> &str {
+    pub fn get_title(&self) -> &str {
         self.record.get_api().get_title()
     }
     pub(crate) fn get_channel_id(&self) -> &crate::model::ChannelId {
@@ -192,16 +193,21 @@ impl VerifiedVideo {
         let (oks, errs): (Vec<_>, Vec<_>) = clips
             .into_iter()
             .map(|clip| clip.try_into_verified_clip(api_info.get_duration()))
+            .enumerate()
             // ここでoks, errsに分割しているため後方の処理では
             // それぞれunwrapを使用. パニックしない.
-            .partition(Result::is_ok);
+            .partition(|(_, result)| result.is_ok());
 
         if !errs.is_empty() {
-            Err(super::VerifiedVideoError::InvalidClip(
-                errs.into_iter().map(Result::unwrap_err).collect(),
-            ))
+            Err(super::VerifiedVideoError::InvalidClip {
+                id: local_info.get_video_id().clone(),
+                errs: errs
+                    .into_iter()
+                    .map(|(index, result)| (index, result.unwrap_err()))
+                    .collect(),
+            })
         } else {
-            let clips = oks.into_iter().map(Result::unwrap).collect();
+            let clips = oks.into_iter().map(|(_, result)| result.unwrap()).collect();
             // 上で同じvideo_idであることをすでに保証しているためunwrapで処理
             let record = crate::model::VideoRecord::new(local_info, api_info).unwrap();
             Self::new(record, clips)
@@ -242,6 +248,7 @@ impl VerifiedVideo {
 
         match Self::verify_clips_from_unverified(
             unverified_clips,
+            record.get_video_id(),
             record.get_api().get_duration(),
         ) {
             Ok(verified_clips) => Self::new(record, verified_clips),
@@ -249,6 +256,36 @@ impl VerifiedVideo {
         }
     }
 
+    /// 動画タグ, クリップを編集し, 再度検証した`VerifiedVideo`を返す
+    ///
+    /// - `edit`には動画タグと, 開始時間順のクリップが渡される
+    /// - `edit`の戻り値をそのまま返す
+    ///
+    /// Error: 編集後のクリップが不正なとき, 範囲が重複したとき
+    pub(crate) fn try_edit<R>(
+        self,
+        edit: impl FnOnce(
+            &mut crate::model::VideoTagIds,
+            &mut [crate::model::UnverifiedClip],
+        ) -> R,
+    ) -> Result<(Self, R), super::VerifiedVideoError> {
+        let mut record = self.record;
+        let mut unverified_clips: Vec<crate::model::UnverifiedClip> = self
+            .clips
+            .into_iter()
+            .map(crate::model::UnverifiedClip::from_verified_clip)
+            .collect();
+
+        let edited = edit(record.video_tags_mut(), &mut unverified_clips);
+
+        let verified_clips = Self::verify_clips_from_unverified(
+            unverified_clips,
+            record.get_video_id(),
+            record.get_api().get_duration(),
+        )?;
+        Ok((Self::new(record, verified_clips)?, edited))
+    }
+
     /// 新しい`VerifiedVideo`を作成
     ///
     /// `Self::validate_consistency`は通す
@@ -285,22 +322,23 @@ impl VerifiedVideo {
         }
 
         // クリップの範囲が重複していないか確認
-        let mut overlap_clips = Vec::new();
-        for w in clips.windows(2) {
+        let mut overlap_indices = Vec::new();
+        for (index, w) in clips.windows(2).enumerate() {
             if w[0].get_end_time() > w[1].get_start_time() {
-                overlap_clips.push(w[0].clone());
-                overlap_clips.push(w[1].clone());
+                overlap_indices.push(index);
+                overlap_indices.push(index + 1);
             }
         }
-        if overlap_clips.is_empty() {
+        if overlap_indices.is_empty() {
             Ok(())
         } else {
             Err(super::VerifiedVideoError::ClipsOverlap {
                 id: video_id.clone(),
-                clips_title: overlap_clips
+                clips_title: overlap_indices
                     .iter()
-                    .map(|c| c.get_song_title().to_string())
+                    .map(|&index| clips[index].get_song_title().to_string())
                     .collect(),
+                clip_indices: overlap_indices,
             })
         }
     }
@@ -311,21 +349,27 @@ impl VerifiedVideo {
     /// - Ok(verified_clips): 認証されたクリップのリスト
     fn verify_clips_from_unverified(
         clips: Vec<crate::model::UnverifiedClip>,
+        video_id: &crate::model::VideoId,
         video_duration: &crate::model::Duration,
     ) -> Result<Vec<crate::model::VerifiedClip>, super::VerifiedVideoError> {
         let (oks, errs): (Vec<_>, Vec<_>) = clips
             .into_iter()
             .map(|clip| clip.try_into_verified_clip(video_duration))
+            .enumerate()
             // ここでoks, errsに分割しているため後方の処理では
             // それぞれunwrapを使用. パニックしない.
-            .partition(Result::is_ok);
+            .partition(|(_, result)| result.is_ok());
 
         if !errs.is_empty() {
-            Err(super::VerifiedVideoError::InvalidClip(
-                errs.into_iter().map(Result::unwrap_err).collect(),
-            ))
+            Err(super::VerifiedVideoError::InvalidClip {
+                id: video_id.clone(),
+                errs: errs
+                    .into_iter()
+                    .map(|(index, result)| (index, result.unwrap_err()))
+                    .collect(),
+            })
         } else {
-            Ok(oks.into_iter().map(Result::unwrap).collect())
+            Ok(oks.into_iter().map(|(_, result)| result.unwrap()).collect())
         }
     }
 
@@ -356,6 +400,18 @@ impl VerifiedVideo {
         ];
         Self::new(detail, clips).expect("should create valid VerifiedVideo")
     }
+    /// `self_a`の最初のクリップに`clip_tags`を付けたもの
+    pub(crate) fn self_a_with_clip_tags(clip_tags: crate::model::VideoTagIds) -> Self {
+        let detail = crate::model::VideoRecord::self_a();
+        let mut clip_1 = crate::model::VerifiedClip::self_a_1();
+        clip_1.set_clip_tags(clip_tags);
+        let clips = vec![
+            clip_1,
+            crate::model::VerifiedClip::self_a_2(),
+            crate::model::VerifiedClip::self_a_3(),
+        ];
+        Self::new(detail, clips).expect("should create valid VerifiedVideo")
+    }
 }
 
 // MARK: Tests
@@ -492,7 +548,32 @@ mod tests {
         );
         assert!(matches!(
             res,
-            Err(super::super::VerifiedVideoError::ClipsOverlap { .. })
+            Err(super::super::VerifiedVideoError::ClipsOverlap { clip_indices, .. })
+                if clip_indices == [0, 1]
         ));
     }
+
+    #[test]
+    fn test_verified_video_try_edit() {
+        let (video, changed) = VerifiedVideo::self_a()
+            .try_edit(|video_tags, clips| {
+                video_tags.insert(tagctl::model::VideoTagId::new("acoustic").unwrap());
+                clips[0].set_song_title("Edited Song")
+            })
+            .unwrap();
+        assert!(changed);
+        assert_eq!(video.get_video_tags().to_vec(), vec!["acoustic", "karaoke"]);
+        let titles = video
+            .clips()
+            .map(|clip| clip.get_song_title())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Edited Song", "Test Song A2", "Test Song A3"]);
+    }
+
+    #[test]
+    fn test_verified_video_try_edit_without_changes() {
+        // 変更がなければ元の動画と同じ
+        let (video, _) = VerifiedVideo::self_a().try_edit(|_, _| ()).unwrap();
+        assert_eq!(video, VerifiedVideo::self_a());
+    }
 }
@@ -215,6 +215,13 @@ impl VerifiedVideos {
         self.inner.values()
     }
 
+    pub(crate) fn get(
+        &self,
+        video_id: &crate::model::VideoId,
+    ) -> Option<&super::VerifiedVideo> {
+        self.inner.get(video_id)
+    }
+
     pub(crate) fn to_video_ids(&self) -> crate::model::VideoIds {
         self.inner
             .keys()
@@ -55,6 +55,12 @@ impl VideoId {
     }
 }
 
+impl cmn_rs::json_schema::JsonSchema for VideoId {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "string", "pattern": "^[A-Za-z0-9_-]{11}$" })
+    }
+}
+
 impl std::fmt::Display for VideoId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -34,6 +34,11 @@ impl VideoPublishedAt {
         self.0.month() as usize
     }
 
+    /// UTCでの日付
+    pub(crate) fn get_date(&self) -> chrono::NaiveDate {
+        self.0.date_naive()
+    }
+
     /// 符号なし48ビットのタイムスタンプの範囲内であることを検証
     fn validate_unsigned_48bit_timestamp(
         dt: chrono::DateTime<chrono::Utc>,
@@ -0,0 +1,88 @@
+/// クリップの再生音量(%)
+///
+/// 配信ごとの音量差をならすため, 元の音量に対する割合を指定する
+///
+/// - 1以上100以下
+#[derive(Debug, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VolumePercent(u8);
+
+impl<'de> serde::Deserialize<'de> for VolumePercent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let percent = u8::deserialize(deserializer)?;
+        VolumePercent::new(percent).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::str::FromStr for VolumePercent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let percent = s.trim().parse::<u8>().map_err(|_| {
+            format!("volume percent must be an integer from 1 to 100: {s}")
+        })?;
+        Self::new(percent)
+    }
+}
+
+impl VolumePercent {
+    pub(crate) fn new(percent: u8) -> Result<Self, String> {
+        if (1..=100).contains(&percent) {
+            Ok(Self(percent))
+        } else {
+            Err(format!(
+                "volume percent must be from 1 to 100, got {percent}"
+            ))
+        }
+    }
+
+    pub(crate) fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl cmn_rs::json_schema::JsonSchema for VolumePercent {
+    fn json_schema(_: &cmn_rs::json_schema::SchemaContext) -> serde_json::Value {
+        serde_json::json!({ "type": "integer", "minimum": 1, "maximum": 100 })
+    }
+}
+
+impl std::fmt::Display for VolumePercent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_percent_range() {
+        assert_eq!(VolumePercent::new(1).unwrap().get(), 1);
+        assert_eq!(VolumePercent::new(100).unwrap().get(), 100);
+        assert!(VolumePercent::new(0).is_err());
+        assert!(VolumePercent::new(101).is_err());
+    }
+
+    #[test]
+    fn test_volume_percent_deserialize() {
+        let percent: VolumePercent = serde_json::from_str("50").unwrap();
+        assert_eq!(percent.get(), 50);
+        assert!(serde_json::from_str::<VolumePercent>("0").is_err());
+        assert!(serde_json::from_str::<VolumePercent>("150").is_err());
+        assert!(serde_json::from_str::<VolumePercent>("50.5").is_err());
+        assert!(serde_json::from_str::<VolumePercent>("-1").is_err());
+    }
+
+    #[test]
+    fn test_volume_percent_from_str() {
+        assert_eq!("80".parse::<VolumePercent>().unwrap().get(), 80);
+        assert!("0".parse::<VolumePercent>().is_err());
+        assert!("loud".parse::<VolumePercent>().is_err());
+    }
+}
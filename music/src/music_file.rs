@@ -2,6 +2,8 @@ mod error;
 mod file;
 pub(crate) mod fs_util;
 mod library;
+mod lock;
+mod migration;
 mod repository;
 
 pub(super) mod videos;
@@ -11,4 +13,6 @@ pub(crate) use file::MusicFile;
 pub(crate) use videos::DuplicateVideoPolicy;
 
 pub use library::MusicLibrary;
+pub use lock::MusicRootLock;
+pub use migration::{CURRENT_SCHEMA_VERSION, MigrationError};
 pub use repository::MusicLibraryRepository;
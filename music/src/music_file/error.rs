@@ -50,17 +50,29 @@ pub enum MusicFileError {
         msg: String,
         when: String,
     },
+    /// 他の`musictl`が楽曲情報のルートを書き換えている
+    #[error(
+        "Music root is locked by another musictl process ({holder}): {path}. \
+        Wait for it to finish and retry"
+    )]
+    Locked {
+        path: std::path::PathBuf,
+        holder: String,
+    },
     /// ファイルの読み込みに失敗
     #[error("Failed to write content to file {path}: {msg}")]
     FileWrite {
         path: std::path::PathBuf,
         msg: String,
     },
-    /// ファイルの内容のデシリアライズに失敗
-    #[error("Failed to deserialize file {path}: {msg}")]
-    Deserialize {
+    /// ファイルの内容のデシリアライズに失敗. ファイル上の位置を持つ
+    #[error("Failed to deserialize file {0}")]
+    Deserialize(cmn_rs::source_diagnostic::SourceDiagnostic),
+    /// 月別ファイルを最新のスキーマバージョンに変換できない
+    #[error("Failed to migrate month file {path}: {source}")]
+    Migration {
         path: std::path::PathBuf,
-        msg: String,
+        source: super::MigrationError,
     },
     /// データベースの内容が不正
     #[error("Invalid content in database: {msg}")]
@@ -85,6 +97,25 @@ impl MusicFileError {
     pub fn into_errors(self) -> MusicFileErrors {
         MusicFileErrors { errs: vec![self] }
     }
+
+    /// ファイル上の位置を特定できたエラー
+    pub fn diagnostic(&self) -> Option<&cmn_rs::source_diagnostic::SourceDiagnostic> {
+        match self {
+            Self::Deserialize(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+}
+
+impl MusicFileErrors {
+    /// ファイル上の位置を特定できたエラーの一覧
+    pub fn diagnostics(&self) -> Vec<cmn_rs::source_diagnostic::SourceDiagnostic> {
+        self.errs
+            .iter()
+            .filter_map(MusicFileError::diagnostic)
+            .cloned()
+            .collect()
+    }
 }
 
 impl From<Vec<MusicFileError>> for MusicFileErrors {
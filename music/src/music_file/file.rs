@@ -7,6 +7,8 @@
 pub(crate) struct MusicFile {
     path: std::path::PathBuf,
     videos: super::videos::VideosSameYearMonth,
+    /// 読み込んだときのスキーマバージョン. 新しく作ったときは最新のバージョン
+    schema_version: u32,
 }
 
 impl MusicFile {
@@ -25,6 +27,9 @@ impl MusicFile {
     pub(crate) fn get_path(&self) -> &std::path::Path {
         &self.path
     }
+    pub(crate) fn get_schema_version(&self) -> u32 {
+        self.schema_version
+    }
 
     /// `(year, month)`
     pub(crate) fn get_year_month(&self) -> (usize, usize) {
@@ -50,23 +55,31 @@ impl MusicFile {
         let videos =
             super::videos::VideosSameYearMonth::new(year, month, video.into_videos())
                 .unwrap();
-        Self { path, videos }
+        Self {
+            path,
+            videos,
+            schema_version: super::CURRENT_SCHEMA_VERSION,
+        }
     }
 
     /// ファイルから楽曲情報を読み込む
+    ///
+    /// 古いスキーマバージョンのファイルは最新のバージョンに変換する. ファイルは書き換えない
     pub(super) fn load(
         path: std::path::PathBuf,
         root: &std::path::Path,
     ) -> Result<Self, super::MusicFileError> {
-        let videos = super::fs_util::deserialize_from_file(&path)?;
-        Self::new(path, root, videos)
+        let (videos, schema_version) = super::fs_util::deserialize_month_file(&path)?;
+        let mut file = Self::new(path, root, videos)?;
+        file.schema_version = schema_version;
+        Ok(file)
     }
 
-    /// ファイルに楽曲情報を書き込む
+    /// ファイルに書き込む内容
     ///
-    /// pretty形式
-    pub(crate) fn save(&self) -> Result<(), super::MusicFileError> {
-        super::fs_util::serialize_to_file(&self.path, self.videos.get_videos(), false)
+    /// 最新のスキーマバージョン, pretty形式
+    pub(super) fn to_bytes(&self) -> Result<Vec<u8>, super::MusicFileError> {
+        super::fs_util::serialize_month_file(&self.path, self.videos.get_videos())
     }
 
     /// 動画情報を追加
@@ -138,7 +151,11 @@ impl MusicFile {
             "music videos loaded: {year}-{month:02}, {} videos",
             videos.len()
         );
-        Ok(Self { path, videos })
+        Ok(Self {
+            path,
+            videos,
+            schema_version: super::CURRENT_SCHEMA_VERSION,
+        })
     }
 
     /// 引数の`root`から `/YYYY/MM.json` の形式であることを確認し, 年と月を抽出
@@ -320,4 +337,119 @@ mod tests {
         };
         assert_eq!(mf.unwrap_err(), expect);
     }
+
+    #[test]
+    fn test_music_file_load_migrates_golden() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("2024/01.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, include_str!("migration/golden/v1_to_v2/before.json"))
+            .unwrap();
+
+        let file = MusicFile::load(path.clone(), root.path()).unwrap();
+        assert_eq!(file.get_schema_version(), 1);
+        // 書き込みは最新のバージョン. ゴールデンファイルは変換を順にたどるため, 最後の変換後と同じ
+        let (_, latest) = super::super::migration::GOLDEN_FILES.last().unwrap();
+        assert_eq!(
+            String::from_utf8(file.to_bytes().unwrap()).unwrap(),
+            *latest
+        );
+        // 読み込みではファイルを書き換えない
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            include_str!("migration/golden/v1_to_v2/before.json")
+        );
+
+        std::fs::write(&path, latest).unwrap();
+        let file = MusicFile::load(path, root.path()).unwrap();
+        assert_eq!(
+            file.get_schema_version(),
+            super::super::CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_music_file_load_round_trips_clip_fields() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("2024/01.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, include_str!("testdata/clip_fields.json")).unwrap();
+
+        let file = MusicFile::load(path, root.path()).unwrap();
+        assert_eq!(
+            file.get_schema_version(),
+            super::super::CURRENT_SCHEMA_VERSION
+        );
+        assert_eq!(
+            String::from_utf8(file.to_bytes().unwrap()).unwrap(),
+            include_str!("testdata/clip_fields.json")
+        );
+    }
+
+    #[test]
+    fn test_music_file_load_newer_version() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("2024/01.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"schemaVersion": 99, "videos": []}"#).unwrap();
+
+        let err = MusicFile::load(path.clone(), root.path()).unwrap_err();
+        assert_eq!(
+            err,
+            super::super::MusicFileError::Migration {
+                path,
+                source: super::super::MigrationError::NewerVersion { version: 99 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_music_file_load_error_has_position() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("2024/01.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "{\n  \"schemaVersion\": 2,\n  \"videos\": [\n    {\"videoId\": 1}\n  ]\n}\n",
+        )
+        .unwrap();
+
+        let err = MusicFile::load(path.clone(), root.path()).unwrap_err();
+        let diagnostic = err.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, path);
+        let position = diagnostic.position.as_ref().unwrap();
+        assert_eq!((position.line, position.column), (4, 17));
+        assert!(diagnostic.message.starts_with("invalid type: integer `1`"));
+    }
+
+    #[test]
+    fn test_music_file_load_migrated_error_has_position() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("2024/01.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // 2件目のクリップのライバーidが未定義
+        let before = include_str!("migration/golden/v1_to_v2/before.json");
+        let invalid = before.replacen("\"yugamin\"", "\"unknown-liver\"", 1);
+        assert_ne!(invalid, before);
+        std::fs::write(&path, &invalid).unwrap();
+
+        let err = MusicFile::load(path.clone(), root.path()).unwrap_err();
+        let diagnostic = err.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, path);
+        let position = diagnostic.position.as_ref().unwrap();
+        let (line, column) = invalid
+            .lines()
+            .enumerate()
+            .find_map(|(i, line)| {
+                line.find("\"Idol\"").map(|column| (i + 1, column + 1))
+            })
+            .unwrap();
+        // 移行後の値ではなく, 元のファイルのクリップの曲名を指す
+        assert_eq!((position.line, position.column), (line, column));
+        assert!(
+            diagnostic.message.contains("unknown-liver"),
+            "{}",
+            diagnostic.message
+        );
+    }
 }
@@ -1,26 +1,140 @@
-/// jsonファイルから楽曲情報をデシリアライズする
-pub(crate) fn deserialize_from_file(
+/// 最新のスキーマバージョンの月別ファイル
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MonthFileDocument {
+    // `migration::schema_version`で検証済み
+    #[allow(dead_code)]
+    schema_version: u32,
+    videos: crate::model::VerifiedVideos,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MonthFileDocumentRef<'a> {
+    schema_version: u32,
+    videos: &'a crate::model::VerifiedVideos,
+}
+
+/// 月別ファイルから楽曲情報をデシリアライズする
+///
+/// 古いスキーマバージョンのファイルは最新のバージョンに変換してから読み込む
+///
+/// # Returns
+/// - `(動画情報, ファイルのスキーマバージョン)`
+pub(crate) fn deserialize_month_file(
     file: &std::path::Path,
-) -> Result<crate::model::VerifiedVideos, super::MusicFileError> {
+) -> Result<(crate::model::VerifiedVideos, u32), super::MusicFileError> {
     use super::MusicFileError;
 
-    let file_handle =
-        std::fs::File::open(file).map_err(|e| MusicFileError::FileOpen {
-            path: file.to_string_lossy().to_string(),
-            msg: e.to_string(),
-            when: "deserializing from file".to_string(),
-        })?;
+    let text = std::fs::read_to_string(file).map_err(|e| MusicFileError::FileOpen {
+        path: file.to_string_lossy().to_string(),
+        msg: e.to_string(),
+        when: "deserializing from file".to_string(),
+    })?;
+    let to_deserialize_err = |e: serde_json::Error| {
+        let source = cmn_rs::source_diagnostic::SourceFile::new(file, text.clone());
+        let whole = cmn_rs::json_span::Span {
+            start: 0,
+            end: text.len(),
+        };
+        MusicFileError::Deserialize(crate::validate::locate_serde_error(
+            &source, whole, &e,
+        ))
+    };
+    let to_migration_err = |source| MusicFileError::Migration {
+        path: file.to_path_buf(),
+        source,
+    };
 
-    let reader = std::io::BufReader::new(file_handle);
+    let value: serde_json::Value =
+        serde_json::from_str(&text).map_err(to_deserialize_err)?;
+    let version = super::migration::schema_version(&value).map_err(to_migration_err)?;
 
-    serde_json::from_reader(reader).map_err(|e| MusicFileError::Deserialize {
-        path: file.to_path_buf(),
-        msg: e.to_string(),
-    })
+    let document: MonthFileDocument = if version == super::CURRENT_SCHEMA_VERSION {
+        // エラーの位置を求めるため, 元の文字列から読み込む
+        serde_json::from_str(&text).map_err(to_deserialize_err)?
+    } else {
+        let (value, _) =
+            super::migration::migrate_to_current(value).map_err(to_migration_err)?;
+        <MonthFileDocument as serde::Deserialize>::deserialize(&value).map_err(|e| {
+            MusicFileError::Deserialize(locate_migrated_error(file, &text, &value, &e))
+        })?
+    };
+    Ok((document.videos, version))
+}
+
+/// 移行後の値の読み込みエラーを, 元のファイルの位置付きにする
+///
+/// 移行後の値は元の文字列と位置が対応しないため, 読み込めない動画, クリップを1件ずつ探し,
+/// 元のファイルの同じ添字の動画, クリップを指す. 移行は動画, クリップの順を変えない
+fn locate_migrated_error(
+    file: &std::path::Path,
+    text: &str,
+    migrated: &serde_json::Value,
+    err: &serde_json::Error,
+) -> cmn_rs::source_diagnostic::SourceDiagnostic {
+    let source = cmn_rs::source_diagnostic::SourceFile::new(file, text.to_string());
+    // スキーマバージョン1はルートが動画の配列
+    let original_videos = source.root().map(|root| root.get("videos").unwrap_or(root));
+    let whole = original_videos.map_or(
+        cmn_rs::json_span::Span {
+            start: 0,
+            end: text.len(),
+        },
+        |videos| videos.span,
+    );
+    let migrated_videos = migrated["videos"].as_array().map_or(&[][..], Vec::as_slice);
+    for (video_index, video) in migrated_videos.iter().enumerate() {
+        let Err(video_err) =
+            <crate::model::VerifiedVideo as serde::Deserialize>::deserialize(video)
+        else {
+            continue;
+        };
+        let original_video = original_at(original_videos, video_index);
+        let clips = video["clips"].as_array().map_or(&[][..], Vec::as_slice);
+        let clip_err = clips.iter().enumerate().find_map(|(clip_index, clip)| {
+            <crate::model::UnverifiedClip as serde::Deserialize>::deserialize(clip)
+                .err()
+                .map(|e| (clip_index, e))
+        });
+        let (span, e) = match clip_err {
+            Some((clip_index, e)) => (
+                original_at(original_video.and_then(|v| v.get("clips")), clip_index),
+                e,
+            ),
+            None => (original_video, video_err),
+        };
+        let span = span.map_or(whole, |node| node.span);
+        return crate::validate::locate_serde_error(&source, span, &e);
+    }
+    // 動画ごとには読めるとき. e.g. 動画idの重複
+    crate::validate::locate_serde_error(&source, whole, err)
+}
+
+/// 配列`node`の`index`番目の要素
+fn original_at(
+    node: Option<&cmn_rs::json_span::JsonNode>,
+    index: usize,
+) -> Option<&cmn_rs::json_span::JsonNode> {
+    node?.as_array()?.get(index)
+}
+
+/// 月別ファイルに書き込む内容. 最新のスキーマバージョンで, pretty形式
+pub(crate) fn serialize_month_file(
+    file: &std::path::Path,
+    videos: &crate::model::VerifiedVideos,
+) -> Result<Vec<u8>, super::MusicFileError> {
+    let document = MonthFileDocumentRef {
+        schema_version: super::CURRENT_SCHEMA_VERSION,
+        videos,
+    };
+    serialize_to_bytes(file, &document, false)
 }
 
 /// jsonファイルに楽曲情報を書き込む
 ///
+/// 一時ファイルに書き出してからrenameするため, 途中で失敗しても元のファイルは壊れない
+///
 /// # Arguments
 /// - `file`: 書き込むファイルのパス
 /// - `content`: 書き込む内容
@@ -33,38 +147,52 @@ pub(crate) fn serialize_to_file<T>(
 where
     T: serde::Serialize,
 {
-    use super::MusicFileError;
-    use std::io::Write;
-
-    fn to_err(e: &dyn std::fmt::Display, file: &std::path::Path) -> MusicFileError {
-        MusicFileError::FileWrite {
-            msg: e.to_string(),
+    let bytes = serialize_to_bytes(file, content, is_minimized)?;
+    create_parent_dir(file)?;
+    cmn_rs::atomic_write::write_file(file, &bytes).map_err(|e| {
+        super::MusicFileError::FileWrite {
             path: file.to_path_buf(),
-        }
-    }
-
-    if let Some(parent) = file.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| MusicFileError::FileOpen {
-            path: parent.to_string_lossy().to_string(),
             msg: e.to_string(),
-            when: "creating parent directories".to_string(),
-        })?;
-    }
-
-    let file_handle =
-        std::fs::File::create(file).map_err(|e| MusicFileError::FileOpen {
-            path: file.to_string_lossy().to_string(),
-            msg: e.to_string(),
-            when: "serializing to file".to_string(),
-        })?;
-
-    let mut writer = std::io::BufWriter::new(file_handle);
+        }
+    })
+}
 
-    if is_minimized {
-        serde_json::to_writer(writer, content).map_err(|e| to_err(&e, file))
+/// 楽曲情報をファイルに書き込む内容に変換する
+///
+/// - `file`: エラーに含めるパス
+/// - `is_minimized`: minimizedでないときは末尾に改行付与
+pub(crate) fn serialize_to_bytes<T>(
+    file: &std::path::Path,
+    content: &T,
+    is_minimized: bool,
+) -> Result<Vec<u8>, super::MusicFileError>
+where
+    T: serde::Serialize,
+{
+    let result = if is_minimized {
+        serde_json::to_vec(content)
     } else {
-        serde_json::to_writer_pretty(&mut writer, content)
-            .map_err(|e| to_err(&e, file))
-            .and_then(|_| writer.write_all(b"\n").map_err(|e| to_err(&e, file)))
-    }
+        serde_json::to_vec_pretty(content).map(|mut bytes| {
+            bytes.push(b'\n');
+            bytes
+        })
+    };
+    result.map_err(|e| super::MusicFileError::FileWrite {
+        path: file.to_path_buf(),
+        msg: e.to_string(),
+    })
+}
+
+/// 親ディレクトリが無ければ作成する
+pub(crate) fn create_parent_dir(
+    file: &std::path::Path,
+) -> Result<(), super::MusicFileError> {
+    let Some(parent) = file.parent() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(parent).map_err(|e| super::MusicFileError::FileOpen {
+        path: parent.to_string_lossy().to_string(),
+        msg: e.to_string(),
+        when: "creating parent directories".to_string(),
+    })
 }
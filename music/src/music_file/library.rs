@@ -21,6 +21,10 @@ impl MusicLibrary {
         }
     }
 
+    pub(crate) fn get_root_dir(&self) -> &std::path::Path {
+        &self.root_dir
+    }
+
     pub(crate) fn iter_files(
         &self,
     ) -> impl Iterator<Item = &crate::music_file::MusicFile> {
@@ -107,3 +111,33 @@ impl MusicLibrary {
         }
     }
 }
+
+// MARK: For Tests
+#[cfg(test)]
+impl MusicLibrary {
+    /// 一時ディレクトリをルートとし, `videos`を持つライブラリ. ファイルには書き込まない
+    ///
+    /// ライブラリを使い終わるまで一時ディレクトリを保持すること
+    pub(crate) fn with_videos(
+        videos: Vec<crate::model::VerifiedVideo>,
+    ) -> (tempfile::TempDir, Self) {
+        let root = tempfile::tempdir().unwrap();
+        let mut library =
+            Self::new(root.path().to_path_buf(), std::collections::HashMap::new());
+        library
+            .extend_videos(
+                crate::model::VerifiedVideos::try_from_vec(videos).unwrap(),
+                crate::music_file::DuplicateVideoPolicy::Reject,
+            )
+            .unwrap();
+        (root, library)
+    }
+
+    /// `self_a`, `self_b`を持つライブラリ
+    pub(crate) fn self_a_b() -> (tempfile::TempDir, Self) {
+        Self::with_videos(vec![
+            crate::model::VerifiedVideo::self_a(),
+            crate::model::VerifiedVideo::self_b(),
+        ])
+    }
+}
@@ -0,0 +1,134 @@
+/// 楽曲情報のルートに対する書き込みのロック
+///
+/// - ルート直下のロックファイルに対するadvisory lock. 同じルートを書き換える`musictl`同士で排他する
+/// - dropで解放する. ロックファイル自体は削除しない(削除と取得が競合するため)
+/// - 取得したときに, 途中で終了した書き込みのジャーナルが残っていれば元に戻す
+#[derive(Debug)]
+pub struct MusicRootLock {
+    path: std::path::PathBuf,
+    // 保持している間ロックされる
+    _file: std::fs::File,
+}
+
+impl MusicRootLock {
+    /// ロックを取得する. 他のプロセスが保持しているときは待たずに失敗する
+    ///
+    /// # Errors
+    /// - 他のプロセスがロックを保持しているとき
+    /// - ロックファイルを開けないとき
+    pub fn acquire(root: &std::path::Path) -> Result<Self, super::MusicFileError> {
+        use std::io::Write;
+
+        let path = root.join(crate::cfg::MUSIC_ROOT_LOCK_FILE_NAME);
+        let open_error = |e: std::io::Error| super::MusicFileError::FileOpen {
+            path: path.to_string_lossy().to_string(),
+            msg: e.to_string(),
+            when: "locking the music root".to_string(),
+        };
+
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(open_error)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(std::fs::TryLockError::WouldBlock) => {
+                let holder = std::fs::read_to_string(&path).unwrap_or_default();
+                return Err(super::MusicFileError::Locked {
+                    path,
+                    holder: holder.trim().to_string(),
+                });
+            }
+            Err(std::fs::TryLockError::Error(e)) => return Err(open_error(e)),
+        }
+
+        // 誰が保持しているかをエラーメッセージに出すため. 失敗してもロックは有効
+        let _ = file
+            .set_len(0)
+            .and_then(|_| write!(file, "pid {}", std::process::id()));
+
+        tracing::debug!("Acquired music root lock: {}", path.display());
+
+        let journal = root.join(crate::cfg::MUSIC_ROOT_JOURNAL_FILE_NAME);
+        cmn_rs::atomic_write::recover(&journal).map_err(|e| {
+            super::MusicFileError::FileWrite {
+                path: journal.clone(),
+                msg: format!("failed to recover an interrupted write: {e}"),
+            }
+        })?;
+
+        Ok(Self { path, _file: file })
+    }
+
+    pub fn get_path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_is_exclusive_until_dropped() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let lock = MusicRootLock::acquire(tmp.path()).unwrap();
+        assert!(lock.get_path().exists());
+
+        let err = MusicRootLock::acquire(tmp.path()).unwrap_err();
+        match err {
+            crate::music_file::MusicFileError::Locked { holder, .. } => {
+                assert_eq!(holder, format!("pid {}", std::process::id()));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        drop(lock);
+        MusicRootLock::acquire(tmp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_acquire_rolls_back_interrupted_write() {
+        let tmp = tempfile::tempdir().unwrap();
+        // `2024/01.json`を新しく作った直後に終了した状態
+        let created = tmp.path().join("2024/01.json");
+        std::fs::create_dir_all(created.parent().unwrap()).unwrap();
+        std::fs::write(&created, "[]\n").unwrap();
+        let journal = tmp.path().join(crate::cfg::MUSIC_ROOT_JOURNAL_FILE_NAME);
+        std::fs::write(
+            &journal,
+            serde_json::json!({
+                "state": "prepared",
+                "entries": [{
+                    "path": created,
+                    "tmp": tmp.path().join("2024/.01.json.tmp-1"),
+                    "backup": null,
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let _lock = MusicRootLock::acquire(tmp.path()).unwrap();
+
+        assert!(!created.exists());
+        assert!(!journal.exists());
+    }
+
+    #[test]
+    fn test_acquire_missing_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = MusicRootLock::acquire(&tmp.path().join("missing")).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::music_file::MusicFileError::FileOpen { .. }
+        ));
+    }
+}
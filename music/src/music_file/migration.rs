@@ -0,0 +1,161 @@
+mod v1_to_v2;
+
+/// 月別ファイルの最新のスキーマバージョン. 書き込みは常にこのバージョンで行う
+///
+/// 既存のファイルを変換しないと読めない変更のときだけ上げる.
+/// 省略可能なフィールドの追加では上げない(古い`musictl`は未知のフィールドをエラーにする)
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// スキーマバージョンを1つ上げる変換
+struct MigrationStep {
+    /// 変換前のバージョン. 変換後は`from + 1`
+    from: u32,
+    migrate: fn(serde_json::Value) -> Result<serde_json::Value, String>,
+}
+
+/// `from`の昇順. 1から`CURRENT_SCHEMA_VERSION - 1`まで欠けなく並べる
+const STEPS: &[MigrationStep] = &[MigrationStep {
+    from: 1,
+    migrate: v1_to_v2::migrate,
+}];
+
+/// 各変換のゴールデンファイル(変換前, 変換後). `STEPS`と同じ順
+///
+/// 変換後は次の変換の変換前と同じ内容にし, 最初の変換前から最新までを1本の流れにする
+#[cfg(test)]
+pub(super) const GOLDEN_FILES: &[(&str, &str)] = &[(
+    include_str!("migration/golden/v1_to_v2/before.json"),
+    include_str!("migration/golden/v1_to_v2/after.json"),
+)];
+
+/// 月別ファイルの移行に関するエラー
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// トップレベルが配列でもオブジェクトでもない, または`schemaVersion`が不正
+    #[error("Unrecognized month file layout: {0}")]
+    UnrecognizedLayout(String),
+    /// このmusictlより新しいバージョン
+    #[error(
+        "schemaVersion {version} is newer than the latest supported version \
+        {CURRENT_SCHEMA_VERSION}. Update musictl"
+    )]
+    NewerVersion { version: u32 },
+    /// 変換に失敗
+    #[error("Failed to migrate from schemaVersion {from} to {}: {msg}", from + 1)]
+    Step { from: u32, msg: String },
+}
+
+/// 月別ファイルのスキーマバージョンを返す
+///
+/// - バージョン1はトップレベルが動画の配列で, `schemaVersion`を持たない
+/// - バージョン2以降はトップレベルのオブジェクトに`schemaVersion`を持つ
+pub(crate) fn schema_version(value: &serde_json::Value) -> Result<u32, MigrationError> {
+    match value {
+        serde_json::Value::Array(_) => Ok(1),
+        serde_json::Value::Object(object) => {
+            let version = object.get("schemaVersion").ok_or_else(|| {
+                MigrationError::UnrecognizedLayout("missing schemaVersion".to_string())
+            })?;
+            version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .filter(|version| *version >= 2)
+                .ok_or_else(|| {
+                    MigrationError::UnrecognizedLayout(format!(
+                        "invalid schemaVersion {version}"
+                    ))
+                })
+        }
+        _ => Err(MigrationError::UnrecognizedLayout(
+            "top level must be an array or an object".to_string(),
+        )),
+    }
+}
+
+/// 最新のバージョンまで1つずつ変換する
+///
+/// 戻り値は(変換後の内容, 変換前のバージョン)
+pub(crate) fn migrate_to_current(
+    value: serde_json::Value,
+) -> Result<(serde_json::Value, u32), MigrationError> {
+    let original = schema_version(&value)?;
+    if original > CURRENT_SCHEMA_VERSION {
+        return Err(MigrationError::NewerVersion { version: original });
+    }
+
+    let mut value = value;
+    for step in STEPS.iter().filter(|step| step.from >= original) {
+        value = (step.migrate)(value).map_err(|msg| MigrationError::Step {
+            from: step.from,
+            msg,
+        })?;
+    }
+    Ok((value, original))
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_cover_every_version() {
+        let froms = STEPS.iter().map(|step| step.from).collect::<Vec<_>>();
+        assert_eq!(froms, (1..CURRENT_SCHEMA_VERSION).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_golden_files_chain() {
+        let parse = |s: &str| serde_json::from_str::<serde_json::Value>(s).unwrap();
+        assert_eq!(GOLDEN_FILES.len(), STEPS.len());
+        for (step, (before, after)) in STEPS.iter().zip(GOLDEN_FILES) {
+            assert_eq!(schema_version(&parse(before)), Ok(step.from));
+            assert_eq!((step.migrate)(parse(before)), Ok(parse(after)));
+        }
+        for pair in GOLDEN_FILES.windows(2) {
+            assert_eq!(parse(pair[0].1), parse(pair[1].0));
+        }
+    }
+
+    #[test]
+    fn test_schema_version() {
+        assert_eq!(schema_version(&serde_json::json!([])), Ok(1));
+        assert_eq!(
+            schema_version(&serde_json::json!({"schemaVersion": 2, "videos": []})),
+            Ok(2)
+        );
+        for invalid in [
+            serde_json::json!({"videos": []}),
+            serde_json::json!({"schemaVersion": 1, "videos": []}),
+            serde_json::json!({"schemaVersion": "2"}),
+            serde_json::json!("2"),
+        ] {
+            assert!(matches!(
+                schema_version(&invalid),
+                Err(MigrationError::UnrecognizedLayout(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_migrate_to_current() {
+        let (value, original) = migrate_to_current(serde_json::json!([])).unwrap();
+        assert_eq!(original, 1);
+        assert_eq!(
+            value,
+            serde_json::json!({"schemaVersion": CURRENT_SCHEMA_VERSION, "videos": []})
+        );
+
+        // 最新のバージョンはそのまま
+        assert_eq!(
+            migrate_to_current(value.clone()).unwrap(),
+            (value, CURRENT_SCHEMA_VERSION)
+        );
+
+        assert_eq!(
+            migrate_to_current(serde_json::json!({"schemaVersion": 99})),
+            Err(MigrationError::NewerVersion { version: 99 })
+        );
+    }
+}
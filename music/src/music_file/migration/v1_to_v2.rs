@@ -0,0 +1,49 @@
+//! 1 -> 2: トップレベルの動画の配列を, `schemaVersion`を持つオブジェクトで包む
+
+pub(super) fn migrate(value: serde_json::Value) -> Result<serde_json::Value, String> {
+    match value {
+        serde_json::Value::Array(videos) => Ok(serde_json::json!({
+            "schemaVersion": 2,
+            "videos": videos,
+        })),
+        other => Err(format!(
+            "expected an array of videos, found {}",
+            json_type_name(&other)
+        )),
+    }
+}
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_wraps_videos() {
+        let videos = serde_json::json!([{"videoId": "a"}, {"videoId": "b"}]);
+        assert_eq!(
+            migrate(videos.clone()),
+            Ok(serde_json::json!({"schemaVersion": 2, "videos": videos}))
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_object() {
+        assert_eq!(
+            migrate(serde_json::json!({})),
+            Err("expected an array of videos, found an object".to_string())
+        );
+    }
+}
@@ -47,28 +47,66 @@ impl MusicLibraryRepository {
         ))
     }
 
+    /// 楽曲情報のルートを書き換える前にロックを取得する
+    ///
+    /// 書き込みを行うコマンドは, 読み込みの前に取得して書き込みが終わるまで保持すること.
+    /// 前回の書き込みが途中で終了していたときは, 元に戻してから返す
+    pub fn lock(
+        dir: &std::path::Path,
+    ) -> Result<crate::music_file::MusicRootLock, crate::music_file::MusicFileErrors>
+    {
+        crate::music_file::MusicRootLock::acquire(dir).map_err(|e| e.into_errors())
+    }
+
+    /// 内容が変わった月別ファイルを全て書き込む
+    ///
+    /// 全てのファイルを一時ファイルに書き出してfsyncしてから置き換える.
+    /// 途中で失敗したときは置き換え済みのファイルを元に戻すため, 一部だけ更新された状態にはならない.
+    /// 途中でプロセスが終了したときは, 次に[`Self::lock`]でロックを取ったときに元に戻す
     pub fn save_month_files(
         library: &crate::music_file::MusicLibrary,
     ) -> Result<(), crate::music_file::MusicFileErrors> {
         tracing::info!("Saving monthly music files to disk...");
 
         let mut errs = Vec::new();
+        let mut changed = Vec::new();
 
         for file in library.iter_files() {
-            if let Err(e) = file.save() {
-                errs.push(crate::music_file::MusicFileError::FileWrite {
-                    path: file.get_path().to_path_buf(),
-                    msg: e.to_string(),
-                });
+            let path = file.get_path();
+            let bytes = match file.to_bytes() {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    errs.push(e);
+                    continue;
+                }
+            };
+            if std::fs::read(path).is_ok_and(|current| current == bytes) {
+                continue;
+            }
+            if let Err(e) = super::fs_util::create_parent_dir(path) {
+                errs.push(e);
+                continue;
             }
+            changed.push((path.to_path_buf(), bytes));
         }
 
-        if errs.is_empty() {
-            tracing::info!("Saved all monthly music files saved successfully.");
-            Ok(())
-        } else {
-            Err(errs.into())
+        if !errs.is_empty() {
+            return Err(errs.into());
         }
+
+        let journal = library
+            .get_root_dir()
+            .join(crate::cfg::MUSIC_ROOT_JOURNAL_FILE_NAME);
+        cmn_rs::atomic_write::write_files(&journal, &changed).map_err(|e| {
+            crate::music_file::MusicFileError::FileWrite {
+                path: library.get_root_dir().to_path_buf(),
+                msg: format!("no monthly music file was changed: {e}"),
+            }
+            .into_errors()
+        })?;
+
+        tracing::info!("Saved {} changed monthly music files.", changed.len());
+        Ok(())
     }
 
     fn collect_music_file_paths_in_dir(
@@ -175,3 +213,50 @@ impl MusicLibraryRepository {
         (files, errs)
     }
 }
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_month_files_skips_unchanged_files() {
+        let (tmp, library) = crate::music_file::MusicLibrary::self_a_b();
+        MusicLibraryRepository::save_month_files(&library).unwrap();
+
+        let january = tmp.path().join("2024/01.json");
+        let modified = std::fs::metadata(&january).unwrap().modified().unwrap();
+        MusicLibraryRepository::save_month_files(&library).unwrap();
+        assert_eq!(
+            std::fs::metadata(&january).unwrap().modified().unwrap(),
+            modified
+        );
+
+        // 一時ファイル, 退避ファイルは残らない
+        assert_eq!(
+            std::fs::read_dir(tmp.path().join("2024")).unwrap().count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_save_month_files_writes_nothing_on_failure() {
+        let (tmp, library) = crate::music_file::MusicLibrary::self_a_b();
+        let january = tmp.path().join("2024/01.json");
+        std::fs::create_dir_all(january.parent().unwrap()).unwrap();
+        std::fs::write(&january, "[]\n").unwrap();
+        // 2月の一時ファイルを作れないようにする
+        std::fs::create_dir(
+            tmp.path()
+                .join(format!("2024/.02.json.tmp-{}", std::process::id())),
+        )
+        .unwrap();
+
+        let result = MusicLibraryRepository::save_month_files(&library);
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&january).unwrap(), "[]\n");
+        assert!(!tmp.path().join("2024/02.json").exists());
+    }
+}
@@ -1,8 +1,17 @@
+mod edit_month_files;
+mod edit_videos;
 mod find_duplicate_video_ids;
+mod import_setlist;
 mod merge_input_files;
+mod suggest_song_ids;
 
+pub(crate) use edit_videos::{LiverReplacement, VideoEdits, edit_videos};
 pub(crate) use find_duplicate_video_ids::find_duplicate_video_ids;
+pub(crate) use import_setlist::{
+    ImportSetlistOptions, import_setlist, write_setlist_input_file,
+};
 pub(crate) use merge_input_files::merge_input_files;
+pub(crate) use suggest_song_ids::{apply_song_id_suggestions, suggest_song_ids};
 
 #[derive(thiserror::Error, Debug)]
 pub enum OperationError {
@@ -12,4 +21,10 @@ pub enum OperationError {
     Json(#[from] serde_json::Error),
     #[error(transparent)]
     AnonymousVideoValidation(#[from] crate::validate::AnonymousVideoValidateErrors),
+    #[error("Invalid setlist: {0}")]
+    Setlist(String),
+    #[error("Invalid edit: {0}")]
+    Edit(String),
+    #[error(transparent)]
+    MusicFile(#[from] crate::music_file::MusicFileError),
 }
@@ -0,0 +1,95 @@
+/// 動画を置き換えた月別ファイル
+#[derive(Debug, Clone)]
+pub(crate) struct ReplacedMonthFile {
+    pub(crate) path: std::path::PathBuf,
+    /// 置き換えた後の動画
+    pub(crate) after: crate::model::VerifiedVideos,
+}
+
+/// 全ての月別ファイルの動画に`edit`を適用し, 内容が変わったファイルの動画を置き換える
+///
+/// - `edit`は編集後の動画と, 内容が変わったかどうかを返す
+/// - ファイルには書き込まない. `library`の内容のみ変更する
+/// - 戻り値は動画を置き換えた月別ファイル. パス順
+///
+/// # Errors
+/// - `edit`が失敗したとき
+/// - 編集後の動画で月別ファイルを置き換えられないとき
+///   - e.g. 動画IDの重複, 公開日時の年/月がファイルと異なる
+pub(crate) fn edit_month_files<F>(
+    library: &mut crate::music_file::MusicLibrary,
+    mut edit: F,
+) -> Result<Vec<ReplacedMonthFile>, super::OperationError>
+where
+    F: FnMut(
+        crate::model::VerifiedVideo,
+    ) -> Result<(crate::model::VerifiedVideo, bool), super::OperationError>,
+{
+    let mut replaced = Vec::new();
+
+    for file in library.iter_files_mut() {
+        let mut videos = Vec::new();
+        let mut file_changed = false;
+        for video in file.videos().clone().into_sorted_vec() {
+            let (video, changed) = edit(video)?;
+            file_changed |= changed;
+            videos.push(video);
+        }
+        if !file_changed {
+            continue;
+        }
+
+        let path = file.get_path().to_path_buf();
+        let videos =
+            crate::model::VerifiedVideos::try_from_vec(videos).map_err(|ids| {
+                super::OperationError::Edit(format!(
+                    "{}: duplicated video ids after editing: {ids}",
+                    path.display()
+                ))
+            })?;
+        file.replace_videos(videos)?;
+        replaced.push(ReplacedMonthFile {
+            path,
+            after: file.videos().clone(),
+        });
+    }
+    replaced.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(replaced)
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_month_files_replaces_only_changed_files() {
+        let (_root, mut library) = crate::music_file::MusicLibrary::self_a_b();
+
+        let replaced =
+            edit_month_files(&mut library, |video| Ok((video, false))).unwrap();
+        assert!(replaced.is_empty());
+
+        let replaced =
+            edit_month_files(&mut library, |video| Ok((video, true))).unwrap();
+        assert!(!replaced.is_empty());
+        assert!(replaced.is_sorted_by(|a, b| a.path <= b.path));
+    }
+
+    #[test]
+    fn test_edit_month_files_returns_error_instead_of_panicking() {
+        let (_root, mut library) = crate::music_file::MusicLibrary::self_a_b();
+        let video = crate::model::VerifiedVideo::self_a();
+
+        // 別の月の動画で置き換えると, 公開日時の年/月がファイルと異なる
+        let result = edit_month_files(&mut library, |_| Ok((video.clone(), true)));
+        assert!(matches!(
+            result,
+            Err(crate::operations::OperationError::MusicFile(
+                crate::music_file::MusicFileError::VideoPublishDateMismatch { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,371 @@
+/// 動画, クリップに適用する編集内容
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VideoEdits {
+    /// 対象のクリップを含む動画に追加するタグ
+    pub(crate) add_tags: Vec<tagctl::model::VideoTagId>,
+    /// 対象のクリップを含む動画から取り除くタグ
+    pub(crate) remove_tags: Vec<String>,
+    /// 対象のクリップに設定する曲名
+    pub(crate) set_song_title: Option<String>,
+    /// 対象のクリップの歌唱者の置き換え
+    pub(crate) replace_livers: Vec<LiverReplacement>,
+    /// 対象のクリップに設定する再生音量
+    pub(crate) set_volume_percent: Option<crate::model::VolumePercent>,
+    /// 対象のクリップの再生音量を取り除くかどうか
+    pub(crate) unset_volume_percent: bool,
+}
+
+/// 歌唱者のライバーIDの置き換え. `OLD=NEW`の形式
+#[derive(Debug, Clone)]
+pub(crate) struct LiverReplacement {
+    old: String,
+    new: String,
+}
+
+/// 編集で内容が変わった月別ファイル
+#[derive(Debug, Clone)]
+pub(crate) struct EditedMonthFile {
+    pub(crate) path: std::path::PathBuf,
+    /// ディスク上の現在のファイルの内容. 存在しないときは空
+    pub(crate) before: String,
+    /// 編集後にファイルに書き込む内容
+    pub(crate) after: String,
+}
+
+/// 編集の結果
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EditReport {
+    /// 内容が変わった動画の数
+    pub(crate) edited_videos: usize,
+    /// 内容が変わったクリップの数
+    pub(crate) edited_clips: usize,
+    /// 内容が変わった月別ファイル. パス順
+    pub(crate) edited_files: Vec<EditedMonthFile>,
+}
+
+impl VideoEdits {
+    /// 動画タグに編集を適用する. 変更があったとき`true`
+    fn apply_to_tags(&self, video_tags: &mut crate::model::VideoTagIds) -> bool {
+        let mut changed = false;
+        for tag in &self.remove_tags {
+            changed |= video_tags.remove(tag);
+        }
+        for tag in &self.add_tags {
+            changed |= video_tags.insert(tag.clone());
+        }
+        changed
+    }
+
+    /// クリップに編集を適用する. 変更があったとき`true`
+    fn apply_to_clip(&self, clip: &mut crate::model::UnverifiedClip) -> bool {
+        let mut changed = false;
+        if let Some(song_title) = &self.set_song_title {
+            changed |= clip.set_song_title(song_title);
+        }
+        if !self.replace_livers.is_empty() {
+            let liver_ids = clip
+                .get_liver_ids()
+                .to_vec()
+                .into_iter()
+                .map(|id| {
+                    self.replace_livers
+                        .iter()
+                        .find(|r| r.old == id)
+                        .map_or(id, |r| r.new.as_str())
+                })
+                .collect();
+            // 置き換え前後のIDは共に検証済み
+            let liver_ids =
+                artistctl::model::LiverIds::new(liver_ids).expect("will be valid");
+            changed |= clip.set_liver_ids(liver_ids);
+        }
+        if self.set_volume_percent.is_some() {
+            changed |= clip.set_volume_percent(self.set_volume_percent);
+        } else if self.unset_volume_percent {
+            changed |= clip.set_volume_percent(None);
+        }
+        changed
+    }
+}
+
+impl std::str::FromStr for LiverReplacement {
+    type Err = String;
+
+    /// - Err: `OLD=NEW`の形式でないとき, 存在しないライバーIDのとき
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (old, new) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected OLD=NEW, got `{s}`"))?;
+        let (old, new) = (old.trim(), new.trim());
+        artistctl::model::LiverIds::new(vec![old, new])?;
+        Ok(Self {
+            old: old.to_string(),
+            new: new.to_string(),
+        })
+    }
+}
+
+impl EditReport {
+    /// 変更があった月別ファイルのunified diff
+    pub(crate) fn diff(&self) -> String {
+        self.edited_files
+            .iter()
+            .map(|file| {
+                cmn_rs::diff::unified(
+                    &file.path.to_string_lossy(),
+                    &file.before,
+                    &file.after,
+                )
+            })
+            .collect()
+    }
+}
+
+/// `filter`に一致するクリップに`edits`を適用する
+///
+/// - タグの編集は一致するクリップを含む動画に適用する
+/// - 編集した動画は`VerifiedVideo`として再度検証する
+/// - ファイルには書き込まない. `library`の内容のみ変更する
+///
+/// # Errors
+/// - 編集後の動画が不正なとき
+///   - e.g. 切り抜いた動画があるクリップに再生音量を設定したとき
+pub(crate) fn edit_videos(
+    library: &mut crate::music_file::MusicLibrary,
+    filter: &crate::clip_filter::ClipFilter,
+    edits: &VideoEdits,
+) -> Result<EditReport, super::OperationError> {
+    let mut report = EditReport::default();
+
+    let replaced = super::edit_month_files::edit_month_files(library, |video| {
+        let selected_clips = video
+            .clips()
+            .filter(|clip| {
+                filter.matches_video(&video) && filter.matches_clip(&video, clip)
+            })
+            .map(|clip| clip.get_uuid().clone())
+            .collect::<std::collections::HashSet<_>>();
+        if selected_clips.is_empty() {
+            return Ok((video, false));
+        }
+
+        let video_id = video.get_video_id().clone();
+        let (video, (tags_changed, edited_clips)) = video
+            .try_edit(|video_tags, clips| {
+                let tags_changed = edits.apply_to_tags(video_tags);
+                let edited_clips = clips
+                    .iter_mut()
+                    .filter(|clip| selected_clips.contains(clip.get_uuid()))
+                    .map(|clip| edits.apply_to_clip(clip))
+                    .filter(|&changed| changed)
+                    .count();
+                (tags_changed, edited_clips)
+            })
+            .map_err(|e| super::OperationError::Edit(format!("{video_id}: {e}")))?;
+
+        let changed = tags_changed || edited_clips > 0;
+        if changed {
+            report.edited_videos += 1;
+            report.edited_clips += edited_clips;
+        }
+        Ok((video, changed))
+    })?;
+
+    for file in replaced {
+        report.edited_files.push(EditedMonthFile {
+            before: read_current(&file.path)?,
+            after: serialize(&file.path, &file.after)?,
+            path: file.path,
+        });
+    }
+
+    Ok(report)
+}
+
+/// 月別ファイルの現在の内容. ファイルが存在しないときは空
+fn read_current(path: &std::path::Path) -> Result<String, super::OperationError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 月別ファイルに書き込む内容
+fn serialize(
+    path: &std::path::Path,
+    videos: &crate::model::VerifiedVideos,
+) -> Result<String, super::OperationError> {
+    let bytes = crate::music_file::fs_util::serialize_month_file(path, videos)?;
+    // `serde_json`の出力は常にUTF-8
+    Ok(String::from_utf8(bytes).expect("serialized json should be valid utf-8"))
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `self_a`, `self_b`を持ち, 月別ファイルを書き込んだライブラリ
+    fn saved_library() -> (tempfile::TempDir, crate::music_file::MusicLibrary) {
+        let (root, library) = crate::music_file::MusicLibrary::self_a_b();
+        crate::music_file::MusicLibraryRepository::save_month_files(&library).unwrap();
+        (root, library)
+    }
+
+    fn song_titles(library: &crate::music_file::MusicLibrary) -> Vec<String> {
+        let mut titles = library
+            .iter_files()
+            .flat_map(|file| file.videos().iter())
+            .flat_map(|video| video.clips())
+            .map(|clip| clip.get_song_title().to_string())
+            .collect::<Vec<_>>();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn test_edit_videos_set_song_title_and_tags() {
+        let (_root, mut library) = saved_library();
+        let filter = crate::clip_filter::ClipFilter {
+            song_title: Some(regex::Regex::new("^Test Song A[12]$").unwrap()),
+            ..Default::default()
+        };
+        let edits = VideoEdits {
+            add_tags: vec![tagctl::model::VideoTagId::new("acoustic").unwrap()],
+            set_song_title: Some("Edited".to_string()),
+            ..Default::default()
+        };
+
+        let report = edit_videos(&mut library, &filter, &edits).unwrap();
+        assert_eq!(report.edited_videos, 1);
+        assert_eq!(report.edited_clips, 2);
+        assert_eq!(report.edited_files.len(), 1);
+        assert!(
+            report
+                .diff()
+                .contains("+          \"songTitle\": \"Edited\",")
+        );
+        // 差分は書き込まれるファイルの内容から作る
+        let file = &report.edited_files[0];
+        assert_eq!(std::fs::read_to_string(&file.path).unwrap(), file.before);
+        crate::music_file::MusicLibraryRepository::save_month_files(&library).unwrap();
+        assert_eq!(std::fs::read_to_string(&file.path).unwrap(), file.after);
+        assert_eq!(
+            song_titles(&library),
+            vec![
+                "Edited",
+                "Edited",
+                "Test Song A3",
+                "Test Song B1",
+                "Test Song B2",
+                "Test Song B3"
+            ]
+        );
+
+        let video_a = library
+            .iter_files()
+            .find_map(|file| file.videos().get(&crate::model::VideoId::test_id_1()))
+            .unwrap();
+        assert_eq!(
+            video_a.get_video_tags().to_vec(),
+            vec!["acoustic", "karaoke"]
+        );
+    }
+
+    #[test]
+    fn test_edit_videos_replace_liver() {
+        let (_root, mut library) = crate::music_file::MusicLibrary::self_a_b();
+        let edits = VideoEdits {
+            replace_livers: vec!["yugamin=riku-tazumi".parse().unwrap()],
+            ..Default::default()
+        };
+
+        let report = edit_videos(
+            &mut library,
+            &crate::clip_filter::ClipFilter::default(),
+            &edits,
+        )
+        .unwrap();
+        assert_eq!(report.edited_videos, 2);
+        assert_eq!(report.edited_clips, 2);
+        assert!(
+            library
+                .iter_files()
+                .flat_map(|file| file.videos().iter())
+                .flat_map(|video| video.clips())
+                .all(|clip| !clip.get_liver_ids().to_vec().contains(&"yugamin"))
+        );
+    }
+
+    #[test]
+    fn test_edit_videos_volume_percent() {
+        let (_root, mut library) = saved_library();
+        let filter = crate::clip_filter::ClipFilter {
+            song_title: Some(regex::Regex::new("^Test Song A[13]$").unwrap()),
+            ..Default::default()
+        };
+        let mut edits = VideoEdits {
+            set_volume_percent: Some(crate::model::VolumePercent::new(70).unwrap()),
+            ..Default::default()
+        };
+
+        let report = edit_videos(&mut library, &filter, &edits).unwrap();
+        assert_eq!(report.edited_clips, 2);
+        assert!(report.diff().contains("+          \"volumePercent\": 70,"));
+        crate::music_file::MusicLibraryRepository::save_month_files(&library).unwrap();
+
+        edits.set_volume_percent = None;
+        edits.unset_volume_percent = true;
+        let report = edit_videos(&mut library, &filter, &edits).unwrap();
+        assert_eq!(report.edited_clips, 2);
+        assert!(report.diff().contains("-          \"volumePercent\": 70,"));
+    }
+
+    #[test]
+    fn test_edit_videos_volume_percent_on_clipped_video() {
+        let (_root, mut library) = crate::music_file::MusicLibrary::self_a_b();
+        // `Test Song A2`は切り抜いた動画がある
+        let filter = crate::clip_filter::ClipFilter {
+            song_title: Some(regex::Regex::new("^Test Song A2$").unwrap()),
+            ..Default::default()
+        };
+        let edits = VideoEdits {
+            set_volume_percent: Some(crate::model::VolumePercent::new(70).unwrap()),
+            ..Default::default()
+        };
+
+        let result = edit_videos(&mut library, &filter, &edits);
+        assert!(matches!(
+            result,
+            Err(crate::operations::OperationError::Edit(_))
+        ));
+    }
+
+    #[test]
+    fn test_edit_videos_no_change() {
+        let (_root, mut library) = crate::music_file::MusicLibrary::self_a_b();
+        let edits = VideoEdits {
+            remove_tags: vec!["acoustic".to_string()],
+            ..Default::default()
+        };
+
+        let report = edit_videos(
+            &mut library,
+            &crate::clip_filter::ClipFilter::default(),
+            &edits,
+        )
+        .unwrap();
+        assert_eq!(report.edited_videos, 0);
+        assert!(report.edited_files.is_empty());
+        assert_eq!(report.diff(), "");
+    }
+
+    #[test]
+    fn test_liver_replacement_from_str() {
+        assert!("yugamin=riku-tazumi".parse::<LiverReplacement>().is_ok());
+        assert!("yugamin".parse::<LiverReplacement>().is_err());
+        assert!("yugamin=unknown-liver".parse::<LiverReplacement>().is_err());
+    }
+}
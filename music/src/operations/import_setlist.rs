@@ -0,0 +1,633 @@
+/// タイムスタンプ行. 例: `1:10 おねがいダーリン / 栞葉るり`, `1:02:03 - 1:05:00 曲名`
+///
+/// 先頭の箇条書き記号や番号(`-`, `・`, `1.`, `2)`)は無視する
+static RE_SETLIST_LINE: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(
+            r"^(?:[-*・•]\s*|\d{1,3}[.)]\s+)?(?P<start>(?:\d{1,2}:)?\d{1,2}:\d{2})(?:\s*[-~〜～]\s*(?P<end>(?:\d{1,2}:)?\d{1,2}:\d{2}))?(?:\s+[-–—|]\s*|\s+|\s*[|｜]\s*)(?P<rest>\S.*)$",
+        )
+        .unwrap()
+    });
+
+/// 歌唱者の区切り
+static RE_SINGER_SEPARATOR: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"\s*(?:[、,，&＆×]|\s+x\s+)\s*").unwrap()
+    });
+
+pub(crate) struct ImportSetlistOptions {
+    /// 次の曲の開始時刻から`endTime`を推定するときに差し引く秒数
+    pub(crate) end_gap_secs: u32,
+    /// 最後の曲の`endTime`
+    pub(crate) last_end_time: Option<crate::model::Duration>,
+    /// 最後の曲の`endTime`が不明なときに仮に使う長さ[secs]
+    pub(crate) default_last_clip_secs: u32,
+    /// 歌唱者の記載が無い(または全て解決できなかった)ときのライバーID
+    pub(crate) default_liver_ids: Vec<String>,
+}
+
+pub(crate) struct ImportSetlistResult {
+    /// 入力ファイルとして妥当であることを検証済みの動画情報
+    pub(crate) videos: crate::model::AnonymousVideos,
+    pub(crate) warnings: Vec<SetlistWarning>,
+}
+
+/// 取り込み時の警告. 取り込みは続行する
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SetlistWarning {
+    /// 1始まりの行番号
+    pub(crate) line: usize,
+    pub(crate) msg: String,
+}
+
+/// タイムスタンプ行を解析したもの
+#[derive(Debug, Clone, PartialEq)]
+struct SetlistEntry {
+    line: usize,
+    start_secs: u32,
+    end_secs: Option<u32>,
+    song_title: String,
+    singers: Vec<String>,
+}
+
+/// 出力する入力ファイルの動画
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInputVideo {
+    video_id: String,
+    clips: Vec<RawInputClip>,
+}
+
+/// 出力する入力ファイルのクリップ
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInputClip {
+    song_title: String,
+    liver_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_artists_name: Vec<String>,
+    start_time: String,
+    end_time: String,
+}
+
+/// 名前(日本語名, 平仮名, 英語名, エイリアス, ID)からライバーIDを引く
+struct SingerResolver {
+    /// 正規化した名前 -> ライバーID. 複数のライバーに該当する名前は`None`
+    names: std::collections::HashMap<String, Option<String>>,
+}
+
+/// セットリストのテキストから入力ファイルの動画情報を作成
+///
+/// # Errors
+/// - タイムスタンプ行が1つも無いとき
+/// - 作成した動画情報が入力ファイルとして不正なとき
+pub(crate) fn import_setlist(
+    video_id: &crate::model::VideoId,
+    text: &str,
+    options: &ImportSetlistOptions,
+) -> Result<ImportSetlistResult, crate::operations::OperationError> {
+    let resolver = SingerResolver::from_loaded_livers();
+    import_setlist_with_resolver(video_id, text, options, &resolver)
+}
+
+/// 入力ファイルとして書き出す
+///
+/// 既存のファイルは上書きしない
+pub(crate) fn write_setlist_input_file(
+    videos: &crate::model::AnonymousVideos,
+    output_file: &std::path::Path,
+) -> Result<(), crate::operations::OperationError> {
+    use std::io::Write;
+
+    if let Some(parent) = output_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(output_file)?;
+    serde_json::to_writer_pretty(&mut file, videos)?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+fn import_setlist_with_resolver(
+    video_id: &crate::model::VideoId,
+    text: &str,
+    options: &ImportSetlistOptions,
+    resolver: &SingerResolver,
+) -> Result<ImportSetlistResult, crate::operations::OperationError> {
+    let (entries, mut warnings) = parse_setlist(text);
+    if entries.is_empty() {
+        return Err(crate::operations::OperationError::Setlist(
+            "no timestamp lines found".to_string(),
+        ));
+    }
+
+    // 同じ開始時刻の行があると, 片方の`endTime`が`startTime`と同じになる
+    if let Some(pair) = entries
+        .windows(2)
+        .find(|pair| pair[0].start_secs == pair[1].start_secs)
+    {
+        let (first, second) = (
+            pair[0].line.min(pair[1].line),
+            pair[0].line.max(pair[1].line),
+        );
+        return Err(crate::operations::OperationError::Setlist(format!(
+            "lines {first} and {second} have the same start time, fix or remove one of them"
+        )));
+    }
+
+    let ends = infer_end_secs(&entries, options, &mut warnings);
+
+    let mut clips = Vec::with_capacity(entries.len());
+    for (entry, end_secs) in entries.into_iter().zip(ends) {
+        let line = entry.line;
+        match to_raw_clip(entry, end_secs, options, resolver, &mut warnings) {
+            Some(clip) => clips.push(clip),
+            None => warnings.push(SetlistWarning {
+                line,
+                msg: "no liver could be assigned, clip skipped \
+                    (use `--liver` to set a default liver)"
+                    .to_string(),
+            }),
+        }
+    }
+
+    let raw = vec![RawInputVideo {
+        video_id: video_id.to_string(),
+        clips,
+    }];
+    // 手書きの入力ファイルと同じ検証を通す
+    let videos: crate::model::AnonymousVideos =
+        serde_json::from_value(serde_json::to_value(raw)?)?;
+
+    warnings.sort_by_key(|warning| warning.line);
+    Ok(ImportSetlistResult { videos, warnings })
+}
+
+/// テキストを行ごとに解析する
+///
+/// タイムスタンプ行として解釈できない行は警告を出して飛ばす
+fn parse_setlist(text: &str) -> (Vec<SetlistEntry>, Vec<SetlistWarning>) {
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw_line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match parse_setlist_line(line, trimmed) {
+            Ok(entry) => entries.push(entry),
+            Err(msg) => warnings.push(SetlistWarning {
+                line,
+                msg: format!("{msg}, skipped: `{trimmed}`"),
+            }),
+        }
+    }
+
+    if !entries.is_sorted_by_key(|entry| entry.start_secs) {
+        warnings.push(SetlistWarning {
+            line: 0,
+            msg: "timestamps are not in ascending order, sorted by start time"
+                .to_string(),
+        });
+        entries.sort_by_key(|entry| entry.start_secs);
+    }
+
+    (entries, warnings)
+}
+
+fn parse_setlist_line(line: usize, text: &str) -> Result<SetlistEntry, String> {
+    let caps = RE_SETLIST_LINE
+        .captures(text)
+        .ok_or_else(|| "no timestamp".to_string())?;
+
+    let start_secs = parse_timestamp(&caps["start"])?;
+    let end_secs = caps
+        .name("end")
+        .map(|end| parse_timestamp(end.as_str()))
+        .transpose()?;
+
+    let (song_title, singers) = split_title_and_singers(&caps["rest"]);
+    if song_title.is_empty() {
+        return Err("empty song title".to_string());
+    }
+
+    Ok(SetlistEntry {
+        line,
+        start_secs,
+        end_secs,
+        song_title,
+        singers,
+    })
+}
+
+/// `M:SS`, `MM:SS`, `H:MM:SS` を秒に変換
+fn parse_timestamp(timestamp: &str) -> Result<u32, String> {
+    let parts = timestamp
+        .split(':')
+        .map(|part| part.parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_e| format!("invalid timestamp `{timestamp}`"))?;
+
+    let (hours, mins, secs) = match parts.as_slice() {
+        [mins, secs] => (0, *mins, *secs),
+        [hours, mins, secs] if *mins < 60 => (*hours, *mins, *secs),
+        _ => return Err(format!("invalid timestamp `{timestamp}`")),
+    };
+    if secs >= 60 {
+        return Err(format!("invalid timestamp `{timestamp}`"));
+    }
+    Ok(hours * 3600 + mins * 60 + secs)
+}
+
+/// `曲名 / 歌唱者1、歌唱者2` を分割
+///
+/// 曲名に`/`を含むことがあるので(`Fate/Zero`など), 区切りは最後の` / `(無ければ`／`)とみなす.
+/// 空白で挟まれていない`/`では分割しない
+fn split_title_and_singers(rest: &str) -> (String, Vec<String>) {
+    let rest = rest.trim();
+    let split_at = [" / ", "／"]
+        .iter()
+        .find_map(|sep| rest.rfind(sep).map(|pos| (pos, sep.len())));
+
+    match split_at {
+        Some((pos, sep_len)) => {
+            let title = rest[..pos].trim().to_string();
+            let singers = RE_SINGER_SEPARATOR
+                .split(&rest[pos + sep_len..])
+                .map(str::trim)
+                .filter(|singer| !singer.is_empty())
+                .map(str::to_string)
+                .collect();
+            (title, singers)
+        }
+        None => (rest.to_string(), Vec::new()),
+    }
+}
+
+/// 各クリップの`endTime`[secs]を決める
+///
+/// - 行に終了時刻があればそれを使う
+/// - 無ければ次の曲の開始時刻から`end_gap_secs`を引いたもの
+/// - 最後の曲は`last_end_time`, 無ければ仮の長さを足して警告する
+fn infer_end_secs(
+    entries: &[SetlistEntry],
+    options: &ImportSetlistOptions,
+    warnings: &mut Vec<SetlistWarning>,
+) -> Vec<u32> {
+    let mut ends = Vec::with_capacity(entries.len());
+
+    for (index, entry) in entries.iter().enumerate() {
+        let end = if let Some(end) = entry.end_secs {
+            end
+        } else if let Some(next) = entries.get(index + 1) {
+            let end = next.start_secs.saturating_sub(options.end_gap_secs);
+            if end <= entry.start_secs {
+                // 間隔が短すぎるときは差し引かない
+                next.start_secs
+            } else {
+                end
+            }
+        } else if let Some(last_end) = &options.last_end_time {
+            last_end.as_secs()
+        } else {
+            warnings.push(SetlistWarning {
+                line: entry.line,
+                msg: format!(
+                    "endTime of the last clip is unknown, assumed {} seconds \
+                    (use `--last-end-time` to set it)",
+                    options.default_last_clip_secs
+                ),
+            });
+            entry.start_secs + options.default_last_clip_secs
+        };
+        ends.push(end);
+    }
+
+    ends
+}
+
+fn to_raw_clip(
+    entry: SetlistEntry,
+    end_secs: u32,
+    options: &ImportSetlistOptions,
+    resolver: &SingerResolver,
+    warnings: &mut Vec<SetlistWarning>,
+) -> Option<RawInputClip> {
+    let mut liver_ids = Vec::new();
+    let mut external_artists_name = Vec::new();
+
+    for singer in entry.singers {
+        match resolver.resolve(&singer) {
+            Ok(liver_id) => liver_ids.push(liver_id),
+            Err(reason) => {
+                warnings.push(SetlistWarning {
+                    line: entry.line,
+                    msg: format!("`{singer}` {reason}, added to externalArtistsName"),
+                });
+                external_artists_name.push(singer);
+            }
+        }
+    }
+
+    if liver_ids.is_empty() {
+        liver_ids.extend(options.default_liver_ids.iter().cloned());
+    }
+    if liver_ids.is_empty() {
+        return None;
+    }
+
+    Some(RawInputClip {
+        song_title: entry.song_title,
+        liver_ids,
+        external_artists_name,
+        start_time: secs_to_duration_string(entry.start_secs),
+        end_time: secs_to_duration_string(end_secs),
+    })
+}
+
+/// ISO 8601 形式に変換. 範囲外の値はそのまま後続の検証でエラーにする
+fn secs_to_duration_string(secs: u32) -> String {
+    match crate::model::Duration::from_chrono_duration(chrono::Duration::seconds(
+        i64::from(secs),
+    )) {
+        Ok(duration) => duration.to_string(),
+        Err(_e) => format!("PT{secs}S"),
+    }
+}
+
+impl SingerResolver {
+    fn from_loaded_livers() -> Self {
+        let livers = artistctl::model::LOADED_LIVER_DATA.clone().into_iter().map(
+            |(id, liver)| {
+                let inner = liver.into_inner();
+                let mut names = vec![inner.ja, inner.jah, inner.en];
+                names.extend(inner.aliases);
+                (id.as_str().to_string(), names)
+            },
+        );
+        Self::new(livers)
+    }
+
+    /// `(ライバーID, 名前のリスト)`から作成. ライバーID自体も名前として扱う
+    fn new(livers: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        use std::collections::hash_map::Entry;
+
+        let mut names: std::collections::HashMap<String, Option<String>> =
+            std::collections::HashMap::new();
+        for (liver_id, liver_names) in livers {
+            for name in std::iter::once(liver_id.clone()).chain(liver_names) {
+                match names.entry(cmn_rs::text::match_key(&name)) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(Some(liver_id.clone()));
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().as_deref() != Some(liver_id.as_str()) {
+                            entry.insert(None);
+                        }
+                    }
+                }
+            }
+        }
+        Self { names }
+    }
+
+    /// `Err`: 解決できなかった理由
+    fn resolve(&self, name: &str) -> Result<String, &'static str> {
+        match self.names.get(&cmn_rs::text::match_key(name)) {
+            Some(Some(liver_id)) => Ok(liver_id.clone()),
+            Some(None) => Err("matches multiple livers"),
+            None => Err("is not a known liver"),
+        }
+    }
+}
+
+impl std::fmt::Display for SetlistWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.msg)
+        } else {
+            write!(f, "line {}: {}", self.line, self.msg)
+        }
+    }
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> ImportSetlistOptions {
+        ImportSetlistOptions {
+            end_gap_secs: 0,
+            last_end_time: None,
+            default_last_clip_secs: 240,
+            default_liver_ids: Vec::new(),
+        }
+    }
+
+    fn test_resolver() -> SingerResolver {
+        SingerResolver::from_loaded_livers()
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1:10"), Ok(70));
+        assert_eq!(parse_timestamp("07:12"), Ok(432));
+        assert_eq!(parse_timestamp("1:02:03"), Ok(3723));
+        assert!(parse_timestamp("1:60").is_err());
+        assert!(parse_timestamp("1:60:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_setlist_line_formats() {
+        let entry = parse_setlist_line(1, "1:10 おねがいダーリン / 栞葉るり").unwrap();
+        assert_eq!(entry.start_secs, 70);
+        assert_eq!(entry.end_secs, None);
+        assert_eq!(entry.song_title, "おねがいダーリン");
+        assert_eq!(entry.singers, vec!["栞葉るり"]);
+
+        let entry = parse_setlist_line(2, "07:12 命に嫌われている。").unwrap();
+        assert_eq!(entry.start_secs, 432);
+        assert_eq!(entry.song_title, "命に嫌われている。");
+        assert!(entry.singers.is_empty());
+
+        let entry =
+            parse_setlist_line(3, "3. 1:02:03 ~ 1:05:00 - Fate/Zero / A、B & C")
+                .unwrap();
+        assert_eq!(entry.start_secs, 3723);
+        assert_eq!(entry.end_secs, Some(3900));
+        assert_eq!(entry.song_title, "Fate/Zero");
+        assert_eq!(entry.singers, vec!["A", "B", "C"]);
+
+        let entry = parse_setlist_line(4, "・12:00　曲名／歌手").unwrap();
+        assert_eq!(entry.start_secs, 720);
+        assert_eq!(entry.song_title, "曲名");
+        assert_eq!(entry.singers, vec!["歌手"]);
+
+        // 空白で挟まれていない`/`は曲名の一部
+        let entry = parse_setlist_line(5, "12:30 Fate/Zero").unwrap();
+        assert_eq!(entry.song_title, "Fate/Zero");
+        assert!(entry.singers.is_empty());
+
+        assert!(parse_setlist_line(5, "セットリスト").is_err());
+        assert!(parse_setlist_line(6, "1:10").is_err());
+    }
+
+    #[test]
+    fn test_parse_setlist_skips_and_sorts() {
+        let text = "セトリ\n\n5:00 曲B\n1:00 曲A\n";
+        let (entries, warnings) = parse_setlist(text);
+        assert_eq!(
+            entries.iter().map(|e| e.start_secs).collect::<Vec<_>>(),
+            vec![60, 300]
+        );
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].line, 1);
+    }
+
+    #[test]
+    fn test_infer_end_secs() {
+        let (entries, _) = parse_setlist("1:00 a\n2:00 - 2:50 b\n3:00 c\n3:01 d\n");
+        let mut warnings = Vec::new();
+        let opts = ImportSetlistOptions {
+            end_gap_secs: 5,
+            ..options()
+        };
+        let ends = infer_end_secs(&entries, &opts, &mut warnings);
+        // 明示した終了時刻, 差し引けないときは次の開始時刻, 最後は仮の長さ
+        assert_eq!(ends, vec![115, 170, 181, 181 + 240]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 4);
+
+        let mut warnings = Vec::new();
+        let opts = ImportSetlistOptions {
+            last_end_time: Some(crate::model::Duration::from_secs_u16(200)),
+            ..options()
+        };
+        let ends = infer_end_secs(&entries[..1], &opts, &mut warnings);
+        assert_eq!(ends, vec![200]);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_singer_resolver() {
+        let resolver = SingerResolver::new(vec![
+            (
+                "a-liver".to_string(),
+                vec!["あ".to_string(), "Same Name".to_string()],
+            ),
+            (
+                "b-liver".to_string(),
+                vec!["い".to_string(), "same name".to_string()],
+            ),
+        ]);
+        assert_eq!(resolver.resolve("あ"), Ok("a-liver".to_string()));
+        assert_eq!(resolver.resolve(" A-Liver "), Ok("a-liver".to_string()));
+        assert!(resolver.resolve("SameName").is_err());
+        assert!(resolver.resolve("う").is_err());
+    }
+
+    #[test]
+    fn test_import_setlist_resolves_livers_and_externals() {
+        let text = "\
+0:10 Song 1 / 田角陸、Someone
+1:00 Song 2 / りっくん & ゆがみん
+2:00 Song 3
+";
+        let opts = ImportSetlistOptions {
+            default_liver_ids: vec!["yudorikku".to_string()],
+            last_end_time: Some(crate::model::Duration::from_secs_u16(180)),
+            ..options()
+        };
+        let result = import_setlist_with_resolver(
+            &crate::model::VideoId::test_id_1(),
+            text,
+            &opts,
+            &test_resolver(),
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&result.videos).unwrap();
+        let clips = json[0]["clips"].as_array().unwrap();
+        assert_eq!(clips.len(), 3);
+        assert_eq!(clips[0]["liverIds"], serde_json::json!(["riku-tazumi"]));
+        assert_eq!(
+            clips[0]["externalArtistsName"],
+            serde_json::json!(["Someone"])
+        );
+        assert_eq!(clips[0]["startTime"], "PT10S");
+        assert_eq!(clips[0]["endTime"], "PT1M");
+        assert_eq!(
+            clips[1]["liverIds"],
+            serde_json::json!(["riku-tazumi", "yugamin"])
+        );
+        assert_eq!(clips[2]["liverIds"], serde_json::json!(["yudorikku"]));
+        assert_eq!(clips[2]["endTime"], "PT3M");
+
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 1);
+        assert!(result.warnings[0].msg.contains("Someone"));
+    }
+
+    #[test]
+    fn test_import_setlist_skips_clip_without_liver() {
+        let text = "0:10 Song 1\n1:00 Song 2 / ゆがみん\n";
+        let result = import_setlist_with_resolver(
+            &crate::model::VideoId::test_id_1(),
+            text,
+            &options(),
+            &test_resolver(),
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&result.videos).unwrap();
+        assert_eq!(json[0]["clips"].as_array().unwrap().len(), 1);
+        assert!(result.warnings.iter().any(|w| w.line == 1));
+    }
+
+    #[test]
+    fn test_import_setlist_errors() {
+        let result = import_setlist_with_resolver(
+            &crate::model::VideoId::test_id_1(),
+            "no timestamps here",
+            &options(),
+            &test_resolver(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::operations::OperationError::Setlist(_))
+        ));
+
+        let result = import_setlist_with_resolver(
+            &crate::model::VideoId::test_id_1(),
+            "0:10 Song 1 / ゆがみん\n1:00 Song 2 / ゆがみん\n0:10 Song 3 / ゆがみん\n",
+            &options(),
+            &test_resolver(),
+        );
+        match result {
+            Err(crate::operations::OperationError::Setlist(msg)) => {
+                assert!(msg.contains("lines 1 and 3"), "{msg}");
+            }
+            _ => panic!("duplicate start times must be rejected"),
+        }
+
+        // 全てのクリップが飛ばされると動画情報として不正
+        let result = import_setlist_with_resolver(
+            &crate::model::VideoId::test_id_1(),
+            "0:10 Song 1",
+            &options(),
+            &test_resolver(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::operations::OperationError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+/// クリップに付与する楽曲IDの候補
+#[derive(Debug, Clone)]
+pub(crate) struct SongIdSuggestion {
+    pub(crate) video_id: crate::model::VideoId,
+    pub(crate) uuid: crate::model::UuidVer4,
+    pub(crate) song_title: String,
+    pub(crate) song_id: songctl::model::SongId,
+    /// 曲名の類似度. 0.0-1.0
+    pub(crate) score: f64,
+}
+
+/// `songId`を持たないクリップについて, 曲名が最も近い楽曲を候補として返す
+///
+/// - 類似度が`min_score`未満のものは返さない
+/// - 動画の公開日時, クリップの開始時間の順
+pub(crate) fn suggest_song_ids(
+    library: &crate::music_file::MusicLibrary,
+    min_score: f64,
+) -> Vec<SongIdSuggestion> {
+    let mut videos = library
+        .iter_files()
+        .flat_map(|file| file.videos().iter())
+        .collect::<Vec<_>>();
+    videos.sort_by(|a, b| {
+        a.get_published_at()
+            .cmp(b.get_published_at())
+            .then_with(|| a.get_video_id().cmp(b.get_video_id()))
+    });
+
+    videos
+        .into_iter()
+        .flat_map(|video| video.clips().map(move |clip| (video, clip)))
+        .filter(|(_, clip)| clip.get_song_id().is_none())
+        .filter_map(|(video, clip)| {
+            let found = songctl::model::LOADED_SONG_DATA
+                .find_best_match(clip.get_song_title())?;
+            (found.get_score() >= min_score).then(|| SongIdSuggestion {
+                video_id: video.get_video_id().clone(),
+                uuid: clip.get_uuid().clone(),
+                song_title: clip.get_song_title().to_string(),
+                song_id: found.get_song_id().clone(),
+                score: found.get_score(),
+            })
+        })
+        .collect()
+}
+
+/// 候補の楽曲IDをクリップに設定する
+///
+/// - ファイルには書き込まない. `library`の内容のみ変更する
+/// - 戻り値は楽曲IDを設定したクリップの数
+///
+/// # Errors
+/// - 編集後の動画が不正なとき
+pub(crate) fn apply_song_id_suggestions(
+    library: &mut crate::music_file::MusicLibrary,
+    suggestions: &[SongIdSuggestion],
+) -> Result<usize, super::OperationError> {
+    let song_ids = suggestions
+        .iter()
+        .map(|s| (&s.uuid, &s.song_id))
+        .collect::<std::collections::HashMap<_, _>>();
+    let mut applied = 0;
+
+    super::edit_month_files::edit_month_files(library, |video| {
+        if !video
+            .clips()
+            .any(|clip| song_ids.contains_key(clip.get_uuid()))
+        {
+            return Ok((video, false));
+        }
+
+        let video_id = video.get_video_id().clone();
+        let (video, changed) = video
+            .try_edit(|_, clips| {
+                clips
+                    .iter_mut()
+                    .filter(|clip| clip.get_song_id().is_none())
+                    .filter_map(|clip| {
+                        let song_id = song_ids.get(clip.get_uuid())?;
+                        Some(clip.set_song_id((*song_id).clone()))
+                    })
+                    .filter(|&changed| changed)
+                    .count()
+            })
+            .map_err(|e| super::OperationError::Edit(format!("{video_id}: {e}")))?;
+
+        applied += changed;
+        Ok((video, changed > 0))
+    })?;
+
+    Ok(applied)
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONTH_FILE: &str = r#"[
+  {
+    "videoId": "11111111111",
+    "title": "Test Video",
+    "channelId": "UCivwPlOp0ojnMPZj5pNOPPA",
+    "publishedAt": "2024-01-01T00:00:00Z",
+    "syncedAt": "2024-01-02T00:00:00Z",
+    "duration": "PT10M",
+    "privacyStatus": "public",
+    "embeddable": true,
+    "clips": [
+      {
+        "songTitle": "ふわふわ時間 ",
+        "liverIds": ["riku-tazumi"],
+        "startTime": "PT1M",
+        "endTime": "PT2M",
+        "uuid": "11786ebd-4b42-428b-81f8-ecf791887321"
+      },
+      {
+        "songTitle": "アイドル",
+        "songId": "idol",
+        "liverIds": ["riku-tazumi"],
+        "startTime": "PT3M",
+        "endTime": "PT4M",
+        "uuid": "11786ebd-4b42-428b-81f8-ecf791887322"
+      },
+      {
+        "songTitle": "全く別の曲",
+        "liverIds": ["riku-tazumi"],
+        "startTime": "PT5M",
+        "endTime": "PT6M",
+        "uuid": "11786ebd-4b42-428b-81f8-ecf791887323"
+      }
+    ]
+  }
+]"#;
+
+    fn library() -> (tempfile::TempDir, crate::music_file::MusicLibrary) {
+        crate::music_file::MusicLibrary::with_videos(
+            serde_json::from_str(MONTH_FILE).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_suggest_song_ids_skips_clips_with_song_id() {
+        let (_root, library) = library();
+
+        let suggestions = suggest_song_ids(&library, 0.8);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].song_title, "ふわふわ時間 ");
+        assert_eq!(suggestions[0].song_id.as_str(), "fuwafuwa-time");
+        assert_eq!(suggestions[0].score, 1.0);
+
+        // しきい値を下げても`songId`を持つクリップは候補にしない
+        let suggestions = suggest_song_ids(&library, 0.0);
+        assert!(suggestions.iter().all(|s| s.song_title != "アイドル"));
+        assert_eq!(suggestions.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_song_id_suggestions() {
+        let (_root, mut library) = library();
+
+        let suggestions = suggest_song_ids(&library, 0.8);
+        assert_eq!(
+            apply_song_id_suggestions(&mut library, &suggestions).unwrap(),
+            1
+        );
+        assert!(suggest_song_ids(&library, 0.8).is_empty());
+
+        let song_ids = library
+            .iter_files()
+            .flat_map(|file| file.videos().iter())
+            .flat_map(|video| video.clips())
+            .map(|clip| clip.get_song_id().map(|id| id.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(song_ids, vec![Some("fuwafuwa-time"), Some("idol"), None]);
+    }
+}
@@ -0,0 +1,117 @@
+/// 入力ファイル(`music/data/input/*.json`)のスキーマ
+pub fn files(
+    ctx: &cmn_rs::json_schema::SchemaContext,
+) -> Vec<cmn_rs::json_schema::SchemaFile> {
+    vec![cmn_rs::json_schema::SchemaFile::new(
+        "music.schema.json",
+        "VideoData",
+        <crate::model::AnonymousVideos as cmn_rs::json_schema::JsonSchema>::json_schema(
+            ctx,
+        ),
+    )]
+}
+
+/// 補完に使うライバーID, 動画タグIDの列挙を定義ファイルから読み込む
+///
+/// # Errors
+/// - 定義ファイルの読み込み, パースに失敗したとき
+pub fn context() -> anyhow::Result<cmn_rs::json_schema::SchemaContext> {
+    let ctx = artistctl::schema::with_liver_ids(Default::default())?;
+    tagctl::schema::with_video_tag_ids(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_committed_schema_is_up_to_date() {
+        let dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tools/schema");
+        let stale = cmn_rs::json_schema::stale_files(&dir, &files(&context().unwrap()))
+            .unwrap();
+        assert!(
+            stale.is_empty(),
+            "schema files are out of date, run `musictl schema`: {stale:?}"
+        );
+    }
+
+    /// テスト用のライバー, タグのIDで列挙を作ったスキーマ
+    fn test_schema() -> serde_json::Value {
+        let ctx = cmn_rs::json_schema::SchemaContext::default()
+            .with_enum(artistctl::schema::LIVER_IDS, ["riku-tazumi", "yugamin"])
+            .with_enum(tagctl::schema::VIDEO_TAG_IDS, ["3d", "3d-debut", "karaoke"]);
+        serde_json::from_str(&files(&ctx)[0].render()).unwrap()
+    }
+
+    #[test]
+    fn test_schema_covers_every_field() {
+        // `volumePercent`と`clippedVideoId`は同じクリップに書けないため, クリップを分ける
+        let instance = cmn_rs::json_schema::round_trip::<crate::model::AnonymousVideos>(
+            r#"[{
+                "videoId": "xxxxxxxxxxx",
+                "uploaderName": "uploader",
+                "videoTags": ["karaoke"],
+                "clips": [
+                    {
+                        "songTitle": "Test Song",
+                        "songId": "idol",
+                        "liverIds": ["riku-tazumi"],
+                        "externalArtistsName": ["Apple Mike"],
+                        "startTime": "PT1M",
+                        "endTime": "PT1M30S",
+                        "volumePercent": 80,
+                        "clipTags": ["3d-debut"]
+                    },
+                    {
+                        "songTitle": "Clipped Song",
+                        "songId": "idol",
+                        "liverIds": ["yugamin"],
+                        "clippedVideoId": "yyyyyyyyyyy",
+                        "startTime": "PT2M",
+                        "endTime": "PT3M",
+                        "clipTags": ["3d"]
+                    }
+                ]
+            }]"#,
+        );
+        let schema = test_schema();
+        assert_eq!(
+            cmn_rs::json_schema::property_name_mismatches(&schema, &instance),
+            vec!["/0/clips/1: `volumePercent` is not in the instance"]
+        );
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &instance),
+            Vec::<String>::new()
+        );
+
+        let mut invalid = instance;
+        invalid[0]["clips"][0]["liverIds"] = serde_json::json!(["unknown"]);
+        invalid[0]["clips"][0]["volumePercent"] = 101.into();
+        invalid[0]["clips"][1]["startTime"] = "1:00".into();
+        assert_eq!(
+            cmn_rs::json_schema::validate(&schema, &invalid),
+            vec![
+                "/0/clips/0/liverIds/0: \"unknown\" is not one of the allowed values",
+                "/0/clips/0/volumePercent: does not match any of the schemas",
+                "/0/clips/1/startTime: \"1:00\" does not match `^PT([0-9]+H)?([0-9]+M)?([0-9]+S)?$`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minimal_input_matches_schema() {
+        let instance = cmn_rs::json_schema::round_trip::<crate::model::AnonymousVideos>(
+            r#"[{
+                "videoId": "xxxxxxxxxxx",
+                "clips": [
+                    {"songTitle": "Test Song", "liverIds": ["riku-tazumi"], "startTime": "PT1M", "endTime": "PT2M"}
+                ]
+            }]"#,
+        );
+        assert_eq!(
+            cmn_rs::json_schema::validate(&test_schema(), &instance),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,328 @@
+mod markdown;
+
+/// 統計の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum StatsFormat {
+    /// Markdown tables for the README
+    Markdown,
+    /// JSON object for the frontend
+    Json,
+}
+
+/// 楽曲情報全体の統計
+///
+/// - 同じ楽曲情報からは常に同じ内容になるよう, 全ての一覧の順序を固定する
+/// - 生成日時などの入力以外に依存する値は持たない
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryStats {
+    pub(crate) video_count: usize,
+    pub(crate) clip_count: usize,
+    /// クリップの長さの合計(秒)
+    pub(crate) total_clip_secs: u64,
+    /// クリップの長さの平均(秒). 小数第1位に丸める
+    pub(crate) average_clip_secs: f64,
+    /// クリップ数の多い順, ライバーIDの順
+    pub(crate) clips_per_liver: Vec<LiverCount>,
+    /// クリップ数の多い順, タグの順
+    pub(crate) clips_per_tag: Vec<TagCount>,
+    /// 年月の順
+    pub(crate) clips_per_month: Vec<MonthCount>,
+    /// クリップ数の多い順, 曲名の順
+    pub(crate) top_songs: Vec<SongCount>,
+    /// クリップを持たない動画. 動画IDの順
+    pub(crate) videos_without_clips: Vec<crate::model::VideoId>,
+    /// `livers.json`にあるがクリップを持たないライバー. ライバーIDの順
+    pub(crate) livers_without_clips: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LiverCount {
+    pub(crate) liver_id: String,
+    pub(crate) clips: usize,
+}
+
+/// タグを持つ動画の数と, タグを持つクリップの数
+///
+/// クリップは動画のタグとクリップのタグを合わせたものを持つとみなす.
+/// 動画は, 動画自体かそのクリップのいずれかがタグを持つときに数える
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagCount {
+    pub(crate) tag: String,
+    pub(crate) videos: usize,
+    pub(crate) clips: usize,
+}
+
+/// 動画の公開日時の年月ごとの数
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonthCount {
+    /// `YYYY-MM`
+    pub(crate) month: String,
+    pub(crate) videos: usize,
+    pub(crate) clips: usize,
+}
+
+/// 楽曲ごとのクリップの数
+///
+/// `songId`を持つクリップは楽曲IDで, 持たないものは前後の空白を除いた曲名でまとめる
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SongCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) song_id: Option<String>,
+    /// まとめたクリップの曲名のうち, 辞書順で最初のもの
+    pub(crate) song_title: String,
+    pub(crate) clips: usize,
+}
+
+/// 楽曲情報の統計を求める
+///
+/// - `top_songs`: 曲ごとの集計に含める件数
+pub(crate) fn compute(
+    library: &crate::music_file::MusicLibrary,
+    top_songs: usize,
+) -> LibraryStats {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let mut video_count = 0;
+    let mut clip_count = 0;
+    let mut total_clip_secs = 0_u64;
+    let mut per_liver: BTreeMap<String, usize> = BTreeMap::new();
+    let mut per_tag: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut per_month: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    // (楽曲ID, 曲名) -> (曲名の一覧, クリップ数)
+    let mut per_song: BTreeMap<(Option<String>, String), (BTreeSet<String>, usize)> =
+        BTreeMap::new();
+    let mut videos_without_clips = Vec::new();
+
+    for video in library.iter_files().flat_map(|file| file.videos().iter()) {
+        let clips = video.clips().collect::<Vec<_>>();
+        video_count += 1;
+        clip_count += clips.len();
+        if clips.is_empty() {
+            videos_without_clips.push(video.get_video_id().clone());
+        }
+
+        // タグ -> タグを持つクリップの数
+        let mut tag_clips: BTreeMap<&str, usize> = BTreeMap::new();
+        let video_tags = video.get_video_tags().to_vec();
+        for clip in &clips {
+            let mut tags = video_tags.clone();
+            tags.extend(clip.get_clip_tags().to_vec());
+            tags.sort_unstable();
+            tags.dedup();
+            for tag in tags {
+                *tag_clips.entry(tag).or_default() += 1;
+            }
+        }
+        for tag in &video_tags {
+            tag_clips.entry(tag).or_default();
+        }
+        for (tag, clips) in tag_clips {
+            let entry = per_tag.entry(tag.to_string()).or_default();
+            entry.0 += 1;
+            entry.1 += clips;
+        }
+        let month = format!("{:04}-{:02}", video.get_year(), video.get_month());
+        let entry = per_month.entry(month).or_default();
+        entry.0 += 1;
+        entry.1 += clips.len();
+
+        for clip in clips {
+            total_clip_secs += u64::from(
+                clip.get_end_time().as_secs() - clip.get_start_time().as_secs(),
+            );
+            for liver_id in clip.get_liver_ids().to_vec() {
+                *per_liver.entry(liver_id.to_string()).or_default() += 1;
+            }
+
+            let title = clip.get_song_title().trim().to_string();
+            let key = match clip.get_song_id() {
+                Some(song_id) => (Some(song_id.to_string()), String::new()),
+                None => (None, title.clone()),
+            };
+            let entry = per_song.entry(key).or_default();
+            entry.0.insert(title);
+            entry.1 += 1;
+        }
+    }
+    videos_without_clips.sort();
+
+    let livers_without_clips = {
+        let mut ids = artistctl::model::LOADED_LIVER_DATA
+            .clone()
+            .into_iter()
+            .map(|(id, _)| id.as_str().to_string())
+            .filter(|id| !per_liver.contains_key(id))
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+
+    let mut clips_per_liver = per_liver
+        .into_iter()
+        .map(|(liver_id, clips)| LiverCount { liver_id, clips })
+        .collect::<Vec<_>>();
+    // BTreeMapの順(ID順)を保ったまま安定ソート
+    clips_per_liver.sort_by_key(|liver| std::cmp::Reverse(liver.clips));
+
+    let mut clips_per_tag = per_tag
+        .into_iter()
+        .map(|(tag, (videos, clips))| TagCount { tag, videos, clips })
+        .collect::<Vec<_>>();
+    clips_per_tag.sort_by_key(|tag| std::cmp::Reverse(tag.clips));
+
+    let clips_per_month = per_month
+        .into_iter()
+        .map(|(month, (videos, clips))| MonthCount {
+            month,
+            videos,
+            clips,
+        })
+        .collect();
+
+    let mut songs = per_song
+        .into_iter()
+        .map(|((song_id, _), (titles, clips))| SongCount {
+            song_id,
+            song_title: titles.into_iter().next().unwrap_or_default(),
+            clips,
+        })
+        .collect::<Vec<_>>();
+    songs.sort_by(|a, b| {
+        b.clips
+            .cmp(&a.clips)
+            .then_with(|| a.song_title.cmp(&b.song_title))
+            .then_with(|| a.song_id.cmp(&b.song_id))
+    });
+    songs.truncate(top_songs);
+
+    let average_clip_secs = if clip_count == 0 {
+        0.0
+    } else {
+        (total_clip_secs as f64 / clip_count as f64 * 10.0).round() / 10.0
+    };
+
+    LibraryStats {
+        video_count,
+        clip_count,
+        total_clip_secs,
+        average_clip_secs,
+        clips_per_liver,
+        clips_per_tag,
+        clips_per_month,
+        top_songs: songs,
+        videos_without_clips,
+        livers_without_clips,
+    }
+}
+
+/// 統計を指定した形式の文字列に変換する
+pub(crate) fn render(
+    format: StatsFormat,
+    stats: &LibraryStats,
+) -> Result<String, serde_json::Error> {
+    Ok(match format {
+        StatsFormat::Markdown => markdown::to_markdown(stats),
+        StatsFormat::Json => serde_json::to_string_pretty(stats)? + "\n",
+    })
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute() {
+        let (_root, library) = crate::music_file::MusicLibrary::self_a_b();
+        let stats = compute(&library, 2);
+
+        assert_eq!(stats.video_count, 2);
+        assert_eq!(stats.clip_count, 6);
+        // 5秒 * 3 + 10秒 * 3
+        assert_eq!(stats.total_clip_secs, 45);
+        assert_eq!(stats.average_clip_secs, 7.5);
+        let liver = |liver_id: &str, clips| LiverCount {
+            liver_id: liver_id.to_string(),
+            clips,
+        };
+        assert_eq!(
+            stats.clips_per_liver,
+            vec![
+                liver("riku-tazumi", 3),
+                liver("yugamin", 2),
+                liver("yudorikku", 1),
+            ]
+        );
+        let tag = |tag: &str| TagCount {
+            tag: tag.to_string(),
+            videos: 1,
+            clips: 3,
+        };
+        assert_eq!(stats.clips_per_tag, vec![tag("3d"), tag("karaoke")]);
+        let month = |month: &str| MonthCount {
+            month: month.to_string(),
+            videos: 1,
+            clips: 3,
+        };
+        assert_eq!(
+            stats.clips_per_month,
+            vec![month("2024-01"), month("2024-02")]
+        );
+        let song = |song_title: &str| SongCount {
+            song_id: None,
+            song_title: song_title.to_string(),
+            clips: 1,
+        };
+        assert_eq!(
+            stats.top_songs,
+            vec![song("Test Song A1"), song("Test Song A2")]
+        );
+        assert_eq!(
+            compute(&library, 10).top_songs,
+            ["A1", "A2", "A3", "B1", "B2", "B3"]
+                .map(|id| song(&format!("Test Song {id}")))
+        );
+        assert!(stats.videos_without_clips.is_empty());
+        assert!(stats.livers_without_clips.is_empty());
+    }
+
+    #[test]
+    fn test_compute_counts_clip_tags() {
+        // 最初のクリップは動画と同じ`karaoke`と, クリップだけの`acoustic`を持つ
+        let video = crate::model::VerifiedVideo::self_a_with_clip_tags(
+            crate::model::VideoTagIds::self_3(),
+        );
+        let (_root, library) = crate::music_file::MusicLibrary::with_videos(vec![
+            video,
+            crate::model::VerifiedVideo::self_b(),
+        ]);
+        let stats = compute(&library, 0);
+
+        let tag = |tag: &str, clips| TagCount {
+            tag: tag.to_string(),
+            videos: 1,
+            clips,
+        };
+        assert_eq!(
+            stats.clips_per_tag,
+            vec![tag("3d", 3), tag("karaoke", 3), tag("acoustic", 1)]
+        );
+    }
+
+    #[test]
+    fn test_render_is_reproducible() {
+        let (_root, library) = crate::music_file::MusicLibrary::self_a_b();
+        for format in [StatsFormat::Markdown, StatsFormat::Json] {
+            assert_eq!(
+                render(format, &compute(&library, 10)).unwrap(),
+                render(format, &compute(&library, 10)).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// READMEに貼るためのMarkdown
+pub(super) fn to_markdown(stats: &super::LibraryStats) -> String {
+    let mut md = String::from("## Library statistics\n\n");
+
+    md.push_str("| Item | Value |\n|:---|---:|\n");
+    md.push_str(&format!("| Videos | {} |\n", stats.video_count));
+    md.push_str(&format!("| Clips | {} |\n", stats.clip_count));
+    md.push_str(&format!(
+        "| Total clip length | {} |\n",
+        secs_to_hms(stats.total_clip_secs)
+    ));
+    md.push_str(&format!(
+        "| Average clip length | {:.1}s |\n",
+        stats.average_clip_secs
+    ));
+
+    md.push_str("\n### Clips per liver\n\n");
+    md.push_str("| Liver | Clips |\n|:---|---:|\n");
+    for liver in &stats.clips_per_liver {
+        md.push_str(&format!(
+            "| {} | {} |\n",
+            liver_label(&liver.liver_id),
+            liver.clips
+        ));
+    }
+
+    md.push_str("\n### Clips per tag\n\n");
+    md.push_str("| Tag | Videos | Clips |\n|:---|---:|---:|\n");
+    for tag in &stats.clips_per_tag {
+        md.push_str(&format!(
+            "| `{}` | {} | {} |\n",
+            tag.tag, tag.videos, tag.clips
+        ));
+    }
+
+    md.push_str("\n### Clips per month\n\n");
+    md.push_str("| Month | Videos | Clips |\n|:---|---:|---:|\n");
+    for month in &stats.clips_per_month {
+        md.push_str(&format!(
+            "| {} | {} | {} |\n",
+            month.month, month.videos, month.clips
+        ));
+    }
+
+    md.push_str("\n### Top songs\n\n");
+    md.push_str("| # | Song | Clips |\n|---:|:---|---:|\n");
+    for (index, song) in stats.top_songs.iter().enumerate() {
+        md.push_str(&format!(
+            "| {} | {} | {} |\n",
+            index + 1,
+            escape_cell(&song.song_title),
+            song.clips
+        ));
+    }
+
+    md.push_str("\n### Videos without clips\n\n");
+    md.push_str(&bullet_list(
+        stats
+            .videos_without_clips
+            .iter()
+            .map(|id| format!("[{id}](https://youtu.be/{id})")),
+    ));
+
+    md.push_str("\n### Livers without clips\n\n");
+    md.push_str(&bullet_list(
+        stats.livers_without_clips.iter().map(|id| liver_label(id)),
+    ));
+
+    md
+}
+
+/// `田角陸 (riku-tazumi)`. 名前が分からないときはIDのみ
+fn liver_label(liver_id: &str) -> String {
+    let name = artistctl::model::LiverIds::new(vec![liver_id])
+        .ok()
+        .and_then(|ids| ids.get_artists_ja_name().into_iter().next());
+    match name {
+        Some(name) => format!("{name} (`{liver_id}`)"),
+        None => format!("`{liver_id}`"),
+    }
+}
+
+fn bullet_list(items: impl Iterator<Item = String>) -> String {
+    let list = items.map(|item| format!("- {item}\n")).collect::<String>();
+    if list.is_empty() {
+        "None\n".to_string()
+    } else {
+        list
+    }
+}
+
+/// 表のセルを壊さないように`|`をエスケープする
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// `1:02:03`
+fn secs_to_hms(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
+
+// MARK: Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let stats = crate::stats::LibraryStats {
+            video_count: 1,
+            clip_count: 2,
+            total_clip_secs: 3725,
+            average_clip_secs: 1862.5,
+            clips_per_liver: vec![crate::stats::LiverCount {
+                liver_id: "riku-tazumi".to_string(),
+                clips: 2,
+            }],
+            clips_per_tag: vec![],
+            clips_per_month: vec![crate::stats::MonthCount {
+                month: "2026-01".to_string(),
+                videos: 1,
+                clips: 2,
+            }],
+            top_songs: vec![crate::stats::SongCount {
+                song_id: None,
+                song_title: "A|B".to_string(),
+                clips: 2,
+            }],
+            videos_without_clips: vec![],
+            livers_without_clips: vec!["yugamin".to_string()],
+        };
+        let md = to_markdown(&stats);
+
+        assert!(md.contains("| Total clip length | 1:02:05 |\n"));
+        assert!(md.contains("| Average clip length | 1862.5s |\n"));
+        assert!(md.contains("| 田角陸 (`riku-tazumi`) | 2 |\n"));
+        assert!(md.contains("| 2026-01 | 1 | 2 |\n"));
+        assert!(md.contains("| 1 | A\\|B | 2 |\n"));
+        assert!(md.contains("### Videos without clips\n\nNone\n"));
+        assert!(md.contains("- ゆがみん (`yugamin`)\n"));
+    }
+}
@@ -9,36 +9,46 @@ impl std::error::Error for AnonymousVideoValidateErrors {}
 /// anonymous videoの検証エラー
 #[derive(Debug)]
 pub(crate) enum AnonymousVideoValidateError {
-    /// 動画idが重複
-    DuplicateVideoId(Vec<crate::model::VideoId>),
+    /// 動画idが重複. 後から現れた方の`videoId`の位置を持つ
+    DuplicateVideoId(cmn_rs::source_diagnostic::SourceDiagnostic),
     /// ファイルの読み込み失敗
     FileReadError {
         path: std::path::PathBuf,
         msg: String,
     },
     /// ファイルの内容が不正
-    InvalidFileContent {
-        path: std::path::PathBuf,
-        msg: String,
-    },
+    InvalidFileContent(cmn_rs::source_diagnostic::SourceDiagnostic),
+}
+
+/// 読み込んだ入力ファイルと, 動画idからその動画の位置を引く索引
+///
+/// `musictl add apply`で動画の詳細情報と合わせたときのエラーを, 入力ファイルの位置付きにするために使う
+#[derive(Debug)]
+pub(crate) struct AnonymousVideoSources {
+    files: Vec<cmn_rs::source_diagnostic::SourceFile>,
+    videos: std::collections::HashMap<crate::model::VideoId, VideoSpans>,
+}
+
+/// 入力ファイルの動画1件の位置
+#[derive(Debug)]
+struct VideoSpans {
+    /// 動画があるファイルの`AnonymousVideoSources::files`での添字
+    file: usize,
+    /// 動画の`videoId`の値の範囲. 無ければ動画全体
+    video_id: cmn_rs::json_span::Span,
+    /// クリップの値の範囲. `AnonymousVideo`のクリップと同じく開始時間順
+    clips: Vec<cmn_rs::json_span::Span>,
 }
 
 impl std::fmt::Display for AnonymousVideoValidateError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::DuplicateVideoId(ids) => {
-                let ids_str = ids
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                write!(f, "duplicate video IDs found: {ids_str}")
-            }
+            Self::DuplicateVideoId(diagnostic) => write!(f, "{diagnostic}"),
             Self::FileReadError { path, msg } => {
                 write!(f, "failed to read file {}: {msg}", path.display())
             }
-            Self::InvalidFileContent { path, msg } => {
-                write!(f, "invalid content in file {}: {msg}", path.display())
+            Self::InvalidFileContent(diagnostic) => {
+                write!(f, "invalid content in file {diagnostic}")
             }
         }
     }
@@ -46,6 +56,21 @@ impl std::fmt::Display for AnonymousVideoValidateError {
 
 impl std::error::Error for AnonymousVideoValidateError {}
 
+impl AnonymousVideoValidateError {
+    fn diagnostic(&self) -> cmn_rs::source_diagnostic::SourceDiagnostic {
+        match self {
+            Self::DuplicateVideoId(diagnostic)
+            | Self::InvalidFileContent(diagnostic) => diagnostic.clone(),
+            Self::FileReadError { path, msg } => {
+                cmn_rs::source_diagnostic::SourceDiagnostic::without_position(
+                    path,
+                    format!("failed to read file: {msg}"),
+                )
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for AnonymousVideoValidateErrors {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -60,6 +85,18 @@ impl std::fmt::Display for AnonymousVideoValidateErrors {
     }
 }
 
+impl AnonymousVideoValidateErrors {
+    /// 各エラーのファイル上の位置
+    pub fn diagnostics(&self) -> cmn_rs::source_diagnostic::SourceDiagnostics {
+        cmn_rs::source_diagnostic::SourceDiagnostics(
+            self.errs
+                .iter()
+                .map(AnonymousVideoValidateError::diagnostic)
+                .collect(),
+        )
+    }
+}
+
 impl From<AnonymousVideoValidateErrors> for Vec<AnonymousVideoValidateError> {
     fn from(value: AnonymousVideoValidateErrors) -> Self {
         value.errs
@@ -70,53 +107,661 @@ impl From<AnonymousVideoValidateErrors> for Vec<AnonymousVideoValidateError> {
 pub fn try_load_anonymous_videos(
     files: &[std::path::PathBuf],
 ) -> Result<crate::model::AnonymousVideos, AnonymousVideoValidateErrors> {
+    try_load_anonymous_videos_with_sources(files).map(|(videos, _)| videos)
+}
+
+/// anonymous videosのファイルを読み込み, 後のエラーの位置を求めるためにファイルの内容も返す
+///
+/// 不正な動画は1ファイルにつき最初の1件でなく, 動画, クリップごとに全て報告する
+pub(crate) fn try_load_anonymous_videos_with_sources(
+    files: &[std::path::PathBuf],
+) -> Result<
+    (crate::model::AnonymousVideos, AnonymousVideoSources),
+    AnonymousVideoValidateErrors,
+> {
     let mut videos = crate::model::AnonymousVideos::new();
-    let mut duplicate_ids: Vec<crate::model::VideoId> = Vec::new();
+    let mut sources = AnonymousVideoSources {
+        files: Vec::new(),
+        videos: std::collections::HashMap::new(),
+    };
     let mut errs: Vec<AnonymousVideoValidateError> = Vec::new();
 
     for file in files {
-        match deserialize_anonymous_from_file(file) {
+        let source = match std::fs::read_to_string(file) {
+            Ok(text) => cmn_rs::source_diagnostic::SourceFile::new(file, text),
+            Err(e) => {
+                errs.push(AnonymousVideoValidateError::FileReadError {
+                    path: file.to_path_buf(),
+                    msg: e.to_string(),
+                });
+                continue;
+            }
+        };
+        match deserialize_anonymous(&source) {
             Ok(anonymous_videos) => {
-                if let Some(ids) = videos.extend(anonymous_videos) {
-                    duplicate_ids.extend(ids);
-                }
+                errs.extend(sources.insert(source));
+                // 重複は`sources`で位置付きで検出しているため, ここでは無視する
+                let _duplicated_ids = videos.extend(anonymous_videos);
             }
-            Err(e) => errs.push(e),
+            Err(file_errs) => errs.extend(file_errs),
         }
     }
 
-    if !duplicate_ids.is_empty() {
-        let duplicate_ids = duplicate_ids
-            .into_iter()
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-        errs.push(AnonymousVideoValidateError::DuplicateVideoId(duplicate_ids));
-    }
-
     if errs.is_empty() {
-        Ok(videos)
+        Ok((videos, sources))
     } else {
         Err(AnonymousVideoValidateErrors { errs })
     }
 }
 
-fn deserialize_anonymous_from_file(
-    file: &std::path::Path,
-) -> Result<crate::model::AnonymousVideos, AnonymousVideoValidateError> {
-    let file_handle = std::fs::File::open(file).map_err(|e| {
-        AnonymousVideoValidateError::FileReadError {
-            path: file.to_path_buf(),
-            msg: e.to_string(),
+/// ファイル全体を読み込めないときは, 動画とクリップを1件ずつ読み込んで不正なものを全て集める
+fn deserialize_anonymous(
+    source: &cmn_rs::source_diagnostic::SourceFile,
+) -> Result<crate::model::AnonymousVideos, Vec<AnonymousVideoValidateError>> {
+    serde_json::from_str(&source.text).map_err(|e| {
+        let each_video = source
+            .root()
+            .and_then(cmn_rs::json_span::JsonNode::as_array)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|video| video_diagnostics(source, video))
+            .map(AnonymousVideoValidateError::InvalidFileContent)
+            .collect::<Vec<_>>();
+        // 動画ごとには正しく, ファイル内で動画idが重複しているときなど
+        if each_video.is_empty() {
+            let whole = source.root().map_or(
+                cmn_rs::json_span::Span {
+                    start: 0,
+                    end: source.text.len(),
+                },
+                |root| root.span,
+            );
+            vec![AnonymousVideoValidateError::InvalidFileContent(
+                locate_serde_error(source, whole, &e),
+            )]
+        } else {
+            each_video
         }
-    })?;
+    })
+}
+
+/// 動画1件のエラー. クリップのエラーはクリップごとに分ける
+fn video_diagnostics(
+    source: &cmn_rs::source_diagnostic::SourceFile,
+    video: &cmn_rs::json_span::JsonNode,
+) -> Vec<cmn_rs::source_diagnostic::SourceDiagnostic> {
+    let Err(video_err) =
+        serde_json::from_str::<crate::model::AnonymousVideo>(source.slice(video.span))
+    else {
+        return Vec::new();
+    };
 
-    let reader = std::io::BufReader::new(file_handle);
+    let clips_node = video.get("clips");
+    let clips = clips_node
+        .and_then(cmn_rs::json_span::JsonNode::as_array)
+        .unwrap_or_default();
+    let mut diagnostics = clips
+        .iter()
+        .filter_map(|clip| {
+            serde_json::from_str::<crate::model::AnonymousClip>(source.slice(clip.span))
+                .err()
+                .map(|e| locate_serde_error(source, clip.span, &e))
+        })
+        .collect::<Vec<_>>();
 
-    serde_json::from_reader(reader).map_err(|e| {
-        AnonymousVideoValidateError::InvalidFileContent {
-            path: file.to_path_buf(),
-            msg: e.to_string(),
+    // クリップの外(動画タグや, クリップ同士の重複など)のエラーも報告する
+    let in_clips = source
+        .serde_error_span(video.span.start, &video_err)
+        .zip(clips_node)
+        .is_some_and(|(span, clips_node)| {
+            clips_node.span.start <= span.start && span.end <= clips_node.span.end
+        });
+    if !in_clips || diagnostics.is_empty() {
+        diagnostics.insert(0, locate_serde_error(source, video.span, &video_err));
+    }
+    diagnostics
+}
+
+/// `serde_json`のエラーを位置付きにする
+///
+/// `within`は`serde_json`に渡した値のファイル内の範囲.
+/// 読み込んだ後の検証のエラーは位置を持たないため, `within`の値を指す
+pub(crate) fn locate_serde_error(
+    source: &cmn_rs::source_diagnostic::SourceFile,
+    within: cmn_rs::json_span::Span,
+    err: &serde_json::Error,
+) -> cmn_rs::source_diagnostic::SourceDiagnostic {
+    let message = cmn_rs::source_diagnostic::serde_error_message(err);
+    let span = match source.serde_error_span(within.start, err) {
+        Some(span) => focus(source, span).unwrap_or(span),
+        None => match focus(source, within) {
+            Some(span) => span,
+            None => {
+                return cmn_rs::source_diagnostic::SourceDiagnostic::without_position(
+                    &source.path,
+                    message,
+                );
+            }
+        },
+    };
+    source.diagnostic(span, message)
+}
+
+/// 動画やクリップ全体の範囲を, 探しやすいように値の範囲にする
+///
+/// - 動画は`videoId`
+/// - クリップは読み込んだ後の検証で不正になる値. 無ければ`songTitle`
+fn focus(
+    source: &cmn_rs::source_diagnostic::SourceFile,
+    span: cmn_rs::json_span::Span,
+) -> Option<cmn_rs::json_span::Span> {
+    let node = source.node_at(span)?;
+    node.get("videoId")
+        .or_else(|| invalid_clip_value(node))
+        .or_else(|| node.get("songTitle"))
+        .map(|node| node.span)
+}
+
+/// クリップを読み込んだ後の検証で不正になる値
+///
+/// 検証と同じ順に調べる. 時間の前後関係は`VerifiedClipError::source_key`と揃えて`startTime`
+fn invalid_clip_value(
+    clip: &cmn_rs::json_span::JsonNode,
+) -> Option<&cmn_rs::json_span::JsonNode> {
+    let invalid_time = |node: &cmn_rs::json_span::JsonNode| {
+        node.as_str()
+            .is_some_and(|s| s.parse::<crate::model::Duration>().is_err())
+    };
+    if let (Some(start_time), Some(end_time)) =
+        (clip.get("startTime"), clip.get("endTime"))
+    {
+        if invalid_time(start_time) {
+            return Some(start_time);
         }
-    })
+        if invalid_time(end_time) {
+            return Some(end_time);
+        }
+        if let (Some(start), Some(end)) = (start_time.as_str(), end_time.as_str())
+            && validate_clip_time_range(start, end).is_err()
+        {
+            return Some(start_time);
+        }
+    }
+
+    let volume_percent = clip
+        .get("volumePercent")
+        .filter(|node| node.value != cmn_rs::json_span::JsonValue::Null)?;
+    clip.get("clippedVideoId")
+        .is_some_and(|node| node.as_str().is_some())
+        .then_some(volume_percent)
+}
+
+impl AnonymousVideoSources {
+    /// 読み込めたファイルを加える. 既に読み込んだ動画と動画idが重複していればエラー
+    fn insert(
+        &mut self,
+        source: cmn_rs::source_diagnostic::SourceFile,
+    ) -> Vec<AnonymousVideoValidateError> {
+        let index = self.files.len();
+        let mut errs = Vec::new();
+        for video in source
+            .root()
+            .and_then(cmn_rs::json_span::JsonNode::as_array)
+            .unwrap_or_default()
+        {
+            let Some(id) = video
+                .get("videoId")
+                .and_then(cmn_rs::json_span::JsonNode::as_str)
+                .and_then(|id| crate::model::VideoId::new(id.to_string()).ok())
+            else {
+                continue;
+            };
+            if let Some(first) = self.locate_video(&id) {
+                let first = first.position.map_or_else(
+                    || first.path.display().to_string(),
+                    |position| {
+                        format!(
+                            "{}:{}:{}",
+                            first.path.display(),
+                            position.line,
+                            position.column
+                        )
+                    },
+                );
+                errs.push(AnonymousVideoValidateError::DuplicateVideoId(
+                    source.diagnostic(
+                        video_id_span(video),
+                        format!("duplicate video ID `{id}`, first defined at {first}"),
+                    ),
+                ));
+            } else {
+                let _prev = self.videos.insert(
+                    id,
+                    VideoSpans {
+                        file: index,
+                        video_id: video_id_span(video),
+                        clips: sorted_clip_spans(video),
+                    },
+                );
+            }
+        }
+        self.files.push(source);
+        errs
+    }
+
+    /// 動画idの動画があるファイルと, その動画の位置
+    fn video(
+        &self,
+        id: &crate::model::VideoId,
+    ) -> Option<(&cmn_rs::source_diagnostic::SourceFile, &VideoSpans)> {
+        let video = self.videos.get(id)?;
+        Some((&self.files[video.file], video))
+    }
+
+    /// 動画idの`videoId`の位置
+    fn locate_video(
+        &self,
+        id: &crate::model::VideoId,
+    ) -> Option<cmn_rs::source_diagnostic::SourceDiagnostic> {
+        let (source, video) = self.video(id)?;
+        Some(source.diagnostic(video.video_id, ""))
+    }
+
+    /// 動画の詳細情報と合わせたときのエラーを, 入力ファイルの位置付きにする
+    ///
+    /// 入力ファイルに無い動画のエラーがあれば`None`
+    pub(crate) fn locate(
+        &self,
+        errs: &crate::model::VerifiedVideoErrors,
+    ) -> Option<cmn_rs::source_diagnostic::SourceDiagnostics> {
+        use crate::model::VerifiedVideoError;
+
+        let mut diagnostics = Vec::new();
+        for err in errs.iter() {
+            match err {
+                VerifiedVideoError::InvalidClip { id, errs } => {
+                    let (source, video) = self.video(id)?;
+                    diagnostics.extend(errs.iter().map(|(index, clip_err)| {
+                        let span = clip_value_span(
+                            source,
+                            video,
+                            *index,
+                            clip_err.source_key(),
+                        );
+                        source.diagnostic(span, clip_err.to_string())
+                    }));
+                }
+                VerifiedVideoError::ClipsOverlap {
+                    id, clip_indices, ..
+                } => {
+                    let (source, video) = self.video(id)?;
+                    let mut indices = clip_indices.clone();
+                    indices.dedup();
+                    diagnostics.extend(indices.into_iter().map(|index| {
+                        let span = clip_value_span(source, video, index, "startTime");
+                        source.diagnostic(span, err.to_string())
+                    }));
+                }
+                VerifiedVideoError::VideoIdMismatch { local: id, .. }
+                | VerifiedVideoError::NoClips(id)
+                | VerifiedVideoError::MissingApiInfo(id) => {
+                    let (source, video) = self.video(id)?;
+                    diagnostics
+                        .push(source.diagnostic(video.video_id, err.to_string()));
+                }
+            }
+        }
+        Some(cmn_rs::source_diagnostic::SourceDiagnostics(diagnostics))
+    }
+}
+
+/// 動画の`videoId`の値の範囲. 無ければ動画全体
+fn video_id_span(video: &cmn_rs::json_span::JsonNode) -> cmn_rs::json_span::Span {
+    video.get("videoId").map_or(video.span, |node| node.span)
+}
+
+/// クリップの値の範囲を`AnonymousVideo::new`と同じ開始時間順に並べたもの
+///
+/// 読み込めたファイルの動画にだけ使うため, 開始時間は必ず読める
+fn sorted_clip_spans(
+    video: &cmn_rs::json_span::JsonNode,
+) -> Vec<cmn_rs::json_span::Span> {
+    let mut clips = video
+        .get("clips")
+        .and_then(cmn_rs::json_span::JsonNode::as_array)
+        .unwrap_or_default()
+        .iter()
+        .map(|clip| {
+            let start_secs = clip
+                .get("startTime")
+                .and_then(cmn_rs::json_span::JsonNode::as_str)
+                .and_then(|s| s.parse::<crate::model::Duration>().ok())
+                .map(|start_time| start_time.as_secs());
+            (start_secs, clip.span)
+        })
+        .collect::<Vec<_>>();
+    clips.sort_by_key(|&(start_secs, _)| start_secs);
+    clips.into_iter().map(|(_, span)| span).collect()
+}
+
+/// 開始時間順で`index`番目のクリップの`key`の値の範囲
+///
+/// 値が無ければクリップ全体, クリップが無ければ`videoId`
+fn clip_value_span(
+    source: &cmn_rs::source_diagnostic::SourceFile,
+    video: &VideoSpans,
+    index: usize,
+    key: &str,
+) -> cmn_rs::json_span::Span {
+    let Some(&clip_span) = video.clips.get(index) else {
+        return video.video_id;
+    };
+    source
+        .node_at(clip_span)
+        .and_then(|clip| clip.get(key))
+        .map_or(clip_span, |node| node.span)
+}
+
+/// 入力ファイルの動画1件を検証する
+///
+/// エディタでファイル全体の読み込みを待たずに動画ごとのエラーを出すために使う
+///
+/// # Errors
+/// - `musictl add`で入力ファイルを読み込むときと同じ条件で不正なとき
+pub fn validate_anonymous_video(value: &serde_json::Value) -> Result<(), String> {
+    <crate::model::AnonymousVideo as serde::Deserialize>::deserialize(value)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// 入力ファイルのクリップ1件を検証する
+///
+/// # Errors
+/// - `musictl add`で入力ファイルを読み込むときと同じ条件で不正なとき
+pub fn validate_anonymous_clip(value: &serde_json::Value) -> Result<(), String> {
+    <crate::model::AnonymousClip as serde::Deserialize>::deserialize(value)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// ISO 8601形式(`PT1M30S`)の時間を秒にする
+///
+/// # Errors
+/// - 形式が不正, または24時間以上のとき
+pub fn parse_duration_secs(s: &str) -> Result<u32, &'static str> {
+    s.parse::<crate::model::Duration>()
+        .map(|duration| duration.as_secs())
+}
+
+/// クリップの開始, 終了時間を検証する
+///
+/// # Errors
+/// - どちらかの形式が不正なとき
+/// - `start` >= `end`のとき
+pub fn validate_clip_time_range(start: &str, end: &str) -> Result<(), String> {
+    let start = start
+        .parse::<crate::model::Duration>()
+        .map_err(|e| format!("invalid startTime: {e}"))?;
+    let end = end
+        .parse::<crate::model::Duration>()
+        .map_err(|e| format!("invalid endTime: {e}"))?;
+    crate::model::clip::validate_start_end_times(&start, &end)
+}
+
+/// 動画idの形式を検証する
+///
+/// # Errors
+/// - `a-z`, `A-Z`, `0-9`, `-`, `_` の11文字でないとき
+pub fn validate_video_id(id: &str) -> Result<(), &'static str> {
+    crate::model::VideoId::new(id.to_string()).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    const INPUT: &str = r#"[
+  {
+    "videoId": "ZeFvqdvutb4",
+    "videoTags": ["karaoke"],
+    "clips": [
+      {"songTitle": "a", "liverIds": ["riku-tazumi"], "startTime": "PT2M", "endTime": "PT1M"},
+      {"songTitle": "b", "liverIds": ["ghost"], "startTime": "PT3M", "endTime": "PT4M"},
+      {"songTitle": "c", "liverIds": ["riku-tazumi"], "startTime": "PT5M", "endTime": "PT6M"}
+    ]
+  },
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "videoTags": ["unknown-tag"],
+    "clips": [
+      {"songTitle": "d", "liverIds": ["yugamin"], "startTime": "PT1M", "endTime": "PT2M"}
+    ]
+  }
+]
+"#;
+
+    /// 入力ファイルが全て正しくなるように`INPUT`を直したもの
+    fn valid_input() -> String {
+        INPUT
+            .replace(
+                r#""PT2M", "endTime": "PT1M""#,
+                r#""PT1M", "endTime": "PT2M""#,
+            )
+            .replace("ghost", "yugamin")
+            .replace("unknown-tag", "3d")
+    }
+
+    fn write_inputs(
+        dir: &std::path::Path,
+        files: &[(&str, &str)],
+    ) -> Vec<std::path::PathBuf> {
+        files
+            .iter()
+            .map(|(name, text)| {
+                let path = dir.join(name);
+                std::fs::write(&path, text).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_load_reports_every_invalid_clip_and_video() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_inputs(dir.path(), &[("a.json", INPUT)]);
+
+        let errs = super::try_load_anonymous_videos(&paths).unwrap_err();
+        let positions = errs
+            .diagnostics()
+            .0
+            .into_iter()
+            .map(|d| {
+                let position = d.position.unwrap();
+                (position.line, position.column)
+            })
+            .collect::<Vec<_>>();
+        // クリップ`a`の開始時間, クリップ`b`のライバー, 2件目の動画タグ
+        assert_eq!(positions, vec![(6, 68), (7, 38), (13, 18)]);
+    }
+
+    #[test]
+    fn test_locate_clip_errors_at_offending_value() {
+        let input = valid_input()
+            .replace(r#""endTime": "PT4M""#, r#""endTime": "4分""#)
+            .replace(
+                r#""startTime": "PT5M""#,
+                r#""volumePercent": 50, "clippedVideoId": "cFc9Ywpk0QU", "startTime": "PT5M""#,
+            );
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_inputs(dir.path(), &[("a.json", &input)]);
+
+        let errs = super::try_load_anonymous_videos(&paths).unwrap_err();
+        let positions = errs
+            .diagnostics()
+            .0
+            .into_iter()
+            .map(|d| {
+                let position = d.position.unwrap();
+                (position.line, position.column)
+            })
+            .collect::<Vec<_>>();
+        // クリップ`b`の終了時間, クリップ`c`の音量
+        assert_eq!(positions, vec![(7, 83), (8, 72)]);
+    }
+
+    #[test]
+    fn test_duplicate_video_id_across_files() {
+        let valid = valid_input();
+        let first_video =
+            valid[..valid.find("  },\n  {").unwrap()].to_string() + "  }\n]";
+        let dir = tempfile::tempdir().unwrap();
+        let paths =
+            write_inputs(dir.path(), &[("a.json", &valid), ("b.json", &first_video)]);
+
+        let errs = super::try_load_anonymous_videos(&paths).unwrap_err();
+        let diagnostics = errs.diagnostics().0;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, paths[1]);
+        assert_eq!(diagnostics[0].position.as_ref().unwrap().line, 3);
+        assert_eq!(
+            diagnostics[0].message,
+            format!(
+                "duplicate video ID `ZeFvqdvutb4`, first defined at {}:3:16",
+                paths[0].display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_locate_verified_video_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_inputs(dir.path(), &[("a.json", &valid_input())]);
+        let (_, sources) =
+            super::try_load_anonymous_videos_with_sources(&paths).unwrap();
+
+        let id = crate::model::VideoId::new("ZeFvqdvutb4".to_string()).unwrap();
+        let errs = crate::model::VerifiedVideoErrors::from(vec![
+            crate::model::VerifiedVideoError::InvalidClip {
+                id: id.clone(),
+                errs: vec![(
+                    2,
+                    crate::model::VerifiedClipError::TimeExceedsVideoDuration {
+                        song_title: "c".to_string(),
+                        start_time: crate::model::Duration::from_secs_u16(300),
+                        end_time: crate::model::Duration::from_secs_u16(360),
+                        video_duration: crate::model::Duration::from_secs_u16(330),
+                    },
+                )],
+            },
+            crate::model::VerifiedVideoError::MissingApiInfo(id),
+        ]);
+        let positions = sources
+            .locate(&errs)
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|d| {
+                let position = d.position.unwrap();
+                (position.line, position.column)
+            })
+            .collect::<Vec<_>>();
+        // クリップ`c`の`endTime`, 動画の`videoId`
+        assert_eq!(positions, vec![(8, 87), (3, 16)]);
+
+        let unknown = crate::model::VerifiedVideoErrors::from(vec![
+            crate::model::VerifiedVideoError::NoClips(
+                crate::model::VideoId::test_id_2(),
+            ),
+        ]);
+        assert!(sources.locate(&unknown).is_none());
+    }
+
+    #[test]
+    fn test_locate_uses_clip_order_instead_of_song_title() {
+        // 同じ曲名のクリップが, 開始時間の逆順に並んでいる
+        let input = r#"[
+  {
+    "videoId": "ZeFvqdvutb4",
+    "clips": [
+      {"songTitle": "a", "liverIds": ["riku-tazumi"], "startTime": "PT5M", "endTime": "PT6M"},
+      {"songTitle": "a", "liverIds": ["riku-tazumi"], "startTime": "PT1M", "endTime": "PT2M"}
+    ]
+  }
+]
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_inputs(dir.path(), &[("a.json", input)]);
+        let (_, sources) =
+            super::try_load_anonymous_videos_with_sources(&paths).unwrap();
+
+        let id = crate::model::VideoId::new("ZeFvqdvutb4".to_string()).unwrap();
+        let errs = crate::model::VerifiedVideoErrors::from(vec![
+            crate::model::VerifiedVideoError::InvalidClip {
+                id: id.clone(),
+                errs: vec![(
+                    0,
+                    crate::model::VerifiedClipError::VolumePercentOnClippedVideo {
+                        song_title: "a".to_string(),
+                        msg: "volumePercent".to_string(),
+                    },
+                )],
+            },
+            crate::model::VerifiedVideoError::ClipsOverlap {
+                id,
+                clips_title: vec!["a".to_string(), "a".to_string()],
+                clip_indices: vec![0, 1],
+            },
+        ]);
+        let positions = sources
+            .locate(&errs)
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|d| {
+                let position = d.position.unwrap();
+                (position.line, position.column)
+            })
+            .collect::<Vec<_>>();
+        // 開始時間が先の2件目のクリップ全体, 各クリップの`startTime`
+        assert_eq!(positions, vec![(6, 7), (6, 68), (5, 68)]);
+    }
+
+    #[test]
+    fn test_validate_anonymous_clip() {
+        let clip = serde_json::json!(crate::model::AnonymousClip::self_a_1());
+        assert_eq!(super::validate_anonymous_clip(&clip), Ok(()));
+
+        let mut reversed = clip.clone();
+        reversed["startTime"] = clip["endTime"].clone();
+        reversed["endTime"] = clip["startTime"].clone();
+        assert!(super::validate_anonymous_clip(&reversed).is_err());
+
+        let mut unknown_liver = clip;
+        unknown_liver["liverIds"] = serde_json::json!(["unknown-liver"]);
+        let err = super::validate_anonymous_clip(&unknown_liver).unwrap_err();
+        assert!(err.contains("invalid liver: unknown-liver"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_anonymous_video() {
+        let video = serde_json::json!({
+            "videoId": crate::model::VideoId::test_id_1().to_string(),
+            "videoTags": ["karaoke"],
+            "clips": [crate::model::AnonymousClip::self_a_1()],
+        });
+        assert_eq!(super::validate_anonymous_video(&video), Ok(()));
+
+        let mut no_clips = video;
+        no_clips["clips"] = serde_json::json!([]);
+        assert!(super::validate_anonymous_video(&no_clips).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_secs_and_video_id() {
+        assert_eq!(super::parse_duration_secs("PT1M30S"), Ok(90));
+        assert!(super::parse_duration_secs("1:30").is_err());
+        assert_eq!(super::validate_clip_time_range("PT10S", "PT1M"), Ok(()));
+        assert!(super::validate_clip_time_range("PT1M", "PT10S").is_err());
+        assert!(super::validate_clip_time_range("PT1M", "1:30").is_err());
+        assert_eq!(super::validate_video_id("ZeFvqdvutb4"), Ok(()));
+        assert!(super::validate_video_id("short").is_err());
+    }
 }
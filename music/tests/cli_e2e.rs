@@ -76,6 +76,32 @@ fn test_add_validate_markdown_e2e() {
         .stdout(contains("# Music Data Summary"));
 }
 
+#[test]
+fn test_add_validate_reports_error_positions() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input_path = tmp.path().join("input/add.json");
+    write_text_file(
+        &input_path,
+        &ANONYMOUS_INPUT_JSON.replace("riku-tazumi", "ghost"),
+    );
+    let input = input_path.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.args(["add", "validate", "--input", &input, "--quiet"])
+        .args(["--diagnostic-format", "human"]);
+    cmd.assert()
+        .failure()
+        .stderr(contains(format!("--> {input}:8:21")))
+        .stderr(contains("8 |         \"liverIds\": [\"ghost\"],"));
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.args(["add", "validate", "--input", &input, "--quiet"])
+        .env("GITHUB_ACTIONS", "true");
+    cmd.assert()
+        .failure()
+        .stderr(contains(format!("::error file={input},line=8,col=21,")));
+}
+
 #[test]
 fn test_update_validate_e2e() {
     let tmp = tempfile::tempdir().unwrap();
@@ -192,3 +218,469 @@ fn test_hash_inputs_prints_sha256() {
     assert_eq!(hash.len(), 64);
     assert!(hash.bytes().all(|byte| byte.is_ascii_hexdigit()));
 }
+
+#[test]
+fn test_util_history_prints_timeline_and_compacts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    write_text_file(
+        &music_root.join("history.jsonl"),
+        concat!(
+            r#"{"videoId":"cFc9Ywpk0QU","recordedAt":"2026-02-01T00:00:00Z","changes":[{"field":"embeddable","old":true,"new":false}]}"#,
+            "\n",
+            r#"{"videoId":"cFc9Ywpk0QU","recordedAt":"2026-03-01T00:00:00Z","changes":[{"field":"privacyStatus","old":"public","new":"unlisted"}]}"#,
+            "\n",
+        ),
+    );
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("util")
+        .arg("history")
+        .arg("cFc9Ywpk0QU")
+        .arg("--music-root-dir")
+        .arg(music_root.to_string_lossy().to_string());
+    cmd.assert()
+        .success()
+        .stdout(contains("2026-02-01T00:00:00Z  embeddable: true -> false"))
+        .stdout(contains(
+            "2026-03-01T00:00:00Z  privacyStatus: public -> unlisted",
+        ));
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("util")
+        .arg("history")
+        .arg("--compact-before")
+        .arg("2027-01-01")
+        .arg("--music-root-dir")
+        .arg(music_root.to_string_lossy().to_string());
+    cmd.assert()
+        .success()
+        .stdout(contains("Compacted 2 history entries into 1"));
+
+    let history = std::fs::read_to_string(music_root.join("history.jsonl")).unwrap();
+    assert_eq!(history.lines().count(), 1);
+}
+
+#[test]
+fn test_util_import_setlist_writes_input_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let setlist_path = tmp.path().join("setlist.txt");
+    let output_path = tmp.path().join("input/setlist.json");
+    write_text_file(
+        &setlist_path,
+        "セットリスト\n1:10 おねがいダーリン / 田角陸\n07:12 命に嫌われている。\n",
+    );
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("util")
+        .arg("import-setlist")
+        .arg("--video-id")
+        .arg("ZeFvqdvutb4")
+        .arg("--file")
+        .arg(setlist_path.to_string_lossy().to_string())
+        .arg("--output")
+        .arg(output_path.to_string_lossy().to_string())
+        .arg("--liver")
+        .arg("yugamin")
+        .arg("--last-end-time")
+        .arg("PT11M34S");
+
+    cmd.assert()
+        .success()
+        .stdout(contains("line 1: no timestamp"));
+
+    let json = read_json(&output_path);
+    let clips = json[0]["clips"].as_array().unwrap();
+    assert_eq!(json[0]["videoId"], "ZeFvqdvutb4");
+    assert_eq!(clips.len(), 2);
+    assert_eq!(clips[0]["songTitle"], "おねがいダーリン");
+    assert_eq!(clips[0]["liverIds"][0], "riku-tazumi");
+    assert_eq!(clips[1]["liverIds"][0], "yugamin");
+    assert_eq!(clips[0]["startTime"], "PT1M10S");
+    assert_eq!(clips[0]["endTime"], "PT7M12S");
+    assert_eq!(clips[1]["endTime"], "PT11M34S");
+}
+
+#[test]
+fn test_export_formats_and_filters() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    let music_root = music_root.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("export")
+        .arg("--format")
+        .arg("setlist")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().success().stdout(
+        "# Test Karaoke Stream (https://youtu.be/cFc9Ywpk0QU)\n\
+         00:03:02 fuwafuwa time\n",
+    );
+
+    let output_path = tmp.path().join("clips.m3u");
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("export")
+        .arg("--format")
+        .arg("m3u")
+        .arg("--tag")
+        .arg("karaoke")
+        .arg("--from")
+        .arg("2026-01-19")
+        .arg("--output")
+        .arg(output_path.to_string_lossy().to_string())
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().success();
+    let m3u = std::fs::read_to_string(&output_path).unwrap();
+    assert!(
+        m3u.contains("https://www.youtube.com/embed/cFc9Ywpk0QU?start=182&end=416")
+    );
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("export")
+        .arg("--format")
+        .arg("jsonl")
+        .arg("--liver")
+        .arg("yugamin")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().success().stdout("");
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("export")
+        .arg("--format")
+        .arg("csv")
+        .arg("--liver")
+        .arg("no-such-liver")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_edit_dry_run_then_apply() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    let month_path = music_root.join("2026/01.json");
+    write_text_file(&month_path, MONTHLY_FILE_JSON);
+    let music_root = music_root.to_string_lossy().to_string();
+
+    // 差分は書き込まれる形式(最新のスキーマバージョン)のファイルに対して取る
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("util")
+        .arg("migrate")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().success();
+    let migrated = std::fs::read_to_string(&month_path).unwrap();
+
+    let edit_args = [
+        "--where",
+        "liver=riku-tazumi",
+        "--where",
+        "title=^fuwafuwa",
+        "--set-song-title",
+        "ふわふわ時間",
+        "--add-tag",
+        "acoustic",
+    ];
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("edit")
+        .args(edit_args)
+        .arg("--dry-run")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("-          \"songTitle\": \"fuwafuwa time\",\n"))
+        .stdout(contains("+          \"songTitle\": \"ふわふわ時間\",\n"))
+        .stdout(contains(
+            "Dry run: 1 clips in 1 videos would be edited (1 files).",
+        ));
+    assert_eq!(std::fs::read_to_string(&month_path).unwrap(), migrated);
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("edit")
+        .args(edit_args)
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("Edited 1 clips in 1 videos (1 files)."));
+    let content = std::fs::read_to_string(&month_path).unwrap();
+    assert!(content.contains("\"songTitle\": \"ふわふわ時間\""));
+    assert!(content.contains("\"acoustic\""));
+
+    // 一致するクリップが無ければ何も書き換えない
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("edit")
+        .arg("--where")
+        .arg("liver=yugamin")
+        .arg("--remove-tag")
+        .arg("karaoke")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("Edited 0 clips in 0 videos (0 files)."));
+    assert_eq!(std::fs::read_to_string(&month_path).unwrap(), content);
+
+    // 操作の指定が無いときはエラー
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("edit")
+        .arg("--where")
+        .arg("liver=riku-tazumi")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_lint_directory_config_and_formats() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    let music_root_str = music_root.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("lint")
+        .arg("--music-root-dir")
+        .arg(&music_root_str);
+    cmd.assert().success().stdout("0 errors, 0 warnings\n");
+
+    // ルートで閾値を上げ, 年のディレクトリでエラーにする
+    write_text_file(
+        &music_root.join("lint.json"),
+        r#"{"shortClipMinSecs": 300}"#,
+    );
+    write_text_file(
+        &music_root.join("2026/lint.json"),
+        r#"{"rules": {"short-clip": "error"}}"#,
+    );
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("--music-root-dir")
+        .arg(&music_root_str);
+    let output = cmd.assert().failure().get_output().stdout.clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["errorCount"], 1);
+    assert_eq!(report["diagnostics"][0]["rule"], "short-clip");
+    assert_eq!(
+        report["diagnostics"][0]["uuid"],
+        "11786ebd-4b42-428b-81f8-ecf791887326"
+    );
+}
+
+#[test]
+fn test_stats_markdown_and_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    let music_root = music_root.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("stats")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("| Clips | 1 |"))
+        .stdout(contains("| `karaoke` | 1 | 1 |"))
+        .stdout(contains("| 2026-01 | 1 | 1 |"));
+
+    let output_path = tmp.path().join("stats.json");
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("stats")
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(output_path.to_string_lossy().to_string())
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert().success();
+    let stats = read_json(&output_path);
+    assert_eq!(stats["clipCount"], 1);
+    assert_eq!(stats["averageClipSecs"], 234.0);
+    assert_eq!(stats["clipsPerLiver"][0]["liverId"], "riku-tazumi");
+    assert_eq!(
+        stats["liversWithoutClips"],
+        serde_json::json!(["yudorikku", "yugamin"])
+    );
+}
+
+#[test]
+fn test_diff_markdown_and_json() {
+    use predicates::prelude::PredicateBooleanExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let before = tmp.path().join("before");
+    let after = tmp.path().join("after");
+    write_text_file(&before.join("2026/01.json"), MONTHLY_FILE_JSON);
+    write_text_file(
+        &after.join("2026/01.json"),
+        &MONTHLY_FILE_JSON
+            .replace("\"fuwafuwa time\"", "\"ふわふわ時間\"")
+            .replace("PT6M56S", "PT6M50S")
+            .replace("2026-04-22T01:57:28Z", "2026-05-01T00:00:00Z"),
+    );
+    let before = before.to_string_lossy().to_string();
+    let after = after.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("diff")
+        .arg(&before)
+        .arg(&after)
+        .arg("--ignore-field")
+        .arg("syncedAt");
+    cmd.assert()
+        .success()
+        .stdout(contains("| Modified | 1 | 1 |"))
+        .stdout(contains(
+            "| ふわふわ時間 | `songTitle` | `\"fuwafuwa time\"` | `\"ふわふわ時間\"` |",
+        ))
+        .stdout(contains(
+            "| ふわふわ時間 | `endTime` | `\"PT6M56S\"` | `\"PT6M50S\"` |",
+        ))
+        .stdout(contains("syncedAt").not());
+
+    let output_path = tmp.path().join("diff.json");
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("diff")
+        .arg(&before)
+        .arg(&after)
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(output_path.to_string_lossy().to_string());
+    cmd.assert().success();
+    let diff = read_json(&output_path);
+    let video = &diff["modifiedVideos"][0];
+    assert_eq!(video["videoId"], "cFc9Ywpk0QU");
+    assert_eq!(video["changes"][0]["field"], "syncedAt");
+    assert_eq!(
+        video["modifiedClips"][0]["uuid"],
+        "11786ebd-4b42-428b-81f8-ecf791887326"
+    );
+    assert_eq!(diff["addedVideos"], serde_json::json!([]));
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet").arg("diff").arg(&before).arg(&before);
+    cmd.assert().success().stdout(contains("No changes."));
+}
+
+#[test]
+fn test_update_apply_takes_music_root_lock() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(&music_root.join("2026/01.json"), MONTHLY_FILE_JSON);
+    // 前回の実行で残ったロックファイルは, 誰も保持していなければ妨げにならない
+    write_text_file(&music_root.join(".musictl.lock"), "pid 1");
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("update")
+        .arg("apply")
+        .arg("--music-root-dir")
+        .arg(music_root.to_string_lossy().to_string());
+    cmd.assert().success();
+
+    // 正規化した形式で書き直される
+    let written = std::fs::read_to_string(music_root.join("2026/01.json")).unwrap();
+    assert!(written.contains(r#""duration": "PT1H","#));
+    let mut entries = std::fs::read_dir(music_root.join("2026"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, vec!["01.json"]);
+}
+
+#[test]
+fn test_update_apply_adds_ancestor_tags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    write_text_file(
+        &music_root.join("2026/01.json"),
+        &MONTHLY_FILE_JSON.replace(r#"["karaoke"]"#, r#"["3d-debut"]"#),
+    );
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("update")
+        .arg("apply")
+        .arg("--music-root-dir")
+        .arg(music_root.to_string_lossy().to_string());
+    cmd.assert().success();
+
+    let month = read_json(&music_root.join("2026/01.json"));
+    assert_eq!(
+        month["videos"][0]["videoTags"],
+        serde_json::json!(["3d", "3d-debut"])
+    );
+}
+
+#[test]
+fn test_util_migrate_check_and_rewrite() {
+    let tmp = tempfile::tempdir().unwrap();
+    let music_root = tmp.path().join("music");
+    let month_path = music_root.join("2026/01.json");
+    write_text_file(&month_path, MONTHLY_FILE_JSON);
+    let music_root = music_root.to_string_lossy().to_string();
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("util")
+        .arg("migrate")
+        .arg("--check")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .failure()
+        .stdout(contains("01.json: schemaVersion 1 -> 2"));
+    assert_eq!(
+        std::fs::read_to_string(&month_path).unwrap(),
+        MONTHLY_FILE_JSON
+    );
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("util")
+        .arg("migrate")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("Migrated 1 month files to schemaVersion 2."));
+    let migrated = read_json(&month_path);
+    assert_eq!(migrated["schemaVersion"], 2);
+    assert_eq!(migrated["videos"][0]["videoId"], "cFc9Ywpk0QU");
+
+    let mut cmd = Command::cargo_bin("musictl").unwrap();
+    cmd.arg("--quiet")
+        .arg("util")
+        .arg("migrate")
+        .arg("--check")
+        .arg("--music-root-dir")
+        .arg(&music_root);
+    cmd.assert()
+        .success()
+        .stdout(contains("All month files are in schemaVersion 2."));
+}
@@ -9,6 +9,9 @@ pub(crate) struct SearchRequest {
     pub(crate) sort: SortSpec,
     pub(crate) page: PageSpec,
     pub(crate) total_mode: TotalMode,
+    /// 省略時は blocked なクリップを除外する
+    #[serde(default)]
+    pub(crate) include_blocked: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -39,9 +42,15 @@ pub(crate) enum QueryNode {
 pub(crate) enum TermNode {
     ArtistAnyIn { values: Vec<String> },
     TagAnyIn { values: Vec<String> },
+    TagDescendantsOf { values: Vec<String> },
+    ClipTagAnyIn { values: Vec<String> },
     ChannelAnyIn { values: Vec<String> },
+    GroupAnyIn { values: Vec<String> },
     IsUnlistedEq { value: bool },
     EmbeddableEq { value: bool },
+    IsSelfUploadEq { value: bool },
+    IsOfficialChannelEq { value: bool },
+    HasGraduatedArtistEq { value: bool },
     PublishedAtRange { range: DateRange },
 }
 
@@ -96,6 +105,8 @@ pub(crate) enum QueryWarning {
     UnknownArtistId { value: String },
     UnknownTagId { value: String },
     UnknownChannelId { value: String },
+    UnknownGroupId { value: String },
+    BlockedTagId { value: String },
 }
 
 impl SearchRequest {
@@ -127,6 +138,7 @@ impl SearchRequest {
                     .transpose()?,
             },
             total_mode: self.total_mode.into_engine(),
+            include_blocked: self.include_blocked,
         })
     }
 }
@@ -159,15 +171,33 @@ impl TermNode {
             Self::TagAnyIn { values } => {
                 engine::api::query::input::TermNode::TagAnyIn { values }
             }
+            Self::TagDescendantsOf { values } => {
+                engine::api::query::input::TermNode::TagDescendantsOf { values }
+            }
+            Self::ClipTagAnyIn { values } => {
+                engine::api::query::input::TermNode::ClipTagAnyIn { values }
+            }
             Self::ChannelAnyIn { values } => {
                 engine::api::query::input::TermNode::ChannelAnyIn { values }
             }
+            Self::GroupAnyIn { values } => {
+                engine::api::query::input::TermNode::GroupAnyIn { values }
+            }
             Self::IsUnlistedEq { value } => {
                 engine::api::query::input::TermNode::IsUnlistedEq { value }
             }
             Self::EmbeddableEq { value } => {
                 engine::api::query::input::TermNode::EmbeddableEq { value }
             }
+            Self::IsSelfUploadEq { value } => {
+                engine::api::query::input::TermNode::IsSelfUploadEq { value }
+            }
+            Self::IsOfficialChannelEq { value } => {
+                engine::api::query::input::TermNode::IsOfficialChannelEq { value }
+            }
+            Self::HasGraduatedArtistEq { value } => {
+                engine::api::query::input::TermNode::HasGraduatedArtistEq { value }
+            }
             Self::PublishedAtRange { range } => {
                 engine::api::query::input::TermNode::PublishedAtRange(
                     range.into_engine(),
@@ -284,6 +314,12 @@ impl QueryWarning {
             engine::api::response::QueryWarning::UnknownChannelId { value } => {
                 Self::UnknownChannelId { value }
             }
+            engine::api::response::QueryWarning::UnknownGroupId { value } => {
+                Self::UnknownGroupId { value }
+            }
+            engine::api::response::QueryWarning::BlockedTagId { value } => {
+                Self::BlockedTagId { value }
+            }
         }
     }
 }
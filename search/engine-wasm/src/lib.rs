@@ -115,6 +115,9 @@ mod tests {
                 artists,
                 tags,
             },
+            tag_hierarchy: index_core::schema::TagHierarchy {
+                parents: vec![None, None],
+            },
             columns: index_core::schema::ColumnStore {
                 clip_ids: vec![0, 1, 2, 3, 4],
                 video_ids: vec![0, 1, 2, 3, 4],
@@ -128,6 +131,9 @@ mod tests {
                 channel_ids: vec![0, 0, 1, 1, 0],
                 is_unlisteds: vec![false, false, false, true, false],
                 embeddables: vec![true, true, true, false, true],
+                is_self_uploads: vec![true, false, true, true, false],
+                is_official_channels: vec![false, false, false, false, false],
+                has_graduated_artists: vec![false, true, true, true, false],
                 artist_id_lists: index_core::util::U32ListColumn::build(&[
                     vec![0],
                     vec![1],
@@ -152,12 +158,24 @@ mod tests {
                     (0, vec![0, 1]),
                     (1, vec![2, 4]),
                 ]),
+                clip_tag_docs: std::collections::HashMap::from([(1, vec![4])]),
                 channel_docs: std::collections::HashMap::from([
                     (0, vec![0, 1, 4]),
                     (1, vec![2, 3]),
                 ]),
                 is_unlisted_docs: [vec![0, 1, 2, 4], vec![3]],
                 embeddable_docs: [vec![3], vec![0, 1, 2, 4]],
+                is_self_upload_docs: [vec![1, 4], vec![0, 2, 3]],
+                is_official_channel_docs: [vec![0, 1, 2, 3, 4], vec![]],
+                has_graduated_artist_docs: [vec![0, 4], vec![1, 2, 3]],
+            },
+            blocked: index_core::schema::BlockedIndex::default(),
+            groups: index_core::schema::GroupIndex {
+                groups: index_core::util::BiMap::from_ordered_strings(vec![
+                    "group-b".to_string(),
+                ])
+                .unwrap(),
+                members: vec![vec![1]],
             },
             sort_indexes: index_core::schema::SortIndexes {
                 published_at: index_core::schema::SortIndex::new(vec![0, 1, 2, 3, 4]),
@@ -300,6 +318,96 @@ mod tests {
         assert!(json["next_cursor"].is_string());
     }
 
+    #[test]
+    fn test_group_any_in_term_and_unknown_group_warning() {
+        let engine = sample_engine();
+        let request = serde_json::from_value::<api::SearchRequest>(serde_json::json!({
+            "query": {
+                "type": "term",
+                "term": {
+                    "type": "group_any_in",
+                    "values": ["group-b", "missing-group"],
+                },
+            },
+            "sort": {
+                "field": "published_at",
+                "order": "desc",
+            },
+            "page": {
+                "limit": 10,
+                "cursor": null,
+            },
+            "total_mode": "exact",
+        }))
+        .unwrap();
+
+        let response = engine.search_request(request).unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            json["clip_uuids"],
+            serde_json::json!(["clip-d", "clip-c", "clip-b"])
+        );
+        assert_eq!(
+            json["warnings"],
+            serde_json::json!([{ "type": "unknown_group_id", "value": "missing-group" }])
+        );
+    }
+
+    #[test]
+    fn test_artist_attribute_flag_terms() {
+        let engine = sample_engine();
+        let request = serde_json::from_value::<api::SearchRequest>(serde_json::json!({
+            "query": {
+                "type": "and",
+                "children": [
+                    {
+                        "type": "term",
+                        "term": { "type": "has_graduated_artist_eq", "value": true },
+                    },
+                    {
+                        "type": "term",
+                        "term": { "type": "is_self_upload_eq", "value": true },
+                    },
+                    {
+                        "type": "not",
+                        "child": {
+                            "type": "term",
+                            "term": { "type": "is_official_channel_eq", "value": true },
+                        },
+                    },
+                ],
+            },
+            "sort": {
+                "field": "published_at",
+                "order": "desc",
+            },
+            "page": {
+                "limit": 10,
+                "cursor": null,
+            },
+            "total_mode": "exact",
+        }))
+        .unwrap();
+
+        let response = engine.search_request(request).unwrap();
+        assert_eq!(
+            response.clip_uuids,
+            vec!["clip-d".to_string(), "clip-c".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_request_include_blocked_defaults_to_false() {
+        let request = sample_request();
+        assert!(!request.include_blocked);
+        assert!(!request.clone().into_engine().unwrap().include_blocked);
+
+        let mut json = serde_json::to_value(&request).unwrap();
+        json["include_blocked"] = serde_json::json!(true);
+        let request = serde_json::from_value::<api::SearchRequest>(json).unwrap();
+        assert!(request.into_engine().unwrap().include_blocked);
+    }
+
     #[test]
     fn test_api_version_mismatch_is_invalid_request() {
         let engine = sample_engine();
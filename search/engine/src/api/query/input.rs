@@ -22,6 +22,10 @@ pub struct SearchRequest {
     pub sort: Vec<SortSpec>,
     pub page: PageSpec,
     pub total_mode: crate::api::query::types::TotalMode,
+    /// `true` のとき `blocked` なタグを持つクリップも結果に含める。
+    ///
+    /// 既定では索引の blocked docs を結果から暗黙に除外する。
+    pub include_blocked: bool,
 }
 
 /// 検索条件 AST。
@@ -73,14 +77,42 @@ pub enum TermNode {
     TagAnyIn {
         values: Vec<index_core::schema::ids::TagIdString>,
     },
+    /// 指定したタグとその子孫タグのいずれかを持つもの。
+    ///
+    /// 解決時に索引が持つタグの親子関係で展開し、`TagAnyIn` として評価する。
+    TagDescendantsOf {
+        values: Vec<index_core::schema::ids::TagIdString>,
+    },
+    /// 動画のタグを含めず、クリップ自身のタグだけで絞り込む。
+    ClipTagAnyIn {
+        values: Vec<index_core::schema::ids::TagIdString>,
+    },
     ChannelAnyIn {
         values: Vec<index_core::schema::ids::ChannelIdString>,
     },
+    /// 指定したグループのメンバーのいずれかが歌うもの。
+    ///
+    /// 解決時に索引が持つグループのメンバーで展開し、`ArtistAnyIn` として評価する。
+    GroupAnyIn {
+        values: Vec<index_core::schema::ids::GroupIdString>,
+    },
     IsUnlistedEq {
         value: bool,
     },
     EmbeddableEq {
         value: bool,
     },
+    /// クリップのアーティストのいずれかのチャンネルで公開された動画か。
+    IsSelfUploadEq {
+        value: bool,
+    },
+    /// 公式チャンネルで公開された動画か。
+    IsOfficialChannelEq {
+        value: bool,
+    },
+    /// クリップのアーティストに卒業したライバーを含むか。
+    HasGraduatedArtistEq {
+        value: bool,
+    },
     PublishedAtRange(crate::api::query::types::DateRange),
 }
@@ -22,6 +22,9 @@ pub enum TermNode {
     TagAnyIn {
         values: Vec<index_core::schema::ids::TagId>,
     },
+    ClipTagAnyIn {
+        values: Vec<index_core::schema::ids::TagId>,
+    },
     ChannelAnyIn {
         values: Vec<index_core::schema::ids::ChannelId>,
     },
@@ -31,5 +34,14 @@ pub enum TermNode {
     EmbeddableEq {
         value: bool,
     },
+    IsSelfUploadEq {
+        value: bool,
+    },
+    IsOfficialChannelEq {
+        value: bool,
+    },
+    HasGraduatedArtistEq {
+        value: bool,
+    },
     PublishedAtRange(crate::api::query::types::DateRange),
 }
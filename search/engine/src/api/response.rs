@@ -9,6 +9,13 @@ pub enum QueryWarning {
     UnknownChannelId {
         value: index_core::schema::ids::ChannelIdString,
     },
+    UnknownGroupId {
+        value: index_core::schema::ids::GroupIdString,
+    },
+    /// `blocked` なタグを指定したが、blocked なクリップを除外している。
+    BlockedTagId {
+        value: index_core::schema::ids::TagIdString,
+    },
 }
 
 /// 公開 API の検索結果。
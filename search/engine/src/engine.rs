@@ -16,15 +16,26 @@ impl SearchEngine {
     ) -> Result<crate::api::response::SearchResponse, crate::EngineError> {
         let request = crate::normalize::validate_and_normalize_request(request)?;
         let reader = self.index.reader()?;
-        let (resolved_query, warnings) = crate::resolve::resolve_query(
+        let (resolved_query, mut warnings) = crate::resolve::resolve_query(
             request.query.as_ref(),
             &self.index.dictionaries,
         )?;
-        let doc_set = crate::eval::evaluate_query(
+        let mut doc_set = crate::eval::evaluate_query(
             &reader,
             self.index.record_count,
             &resolved_query,
         )?;
+        if !request.include_blocked {
+            warnings.extend(crate::resolve::blocked_tag_warnings(
+                request.query.as_ref(),
+                &self.index.dictionaries,
+            ));
+            doc_set = crate::eval::exclude_blocked_docs(
+                &reader,
+                self.index.record_count,
+                doc_set,
+            )?;
+        }
         let internal = crate::paging::paginate(
             &reader,
             self.index.record_count,
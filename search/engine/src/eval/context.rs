@@ -4,9 +4,17 @@ pub(crate) struct EvalContext<'a> {
     pub(crate) published_at_sort: index_core::binary::SortIndexView<'a>,
     pub(crate) artist_docs: index_core::binary::DensePostingsView<'a>,
     pub(crate) tag_docs: index_core::binary::DensePostingsView<'a>,
+    /// 古い索引ではセクションが無く `None`
+    pub(crate) clip_tag_docs: Option<index_core::binary::DensePostingsView<'a>>,
     pub(crate) channel_docs: index_core::binary::DensePostingsView<'a>,
     pub(crate) is_unlisted_docs: index_core::binary::BoolPostingsView<'a>,
     pub(crate) embeddable_docs: index_core::binary::BoolPostingsView<'a>,
+    /// 以下 3 つは古い索引ではセクションが無く `None`
+    pub(crate) is_self_upload_docs: Option<index_core::binary::BoolPostingsView<'a>>,
+    pub(crate) is_official_channel_docs:
+        Option<index_core::binary::BoolPostingsView<'a>>,
+    pub(crate) has_graduated_artist_docs:
+        Option<index_core::binary::BoolPostingsView<'a>>,
 }
 
 impl<'a> EvalContext<'a> {
@@ -20,9 +28,13 @@ impl<'a> EvalContext<'a> {
             published_at_sort: reader.published_at_sort()?,
             artist_docs: reader.artist_docs()?,
             tag_docs: reader.tag_docs()?,
+            clip_tag_docs: reader.clip_tag_docs()?,
             channel_docs: reader.channel_docs()?,
             is_unlisted_docs: reader.is_unlisted_docs()?,
             embeddable_docs: reader.embeddable_docs()?,
+            is_self_upload_docs: reader.is_self_upload_docs()?,
+            is_official_channel_docs: reader.is_official_channel_docs()?,
+            has_graduated_artist_docs: reader.has_graduated_artist_docs()?,
         })
     }
 }
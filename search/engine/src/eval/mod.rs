@@ -11,3 +11,22 @@ pub(crate) fn evaluate_query(
     let context = context::EvalContext::new(reader, record_count)?;
     boolean::evaluate_node(&context, query)
 }
+
+/// `doc_set` から blocked docs を除く. 古い索引でセクションが無いときはそのまま返す
+pub(crate) fn exclude_blocked_docs(
+    reader: &index_core::binary::SearchIndexReader<'_>,
+    record_count: u32,
+    doc_set: crate::doc_set::DocSet,
+) -> Result<crate::doc_set::DocSet, crate::error::EngineError> {
+    let Some(blocked_docs) = reader.blocked_docs()? else {
+        return Ok(doc_set);
+    };
+    let blocked_docs = crate::doc_set::DocSet::from_sorted_doc_ids(
+        blocked_docs.to_vec(),
+        record_count,
+    )?;
+    if blocked_docs.is_empty() {
+        return Ok(doc_set);
+    }
+    crate::doc_set::DocSet::difference(&doc_set, &blocked_docs, record_count)
+}
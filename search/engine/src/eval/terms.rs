@@ -11,6 +11,15 @@ pub(crate) fn evaluate_term(
         TermNode::TagAnyIn { values } => {
             exact_any_in(context.record_count, &context.tag_docs, values)
         }
+        TermNode::ClipTagAnyIn { values } => match &context.clip_tag_docs {
+            Some(clip_tag_docs) => {
+                exact_any_in(context.record_count, clip_tag_docs, values)
+            }
+            None => crate::doc_set::DocSet::from_sorted_doc_ids(
+                Vec::new(),
+                context.record_count,
+            ),
+        },
         TermNode::ChannelAnyIn { values } => {
             exact_any_in(context.record_count, &context.channel_docs, values)
         }
@@ -30,12 +39,47 @@ pub(crate) fn evaluate_term(
             };
             crate::doc_set::DocSet::from_sorted_doc_ids(docs, context.record_count)
         }
+        TermNode::IsSelfUploadEq { value } => optional_bool_eq(
+            context.record_count,
+            context.is_self_upload_docs.as_ref(),
+            *value,
+        ),
+        TermNode::IsOfficialChannelEq { value } => optional_bool_eq(
+            context.record_count,
+            context.is_official_channel_docs.as_ref(),
+            *value,
+        ),
+        TermNode::HasGraduatedArtistEq { value } => optional_bool_eq(
+            context.record_count,
+            context.has_graduated_artist_docs.as_ref(),
+            *value,
+        ),
         TermNode::PublishedAtRange(range) => {
             super::range::evaluate_published_at_range(context, range)
         }
     }
 }
 
+/// セクションが無い索引ではどのクリップも `false` として扱う
+fn optional_bool_eq(
+    record_count: u32,
+    postings: Option<&index_core::binary::BoolPostingsView<'_>>,
+    value: bool,
+) -> Result<crate::doc_set::DocSet, crate::EngineError> {
+    match (postings, value) {
+        (Some(postings), true) => crate::doc_set::DocSet::from_sorted_doc_ids(
+            postings.true_docs().to_vec(),
+            record_count,
+        ),
+        (Some(postings), false) => crate::doc_set::DocSet::from_sorted_doc_ids(
+            postings.false_docs().to_vec(),
+            record_count,
+        ),
+        (None, true) => Ok(crate::doc_set::DocSet::Empty),
+        (None, false) => Ok(crate::doc_set::DocSet::All),
+    }
+}
+
 fn exact_any_in(
     record_count: u32,
     postings: &index_core::binary::DensePostingsView<'_>,
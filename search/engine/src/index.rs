@@ -10,6 +10,36 @@ pub(crate) struct DictionaryCaches {
     >,
     pub(crate) tags:
         std::collections::HashMap<std::sync::Arc<str>, index_core::schema::ids::TagId>,
+    /// `tag_children[tag_id]` はそのタグを親に持つタグ
+    pub(crate) tag_children: Vec<Vec<index_core::schema::ids::TagId>>,
+    /// `blocked` なタグ. 昇順
+    pub(crate) blocked_tag_ids: Vec<index_core::schema::ids::TagId>,
+    /// グループごとのメンバー. 昇順
+    pub(crate) groups: std::collections::HashMap<
+        std::sync::Arc<str>,
+        Vec<index_core::schema::ids::ArtistId>,
+    >,
+}
+
+impl DictionaryCaches {
+    /// `roots` とその子孫タグを sort + dedup して返す
+    pub(crate) fn tag_descendants(
+        &self,
+        roots: Vec<index_core::schema::ids::TagId>,
+    ) -> Vec<index_core::schema::ids::TagId> {
+        let mut visited = vec![false; self.tag_children.len()];
+        let mut stack = roots;
+        let mut out = Vec::new();
+        while let Some(tag_id) = stack.pop() {
+            if std::mem::replace(&mut visited[tag_id as usize], true) {
+                continue;
+            }
+            out.push(tag_id);
+            stack.extend(&self.tag_children[tag_id as usize]);
+        }
+        out.sort_unstable();
+        out
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +71,18 @@ impl LoadedIndex {
                 channels: build_dictionary_cache(reader.channels_dictionary()?)?,
                 artists: build_dictionary_cache(reader.artists_dictionary()?)?,
                 tags: build_dictionary_cache(reader.tags_dictionary()?)?,
+                tag_children: build_tag_children(
+                    reader.tags_dictionary()?.len(),
+                    reader.tag_parents()?,
+                ),
+                blocked_tag_ids: reader
+                    .blocked_tag_ids()?
+                    .map(|tag_ids| tag_ids.to_vec())
+                    .unwrap_or_default(),
+                groups: build_group_cache(
+                    reader.groups_dictionary()?,
+                    reader.group_members()?,
+                )?,
             },
             layout,
         })
@@ -58,6 +100,46 @@ impl LoadedIndex {
     }
 }
 
+fn build_tag_children(
+    tag_count: usize,
+    tag_parents: Option<Vec<Option<index_core::schema::ids::TagId>>>,
+) -> Vec<Vec<index_core::schema::ids::TagId>> {
+    let mut children = vec![Vec::new(); tag_count];
+    for (tag_id, parent) in tag_parents.unwrap_or_default().into_iter().enumerate() {
+        if let Some(parent) = parent {
+            children[parent as usize].push(tag_id as index_core::schema::ids::TagId);
+        }
+    }
+    children
+}
+
+fn build_group_cache(
+    groups: Option<index_core::binary::StringDictionaryView<'_>>,
+    members: Option<index_core::binary::U32ListColumnView<'_>>,
+) -> Result<
+    std::collections::HashMap<
+        std::sync::Arc<str>,
+        Vec<index_core::schema::ids::ArtistId>,
+    >,
+    crate::EngineError,
+> {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let (Some(groups), Some(members)) = (groups, members) else {
+        return Ok(HashMap::new());
+    };
+    let mut out = HashMap::with_capacity(groups.len());
+    for (index, value) in groups.iter().enumerate() {
+        let artist_ids = members
+            .get(index)?
+            .map(|artist_ids| artist_ids.to_vec())
+            .unwrap_or_default();
+        out.insert(Arc::<str>::from(value?), artist_ids);
+    }
+    Ok(out)
+}
+
 fn build_dictionary_cache<Id>(
     dictionary: index_core::binary::StringDictionaryView<'_>,
 ) -> Result<std::collections::HashMap<std::sync::Arc<str>, Id>, crate::EngineError>
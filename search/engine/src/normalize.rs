@@ -11,6 +11,7 @@ pub(crate) struct ValidatedRequest {
     pub(crate) limit: u32,
     pub(crate) cursor: Option<crate::api::pagination::Cursor>,
     pub(crate) total_mode: crate::api::query::types::TotalMode,
+    pub(crate) include_blocked: bool,
     pub(crate) query_fingerprint: u64,
 }
 
@@ -39,7 +40,7 @@ pub(crate) fn validate_and_normalize_request(
         Some(query) => Some(normalize_query(query)?),
         None => None,
     };
-    let query_fingerprint = fingerprint_query(query.as_ref());
+    let query_fingerprint = fingerprint_query(query.as_ref(), request.include_blocked);
 
     Ok(ValidatedRequest {
         query,
@@ -47,6 +48,7 @@ pub(crate) fn validate_and_normalize_request(
         limit: request.page.limit,
         cursor: request.page.cursor.clone(),
         total_mode: request.total_mode,
+        include_blocked: request.include_blocked,
         query_fingerprint,
     })
 }
@@ -161,15 +163,33 @@ fn normalize_term(
         TermNode::TagAnyIn { values } => Ok(TermNode::TagAnyIn {
             values: normalize_values(values)?,
         }),
+        TermNode::TagDescendantsOf { values } => Ok(TermNode::TagDescendantsOf {
+            values: normalize_values(values)?,
+        }),
+        TermNode::ClipTagAnyIn { values } => Ok(TermNode::ClipTagAnyIn {
+            values: normalize_values(values)?,
+        }),
         TermNode::ChannelAnyIn { values } => Ok(TermNode::ChannelAnyIn {
             values: normalize_values(values)?,
         }),
+        TermNode::GroupAnyIn { values } => Ok(TermNode::GroupAnyIn {
+            values: normalize_values(values)?,
+        }),
         TermNode::IsUnlistedEq { value } => {
             Ok(TermNode::IsUnlistedEq { value: *value })
         }
         TermNode::EmbeddableEq { value } => {
             Ok(TermNode::EmbeddableEq { value: *value })
         }
+        TermNode::IsSelfUploadEq { value } => {
+            Ok(TermNode::IsSelfUploadEq { value: *value })
+        }
+        TermNode::IsOfficialChannelEq { value } => {
+            Ok(TermNode::IsOfficialChannelEq { value: *value })
+        }
+        TermNode::HasGraduatedArtistEq { value } => {
+            Ok(TermNode::HasGraduatedArtistEq { value: *value })
+        }
         TermNode::PublishedAtRange(range) => {
             Ok(TermNode::PublishedAtRange(range.clone()))
         }
@@ -235,12 +255,17 @@ fn collapse_boolean_node(
     }
 }
 
-fn fingerprint_query(query: Option<&crate::api::query::input::QueryNode>) -> u64 {
+/// blocked の扱いで結果集合が変わるため, `include_blocked` も cursor の照合対象にする
+fn fingerprint_query(
+    query: Option<&crate::api::query::input::QueryNode>,
+    include_blocked: bool,
+) -> u64 {
     use std::hash::Hash;
     use std::hash::Hasher;
 
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     query.hash(&mut hasher);
+    include_blocked.hash(&mut hasher);
     hasher.finish()
 }
 
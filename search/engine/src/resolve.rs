@@ -16,6 +16,58 @@ pub(crate) fn resolve_query(
     Ok((resolved, warnings))
 }
 
+/// 否定されていないタグの term が `blocked` なタグを指定していれば警告を返す
+///
+/// `blocked` なクリップを除外する検索でのみ使う。
+pub(crate) fn blocked_tag_warnings(
+    query: Option<&crate::api::query::input::QueryNode>,
+    dictionaries: &crate::index::DictionaryCaches,
+) -> Vec<crate::api::response::QueryWarning> {
+    let mut warnings = Vec::new();
+    if !dictionaries.blocked_tag_ids.is_empty()
+        && let Some(query) = query
+    {
+        collect_blocked_tag_warnings(query, dictionaries, &mut warnings);
+    }
+    warnings
+}
+
+fn collect_blocked_tag_warnings(
+    query: &crate::api::query::input::QueryNode,
+    dictionaries: &crate::index::DictionaryCaches,
+    warnings: &mut Vec<crate::api::response::QueryWarning>,
+) {
+    use crate::api::query::input::{QueryNode, TermNode};
+
+    match query {
+        QueryNode::And { children } | QueryNode::Or { children } => {
+            for child in children {
+                collect_blocked_tag_warnings(child, dictionaries, warnings);
+            }
+        }
+        // 正規化後の `Not` は term 直上にだけ現れ, blocked なタグの除外は警告しない
+        QueryNode::Not { .. } => {}
+        QueryNode::Term(
+            TermNode::TagAnyIn { values }
+            | TermNode::TagDescendantsOf { values }
+            | TermNode::ClipTagAnyIn { values },
+        ) => {
+            for value in values {
+                let is_blocked =
+                    dictionaries.tags.get(value.as_str()).is_some_and(|tag_id| {
+                        dictionaries.blocked_tag_ids.binary_search(tag_id).is_ok()
+                    });
+                if is_blocked {
+                    warnings.push(crate::api::response::QueryWarning::BlockedTagId {
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        QueryNode::Term(_) => {}
+    }
+}
+
 fn resolve_node(
     query: &crate::api::query::input::QueryNode,
     dictionaries: &crate::index::DictionaryCaches,
@@ -103,6 +155,28 @@ fn resolve_term(
             },
             |values| crate::api::query::resolved::TermNode::TagAnyIn { values },
         ),
+        crate::api::query::input::TermNode::TagDescendantsOf { values } => {
+            resolve_any_in(
+                values,
+                &dictionaries.tags,
+                warnings,
+                |value| crate::api::response::QueryWarning::UnknownTagId {
+                    value: value.to_string(),
+                },
+                |values| crate::api::query::resolved::TermNode::TagAnyIn {
+                    values: dictionaries.tag_descendants(values),
+                },
+            )
+        }
+        crate::api::query::input::TermNode::ClipTagAnyIn { values } => resolve_any_in(
+            values,
+            &dictionaries.tags,
+            warnings,
+            |value| crate::api::response::QueryWarning::UnknownTagId {
+                value: value.to_string(),
+            },
+            |values| crate::api::query::resolved::TermNode::ClipTagAnyIn { values },
+        ),
         crate::api::query::input::TermNode::ChannelAnyIn { values } => resolve_any_in(
             values,
             &dictionaries.channels,
@@ -112,6 +186,9 @@ fn resolve_term(
             },
             |values| crate::api::query::resolved::TermNode::ChannelAnyIn { values },
         ),
+        crate::api::query::input::TermNode::GroupAnyIn { values } => {
+            Ok(resolve_group_any_in(values, dictionaries, warnings))
+        }
         crate::api::query::input::TermNode::IsUnlistedEq { value } => {
             Ok(crate::api::query::resolved::QueryNode::Term(
                 crate::api::query::resolved::TermNode::IsUnlistedEq { value: *value },
@@ -122,6 +199,25 @@ fn resolve_term(
                 crate::api::query::resolved::TermNode::EmbeddableEq { value: *value },
             ))
         }
+        crate::api::query::input::TermNode::IsSelfUploadEq { value } => {
+            Ok(crate::api::query::resolved::QueryNode::Term(
+                crate::api::query::resolved::TermNode::IsSelfUploadEq { value: *value },
+            ))
+        }
+        crate::api::query::input::TermNode::IsOfficialChannelEq { value } => {
+            Ok(crate::api::query::resolved::QueryNode::Term(
+                crate::api::query::resolved::TermNode::IsOfficialChannelEq {
+                    value: *value,
+                },
+            ))
+        }
+        crate::api::query::input::TermNode::HasGraduatedArtistEq { value } => {
+            Ok(crate::api::query::resolved::QueryNode::Term(
+                crate::api::query::resolved::TermNode::HasGraduatedArtistEq {
+                    value: *value,
+                },
+            ))
+        }
         crate::api::query::input::TermNode::PublishedAtRange(range) => {
             if is_empty_range(range) {
                 Ok(crate::api::query::resolved::QueryNode::Empty)
@@ -166,6 +262,33 @@ where
     })
 }
 
+/// グループをメンバーの和集合に展開し、`ArtistAnyIn` として解決する
+fn resolve_group_any_in(
+    values: &[String],
+    dictionaries: &crate::index::DictionaryCaches,
+    warnings: &mut Vec<crate::api::response::QueryWarning>,
+) -> crate::api::query::resolved::QueryNode {
+    let mut resolved = Vec::new();
+    for value in values {
+        match dictionaries.groups.get(value.as_str()) {
+            Some(artist_ids) => resolved.extend_from_slice(artist_ids),
+            None => warnings.push(crate::api::response::QueryWarning::UnknownGroupId {
+                value: value.clone(),
+            }),
+        }
+    }
+
+    resolved.sort_unstable();
+    resolved.dedup();
+
+    match resolved.is_empty() {
+        true => crate::api::query::resolved::QueryNode::Empty,
+        false => crate::api::query::resolved::QueryNode::Term(
+            crate::api::query::resolved::TermNode::ArtistAnyIn { values: resolved },
+        ),
+    }
+}
+
 fn is_empty_range(range: &crate::api::query::types::DateRange) -> bool {
     match (range.lower, range.upper) {
         (Some(lower), Some(upper)) => {
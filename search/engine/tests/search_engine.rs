@@ -1,4 +1,4 @@
-fn sample_engine() -> engine::SearchEngine {
+fn sample_index() -> index_core::schema::SearchIndex {
     let clips = index_core::util::BiMap::from_ordered_strings(vec![
         "clip-a".to_string(),
         "clip-b".to_string(),
@@ -31,7 +31,7 @@ fn sample_engine() -> engine::SearchEngine {
     ])
     .unwrap();
 
-    let index = index_core::schema::SearchIndex {
+    index_core::schema::SearchIndex {
         meta: index_core::schema::IndexMetadata {
             index_format_version: 1,
             dataset_build_id:
@@ -47,6 +47,9 @@ fn sample_engine() -> engine::SearchEngine {
             artists,
             tags,
         },
+        tag_hierarchy: index_core::schema::TagHierarchy {
+            parents: vec![None, Some(0)],
+        },
         columns: index_core::schema::ColumnStore {
             clip_ids: vec![0, 1, 2, 3, 4],
             video_ids: vec![0, 1, 2, 3, 4],
@@ -60,6 +63,9 @@ fn sample_engine() -> engine::SearchEngine {
             channel_ids: vec![0, 0, 1, 1, 0],
             is_unlisteds: vec![false, false, false, true, false],
             embeddables: vec![true, true, true, false, true],
+            is_self_uploads: vec![true, false, true, true, false],
+            is_official_channels: vec![false, false, false, false, false],
+            has_graduated_artists: vec![false, true, true, true, false],
             artist_id_lists: index_core::util::U32ListColumn::build(&[
                 vec![0],
                 vec![1],
@@ -84,22 +90,51 @@ fn sample_engine() -> engine::SearchEngine {
                 (0, vec![0, 1]),
                 (1, vec![2, 4]),
             ]),
+            clip_tag_docs: std::collections::HashMap::from([(1, vec![4])]),
             channel_docs: std::collections::HashMap::from([
                 (0, vec![0, 1, 4]),
                 (1, vec![2, 3]),
             ]),
             is_unlisted_docs: [vec![0, 1, 2, 4], vec![3]],
             embeddable_docs: [vec![3], vec![0, 1, 2, 4]],
+            is_self_upload_docs: [vec![1, 4], vec![0, 2, 3]],
+            is_official_channel_docs: [vec![0, 1, 2, 3, 4], vec![]],
+            has_graduated_artist_docs: [vec![0, 4], vec![1, 2, 3]],
+        },
+        blocked: index_core::schema::BlockedIndex::default(),
+        groups: index_core::schema::GroupIndex {
+            groups: index_core::util::BiMap::from_ordered_strings(vec![
+                "group-a".to_string(),
+                "group-ab".to_string(),
+            ])
+            .unwrap(),
+            members: vec![vec![0], vec![0, 1]],
         },
         sort_indexes: index_core::schema::SortIndexes {
             published_at: index_core::schema::SortIndex::new(vec![0, 1, 2, 3, 4]),
         },
-    };
+    }
+}
 
-    let bytes = index_core::binary::serialize_search_index(&index).unwrap();
+fn load_engine(index: &index_core::schema::SearchIndex) -> engine::SearchEngine {
+    let bytes = index_core::binary::serialize_search_index(index).unwrap();
     engine::SearchEngine::load(std::sync::Arc::<[u8]>::from(bytes)).unwrap()
 }
 
+fn sample_engine() -> engine::SearchEngine {
+    load_engine(&sample_index())
+}
+
+/// tag-b を持つ clip-c, clip-e をブロックする
+fn sample_engine_with_blocked_tag() -> engine::SearchEngine {
+    let mut index = sample_index();
+    index.blocked = index_core::schema::BlockedIndex {
+        tag_ids: vec![1],
+        docs: vec![2, 4],
+    };
+    load_engine(&index)
+}
+
 fn desc_sort() -> engine::api::query::input::SortSpec {
     engine::api::query::input::SortSpec {
         field: engine::api::query::types::SortField::PublishedAt,
@@ -122,6 +157,7 @@ fn test_search_filters_artist_and_returns_exact_total() {
             cursor: None,
         },
         total_mode: engine::api::query::types::TotalMode::Exact,
+        include_blocked: false,
     };
 
     let response = engine.search(&request).unwrap();
@@ -138,6 +174,159 @@ fn test_search_filters_artist_and_returns_exact_total() {
     assert!(response.warnings.is_empty());
 }
 
+#[test]
+fn test_search_clip_tag_any_in_excludes_video_only_tags() {
+    let engine = sample_engine();
+    let search = |term| {
+        let request = engine::api::query::input::SearchRequest {
+            query: Some(engine::api::query::input::QueryNode::Term(term)),
+            sort: vec![desc_sort()],
+            page: engine::api::query::input::PageSpec {
+                limit: 10,
+                cursor: None,
+            },
+            total_mode: engine::api::query::types::TotalMode::Exact,
+            include_blocked: false,
+        };
+        engine.search(&request).unwrap().clip_uuids
+    };
+
+    assert_eq!(
+        search(engine::api::query::input::TermNode::TagAnyIn {
+            values: vec!["tag-b".to_string()],
+        }),
+        vec!["clip-e".to_string(), "clip-c".to_string()],
+    );
+    assert_eq!(
+        search(engine::api::query::input::TermNode::ClipTagAnyIn {
+            values: vec!["tag-b".to_string()],
+        }),
+        vec!["clip-e".to_string()],
+    );
+}
+
+#[test]
+fn test_search_tag_descendants_of_expands_child_tags() {
+    let engine = sample_engine();
+    let search = |values: Vec<&str>| {
+        let request = engine::api::query::input::SearchRequest {
+            query: Some(engine::api::query::input::QueryNode::Term(
+                engine::api::query::input::TermNode::TagDescendantsOf {
+                    values: values.into_iter().map(str::to_string).collect(),
+                },
+            )),
+            sort: vec![desc_sort()],
+            page: engine::api::query::input::PageSpec {
+                limit: 10,
+                cursor: None,
+            },
+            total_mode: engine::api::query::types::TotalMode::Exact,
+            include_blocked: false,
+        };
+        engine.search(&request).unwrap().clip_uuids
+    };
+
+    // tag-b の親は tag-a
+    assert_eq!(
+        search(vec!["tag-a"]),
+        vec![
+            "clip-e".to_string(),
+            "clip-c".to_string(),
+            "clip-b".to_string(),
+            "clip-a".to_string(),
+        ],
+    );
+    assert_eq!(
+        search(vec!["tag-b"]),
+        vec!["clip-e".to_string(), "clip-c".to_string()],
+    );
+}
+
+#[test]
+fn test_search_group_any_in_expands_members() {
+    let engine = sample_engine();
+    let search = |values: Vec<&str>| {
+        let request = engine::api::query::input::SearchRequest {
+            query: Some(engine::api::query::input::QueryNode::Term(
+                engine::api::query::input::TermNode::GroupAnyIn {
+                    values: values.into_iter().map(str::to_string).collect(),
+                },
+            )),
+            sort: vec![desc_sort()],
+            page: engine::api::query::input::PageSpec {
+                limit: 10,
+                cursor: None,
+            },
+            total_mode: engine::api::query::types::TotalMode::Exact,
+            include_blocked: false,
+        };
+        engine.search(&request).unwrap()
+    };
+
+    let response = search(vec!["group-a", "group-x"]);
+    assert_eq!(
+        response.clip_uuids,
+        vec!["clip-d".to_string(), "clip-a".to_string()],
+    );
+    assert_eq!(
+        response.warnings,
+        vec![engine::api::response::QueryWarning::UnknownGroupId {
+            value: "group-x".to_string(),
+        }],
+    );
+    assert_eq!(search(vec!["group-ab"]).total, Some(4));
+    assert!(search(vec!["group-x"]).clip_uuids.is_empty());
+}
+
+#[test]
+fn test_search_filters_artist_attribute_flags() {
+    let engine = sample_engine();
+    let search = |query: engine::api::query::input::QueryNode| {
+        let request = engine::api::query::input::SearchRequest {
+            query: Some(query),
+            sort: vec![desc_sort()],
+            page: engine::api::query::input::PageSpec {
+                limit: 10,
+                cursor: None,
+            },
+            total_mode: engine::api::query::types::TotalMode::Exact,
+            include_blocked: false,
+        };
+        engine.search(&request).unwrap().clip_uuids
+    };
+    let term = |term| engine::api::query::input::QueryNode::Term(term);
+
+    assert_eq!(
+        search(term(engine::api::query::input::TermNode::IsSelfUploadEq {
+            value: true
+        })),
+        vec![
+            "clip-d".to_string(),
+            "clip-c".to_string(),
+            "clip-a".to_string(),
+        ],
+    );
+    assert_eq!(
+        search(engine::api::query::input::QueryNode::And {
+            children: vec![
+                term(engine::api::query::input::TermNode::HasGraduatedArtistEq {
+                    value: true,
+                }),
+                term(engine::api::query::input::TermNode::IsSelfUploadEq {
+                    value: false,
+                }),
+            ],
+        }),
+        vec!["clip-b".to_string()],
+    );
+    assert!(
+        search(term(
+            engine::api::query::input::TermNode::IsOfficialChannelEq { value: true }
+        ))
+        .is_empty()
+    );
+}
+
 #[test]
 fn test_search_pages_with_desc_tie_and_unknown_warning() {
     let engine = sample_engine();
@@ -155,6 +344,7 @@ fn test_search_pages_with_desc_tie_and_unknown_warning() {
             cursor: None,
         },
         total_mode: engine::api::query::types::TotalMode::None,
+        include_blocked: false,
     };
 
     let first = engine.search(&request).unwrap();
@@ -207,6 +397,7 @@ fn test_search_rejects_cursor_for_different_query() {
             cursor: None,
         },
         total_mode: engine::api::query::types::TotalMode::None,
+        include_blocked: false,
     };
 
     let first = engine.search(&request).unwrap();
@@ -229,3 +420,86 @@ fn test_search_rejects_cursor_for_different_query() {
         engine::EngineError::InvalidCursor("cursor query fingerprint does not match"),
     );
 }
+
+#[test]
+fn test_search_excludes_blocked_docs_unless_included() {
+    let engine = sample_engine_with_blocked_tag();
+    let request = engine::api::query::input::SearchRequest {
+        query: None,
+        sort: vec![desc_sort()],
+        page: engine::api::query::input::PageSpec {
+            limit: 10,
+            cursor: None,
+        },
+        total_mode: engine::api::query::types::TotalMode::Exact,
+        include_blocked: false,
+    };
+
+    let response = engine.search(&request).unwrap();
+    assert_eq!(
+        response.clip_uuids,
+        vec![
+            "clip-d".to_string(),
+            "clip-b".to_string(),
+            "clip-a".to_string(),
+        ],
+    );
+    assert_eq!(response.total, Some(3));
+    assert!(response.warnings.is_empty());
+
+    let response = engine
+        .search(&engine::api::query::input::SearchRequest {
+            include_blocked: true,
+            ..request
+        })
+        .unwrap();
+    assert_eq!(response.total, Some(5));
+}
+
+#[test]
+fn test_search_warns_when_blocked_tag_is_requested() {
+    let engine = sample_engine_with_blocked_tag();
+    let search = |query, include_blocked| {
+        let request = engine::api::query::input::SearchRequest {
+            query: Some(query),
+            sort: vec![desc_sort()],
+            page: engine::api::query::input::PageSpec {
+                limit: 10,
+                cursor: None,
+            },
+            total_mode: engine::api::query::types::TotalMode::Exact,
+            include_blocked,
+        };
+        engine.search(&request).unwrap()
+    };
+    let tag_b = engine::api::query::input::QueryNode::Term(
+        engine::api::query::input::TermNode::TagAnyIn {
+            values: vec!["tag-b".to_string()],
+        },
+    );
+
+    let response = search(tag_b.clone(), false);
+    assert!(response.clip_uuids.is_empty());
+    assert_eq!(
+        response.warnings,
+        vec![engine::api::response::QueryWarning::BlockedTagId {
+            value: "tag-b".to_string(),
+        }],
+    );
+
+    let response = search(tag_b.clone(), true);
+    assert_eq!(
+        response.clip_uuids,
+        vec!["clip-e".to_string(), "clip-c".to_string()],
+    );
+    assert!(response.warnings.is_empty());
+
+    let response = search(
+        engine::api::query::input::QueryNode::Not {
+            child: Box::new(tag_b),
+        },
+        false,
+    );
+    assert_eq!(response.total, Some(3));
+    assert!(response.warnings.is_empty());
+}
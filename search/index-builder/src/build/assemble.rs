@@ -21,6 +21,9 @@ pub(crate) fn build_search_index_from_loaded_data(
     let dictionaries = crate::build::dictionaries::build_dictionaries(&data);
     let normalized =
         crate::build::normalize::normalize_clip_records(&data, &dictionaries)?;
+    let tag_hierarchy = build_tag_hierarchy(&data, &dictionaries)?;
+    let blocked = build_blocked_index(&data, &dictionaries, &normalized)?;
+    let groups = build_group_index(&data, &dictionaries)?;
 
     Ok(index_core::schema::SearchIndex {
         meta: index_core::schema::IndexMetadata {
@@ -31,12 +34,98 @@ pub(crate) fn build_search_index_from_loaded_data(
                 .expect("record count fits within u32"),
         },
         dictionaries,
+        tag_hierarchy,
         columns: build_columns(&normalized),
         exact_indexes: build_exact_indexes(&normalized),
+        blocked,
+        groups,
         sort_indexes: build_sort_indexes(&normalized),
     })
 }
 
+fn build_tag_hierarchy(
+    data: &crate::build::load::LoadedData,
+    dictionaries: &index_core::schema::Dictionaries,
+) -> anyhow::Result<index_core::schema::TagHierarchy> {
+    let tag_id = |value: &str| {
+        dictionaries.tags.get_by_str(value).ok_or_else(|| {
+            anyhow::anyhow!("tag hierarchy refers to unknown tag_id {value}")
+        })
+    };
+
+    let mut parents = vec![None; dictionaries.tags.len()];
+    for tag_parent in &data.tag_parents {
+        parents[tag_id(&tag_parent.tag_id)? as usize] =
+            Some(tag_id(&tag_parent.parent_tag_id)?);
+    }
+    Ok(index_core::schema::TagHierarchy { parents })
+}
+
+fn build_blocked_index(
+    data: &crate::build::load::LoadedData,
+    dictionaries: &index_core::schema::Dictionaries,
+    normalized: &[crate::build::normalize::NormalizedClipRecord],
+) -> anyhow::Result<index_core::schema::BlockedIndex> {
+    let mut tag_ids = data
+        .blocked_tag_ids
+        .iter()
+        .map(|value| {
+            dictionaries.tags.get_by_str(value).ok_or_else(|| {
+                anyhow::anyhow!("blocked tags refer to unknown tag_id {value}")
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    tag_ids.sort_unstable();
+    tag_ids.dedup();
+
+    let docs = normalized
+        .iter()
+        .filter(|record| {
+            record
+                .tag_ids
+                .iter()
+                .any(|tag_id| tag_ids.binary_search(tag_id).is_ok())
+        })
+        .map(|record| record.doc_id)
+        .collect::<Vec<_>>();
+    Ok(index_core::schema::BlockedIndex { tag_ids, docs })
+}
+
+fn build_group_index(
+    data: &crate::build::load::LoadedData,
+    dictionaries: &index_core::schema::Dictionaries,
+) -> anyhow::Result<index_core::schema::GroupIndex> {
+    let groups = index_core::util::BiMap::build(
+        data.groups
+            .iter()
+            .map(|group| group.group_id.clone())
+            .collect(),
+    );
+
+    let mut members = vec![Vec::new(); groups.len()];
+    for group in &data.groups {
+        let group_id = groups
+            .get_by_str(&group.group_id)
+            .expect("group dictionary contains every loaded group");
+        let mut artist_ids = group
+            .member_artist_ids
+            .iter()
+            .map(|value| {
+                dictionaries.artists.get_by_str(value).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "group {} refers to unknown artist_id {value}",
+                        group.group_id
+                    )
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        artist_ids.sort_unstable();
+        artist_ids.dedup();
+        members[group_id as usize] = artist_ids;
+    }
+    Ok(index_core::schema::GroupIndex { groups, members })
+}
+
 fn build_columns(
     normalized: &[crate::build::normalize::NormalizedClipRecord],
 ) -> index_core::schema::ColumnStore {
@@ -50,6 +139,18 @@ fn build_columns(
         channel_ids: normalized.iter().map(|record| record.channel_id).collect(),
         is_unlisteds: normalized.iter().map(|record| record.is_unlisted).collect(),
         embeddables: normalized.iter().map(|record| record.embeddable).collect(),
+        is_self_uploads: normalized
+            .iter()
+            .map(|record| record.is_self_upload)
+            .collect(),
+        is_official_channels: normalized
+            .iter()
+            .map(|record| record.is_official_channel)
+            .collect(),
+        has_graduated_artists: normalized
+            .iter()
+            .map(|record| record.has_graduated_artist)
+            .collect(),
         artist_id_lists: index_core::util::U32ListColumn::build(
             &normalized
                 .iter()
@@ -85,6 +186,13 @@ fn build_exact_indexes(
                 .or_default()
                 .push(record.doc_id);
         }
+        for tag_id in &record.clip_tag_ids {
+            exact_indexes
+                .clip_tag_docs
+                .entry(*tag_id)
+                .or_default()
+                .push(record.doc_id);
+        }
         exact_indexes
             .channel_docs
             .entry(record.channel_id)
@@ -94,6 +202,13 @@ fn build_exact_indexes(
             .push(record.doc_id);
         exact_indexes.embeddable_docs[usize::from(record.embeddable)]
             .push(record.doc_id);
+        exact_indexes.is_self_upload_docs[usize::from(record.is_self_upload)]
+            .push(record.doc_id);
+        exact_indexes.is_official_channel_docs[usize::from(record.is_official_channel)]
+            .push(record.doc_id);
+        exact_indexes.has_graduated_artist_docs
+            [usize::from(record.has_graduated_artist)]
+        .push(record.doc_id);
     }
 
     exact_indexes
@@ -12,7 +12,7 @@ pub(crate) fn build_dictionaries(
 
     for artist in &data.artists {
         artist_keys.insert(artist.artist_id.clone());
-        channel_keys.insert(artist.channel_id.clone());
+        channel_keys.extend(artist.channel_ids.iter().cloned());
     }
     for channel in &data.official_channels {
         channel_keys.insert(channel.channel_id.clone());
@@ -1,7 +1,9 @@
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LoadedArtist {
     pub(crate) artist_id: String,
-    pub(crate) channel_id: String,
+    /// メインのチャンネルを先頭に, サブチャンネルを含む
+    pub(crate) channel_ids: Vec<String>,
+    pub(crate) is_graduated: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,6 +11,18 @@ pub(crate) struct LoadedOfficialChannel {
     pub(crate) channel_id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LoadedGroup {
+    pub(crate) group_id: String,
+    pub(crate) member_artist_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LoadedTagParent {
+    pub(crate) tag_id: String,
+    pub(crate) parent_tag_id: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LoadedClipRecord {
     pub(crate) clip_uuid: String,
@@ -18,14 +32,21 @@ pub(crate) struct LoadedClipRecord {
     pub(crate) is_unlisted: bool,
     pub(crate) embeddable: bool,
     pub(crate) artist_ids: Vec<String>,
+    /// 動画のタグとクリップのタグの和集合
     pub(crate) tag_ids: Vec<String>,
+    /// クリップのタグのみ
+    pub(crate) clip_tag_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LoadedData {
     pub(crate) artists: Vec<LoadedArtist>,
     pub(crate) official_channels: Vec<LoadedOfficialChannel>,
+    pub(crate) groups: Vec<LoadedGroup>,
     pub(crate) tag_ids: Vec<String>,
+    pub(crate) tag_parents: Vec<LoadedTagParent>,
+    /// `blocked`なタグ
+    pub(crate) blocked_tag_ids: Vec<String>,
     pub(crate) clips: Vec<LoadedClipRecord>,
 }
 
@@ -34,10 +55,14 @@ pub(crate) fn load_data(music_root: &std::path::Path) -> anyhow::Result<LoadedDa
         .clone()
         .into_iter()
         .map(|(artist_id, artist)| {
-            let artist = artist.into_inner();
+            let channel_ids = artist
+                .channel_ids()
+                .map(|channel_id| channel_id.to_string())
+                .collect();
             LoadedArtist {
                 artist_id: artist_id.as_str().to_string(),
-                channel_id: artist.channel_id.to_string(),
+                channel_ids,
+                is_graduated: artist.into_inner().is_graduated,
             }
         })
         .collect::<Vec<_>>();
@@ -53,11 +78,42 @@ pub(crate) fn load_data(music_root: &std::path::Path) -> anyhow::Result<LoadedDa
         })
         .collect::<Vec<_>>();
 
+    let groups = artistctl::model::LOADED_GROUP_DATA
+        .sorted_ids()
+        .into_iter()
+        .map(|group_id| LoadedGroup {
+            group_id: group_id.to_string(),
+            member_artist_ids: artistctl::model::LOADED_GROUP_DATA
+                .members_of(group_id)
+                .unwrap_or_default()
+                .iter()
+                .map(|artist_id| artist_id.as_str().to_string())
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
     let tag_ids = tagctl::model::LOADED_VIDEO_TAG_DATA
         .sorted_ids()
         .into_iter()
         .map(str::to_owned)
         .collect::<Vec<_>>();
+    let tag_parents = tag_ids
+        .iter()
+        .filter_map(|tag_id| {
+            tagctl::model::LOADED_VIDEO_TAG_DATA
+                .parent_of(tag_id)
+                .map(|parent| LoadedTagParent {
+                    tag_id: tag_id.clone(),
+                    parent_tag_id: parent.as_str().to_string(),
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let blocked_tag_ids = tag_ids
+        .iter()
+        .filter(|tag_id| tagctl::model::LOADED_VIDEO_TAG_DATA.is_blocked(tag_id))
+        .cloned()
+        .collect::<Vec<_>>();
 
     let library = musictl::music_file::MusicLibraryRepository::load(music_root)?;
     let videos = library.into_videos()?;
@@ -72,6 +128,20 @@ pub(crate) fn load_data(music_root: &std::path::Path) -> anyhow::Result<LoadedDa
         video_tag_ids.sort_unstable();
 
         for clip in video.clips() {
+            let mut clip_tag_ids = clip
+                .clip_tag_ids()
+                .into_iter()
+                .map(str::to_owned)
+                .collect::<Vec<_>>();
+            clip_tag_ids.sort_unstable();
+            let mut tag_ids = video_tag_ids
+                .iter()
+                .chain(&clip_tag_ids)
+                .cloned()
+                .collect::<Vec<_>>();
+            tag_ids.sort_unstable();
+            tag_ids.dedup();
+
             clips.push(LoadedClipRecord {
                 clip_uuid: clip.uuid_string(),
                 video_id: video.video_id_string(),
@@ -84,7 +154,8 @@ pub(crate) fn load_data(music_root: &std::path::Path) -> anyhow::Result<LoadedDa
                     .into_iter()
                     .map(str::to_owned)
                     .collect::<Vec<_>>(),
-                tag_ids: video_tag_ids.clone(),
+                tag_ids,
+                clip_tag_ids,
             });
         }
     }
@@ -92,7 +163,10 @@ pub(crate) fn load_data(music_root: &std::path::Path) -> anyhow::Result<LoadedDa
     Ok(LoadedData {
         artists,
         official_channels,
+        groups,
         tag_ids,
+        tag_parents,
+        blocked_tag_ids,
         clips,
     })
 }
@@ -7,8 +7,12 @@ pub(crate) struct NormalizedClipRecord {
     pub(crate) channel_id: index_core::schema::ids::ChannelId,
     pub(crate) is_unlisted: bool,
     pub(crate) embeddable: bool,
+    pub(crate) is_self_upload: bool,
+    pub(crate) is_official_channel: bool,
+    pub(crate) has_graduated_artist: bool,
     pub(crate) artist_ids: Vec<index_core::schema::ids::ArtistId>,
     pub(crate) tag_ids: Vec<index_core::schema::ids::TagId>,
+    pub(crate) clip_tag_ids: Vec<index_core::schema::ids::TagId>,
 }
 
 pub(crate) fn normalize_clip_records(
@@ -17,16 +21,77 @@ pub(crate) fn normalize_clip_records(
 ) -> anyhow::Result<Vec<NormalizedClipRecord>> {
     let mut clips = data.clips.clone();
     clips.sort_by(|left, right| left.clip_uuid.cmp(&right.clip_uuid));
+    let attributes = ArtistAttributes::new(data);
 
     clips
         .into_iter()
         .enumerate()
-        .map(|(doc_id, clip)| normalize_clip_record(dictionaries, doc_id, clip))
+        .map(|(doc_id, clip)| {
+            normalize_clip_record(dictionaries, &attributes, doc_id, clip)
+        })
         .collect()
 }
 
+/// クリップごとの属性を前計算するための, アーティストとチャンネルの参照表
+struct ArtistAttributes<'a> {
+    /// チャンネルID(サブチャンネルを含む) -> アーティストID
+    channel_artists: std::collections::HashMap<&'a str, &'a str>,
+    graduated_artist_ids: std::collections::HashSet<&'a str>,
+    official_channel_ids: std::collections::HashSet<&'a str>,
+}
+
+impl<'a> ArtistAttributes<'a> {
+    fn new(data: &'a crate::build::load::LoadedData) -> Self {
+        Self {
+            channel_artists: data
+                .artists
+                .iter()
+                .flat_map(|artist| {
+                    artist.channel_ids.iter().map(|channel_id| {
+                        (channel_id.as_str(), artist.artist_id.as_str())
+                    })
+                })
+                .collect(),
+            graduated_artist_ids: data
+                .artists
+                .iter()
+                .filter(|artist| artist.is_graduated)
+                .map(|artist| artist.artist_id.as_str())
+                .collect(),
+            official_channel_ids: data
+                .official_channels
+                .iter()
+                .map(|channel| channel.channel_id.as_str())
+                .collect(),
+        }
+    }
+
+    /// 動画のチャンネルがクリップのアーティストのいずれかのチャンネルか
+    fn is_self_upload(&self, clip: &crate::build::load::LoadedClipRecord) -> bool {
+        self.channel_artists
+            .get(clip.channel_id.as_str())
+            .is_some_and(|owner| {
+                clip.artist_ids.iter().any(|artist_id| artist_id == owner)
+            })
+    }
+
+    fn is_official_channel(&self, clip: &crate::build::load::LoadedClipRecord) -> bool {
+        self.official_channel_ids.contains(clip.channel_id.as_str())
+    }
+
+    fn has_graduated_artist(
+        &self,
+        clip: &crate::build::load::LoadedClipRecord,
+    ) -> bool {
+        clip.artist_ids
+            .iter()
+            .any(|artist_id| self.graduated_artist_ids.contains(artist_id.as_str()))
+    }
+}
+
 fn normalize_clip_record(
     dictionaries: &index_core::schema::Dictionaries,
+    attributes: &ArtistAttributes<'_>,
     doc_id: usize,
     clip: crate::build::load::LoadedClipRecord,
 ) -> anyhow::Result<NormalizedClipRecord> {
@@ -45,20 +110,9 @@ fn normalize_clip_record(
     artist_ids.sort_unstable();
     artist_ids.dedup();
 
-    let mut tag_ids = clip
-        .tag_ids
-        .iter()
-        .map(|tag_id| {
-            dictionaries.tags.get_by_str(tag_id).ok_or_else(|| {
-                anyhow::anyhow!(
-                    "clip {} refers to unknown tag_id {tag_id}",
-                    clip.clip_uuid
-                )
-            })
-        })
-        .collect::<anyhow::Result<Vec<_>>>()?;
-    tag_ids.sort_unstable();
-    tag_ids.dedup();
+    let tag_ids = normalize_tag_ids(dictionaries, &clip.clip_uuid, &clip.tag_ids)?;
+    let clip_tag_ids =
+        normalize_tag_ids(dictionaries, &clip.clip_uuid, &clip.clip_tag_ids)?;
 
     Ok(NormalizedClipRecord {
         doc_id: u32::try_from(doc_id).expect("doc_id fits within u32"),
@@ -87,7 +141,29 @@ fn normalize_clip_record(
             })?,
         is_unlisted: clip.is_unlisted,
         embeddable: clip.embeddable,
+        is_self_upload: attributes.is_self_upload(&clip),
+        is_official_channel: attributes.is_official_channel(&clip),
+        has_graduated_artist: attributes.has_graduated_artist(&clip),
         artist_ids,
         tag_ids,
+        clip_tag_ids,
     })
 }
+
+fn normalize_tag_ids(
+    dictionaries: &index_core::schema::Dictionaries,
+    clip_uuid: &str,
+    tag_ids: &[String],
+) -> anyhow::Result<Vec<index_core::schema::ids::TagId>> {
+    let mut tag_ids = tag_ids
+        .iter()
+        .map(|tag_id| {
+            dictionaries.tags.get_by_str(tag_id).ok_or_else(|| {
+                anyhow::anyhow!("clip {clip_uuid} refers to unknown tag_id {tag_id}")
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    tag_ids.sort_unstable();
+    tag_ids.dedup();
+    Ok(tag_ids)
+}
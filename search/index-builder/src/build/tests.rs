@@ -37,17 +37,28 @@ fn sample_loaded_data() -> crate::build::load::LoadedData {
         artists: vec![
             crate::build::load::LoadedArtist {
                 artist_id: artist_id_2.clone(),
-                channel_id: channel_id_2.clone(),
+                channel_ids: vec![channel_id_2.clone()],
+                is_graduated: true,
             },
             crate::build::load::LoadedArtist {
                 artist_id: artist_id_1.clone(),
-                channel_id: channel_id_1.clone(),
+                channel_ids: vec![channel_id_1],
+                is_graduated: false,
             },
         ],
         official_channels: vec![crate::build::load::LoadedOfficialChannel {
-            channel_id: official_channel_id,
+            channel_id: official_channel_id.clone(),
+        }],
+        groups: vec![crate::build::load::LoadedGroup {
+            group_id: "test-unit".to_string(),
+            member_artist_ids: vec![artist_id_2.clone(), artist_id_1.clone()],
         }],
         tag_ids: vec![tag_id_2.clone(), tag_id_1.clone()],
+        tag_parents: vec![crate::build::load::LoadedTagParent {
+            tag_id: tag_id_2.clone(),
+            parent_tag_id: tag_id_1.clone(),
+        }],
+        blocked_tag_ids: vec![tag_id_1.clone()],
         clips: vec![
             crate::build::load::LoadedClipRecord {
                 clip_uuid: clip_uuid_2,
@@ -57,17 +68,19 @@ fn sample_loaded_data() -> crate::build::load::LoadedData {
                 is_unlisted: true,
                 embeddable: false,
                 artist_ids: vec![artist_id_2, artist_id_1.clone()],
-                tag_ids: vec![tag_id_2.clone(), tag_id_1.clone(), tag_id_1],
+                tag_ids: vec![tag_id_2.clone(), tag_id_1.clone(), tag_id_1.clone()],
+                clip_tag_ids: vec![tag_id_1],
             },
             crate::build::load::LoadedClipRecord {
                 clip_uuid: clip_uuid_1,
                 video_id: video_id_1,
                 published_at: 10,
-                channel_id: channel_id_1,
+                channel_id: official_channel_id,
                 is_unlisted: false,
                 embeddable: true,
                 artist_ids: vec![artist_id_1],
                 tag_ids: vec![tag_id_2],
+                clip_tag_ids: vec![],
             },
         ],
     }
@@ -87,11 +100,49 @@ fn test_build_search_index_from_loaded_data() {
     assert_eq!(index.columns.artist_id_lists.get(0), &[0]);
     assert_eq!(index.columns.artist_id_lists.get(1), &[0, 1]);
     assert_eq!(index.columns.tag_id_lists.get(1), &[0, 1]);
+    assert_eq!(index.tag_hierarchy.parents, vec![None, Some(0)]);
+    assert_eq!(index.blocked.tag_ids, vec![0]);
+    assert_eq!(index.blocked.docs, vec![1]);
+    assert_eq!(index.groups.groups.get_by_str("test-unit"), Some(0));
+    assert_eq!(index.groups.members, vec![vec![0, 1]]);
+    assert_eq!(index.exact_indexes.clip_tag_docs.len(), 1);
+    assert_eq!(index.exact_indexes.clip_tag_docs[&0], vec![1]);
+    assert_eq!(index.exact_indexes.tag_docs[&0], vec![1]);
     assert_eq!(index.sort_indexes.published_at.doc_ids_asc(), &[0, 1]);
     assert_eq!(index.exact_indexes.is_unlisted_docs[0], vec![0]);
     assert_eq!(index.exact_indexes.is_unlisted_docs[1], vec![1]);
     assert_eq!(index.exact_indexes.embeddable_docs[0], vec![1]);
     assert_eq!(index.exact_indexes.embeddable_docs[1], vec![0]);
+    assert_eq!(index.columns.is_self_uploads, vec![false, true]);
+    assert_eq!(index.columns.is_official_channels, vec![true, false]);
+    assert_eq!(index.columns.has_graduated_artists, vec![false, true]);
+    assert_eq!(index.exact_indexes.is_self_upload_docs[1], vec![1]);
+    assert_eq!(index.exact_indexes.is_official_channel_docs[1], vec![0]);
+    assert_eq!(index.exact_indexes.has_graduated_artist_docs[1], vec![1]);
+}
+
+#[test]
+fn test_build_search_index_from_loaded_data_maps_sub_channel_to_artist() {
+    let sub_channel_id = "UC4444444444444444444444".to_string();
+    let mut data = sample_loaded_data();
+    data.artists[1].channel_ids.push(sub_channel_id.clone());
+    data.clips[1].channel_id = sub_channel_id.clone();
+
+    let index = crate::build::assemble::build_search_index_from_loaded_data(
+        data,
+        test_dataset_build_id(),
+    )
+    .unwrap();
+
+    assert!(
+        index
+            .dictionaries
+            .channels
+            .get_by_str(&sub_channel_id)
+            .is_some()
+    );
+    assert_eq!(index.columns.is_self_uploads, vec![true, true]);
+    assert_eq!(index.columns.is_official_channels, vec![false, false]);
 }
 
 #[test]
@@ -107,6 +158,24 @@ fn test_build_search_index_from_loaded_data_rejects_unknown_channel() {
     assert!(err.to_string().contains("unknown channel_id"));
 }
 
+#[test]
+fn test_build_search_index_from_loaded_data_rejects_unknown_group_member() {
+    let mut data = sample_loaded_data();
+    data.groups[0]
+        .member_artist_ids
+        .push("unknown-artist".to_string());
+
+    let err = crate::build::assemble::build_search_index_from_loaded_data(
+        data,
+        test_dataset_build_id(),
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("group test-unit refers to unknown artist_id unknown-artist")
+    );
+}
+
 #[test]
 fn test_build_search_index_binary_roundtrip() {
     let index = crate::build::assemble::build_search_index_from_loaded_data(
@@ -21,8 +21,10 @@ pub(crate) struct BuildArgs {
     pub(crate) dataset_build_id: cmn_rs::min_json::DatasetBuildId,
 }
 
+pub const DEFAULT_OUTPUT_PATH: &str = "public/search/search_index.bin";
+
 fn default_output_path() -> String {
-    "public/search/search_index.bin".to_string()
+    DEFAULT_OUTPUT_PATH.to_string()
 }
 
 impl Cli {
@@ -11,6 +11,16 @@ pub(super) const SECTION_DICT_VIDEOS: u32 = 0x1001;
 pub(super) const SECTION_DICT_CHANNELS: u32 = 0x1002;
 pub(super) const SECTION_DICT_ARTISTS: u32 = 0x1003;
 pub(super) const SECTION_DICT_TAGS: u32 = 0x1004;
+/// 任意セクション. 無い索引はどのタグも親を持たないものとして読む。
+pub(super) const SECTION_TAG_PARENTS: u32 = 0x1005;
+/// `SECTION_TAG_PARENTS` で親が無いことを表す値
+pub(super) const NO_PARENT_TAG_ID: u32 = u32::MAX;
+/// 任意セクション. 無い索引はブロックするタグが無いものとして読む。
+pub(super) const SECTION_BLOCKED_TAG_IDS: u32 = 0x1006;
+/// 任意セクション. 無い索引はグループが無いものとして読む。
+pub(super) const SECTION_DICT_GROUPS: u32 = 0x1007;
+/// 任意セクション. `SECTION_DICT_GROUPS` と組で書く。
+pub(super) const SECTION_GROUP_MEMBERS: u32 = 0x1008;
 pub(super) const SECTION_COLUMN_CLIP_IDS: u32 = 0x2000;
 pub(super) const SECTION_COLUMN_VIDEO_IDS: u32 = 0x2001;
 pub(super) const SECTION_COLUMN_PUBLISHED_ATS: u32 = 0x2002;
@@ -19,11 +29,27 @@ pub(super) const SECTION_COLUMN_IS_UNLISTEDS: u32 = 0x2004;
 pub(super) const SECTION_COLUMN_EMBEDDABLES: u32 = 0x2005;
 pub(super) const SECTION_COLUMN_ARTIST_ID_LISTS: u32 = 0x2006;
 pub(super) const SECTION_COLUMN_TAG_ID_LISTS: u32 = 0x2007;
+/// 任意セクション. 無い索引はどのクリップも該当しないものとして読む。
+pub(super) const SECTION_COLUMN_IS_SELF_UPLOADS: u32 = 0x2008;
+/// 任意セクション. 無い索引はどのクリップも該当しないものとして読む。
+pub(super) const SECTION_COLUMN_IS_OFFICIAL_CHANNELS: u32 = 0x2009;
+/// 任意セクション. 無い索引はどのクリップも該当しないものとして読む。
+pub(super) const SECTION_COLUMN_HAS_GRADUATED_ARTISTS: u32 = 0x200a;
 pub(super) const SECTION_EXACT_ARTIST_DOCS: u32 = 0x3000;
 pub(super) const SECTION_EXACT_TAG_DOCS: u32 = 0x3001;
 pub(super) const SECTION_EXACT_CHANNEL_DOCS: u32 = 0x3002;
 pub(super) const SECTION_EXACT_IS_UNLISTED_DOCS: u32 = 0x3003;
 pub(super) const SECTION_EXACT_EMBEDDABLE_DOCS: u32 = 0x3004;
+/// 任意セクション. 無い索引はクリップのタグが無いものとして読む。
+pub(super) const SECTION_EXACT_CLIP_TAG_DOCS: u32 = 0x3005;
+/// 任意セクション. `SECTION_BLOCKED_TAG_IDS` と組で書く。
+pub(super) const SECTION_EXACT_BLOCKED_DOCS: u32 = 0x3006;
+/// 任意セクション. `SECTION_COLUMN_IS_SELF_UPLOADS` と組で書く。
+pub(super) const SECTION_EXACT_IS_SELF_UPLOAD_DOCS: u32 = 0x3007;
+/// 任意セクション. `SECTION_COLUMN_IS_OFFICIAL_CHANNELS` と組で書く。
+pub(super) const SECTION_EXACT_IS_OFFICIAL_CHANNEL_DOCS: u32 = 0x3008;
+/// 任意セクション. `SECTION_COLUMN_HAS_GRADUATED_ARTISTS` と組で書く。
+pub(super) const SECTION_EXACT_HAS_GRADUATED_ARTIST_DOCS: u32 = 0x3009;
 pub(super) const SECTION_SORT_PUBLISHED_AT: u32 = 0x4000;
 
 pub(super) const REQUIRED_SECTION_IDS: &[u32] = &[
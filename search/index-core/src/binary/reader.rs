@@ -222,6 +222,75 @@ impl<'a> SearchIndexReader<'a> {
         self.read_dictionary_view(crate::binary::format::SECTION_DICT_TAGS)
     }
 
+    /// タグごとの親タグ. 古い索引でセクションが無いときは `None`
+    pub fn tag_parents(
+        &self,
+    ) -> Result<Option<Vec<Option<crate::schema::ids::TagId>>>, crate::binary::Error>
+    {
+        use crate::binary::format::{NO_PARENT_TAG_ID, SECTION_TAG_PARENTS};
+
+        if !self.sections.contains_key(&SECTION_TAG_PARENTS) {
+            return Ok(None);
+        }
+        let tag_count = self.tags_dictionary()?.len();
+        let view = self.read_u32_vector_view(SECTION_TAG_PARENTS, tag_count)?;
+        let parents = view
+            .iter()
+            .map(|parent| match parent {
+                NO_PARENT_TAG_ID => Ok(None),
+                parent if (parent as usize) < tag_count => Ok(Some(parent)),
+                _ => Err(crate::binary::Error::InvalidFormat("tag_parents")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(parents))
+    }
+
+    /// `blocked` なタグ. 昇順. 古い索引でセクションが無いときは `None`
+    pub fn blocked_tag_ids(
+        &self,
+    ) -> Result<Option<crate::binary::U32SliceView<'a>>, crate::binary::Error> {
+        self.read_optional_sorted_ids(
+            crate::binary::format::SECTION_BLOCKED_TAG_IDS,
+            self.tags_dictionary()?.len(),
+            "blocked_tag_ids",
+        )
+    }
+
+    /// グループの辞書. 古い索引でセクションが無いときは `None`
+    pub fn groups_dictionary(
+        &self,
+    ) -> Result<Option<crate::binary::StringDictionaryView<'a>>, crate::binary::Error>
+    {
+        let section_id = crate::binary::format::SECTION_DICT_GROUPS;
+        if !self.sections.contains_key(&section_id) {
+            return Ok(None);
+        }
+        self.read_dictionary_view(section_id).map(Some)
+    }
+
+    /// グループごとのメンバーの `artist_id`. 昇順. 古い索引でセクションが無いときは `None`
+    pub fn group_members(
+        &self,
+    ) -> Result<Option<crate::binary::U32ListColumnView<'a>>, crate::binary::Error>
+    {
+        let section_id = crate::binary::format::SECTION_GROUP_MEMBERS;
+        if !self.sections.contains_key(&section_id) {
+            return Ok(None);
+        }
+        let Some(groups) = self.groups_dictionary()? else {
+            return Err(crate::binary::Error::InvalidFormat(
+                "group members without group dictionary",
+            ));
+        };
+        self.read_u32_list_column_view(
+            section_id,
+            groups.len(),
+            self.artists_dictionary()?.len(),
+            "group_members",
+        )
+        .map(Some)
+    }
+
     pub fn clip_ids(
         &self,
     ) -> Result<crate::binary::U32SliceView<'a>, crate::binary::Error> {
@@ -282,6 +351,33 @@ impl<'a> SearchIndexReader<'a> {
         )
     }
 
+    /// 古い索引でセクションが無いときは `None`
+    pub fn is_self_uploads(
+        &self,
+    ) -> Result<Option<crate::binary::BoolSliceView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_vector_view(
+            crate::binary::format::SECTION_COLUMN_IS_SELF_UPLOADS,
+        )
+    }
+
+    /// 古い索引でセクションが無いときは `None`
+    pub fn is_official_channels(
+        &self,
+    ) -> Result<Option<crate::binary::BoolSliceView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_vector_view(
+            crate::binary::format::SECTION_COLUMN_IS_OFFICIAL_CHANNELS,
+        )
+    }
+
+    /// 古い索引でセクションが無いときは `None`
+    pub fn has_graduated_artists(
+        &self,
+    ) -> Result<Option<crate::binary::BoolSliceView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_vector_view(
+            crate::binary::format::SECTION_COLUMN_HAS_GRADUATED_ARTISTS,
+        )
+    }
+
     pub fn artist_id_lists(
         &self,
     ) -> Result<crate::binary::U32ListColumnView<'a>, crate::binary::Error> {
@@ -322,6 +418,30 @@ impl<'a> SearchIndexReader<'a> {
         )
     }
 
+    /// クリップ自身のタグの postings. 古い索引でセクションが無いときは `None`
+    pub fn clip_tag_docs(
+        &self,
+    ) -> Result<Option<crate::binary::DensePostingsView<'a>>, crate::binary::Error>
+    {
+        let section_id = crate::binary::format::SECTION_EXACT_CLIP_TAG_DOCS;
+        if !self.sections.contains_key(&section_id) {
+            return Ok(None);
+        }
+        self.read_dense_postings_view(section_id, self.tags_dictionary()?.len())
+            .map(Some)
+    }
+
+    /// `blocked` なタグを持つ doc. 昇順. 古い索引でセクションが無いときは `None`
+    pub fn blocked_docs(
+        &self,
+    ) -> Result<Option<crate::binary::U32SliceView<'a>>, crate::binary::Error> {
+        self.read_optional_sorted_ids(
+            crate::binary::format::SECTION_EXACT_BLOCKED_DOCS,
+            self.header.record_count as usize,
+            "blocked_docs",
+        )
+    }
+
     pub fn channel_docs(
         &self,
     ) -> Result<crate::binary::DensePostingsView<'a>, crate::binary::Error> {
@@ -353,6 +473,36 @@ impl<'a> SearchIndexReader<'a> {
         Ok(view)
     }
 
+    /// 古い索引でセクションが無いときは `None`
+    pub fn is_self_upload_docs(
+        &self,
+    ) -> Result<Option<crate::binary::BoolPostingsView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_postings_view(
+            crate::binary::format::SECTION_EXACT_IS_SELF_UPLOAD_DOCS,
+            self.is_self_uploads()?,
+        )
+    }
+
+    /// 古い索引でセクションが無いときは `None`
+    pub fn is_official_channel_docs(
+        &self,
+    ) -> Result<Option<crate::binary::BoolPostingsView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_postings_view(
+            crate::binary::format::SECTION_EXACT_IS_OFFICIAL_CHANNEL_DOCS,
+            self.is_official_channels()?,
+        )
+    }
+
+    /// 古い索引でセクションが無いときは `None`
+    pub fn has_graduated_artist_docs(
+        &self,
+    ) -> Result<Option<crate::binary::BoolPostingsView<'a>>, crate::binary::Error> {
+        self.read_optional_bool_postings_view(
+            crate::binary::format::SECTION_EXACT_HAS_GRADUATED_ARTIST_DOCS,
+            self.has_graduated_artists()?,
+        )
+    }
+
     pub fn published_at_sort(
         &self,
     ) -> Result<crate::binary::SortIndexView<'a>, crate::binary::Error> {
@@ -421,6 +571,29 @@ impl<'a> SearchIndexReader<'a> {
         crate::binary::U32SliceView::new(payload)
     }
 
+    /// 昇順で重複が無く, `max_id` 未満の id 列を持つ任意セクションを読む
+    fn read_optional_sorted_ids(
+        &self,
+        section_id: u32,
+        max_id: usize,
+        name: &'static str,
+    ) -> Result<Option<crate::binary::U32SliceView<'a>>, crate::binary::Error> {
+        let Some(section) = self.sections.get(&section_id).copied() else {
+            return Ok(None);
+        };
+        let view =
+            self.read_u32_vector_view(section_id, section.item_count as usize)?;
+        validate_ids_in_range(&view, max_id, name)?;
+        let mut previous = None;
+        for id in view.iter() {
+            if previous.is_some_and(|previous| previous >= id) {
+                return Err(crate::binary::Error::InvalidFormat(name));
+            }
+            previous = Some(id);
+        }
+        Ok(Some(view))
+    }
+
     fn read_i64_vector_view(
         &self,
         section_id: u32,
@@ -461,6 +634,34 @@ impl<'a> SearchIndexReader<'a> {
         crate::binary::BoolSliceView::new(payload)
     }
 
+    fn read_optional_bool_vector_view(
+        &self,
+        section_id: u32,
+    ) -> Result<Option<crate::binary::BoolSliceView<'a>>, crate::binary::Error> {
+        if !self.sections.contains_key(&section_id) {
+            return Ok(None);
+        }
+        self.read_bool_vector_view(section_id, self.header.record_count as usize)
+            .map(Some)
+    }
+
+    /// postings だけがあり列が無い索引は壊れているとみなす
+    fn read_optional_bool_postings_view(
+        &self,
+        section_id: u32,
+        column: Option<crate::binary::BoolSliceView<'a>>,
+    ) -> Result<Option<crate::binary::BoolPostingsView<'a>>, crate::binary::Error> {
+        if !self.sections.contains_key(&section_id) {
+            return Ok(None);
+        }
+        let column = column.ok_or(crate::binary::Error::InvalidFormat(
+            "bool postings without bool column",
+        ))?;
+        let view = self.read_bool_postings_view(section_id)?;
+        view.validate_matches_column(column)?;
+        Ok(Some(view))
+    }
+
     fn read_u32_list_column_view(
         &self,
         section_id: u32,
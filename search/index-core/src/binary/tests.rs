@@ -55,6 +55,9 @@ pub(crate) fn sample_index() -> crate::schema::SearchIndex {
             artists,
             tags,
         },
+        tag_hierarchy: crate::schema::TagHierarchy {
+            parents: vec![None, Some(0), Some(1)],
+        },
         columns: crate::schema::ColumnStore {
             clip_ids: vec![0, 1, 2],
             video_ids: vec![2, 0, 1],
@@ -62,6 +65,9 @@ pub(crate) fn sample_index() -> crate::schema::SearchIndex {
             channel_ids: vec![1, 0, 1],
             is_unlisteds: vec![false, true, false],
             embeddables: vec![true, false, true],
+            is_self_uploads: vec![true, false, false],
+            is_official_channels: vec![false, true, false],
+            has_graduated_artists: vec![false, false, true],
             artist_id_lists: crate::util::U32ListColumn::build(&[
                 vec![0, 2],
                 vec![],
@@ -84,12 +90,28 @@ pub(crate) fn sample_index() -> crate::schema::SearchIndex {
                 (1, vec![0]),
                 (2, vec![1]),
             ]),
+            clip_tag_docs: std::collections::HashMap::from([(2, vec![1])]),
             channel_docs: std::collections::HashMap::from([
                 (0, vec![1]),
                 (1, vec![0, 2]),
             ]),
             is_unlisted_docs: [vec![0, 2], vec![1]],
             embeddable_docs: [vec![1], vec![0, 2]],
+            is_self_upload_docs: [vec![1, 2], vec![0]],
+            is_official_channel_docs: [vec![0, 2], vec![1]],
+            has_graduated_artist_docs: [vec![0, 1], vec![2]],
+        },
+        blocked: crate::schema::BlockedIndex {
+            tag_ids: vec![2],
+            docs: vec![1],
+        },
+        groups: crate::schema::GroupIndex {
+            groups: crate::util::BiMap::from_ordered_strings(vec![
+                "group-a".to_string(),
+                "group-b".to_string(),
+            ])
+            .unwrap(),
+            members: vec![vec![0, 2], vec![1]],
         },
         sort_indexes: crate::schema::SortIndexes {
             published_at: crate::schema::SortIndex::new(vec![1, 0, 2]),
@@ -193,7 +215,7 @@ fn test_writer_emits_expected_header_and_monotonic_aligned_sections() {
     );
     assert_eq!(
         section_count(&bytes),
-        super::format::REQUIRED_SECTION_IDS.len() as u32,
+        super::format::REQUIRED_SECTION_IDS.len() as u32 + 12,
     );
     assert_eq!(
         super::codec::read_u32_at(&bytes, 16).unwrap(),
@@ -298,6 +320,119 @@ fn test_writer_rejects_exact_index_mismatch() {
     );
 }
 
+#[test]
+fn test_writer_rejects_clip_tag_docs_outside_tag_docs() {
+    let mut index = sample_index();
+    index.exact_indexes.clip_tag_docs.insert(1, vec![2]);
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat(
+            "clip tag postings are not a subset of tag postings"
+        ),
+    );
+}
+
+#[test]
+fn test_writer_rejects_tag_hierarchy_cycle() {
+    let mut index = sample_index();
+    index.tag_hierarchy.parents[0] = Some(2);
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat("tag hierarchy has a cycle")
+    );
+}
+
+#[test]
+fn test_writer_rejects_tag_hierarchy_length_mismatch() {
+    let mut index = sample_index();
+    index.tag_hierarchy.parents.pop();
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat(
+            "tag hierarchy length does not match tag dictionary"
+        ),
+    );
+}
+
+#[test]
+fn test_writer_rejects_blocked_docs_mismatch() {
+    let mut index = sample_index();
+    index.blocked.docs = vec![0, 1];
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat("blocked docs do not match blocked tag ids"),
+    );
+}
+
+#[test]
+fn test_writer_rejects_blocked_tag_id_out_of_range() {
+    let mut index = sample_index();
+    index.blocked.tag_ids = vec![2, 3];
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat(
+            "blocked tag ids must be sorted, unique and in range"
+        ),
+    );
+}
+
+#[test]
+fn test_writer_rejects_group_members_length_mismatch() {
+    let mut index = sample_index();
+    index.groups.members.pop();
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat(
+            "group members length does not match group dictionary"
+        ),
+    );
+}
+
+#[test]
+fn test_writer_rejects_group_without_members() {
+    let mut index = sample_index();
+    index.groups.members[1].clear();
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat("group must have at least one member"),
+    );
+}
+
+#[test]
+fn test_writer_rejects_group_member_out_of_range() {
+    let mut index = sample_index();
+    index.groups.members[1] = vec![1, 3];
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(err, super::Error::InvalidFormat("group_members"));
+}
+
+#[test]
+fn test_writer_rejects_clip_flag_docs_mismatch() {
+    let mut index = sample_index();
+    index.exact_indexes.has_graduated_artist_docs = [vec![0, 2], vec![1]];
+
+    let err = super::serialize_search_index(&index).unwrap_err();
+    assert_eq!(
+        err,
+        super::Error::InvalidFormat("exact indexes do not match column values"),
+    );
+}
+
 #[test]
 fn test_writer_rejects_sort_index_mismatch() {
     let mut index = sample_index();
@@ -352,6 +487,240 @@ fn test_search_index_reader_exposes_borrowed_section_views() {
     assert_eq!(sort.doc_ids_asc().to_vec(), vec![1, 0, 2]);
 }
 
+#[test]
+fn test_search_index_reader_reads_clip_tag_docs() {
+    let index = sample_index();
+    let bytes = serialize_index(&index);
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+
+    let clip_tag_docs = reader.clip_tag_docs().unwrap().unwrap();
+    assert_eq!(clip_tag_docs.get(2).unwrap().unwrap().to_vec(), vec![1]);
+    assert_eq!(
+        clip_tag_docs.get(0).unwrap().unwrap().to_vec(),
+        Vec::<u32>::new()
+    );
+}
+
+#[test]
+fn test_search_index_reader_treats_missing_clip_tag_docs_as_none() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section = read_section(&bytes, super::format::SECTION_EXACT_CLIP_TAG_DOCS);
+    write_u32_at(
+        &mut bytes,
+        section_entry_offset(section.table_index),
+        0x7fff,
+    );
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert!(reader.clip_tag_docs().unwrap().is_none());
+    assert_eq!(
+        reader.tag_docs().unwrap().get(2).unwrap().unwrap().to_vec(),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_search_index_reader_reads_tag_parents() {
+    let index = sample_index();
+    let bytes = serialize_index(&index);
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+
+    assert_eq!(
+        reader.tag_parents().unwrap(),
+        Some(vec![None, Some(0), Some(1)]),
+    );
+}
+
+#[test]
+fn test_search_index_reader_treats_missing_tag_parents_as_none() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section = read_section(&bytes, super::format::SECTION_TAG_PARENTS);
+    write_u32_at(
+        &mut bytes,
+        section_entry_offset(section.table_index),
+        0x7fff,
+    );
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(reader.tag_parents().unwrap(), None);
+}
+
+#[test]
+fn test_search_index_reader_rejects_tag_parent_out_of_range() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section = read_section(&bytes, super::format::SECTION_TAG_PARENTS);
+    let offset = section_payload_range(section).start;
+    write_u32_at(&mut bytes, offset, 3);
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(
+        reader.tag_parents().unwrap_err(),
+        super::Error::InvalidFormat("tag_parents")
+    );
+}
+
+#[test]
+fn test_search_index_reader_reads_blocked_sections() {
+    let index = sample_index();
+    let bytes = serialize_index(&index);
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+
+    assert_eq!(reader.blocked_tag_ids().unwrap().unwrap().to_vec(), vec![2]);
+    assert_eq!(reader.blocked_docs().unwrap().unwrap().to_vec(), vec![1]);
+}
+
+#[test]
+fn test_search_index_reader_treats_missing_blocked_sections_as_none() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    for section_id in [
+        super::format::SECTION_BLOCKED_TAG_IDS,
+        super::format::SECTION_EXACT_BLOCKED_DOCS,
+    ] {
+        let section = read_section(&bytes, section_id);
+        write_u32_at(
+            &mut bytes,
+            section_entry_offset(section.table_index),
+            0x7fff - section.table_index as u32,
+        );
+    }
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert!(reader.blocked_tag_ids().unwrap().is_none());
+    assert!(reader.blocked_docs().unwrap().is_none());
+}
+
+#[test]
+fn test_search_index_reader_rejects_blocked_doc_out_of_range() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section = read_section(&bytes, super::format::SECTION_EXACT_BLOCKED_DOCS);
+    let offset = section_payload_range(section).start;
+    write_u32_at(&mut bytes, offset, 3);
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(
+        reader.blocked_docs().unwrap_err(),
+        super::Error::InvalidFormat("blocked_docs")
+    );
+}
+
+#[test]
+fn test_search_index_reader_reads_group_sections() {
+    let index = sample_index();
+    let bytes = serialize_index(&index);
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+
+    let groups = reader.groups_dictionary().unwrap().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups.get(1).unwrap(), Some("group-b"));
+    let members = reader.group_members().unwrap().unwrap();
+    assert_eq!(members.get(0).unwrap().unwrap().to_vec(), vec![0, 2]);
+    assert_eq!(members.get(1).unwrap().unwrap().to_vec(), vec![1]);
+}
+
+#[test]
+fn test_search_index_reader_treats_missing_group_sections_as_none() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    for section_id in [
+        super::format::SECTION_DICT_GROUPS,
+        super::format::SECTION_GROUP_MEMBERS,
+    ] {
+        let section = read_section(&bytes, section_id);
+        write_u32_at(
+            &mut bytes,
+            section_entry_offset(section.table_index),
+            0x7fff - section.table_index as u32,
+        );
+    }
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert!(reader.groups_dictionary().unwrap().is_none());
+    assert!(reader.group_members().unwrap().is_none());
+}
+
+#[test]
+fn test_search_index_reader_rejects_group_members_without_dictionary() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section = read_section(&bytes, super::format::SECTION_DICT_GROUPS);
+    write_u32_at(
+        &mut bytes,
+        section_entry_offset(section.table_index),
+        0x7fff,
+    );
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(
+        reader.group_members().unwrap_err(),
+        super::Error::InvalidFormat("group members without group dictionary")
+    );
+}
+
+#[test]
+fn test_search_index_reader_reads_clip_flag_sections() {
+    let index = sample_index();
+    let bytes = serialize_index(&index);
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+
+    assert_eq!(
+        reader.is_self_uploads().unwrap().unwrap().to_vec(),
+        vec![true, false, false],
+    );
+    let self_upload_docs = reader.is_self_upload_docs().unwrap().unwrap();
+    assert_eq!(self_upload_docs.true_docs().to_vec(), vec![0]);
+    let official_channel_docs = reader.is_official_channel_docs().unwrap().unwrap();
+    assert_eq!(official_channel_docs.true_docs().to_vec(), vec![1]);
+    let graduated_artist_docs = reader.has_graduated_artist_docs().unwrap().unwrap();
+    assert_eq!(graduated_artist_docs.true_docs().to_vec(), vec![2]);
+    assert_eq!(graduated_artist_docs.false_docs().to_vec(), vec![0, 1]);
+}
+
+#[test]
+fn test_search_index_reader_treats_missing_clip_flag_sections_as_none() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    for section_id in [
+        super::format::SECTION_COLUMN_IS_SELF_UPLOADS,
+        super::format::SECTION_EXACT_IS_SELF_UPLOAD_DOCS,
+    ] {
+        let section = read_section(&bytes, section_id);
+        write_u32_at(
+            &mut bytes,
+            section_entry_offset(section.table_index),
+            0x7fff - section.table_index as u32,
+        );
+    }
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert!(reader.is_self_uploads().unwrap().is_none());
+    assert!(reader.is_self_upload_docs().unwrap().is_none());
+    assert!(reader.is_official_channel_docs().unwrap().is_some());
+}
+
+#[test]
+fn test_search_index_reader_rejects_clip_flag_docs_without_column() {
+    let index = sample_index();
+    let mut bytes = serialize_index(&index);
+    let section =
+        read_section(&bytes, super::format::SECTION_COLUMN_HAS_GRADUATED_ARTISTS);
+    write_u32_at(
+        &mut bytes,
+        section_entry_offset(section.table_index),
+        0x7fff,
+    );
+
+    let reader = super::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(
+        reader.has_graduated_artist_docs().unwrap_err(),
+        super::Error::InvalidFormat("bool postings without bool column")
+    );
+}
+
 #[test]
 fn test_search_index_reader_rejects_missing_required_section() {
     let index = sample_index();
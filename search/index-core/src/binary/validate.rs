@@ -9,6 +9,9 @@ pub(super) fn validate_record_count(
         columns.channel_ids.len(),
         columns.is_unlisteds.len(),
         columns.embeddables.len(),
+        columns.is_self_uploads.len(),
+        columns.is_official_channels.len(),
+        columns.has_graduated_artists.len(),
     ];
 
     if counts.into_iter().all(|count| count == record_count)
@@ -43,6 +46,42 @@ pub(super) fn validate_dictionary_non_empty(
     Ok(())
 }
 
+pub(super) fn validate_tag_hierarchy(
+    tag_hierarchy: &crate::schema::TagHierarchy,
+    dictionaries: &crate::schema::Dictionaries,
+) -> Result<(), crate::binary::Error> {
+    use crate::binary::Error;
+
+    let parents = &tag_hierarchy.parents;
+    if parents.len() != dictionaries.tags.len() {
+        return Err(Error::InvalidFormat(
+            "tag hierarchy length does not match tag dictionary",
+        ));
+    }
+    if parents
+        .iter()
+        .flatten()
+        .any(|&parent| parent as usize >= parents.len())
+    {
+        return Err(Error::InvalidFormat("tag parent is out of range"));
+    }
+    for start in 0..parents.len() {
+        // 親をたどる回数はタグの数を超えない
+        let mut current = start;
+        for _ in 0..=parents.len() {
+            match parents[current] {
+                Some(parent) => current = parent as usize,
+                None => break,
+            }
+        }
+        if parents[current].is_some() {
+            return Err(Error::InvalidFormat("tag hierarchy has a cycle"));
+        }
+    }
+
+    Ok(())
+}
+
 pub(super) fn validate_columns_against_dictionaries(
     columns: &crate::schema::ColumnStore,
     dictionaries: &crate::schema::Dictionaries,
@@ -82,6 +121,9 @@ pub(super) fn validate_exact_indexes_against_columns(
     let mut expected_channel_docs = HashMap::<u32, Vec<u32>>::new();
     let mut expected_is_unlisted_docs = [Vec::new(), Vec::new()];
     let mut expected_embeddable_docs = [Vec::new(), Vec::new()];
+    let mut expected_is_self_upload_docs = [Vec::new(), Vec::new()];
+    let mut expected_is_official_channel_docs = [Vec::new(), Vec::new()];
+    let mut expected_has_graduated_artist_docs = [Vec::new(), Vec::new()];
 
     for doc_id in 0..record_count {
         for &artist_id in columns.artist_id_lists.get(doc_id) {
@@ -104,6 +146,14 @@ pub(super) fn validate_exact_indexes_against_columns(
             .push(doc_id as u32);
         expected_embeddable_docs[usize::from(columns.embeddables[doc_id])]
             .push(doc_id as u32);
+        expected_is_self_upload_docs[usize::from(columns.is_self_uploads[doc_id])]
+            .push(doc_id as u32);
+        expected_is_official_channel_docs
+            [usize::from(columns.is_official_channels[doc_id])]
+        .push(doc_id as u32);
+        expected_has_graduated_artist_docs
+            [usize::from(columns.has_graduated_artists[doc_id])]
+        .push(doc_id as u32);
     }
 
     if exact_indexes.artist_docs != expected_artist_docs
@@ -111,12 +161,93 @@ pub(super) fn validate_exact_indexes_against_columns(
         || exact_indexes.channel_docs != expected_channel_docs
         || exact_indexes.is_unlisted_docs != expected_is_unlisted_docs
         || exact_indexes.embeddable_docs != expected_embeddable_docs
+        || exact_indexes.is_self_upload_docs != expected_is_self_upload_docs
+        || exact_indexes.is_official_channel_docs != expected_is_official_channel_docs
+        || exact_indexes.has_graduated_artist_docs != expected_has_graduated_artist_docs
     {
         return Err(crate::binary::Error::InvalidFormat(
             "exact indexes do not match column values",
         ));
     }
 
+    for (tag_id, doc_ids) in &exact_indexes.clip_tag_docs {
+        let Some(tag_doc_ids) = exact_indexes.tag_docs.get(tag_id) else {
+            return Err(crate::binary::Error::InvalidFormat(
+                "clip tag postings refer to a tag without tag postings",
+            ));
+        };
+        if doc_ids
+            .iter()
+            .any(|doc_id| tag_doc_ids.binary_search(doc_id).is_err())
+        {
+            return Err(crate::binary::Error::InvalidFormat(
+                "clip tag postings are not a subset of tag postings",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn validate_group_index(
+    groups: &crate::schema::GroupIndex,
+    dictionaries: &crate::schema::Dictionaries,
+) -> Result<(), crate::binary::Error> {
+    use crate::binary::Error;
+
+    if groups.members.len() != groups.groups.len() {
+        return Err(Error::InvalidFormat(
+            "group members length does not match group dictionary",
+        ));
+    }
+    if groups.groups.ordered_strings().iter().any(String::is_empty) {
+        return Err(Error::InvalidFormat("dictionary strings must be non-empty"));
+    }
+    if groups.members.iter().any(Vec::is_empty) {
+        return Err(Error::InvalidFormat("group must have at least one member"));
+    }
+    validate_u32_list_column(
+        &crate::util::U32ListColumn::build(&groups.members),
+        groups.groups.len(),
+        dictionaries.artists.len(),
+        "group_members",
+    )
+}
+
+pub(super) fn validate_blocked_index(
+    blocked: &crate::schema::BlockedIndex,
+    columns: &crate::schema::ColumnStore,
+    dictionaries: &crate::schema::Dictionaries,
+) -> Result<(), crate::binary::Error> {
+    use crate::binary::Error;
+
+    if blocked.tag_ids.windows(2).any(|pair| pair[0] >= pair[1])
+        || blocked
+            .tag_ids
+            .iter()
+            .any(|&tag_id| tag_id as usize >= dictionaries.tags.len())
+    {
+        return Err(Error::InvalidFormat(
+            "blocked tag ids must be sorted, unique and in range",
+        ));
+    }
+
+    let expected_docs = (0..columns.clip_ids.len())
+        .filter(|&doc_id| {
+            columns
+                .tag_id_lists
+                .get(doc_id)
+                .iter()
+                .any(|tag_id| blocked.tag_ids.binary_search(tag_id).is_ok())
+        })
+        .map(|doc_id| doc_id as u32)
+        .collect::<Vec<_>>();
+    if blocked.docs != expected_docs {
+        return Err(Error::InvalidFormat(
+            "blocked docs do not match blocked tag ids",
+        ));
+    }
+
     Ok(())
 }
 
@@ -20,9 +20,10 @@ impl<'a> BinaryWriter<'a> {
         };
         use crate::binary::format::{FILE_HEADER_SIZE, PHYSICAL_ENCODING_RAW_LE};
         use crate::binary::validate::{
-            validate_columns_against_dictionaries, validate_dictionary_non_empty,
-            validate_exact_indexes_against_columns, validate_record_count,
-            validate_sort_index,
+            validate_blocked_index, validate_columns_against_dictionaries,
+            validate_dictionary_non_empty, validate_exact_indexes_against_columns,
+            validate_group_index, validate_record_count, validate_sort_index,
+            validate_tag_hierarchy,
         };
 
         if self.index.meta.index_format_version != crate::binary::format::FORMAT_VERSION
@@ -35,6 +36,8 @@ impl<'a> BinaryWriter<'a> {
         let record_count = self.index.meta.record_count as usize;
         validate_record_count(record_count, &self.index.columns)?;
         validate_dictionary_non_empty(&self.index.dictionaries)?;
+        validate_tag_hierarchy(&self.index.tag_hierarchy, &self.index.dictionaries)?;
+        validate_group_index(&self.index.groups, &self.index.dictionaries)?;
         validate_columns_against_dictionaries(
             &self.index.columns,
             &self.index.dictionaries,
@@ -43,6 +46,11 @@ impl<'a> BinaryWriter<'a> {
             &self.index.columns,
             &self.index.exact_indexes,
         )?;
+        validate_blocked_index(
+            &self.index.blocked,
+            &self.index.columns,
+            &self.index.dictionaries,
+        )?;
         validate_sort_index(
             self.index.sort_indexes.published_at.doc_ids_asc(),
             &self.index.columns.published_ats,
@@ -137,15 +145,21 @@ impl<'a> BinaryWriter<'a> {
             encode_u32_slice,
         };
         use crate::binary::format::{
-            SECTION_COLUMN_ARTIST_ID_LISTS, SECTION_COLUMN_CHANNEL_IDS,
-            SECTION_COLUMN_CLIP_IDS, SECTION_COLUMN_EMBEDDABLES,
+            SECTION_BLOCKED_TAG_IDS, SECTION_COLUMN_ARTIST_ID_LISTS,
+            SECTION_COLUMN_CHANNEL_IDS, SECTION_COLUMN_CLIP_IDS,
+            SECTION_COLUMN_EMBEDDABLES, SECTION_COLUMN_HAS_GRADUATED_ARTISTS,
+            SECTION_COLUMN_IS_OFFICIAL_CHANNELS, SECTION_COLUMN_IS_SELF_UPLOADS,
             SECTION_COLUMN_IS_UNLISTEDS, SECTION_COLUMN_PUBLISHED_ATS,
             SECTION_COLUMN_TAG_ID_LISTS, SECTION_COLUMN_VIDEO_IDS,
             SECTION_DICT_ARTISTS, SECTION_DICT_CHANNELS, SECTION_DICT_CLIPS,
-            SECTION_DICT_TAGS, SECTION_DICT_VIDEOS, SECTION_EXACT_ARTIST_DOCS,
-            SECTION_EXACT_CHANNEL_DOCS, SECTION_EXACT_EMBEDDABLE_DOCS,
-            SECTION_EXACT_IS_UNLISTED_DOCS, SECTION_EXACT_TAG_DOCS, SECTION_METADATA,
-            SECTION_SORT_PUBLISHED_AT,
+            SECTION_DICT_GROUPS, SECTION_DICT_TAGS, SECTION_DICT_VIDEOS,
+            SECTION_EXACT_ARTIST_DOCS, SECTION_EXACT_BLOCKED_DOCS,
+            SECTION_EXACT_CHANNEL_DOCS, SECTION_EXACT_CLIP_TAG_DOCS,
+            SECTION_EXACT_EMBEDDABLE_DOCS, SECTION_EXACT_HAS_GRADUATED_ARTIST_DOCS,
+            SECTION_EXACT_IS_OFFICIAL_CHANNEL_DOCS, SECTION_EXACT_IS_SELF_UPLOAD_DOCS,
+            SECTION_EXACT_IS_UNLISTED_DOCS, SECTION_EXACT_TAG_DOCS,
+            SECTION_GROUP_MEMBERS, SECTION_METADATA, SECTION_SORT_PUBLISHED_AT,
+            SECTION_TAG_PARENTS,
         };
 
         let artist_term_count = self.index.dictionaries.artists.len();
@@ -186,6 +200,41 @@ impl<'a> BinaryWriter<'a> {
                 "tag dictionary length",
                 &self.index.dictionaries.tags,
             )?,
+            self.section(
+                SECTION_TAG_PARENTS,
+                self.item_count(tag_term_count, "tag parents length")?,
+                encode_u32_slice(
+                    &self
+                        .index
+                        .tag_hierarchy
+                        .parents
+                        .iter()
+                        .map(|parent| {
+                            parent.unwrap_or(crate::binary::format::NO_PARENT_TAG_ID)
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+            self.dictionary_section(
+                SECTION_DICT_GROUPS,
+                "group dictionary length",
+                &self.index.groups.groups,
+            )?,
+            self.section(
+                SECTION_GROUP_MEMBERS,
+                self.item_count(
+                    self.index.groups.members.len(),
+                    "group members length",
+                )?,
+                encode_u32_list_column(&crate::util::U32ListColumn::build(
+                    &self.index.groups.members,
+                )),
+            ),
+            self.section(
+                SECTION_BLOCKED_TAG_IDS,
+                self.item_count(self.index.blocked.tag_ids.len(), "blocked tag count")?,
+                encode_u32_slice(&self.index.blocked.tag_ids),
+            ),
             self.record_section(
                 SECTION_COLUMN_CLIP_IDS,
                 encode_u32_slice(&self.index.columns.clip_ids),
@@ -210,6 +259,18 @@ impl<'a> BinaryWriter<'a> {
                 SECTION_COLUMN_EMBEDDABLES,
                 encode_bool_slice(&self.index.columns.embeddables),
             ),
+            self.record_section(
+                SECTION_COLUMN_IS_SELF_UPLOADS,
+                encode_bool_slice(&self.index.columns.is_self_uploads),
+            ),
+            self.record_section(
+                SECTION_COLUMN_IS_OFFICIAL_CHANNELS,
+                encode_bool_slice(&self.index.columns.is_official_channels),
+            ),
+            self.record_section(
+                SECTION_COLUMN_HAS_GRADUATED_ARTISTS,
+                encode_bool_slice(&self.index.columns.has_graduated_artists),
+            ),
             self.record_section(
                 SECTION_COLUMN_ARTIST_ID_LISTS,
                 encode_u32_list_column(&self.index.columns.artist_id_lists),
@@ -245,6 +306,15 @@ impl<'a> BinaryWriter<'a> {
                     &self.index.exact_indexes.channel_docs,
                 )?,
             )?,
+            self.postings_section(
+                SECTION_EXACT_CLIP_TAG_DOCS,
+                tag_term_count,
+                "clip tag postings term count",
+                encode_dense_postings(
+                    tag_term_count,
+                    &self.index.exact_indexes.clip_tag_docs,
+                )?,
+            )?,
             self.section(
                 SECTION_EXACT_IS_UNLISTED_DOCS,
                 2,
@@ -255,6 +325,30 @@ impl<'a> BinaryWriter<'a> {
                 2,
                 encode_bool_postings(&self.index.exact_indexes.embeddable_docs)?,
             ),
+            self.section(
+                SECTION_EXACT_IS_SELF_UPLOAD_DOCS,
+                2,
+                encode_bool_postings(&self.index.exact_indexes.is_self_upload_docs)?,
+            ),
+            self.section(
+                SECTION_EXACT_IS_OFFICIAL_CHANNEL_DOCS,
+                2,
+                encode_bool_postings(
+                    &self.index.exact_indexes.is_official_channel_docs,
+                )?,
+            ),
+            self.section(
+                SECTION_EXACT_HAS_GRADUATED_ARTIST_DOCS,
+                2,
+                encode_bool_postings(
+                    &self.index.exact_indexes.has_graduated_artist_docs,
+                )?,
+            ),
+            self.section(
+                SECTION_EXACT_BLOCKED_DOCS,
+                self.item_count(self.index.blocked.docs.len(), "blocked doc count")?,
+                encode_u32_slice(&self.index.blocked.docs),
+            ),
             self.record_section(
                 SECTION_SORT_PUBLISHED_AT,
                 encode_u32_slice(self.index.sort_indexes.published_at.doc_ids_asc()),
@@ -1,16 +1,22 @@
+mod blocked;
 mod column;
 mod dictionary;
 mod exact;
+mod group;
 pub mod ids;
 mod metadata;
 mod search_index;
 mod sort;
+mod tag_hierarchy;
 mod time;
 
+pub use blocked::BlockedIndex;
 pub use column::ColumnStore;
 pub use dictionary::Dictionaries;
 pub use exact::{ExactIndexes, PostingList};
+pub use group::GroupIndex;
 pub use metadata::IndexMetadata;
 pub use search_index::SearchIndex;
 pub use sort::{SortIndex, SortIndexes};
+pub use tag_hierarchy::TagHierarchy;
 pub use time::TimestampSecs;
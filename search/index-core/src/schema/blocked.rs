@@ -0,0 +1,10 @@
+/// 再生をブロックするタグと、そのタグを持つ doc。
+///
+/// `docs` は `tag_id_lists` が `tag_ids` のいずれかを含む doc の集合と一致する。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlockedIndex {
+    /// `blocked` なタグ。昇順。
+    pub tag_ids: Vec<crate::schema::ids::TagId>,
+    /// ブロック対象の doc。昇順。
+    pub docs: crate::schema::PostingList,
+}
@@ -7,6 +7,12 @@ pub struct ColumnStore {
     pub channel_ids: Vec<crate::schema::ids::ChannelId>,
     pub is_unlisteds: Vec<bool>,
     pub embeddables: Vec<bool>,
+    /// 動画のチャンネルがクリップのアーティストのいずれかのチャンネルか
+    pub is_self_uploads: Vec<bool>,
+    /// 動画のチャンネルが公式チャンネルか
+    pub is_official_channels: Vec<bool>,
+    /// クリップのアーティストに卒業したライバーを含むか
+    pub has_graduated_artists: Vec<bool>,
     pub artist_id_lists: crate::util::U32ListColumn,
     pub tag_id_lists: crate::util::U32ListColumn,
 }
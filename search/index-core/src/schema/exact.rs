@@ -11,9 +11,16 @@ pub type PostingList = Vec<crate::schema::ids::DocId>;
 pub struct ExactIndexes {
     pub artist_docs:
         std::collections::HashMap<crate::schema::ids::ArtistId, PostingList>,
+    /// 動画のタグとクリップのタグの和集合で引く。
     pub tag_docs: std::collections::HashMap<crate::schema::ids::TagId, PostingList>,
+    /// クリップ自身に付いたタグだけで引く。`tag_docs` の部分集合。
+    pub clip_tag_docs:
+        std::collections::HashMap<crate::schema::ids::TagId, PostingList>,
     pub channel_docs:
         std::collections::HashMap<crate::schema::ids::ChannelId, PostingList>,
     pub is_unlisted_docs: [PostingList; 2],
     pub embeddable_docs: [PostingList; 2],
+    pub is_self_upload_docs: [PostingList; 2],
+    pub is_official_channel_docs: [PostingList; 2],
+    pub has_graduated_artist_docs: [PostingList; 2],
 }
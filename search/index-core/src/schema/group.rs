@@ -0,0 +1,8 @@
+/// ライバーのグループ(ユニット, 期生など)とそのメンバー。
+///
+/// `members[group_id]` はそのグループに属する `artist_id` の昇順リスト。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GroupIndex {
+    pub groups: crate::util::BiMap<crate::schema::ids::GroupId>,
+    pub members: Vec<Vec<crate::schema::ids::ArtistId>>,
+}
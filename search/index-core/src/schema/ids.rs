@@ -4,9 +4,11 @@ pub type VideoId = u32;
 pub type ChannelId = u32;
 pub type ArtistId = u32;
 pub type TagId = u32;
+pub type GroupId = u32;
 
 pub type ClipUuid = String;
 pub type VideoIdString = String;
 pub type ChannelIdString = String;
 pub type ArtistIdString = String;
 pub type TagIdString = String;
+pub type GroupIdString = String;
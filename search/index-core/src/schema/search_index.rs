@@ -3,7 +3,10 @@
 pub struct SearchIndex {
     pub meta: crate::schema::IndexMetadata,
     pub dictionaries: crate::schema::Dictionaries,
+    pub tag_hierarchy: crate::schema::TagHierarchy,
+    pub groups: crate::schema::GroupIndex,
     pub columns: crate::schema::ColumnStore,
     pub exact_indexes: crate::schema::ExactIndexes,
+    pub blocked: crate::schema::BlockedIndex,
     pub sort_indexes: crate::schema::SortIndexes,
 }
@@ -0,0 +1,8 @@
+/// タグの親子関係。
+///
+/// `parents[tag_id]` がそのタグの親タグ。親が無いタグは `None`。
+/// 長さは tags dictionary と同じで、親をたどって循環しない。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TagHierarchy {
+    pub parents: Vec<Option<crate::schema::ids::TagId>>,
+}
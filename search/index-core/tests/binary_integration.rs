@@ -49,6 +49,9 @@ fn sample_index() -> index_core::schema::SearchIndex {
             artists,
             tags,
         },
+        tag_hierarchy: index_core::schema::TagHierarchy {
+            parents: vec![None, None, None, Some(1)],
+        },
         columns: index_core::schema::ColumnStore {
             clip_ids: vec![0, 1, 2, 3],
             video_ids: vec![3, 2, 1, 0],
@@ -56,6 +59,9 @@ fn sample_index() -> index_core::schema::SearchIndex {
             channel_ids: vec![0, 2, 0, 1],
             is_unlisteds: vec![false, false, true, true],
             embeddables: vec![true, true, false, true],
+            is_self_uploads: vec![true, false, false, false],
+            is_official_channels: vec![false, true, false, false],
+            has_graduated_artists: vec![false, false, false, false],
             artist_id_lists: index_core::util::U32ListColumn::build(&[
                 vec![0, 2],
                 vec![],
@@ -81,6 +87,7 @@ fn sample_index() -> index_core::schema::SearchIndex {
                 (2, vec![3]),
                 (3, vec![1, 3]),
             ]),
+            clip_tag_docs: std::collections::HashMap::from([(3, vec![3])]),
             channel_docs: std::collections::HashMap::from([
                 (0, vec![0, 2]),
                 (1, vec![3]),
@@ -88,7 +95,15 @@ fn sample_index() -> index_core::schema::SearchIndex {
             ]),
             is_unlisted_docs: [vec![0, 1], vec![2, 3]],
             embeddable_docs: [vec![2], vec![0, 1, 3]],
+            is_self_upload_docs: [vec![1, 2, 3], vec![0]],
+            is_official_channel_docs: [vec![0, 2, 3], vec![1]],
+            has_graduated_artist_docs: [vec![0, 1, 2, 3], vec![]],
+        },
+        blocked: index_core::schema::BlockedIndex {
+            tag_ids: vec![2],
+            docs: vec![3],
         },
+        groups: index_core::schema::GroupIndex::default(),
         sort_indexes: index_core::schema::SortIndexes {
             published_at: index_core::schema::SortIndex::new(vec![0, 1, 3, 2]),
         },
@@ -115,6 +130,22 @@ fn test_public_binary_roundtrip_with_sparse_and_tied_values() {
         reader.tag_docs().unwrap().get(3).unwrap().unwrap().to_vec(),
         vec![1, 3],
     );
+    assert_eq!(
+        reader.tag_parents().unwrap(),
+        Some(vec![None, None, None, Some(1)]),
+    );
+    assert_eq!(
+        reader
+            .clip_tag_docs()
+            .unwrap()
+            .unwrap()
+            .get(3)
+            .unwrap()
+            .unwrap()
+            .to_vec(),
+        vec![3],
+    );
+    assert_eq!(reader.blocked_docs().unwrap().unwrap().to_vec(), vec![3]);
     assert_eq!(
         reader.published_at_sort().unwrap().doc_ids_asc().to_vec(),
         vec![0, 1, 3, 2],
@@ -0,0 +1,47 @@
+/// Build every artifact under `public/` from the metadata and music data with a single dataset build ID.
+#[derive(Debug, clap::Parser)]
+#[command(version, about, long_about = None)]
+pub struct Cli {
+    #[command(flatten)]
+    pub(crate) build: BuildArgs,
+
+    #[command(flatten)]
+    pub(crate) trace_level: cmn_rs::tracing::CliTraceOps,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct BuildArgs {
+    /// Root of the repository. The music data and the int ID lock files are read from under it.
+    #[arg(long, value_name = "DIR", default_value_t = default_repo_root())]
+    pub(crate) repo_root: String,
+    /// Directory to publish the generated `music/` and `search/` directories into. Defaults to `public/` under the repository root.
+    #[arg(long, value_name = "DIR")]
+    pub(crate) public_dir: Option<String>,
+    /// Rebuild even if the outputs were already built from the same inputs.
+    #[arg(long)]
+    pub(crate) force: bool,
+}
+
+fn default_repo_root() -> String {
+    ".".to_string()
+}
+
+impl Cli {
+    pub fn file_level(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.trace_level
+            .file_tracing_level
+            .map(|lv| lv.into_tracing_level_filter())
+    }
+
+    pub fn stdout_level(&self) -> Option<tracing::level_filters::LevelFilter> {
+        Some(
+            self.trace_level
+                .stdout_tracing_level
+                .into_tracing_level_filter(),
+        )
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.trace_level.quiet
+    }
+}
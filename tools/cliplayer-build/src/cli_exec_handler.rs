@@ -0,0 +1,17 @@
+pub fn cli_exec_handler(cli: crate::cli::Cli) -> anyhow::Result<()> {
+    handle_build(cli.build)
+}
+
+fn handle_build(args: crate::cli::BuildArgs) -> anyhow::Result<()> {
+    let inputs = crate::layout::InputLayout::new(&args.repo_root);
+    let output = args
+        .public_dir
+        .map_or_else(|| inputs.default_output(), crate::layout::OutputLayout::new);
+    let outcome = crate::pipeline::run(&inputs, &output, args.force)?;
+
+    match outcome {
+        crate::pipeline::BuildOutcome::Built(id) => println!("Built: {id}"),
+        crate::pipeline::BuildOutcome::UpToDate(id) => println!("Up to date: {id}"),
+    }
+    Ok(())
+}
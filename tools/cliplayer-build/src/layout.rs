@@ -0,0 +1,351 @@
+/// ビルドの入力のリポジトリ内の配置
+///
+/// 全ての段階で同じリポジトリの入力を使うため, 入力のパスは全てリポジトリのルートから決める
+#[derive(Debug, Clone)]
+pub(crate) struct InputLayout {
+    root: std::path::PathBuf,
+}
+
+/// 1回のビルドで書き出すディレクトリ, ファイルの配置
+///
+/// 出力先のうち`music/`と`search/`, ビルドが使う隠しファイルだけを持ち, wasmエンジンなど他のファイルには触れない
+#[derive(Debug, Clone)]
+pub(crate) struct OutputLayout {
+    root: std::path::PathBuf,
+}
+
+/// 出力先に書き出している間だけ存在するステージングディレクトリの名前の接頭辞. 後ろにプロセスIDを付ける
+const STAGING_DIR_PREFIX: &str = ".build-staging-";
+/// 同じ出力先へのビルドを排他するロックファイル
+const LOCK_FILE_NAME: &str = ".build.lock";
+/// `music/`, `search/`を置き換えている間だけ存在するジャーナル
+const JOURNAL_FILE_NAME: &str = ".build.journal";
+
+impl InputLayout {
+    pub(crate) fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub(crate) fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+
+    /// 楽曲情報のルート
+    pub(crate) fn music_root(&self) -> std::path::PathBuf {
+        self.root.join(musictl::cfg::DEFAULT_MUSIC_ROOT_DIR)
+    }
+
+    /// ライバー, 公式チャンネルの整数IDのロックファイル
+    pub(crate) fn artist_int_id_lock_path(&self) -> std::path::PathBuf {
+        self.root.join("metadata/artist/data/int_ids.lock.json")
+    }
+
+    /// 動画タグの整数IDのロックファイル
+    pub(crate) fn tag_int_id_lock_path(&self) -> std::path::PathBuf {
+        self.root.join("metadata/tag/data/int_ids.lock.json")
+    }
+
+    /// 出力先の既定. `public/`
+    pub(crate) fn default_output(&self) -> OutputLayout {
+        OutputLayout::new(self.root.join("public"))
+    }
+
+    /// 読み込み済みのメタデータの定義ファイルが, 全てリポジトリの中にあるか確かめる
+    ///
+    /// 定義はプロセス全体で1度だけ読み込むため, パスを渡せない.
+    /// 環境変数で別のファイルを指しているときは, 他の段階と入力が食い違うのでエラーにする
+    ///
+    /// # Errors
+    /// - リポジトリのルート, 定義ファイルが存在しないとき
+    /// - 定義ファイルがリポジトリの外にあるとき
+    pub(crate) fn ensure_contains_definitions(&self) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let root = self.root.canonicalize().with_context(|| {
+            format!(
+                "Failed to resolve repository root `{}`",
+                self.root.display()
+            )
+        })?;
+        let definitions = artistctl::generate::definition_paths()
+            .into_iter()
+            .chain(tagctl::generate::definition_paths())
+            .chain(songctl::generate::definition_paths());
+        for path in definitions {
+            let resolved = path.canonicalize().with_context(|| {
+                format!("Failed to resolve metadata definition `{}`", path.display())
+            })?;
+            if !resolved.starts_with(&root) {
+                anyhow::bail!(
+                    "Metadata definition `{}` is outside the repository root `{}`",
+                    resolved.display(),
+                    root.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OutputLayout {
+    pub(crate) fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub(crate) fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+
+    /// このプロセスのステージングディレクトリ
+    pub(crate) fn staging(&self) -> Self {
+        Self::new(
+            self.root
+                .join(format!("{STAGING_DIR_PREFIX}{}", std::process::id())),
+        )
+    }
+
+    pub(crate) fn lock_path(&self) -> std::path::PathBuf {
+        self.root.join(LOCK_FILE_NAME)
+    }
+
+    pub(crate) fn journal_path(&self) -> std::path::PathBuf {
+        self.root.join(JOURNAL_FILE_NAME)
+    }
+
+    /// 途中で終了したビルドが残したステージングディレクトリ, `music/`, `search/`の退避先か
+    ///
+    /// 退避先の名前は`cmn_rs::atomic_write`の`.<name>.bak-<pid>`
+    pub(crate) fn is_leftover(&self, file_name: &std::ffi::OsStr) -> bool {
+        let Some(name) = file_name.to_str() else {
+            return false;
+        };
+        name.starts_with(STAGING_DIR_PREFIX)
+            || ["music", "search"]
+                .iter()
+                .any(|dir| name.starts_with(&format!(".{dir}.bak-")))
+    }
+
+    /// 最小化したJSONを置くディレクトリ
+    pub(crate) fn music_dir(&self) -> std::path::PathBuf {
+        self.root.join("music")
+    }
+
+    /// 検索インデックスを置くディレクトリ
+    pub(crate) fn search_dir(&self) -> std::path::PathBuf {
+        self.root.join("search")
+    }
+
+    pub(crate) fn min_clips_path(&self) -> std::path::PathBuf {
+        self.music_dir()
+            .join(file_name(musictl::cfg::DEFAULT_MIN_OUTPUT_CLIPS_PATH))
+    }
+
+    pub(crate) fn min_videos_path(&self) -> std::path::PathBuf {
+        self.music_dir()
+            .join(file_name(musictl::cfg::DEFAULT_MIN_OUTPUT_VIDEOS_PATH))
+    }
+
+    pub(crate) fn search_index_path(&self) -> std::path::PathBuf {
+        self.search_dir()
+            .join(file_name(index_builder::cli::DEFAULT_OUTPUT_PATH))
+    }
+
+    /// 最小化したJSONのパス
+    pub(crate) fn min_json_paths(&self) -> Vec<std::path::PathBuf> {
+        let music_dir = self.music_dir();
+        [
+            artistctl::output::MIN_LIVERS_SEARCH_INDEX_FILE_NAME,
+            artistctl::output::MIN_CHANNELS_FILE_NAME,
+            artistctl::output::MIN_LIVERS_FILE_NAME,
+            artistctl::output::MIN_OFFICIAL_CHANNELS_FILE_NAME,
+            artistctl::output::MIN_OFFICIAL_CHANNELS_SEARCH_INDEX_FILE_NAME,
+            artistctl::output::MIN_GROUPS_FILE_NAME,
+            tagctl::output::MIN_TAGS_FILE_NAME,
+            tagctl::output::MIN_TAGS_SEARCH_INDEX_FILE_NAME,
+            songctl::output::MIN_SONGS_FILE_NAME,
+        ]
+        .into_iter()
+        .map(|name| music_dir.join(name))
+        .chain([self.min_clips_path(), self.min_videos_path()])
+        .collect()
+    }
+
+    /// 出力が全て揃っていて, どれも`dataset_build_id`のビルドで書き出されたものか
+    pub(crate) fn is_built_with(
+        &self,
+        dataset_build_id: &cmn_rs::min_json::DatasetBuildId,
+    ) -> bool {
+        let expected = dataset_build_id.as_str();
+        let min_json_ok = self.min_json_paths().iter().all(|path| {
+            let actual = read_min_json_build_id(path);
+            if actual.as_deref() != Some(expected) {
+                tracing::debug!(
+                    "`{}` was not built with the current inputs (found: {actual:?})",
+                    path.display()
+                );
+                return false;
+            }
+            true
+        });
+        if !min_json_ok {
+            return false;
+        }
+
+        let path = self.search_index_path();
+        let actual = read_search_index_build_id(&path);
+        if actual.as_deref() != Some(expected) {
+            tracing::debug!(
+                "`{}` was not built with the current inputs (found: {actual:?})",
+                path.display()
+            );
+            return false;
+        }
+        true
+    }
+}
+
+/// `cfg`の既定の出力パスからファイル名を取り出す
+fn file_name(default_path: &'static str) -> &'static str {
+    std::path::Path::new(default_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .expect("default output paths end with a file name")
+}
+
+/// 最小化したJSONの`datasetBuildId`. 読めないときは`None`
+fn read_min_json_build_id(path: &std::path::Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let envelope: serde_json::Value = serde_json::from_str(&content).ok()?;
+    envelope["datasetBuildId"].as_str().map(str::to_string)
+}
+
+/// 検索インデックスのメタデータに埋め込まれたビルドID. 読めないときは`None`
+fn read_search_index_build_id(path: &std::path::Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let reader = index_core::binary::SearchIndexReader::new(&bytes).ok()?;
+    let metadata = reader.metadata_view().ok()?;
+    Some(metadata.dataset_build_id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUILD_ID: &str = "dataset-build-0123456789abcdef";
+
+    fn write_min_json(layout: &OutputLayout, build_id: &str) {
+        std::fs::create_dir_all(layout.music_dir()).unwrap();
+        for path in layout.min_json_paths() {
+            let envelope = serde_json::json!({
+                "schemaVersion": 1,
+                "datasetBuildId": build_id,
+                "data": [],
+            });
+            std::fs::write(path, envelope.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_min_json_paths_are_unique_files_in_music_dir() {
+        let layout = OutputLayout::new("public");
+        let paths = layout.min_json_paths();
+
+        assert_eq!(paths.len(), 11);
+        assert!(
+            paths
+                .iter()
+                .all(|path| path.parent() == Some(&layout.music_dir()))
+        );
+        let unique = paths.iter().collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(unique.len(), paths.len());
+        assert_eq!(
+            layout.search_index_path(),
+            std::path::Path::new("public/search/search_index.bin")
+        );
+    }
+
+    #[test]
+    fn test_is_leftover() {
+        let layout = OutputLayout::new("public");
+
+        assert!(layout.is_leftover(layout.staging().root().file_name().unwrap()));
+        assert!(layout.is_leftover(".music.bak-123".as_ref()));
+        assert!(layout.is_leftover(".search.bak-123".as_ref()));
+        for name in [
+            "music",
+            "search",
+            "engine.wasm",
+            ".build.lock",
+            ".build.journal",
+        ] {
+            assert!(!layout.is_leftover(name.as_ref()), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_input_paths_are_under_repo_root() {
+        let inputs = InputLayout::new("repo");
+
+        assert_eq!(
+            inputs.music_root(),
+            std::path::Path::new("repo/music/data/music")
+        );
+        assert_eq!(
+            inputs.artist_int_id_lock_path(),
+            std::path::Path::new("repo/metadata/artist/data/int_ids.lock.json")
+        );
+        assert_eq!(
+            inputs.tag_int_id_lock_path(),
+            std::path::Path::new("repo/metadata/tag/data/int_ids.lock.json")
+        );
+        assert_eq!(
+            inputs.default_output().root(),
+            std::path::Path::new("repo/public")
+        );
+    }
+
+    #[test]
+    fn test_ensure_contains_definitions_requires_existing_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(
+            InputLayout::new(tmp.path())
+                .ensure_contains_definitions()
+                .is_ok()
+        );
+
+        let missing = InputLayout::new(tmp.path().join("missing"));
+        let err = missing.ensure_contains_definitions().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to resolve repository root"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_is_built_with_requires_every_output() {
+        let tmp = tempfile::tempdir().unwrap();
+        let layout = OutputLayout::new(tmp.path());
+        let id = BUILD_ID.parse().unwrap();
+
+        assert!(!layout.is_built_with(&id));
+
+        // 検索インデックスが無い
+        write_min_json(&layout, BUILD_ID);
+        assert!(!layout.is_built_with(&id));
+
+        // 検索インデックスとして読めない
+        std::fs::create_dir_all(layout.search_dir()).unwrap();
+        std::fs::write(layout.search_index_path(), b"broken").unwrap();
+        assert!(!layout.is_built_with(&id));
+    }
+
+    #[test]
+    fn test_is_built_with_rejects_other_build_id() {
+        let tmp = tempfile::tempdir().unwrap();
+        let layout = OutputLayout::new(tmp.path());
+        write_min_json(&layout, "dataset-build-other0000");
+
+        assert!(!layout.is_built_with(&BUILD_ID.parse().unwrap()));
+    }
+}
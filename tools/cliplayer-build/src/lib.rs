@@ -0,0 +1,4 @@
+pub mod cli;
+pub mod cli_exec_handler;
+pub mod layout;
+pub mod pipeline;
@@ -0,0 +1,17 @@
+fn main() {
+    use clap::Parser;
+
+    let cli = cliplayer_build::cli::Cli::parse();
+    let _tracing_guard = cmn_rs::tracing::apply_tracing_settings(
+        "cliplayer-build",
+        cli.stdout_level(),
+        cli.file_level(),
+        cli.is_quiet(),
+    );
+    tracing::debug!("Command line arguments: {:?}", cli);
+
+    if let Err(error) = cliplayer_build::cli_exec_handler::cli_exec_handler(cli) {
+        tracing::error!("Command failed: {error:#}");
+        std::process::exit(1);
+    }
+}
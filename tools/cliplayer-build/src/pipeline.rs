@@ -0,0 +1,237 @@
+/// ビルドの結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BuildOutcome {
+    /// 出力を書き出して置き換えた
+    Built(cmn_rs::min_json::DatasetBuildId),
+    /// 入力が前回のビルドから変わっていないので何もしなかった
+    UpToDate(cmn_rs::min_json::DatasetBuildId),
+}
+
+/// 整数IDを検査し, 入力から決めたビルドIDで全ての成果物を書き出して出力先に反映する
+///
+/// 入力は全て`inputs`のリポジトリから読む
+///
+/// 1. アーティスト, タグの整数IDがロックファイルと一致しているか検査する
+/// 2. 各ドメインの`hash-inputs`からビルドIDを1つ決める
+/// 3. 出力先をロックし, 前回のビルドが途中で終了していれば元に戻す
+/// 4. 出力先が同じビルドIDで揃っていれば, `force`でない限りここで終える
+/// 5. 出力先の中のステージングディレクトリに書き出し, `music/`, `search/`を置き換える
+pub(crate) fn run(
+    inputs: &crate::layout::InputLayout,
+    output: &crate::layout::OutputLayout,
+    force: bool,
+) -> anyhow::Result<BuildOutcome> {
+    tracing::info!("Repository root: {}", inputs.root().display());
+    inputs.ensure_contains_definitions()?;
+    check_int_ids(inputs)?;
+
+    let dataset_build_id = dataset_build_id(inputs)?;
+    tracing::info!("Dataset build id: {dataset_build_id}");
+
+    std::fs::create_dir_all(output.root())?;
+    let _lock = lock(output)?;
+    recover(output)?;
+
+    if !force && output.is_built_with(&dataset_build_id) {
+        tracing::info!(
+            "Outputs in `{}` are up to date, skipping the build",
+            output.root().display()
+        );
+        return Ok(BuildOutcome::UpToDate(dataset_build_id));
+    }
+
+    let staging = output.staging();
+    let result = build_into(&staging, inputs, &dataset_build_id)
+        .and_then(|()| publish(&staging, output));
+    if let Err(e) = std::fs::remove_dir_all(staging.root())
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!(
+            "Failed to remove staging directory `{}`: {e}",
+            staging.root().display()
+        );
+    }
+    result?;
+
+    tracing::info!("Published outputs to `{}`", output.root().display());
+    Ok(BuildOutcome::Built(dataset_build_id))
+}
+
+/// 出力先を書き換えるビルドを1つにする. 戻り値を保持している間ロックされる
+///
+/// # Errors
+/// - 他のプロセスが同じ出力先をビルドしているとき
+fn lock(output: &crate::layout::OutputLayout) -> anyhow::Result<std::fs::File> {
+    use anyhow::Context;
+
+    let path = output.lock_path();
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .with_context(|| format!("Failed to open lock file `{}`", path.display()))?;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(std::fs::TryLockError::WouldBlock) => {
+            anyhow::bail!("Another build is writing to `{}`", output.root().display())
+        }
+        Err(std::fs::TryLockError::Error(e)) => {
+            Err(e).with_context(|| format!("Failed to lock `{}`", path.display()))
+        }
+    }
+}
+
+/// 前回のビルドが途中で終了していたときに残ったものを片付ける
+///
+/// - `music/`, `search/`の置き換えの途中なら元に戻し, 置き換えの後なら退避先を消す
+/// - ステージングディレクトリ, 退避先が残っていれば消す
+fn recover(output: &crate::layout::OutputLayout) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let journal = output.journal_path();
+    cmn_rs::atomic_write::recover(&journal).with_context(|| {
+        format!(
+            "Failed to recover an interrupted publish from `{}`",
+            journal.display()
+        )
+    })?;
+
+    for entry in std::fs::read_dir(output.root())? {
+        let entry = entry?;
+        if output.is_leftover(&entry.file_name()) {
+            tracing::warn!("Removing leftover `{}`", entry.path().display());
+            std::fs::remove_dir_all(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// ロックファイルがコミットされていないと, ビルドごとに整数IDが変わりうる
+fn check_int_ids(inputs: &crate::layout::InputLayout) -> anyhow::Result<()> {
+    let diagnostics = [
+        (
+            "artist",
+            artistctl::generate::check_ids_at(
+                &inputs.artist_int_id_lock_path(),
+                false,
+            )?,
+        ),
+        (
+            "tag",
+            tagctl::generate::check_ids_at(&inputs.tag_int_id_lock_path(), false)?,
+        ),
+    ];
+
+    let mut errors = 0;
+    for (domain, diagnostics) in &diagnostics {
+        for diagnostic in diagnostics {
+            if diagnostic.is_error {
+                errors += 1;
+                tracing::error!("{diagnostic}");
+            } else {
+                tracing::warn!("{diagnostic}");
+            }
+        }
+        if diagnostics.iter().any(|d| d.is_error) {
+            tracing::error!(
+                "Run `metadata {domain} check-ids --write` to update the lock file"
+            );
+        }
+    }
+    if errors > 0 {
+        anyhow::bail!("{errors} int id errors found");
+    }
+    Ok(())
+}
+
+fn dataset_build_id(
+    inputs: &crate::layout::InputLayout,
+) -> anyhow::Result<cmn_rs::min_json::DatasetBuildId> {
+    let artist = artistctl::generate::hash_inputs()?;
+    let tag = tagctl::generate::hash_inputs_at(&inputs.tag_int_id_lock_path())?;
+    let song = songctl::generate::hash_inputs()?;
+    let music = musictl::hash_inputs::hash_music_inputs(&inputs.music_root())?;
+
+    Ok(cmn_rs::min_json::DatasetBuildId::from_input_hashes(&[
+        ("artist", &artist),
+        ("tag", &tag),
+        ("song", &song),
+        ("music", &music),
+    ]))
+}
+
+/// 全ての成果物を`staging`に書き出す
+fn build_into(
+    staging: &crate::layout::OutputLayout,
+    inputs: &crate::layout::InputLayout,
+    dataset_build_id: &cmn_rs::min_json::DatasetBuildId,
+) -> anyhow::Result<()> {
+    let music_root = inputs.music_root();
+    if staging.root().exists() {
+        std::fs::remove_dir_all(staging.root())?;
+    }
+    std::fs::create_dir_all(staging.music_dir())?;
+    std::fs::create_dir_all(staging.search_dir())?;
+
+    let music_dir = staging.music_dir().to_string_lossy().into_owned();
+    artistctl::generate::minify(
+        music_dir.clone(),
+        artistctl::output::MIN_LIVERS_SEARCH_INDEX_FILE_NAME.to_string(),
+        artistctl::output::MIN_CHANNELS_FILE_NAME.to_string(),
+        artistctl::output::MIN_LIVERS_FILE_NAME.to_string(),
+        artistctl::output::MIN_OFFICIAL_CHANNELS_FILE_NAME.to_string(),
+        artistctl::output::MIN_OFFICIAL_CHANNELS_SEARCH_INDEX_FILE_NAME.to_string(),
+        artistctl::output::MIN_GROUPS_FILE_NAME.to_string(),
+        dataset_build_id.clone(),
+    )?;
+    tagctl::generate::minify_at(
+        &inputs.tag_int_id_lock_path(),
+        music_dir.clone(),
+        tagctl::output::MIN_TAGS_FILE_NAME.to_string(),
+        tagctl::output::MIN_TAGS_SEARCH_INDEX_FILE_NAME.to_string(),
+        dataset_build_id.clone(),
+    )?;
+    songctl::generate::minify(
+        music_dir,
+        songctl::output::MIN_SONGS_FILE_NAME.to_string(),
+        dataset_build_id.clone(),
+    )?;
+    musictl::min_output::minify(
+        &music_root,
+        &staging.min_clips_path(),
+        &staging.min_videos_path(),
+        dataset_build_id.clone(),
+    )?;
+
+    tracing::info!("Building search index...");
+    let binary = index_builder::build::build_search_index_binary(
+        &music_root,
+        dataset_build_id.clone(),
+    )?;
+    std::fs::write(staging.search_index_path(), binary)?;
+
+    Ok(())
+}
+
+/// `staging`の`music/`, `search/`で出力先のものを置き換える
+///
+/// 失敗, 中断したときは両方とも元に戻す(中断したときは次の`run`で戻す).
+/// 置き換えはディレクトリごとなので, 置き換えている間に読むと一方だけ新しいことがある
+fn publish(
+    staging: &crate::layout::OutputLayout,
+    output: &crate::layout::OutputLayout,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    cmn_rs::atomic_write::replace_dirs(
+        &output.journal_path(),
+        &[
+            (staging.music_dir(), output.music_dir()),
+            (staging.search_dir(), output.search_dir()),
+        ],
+    )
+    .with_context(|| {
+        format!("Failed to publish outputs to `{}`", output.root().display())
+    })
+}
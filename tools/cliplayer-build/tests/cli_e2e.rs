@@ -0,0 +1,243 @@
+use assert_cmd::assert::OutputAssertExt;
+use assert_cmd::prelude::CommandCargoExt;
+
+const MONTHLY_FILE_JSON: &str = r#"[
+  {
+    "videoId": "cFc9Ywpk0QU",
+    "title": "Test Karaoke Stream",
+    "channelId": "UC1111111111111111111111",
+    "publishedAt": "2026-01-19T13:23:27Z",
+    "syncedAt": "2026-04-22T01:57:28Z",
+    "duration": "PT1H0M0S",
+    "privacyStatus": "public",
+    "embeddable": true,
+    "videoTags": ["karaoke"],
+    "clips": [
+      {
+        "songTitle": "fuwafuwa time",
+        "liverIds": ["riku-tazumi"],
+        "startTime": "PT3M2S",
+        "endTime": "PT6M56S",
+        "uuid": "11786ebd-4b42-428b-81f8-ecf791887326"
+      }
+    ]
+  }
+]
+"#;
+
+/// テストデータの整数IDと一致するロックファイル
+const ARTIST_LOCK_JSON: &str = r#"{
+  "livers": {"riku-tazumi": 0, "yudorikku": 1, "yugamin": 2},
+  "officialChannels": {"test-channel-1": 950, "test-channel-2": 951, "test-channel-3": 952}
+}
+"#;
+const TAG_LOCK_JSON: &str = r#"{
+  "tags": {"karaoke": 0, "3d": 1, "acoustic": 2, "3d-debut": 3}
+}
+"#;
+
+fn write_text_file(path: &std::path::Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(path, content).unwrap();
+}
+
+fn read_json(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+const MONTHLY_FILE_PATH: &str = "music/data/music/2026/01.json";
+const TAG_LOCK_PATH: &str = "metadata/tag/data/int_ids.lock.json";
+
+/// 一時ディレクトリに作ったリポジトリ
+struct Fixture {
+    tmp: tempfile::TempDir,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let tmp = tempfile::tempdir().unwrap();
+        write_text_file(&tmp.path().join(MONTHLY_FILE_PATH), MONTHLY_FILE_JSON);
+        write_text_file(
+            &tmp.path().join("metadata/artist/data/int_ids.lock.json"),
+            ARTIST_LOCK_JSON,
+        );
+        write_text_file(&tmp.path().join(TAG_LOCK_PATH), TAG_LOCK_JSON);
+        write_text_file(&tmp.path().join("public/engine.wasm"), "wasm");
+        Self { tmp }
+    }
+
+    fn public_dir(&self) -> std::path::PathBuf {
+        self.tmp.path().join("public")
+    }
+
+    /// 出力先はリポジトリの`public/`
+    fn command(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::cargo_bin("cliplayer-build").unwrap();
+        cmd.arg("--quiet").arg("--repo-root").arg(self.tmp.path());
+        cmd
+    }
+
+    fn build_id(&self) -> String {
+        let clips = read_json(&self.public_dir().join("music/clips.min.json"));
+        clips["datasetBuildId"].as_str().unwrap().to_string()
+    }
+}
+
+#[test]
+fn test_build_publishes_outputs_with_shared_build_id() {
+    let fixture = Fixture::new();
+    let public_dir = fixture.public_dir();
+    write_text_file(&public_dir.join("music/stale.min.json"), "{}");
+
+    let output = fixture
+        .command()
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let build_id = fixture.build_id();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        format!("Built: {build_id}\n")
+    );
+
+    for name in [
+        "livers_search_index.min.json",
+        "channels.min.json",
+        "livers.min.json",
+        "official_channels.min.json",
+        "official_channels_search_index.min.json",
+        "groups.min.json",
+        "tags.min.json",
+        "tags_search_index.min.json",
+        "songs.min.json",
+        "videos.min.json",
+    ] {
+        let json = read_json(&public_dir.join("music").join(name));
+        assert_eq!(json["datasetBuildId"], build_id.as_str(), "{name}");
+    }
+    let bytes = std::fs::read(public_dir.join("search/search_index.bin")).unwrap();
+    let reader = index_core::binary::SearchIndexReader::new(&bytes).unwrap();
+    assert_eq!(reader.metadata_view().unwrap().dataset_build_id(), build_id);
+
+    // 前回のビルドの残りは消え, 管理外のファイルはそのまま
+    assert!(!public_dir.join("music/stale.min.json").exists());
+    assert_eq!(
+        std::fs::read_to_string(public_dir.join("engine.wasm")).unwrap(),
+        "wasm"
+    );
+    let mut entries = std::fs::read_dir(&public_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, [".build.lock", "engine.wasm", "music", "search"]);
+}
+
+#[test]
+fn test_build_recovers_interrupted_publish() {
+    let fixture = Fixture::new();
+    fixture.command().assert().success();
+    let build_id = fixture.build_id();
+    let public_dir = fixture.public_dir();
+
+    // `music/`を置き換えた直後に終了した状態
+    let music = public_dir.join("music");
+    let backup = public_dir.join(".music.bak-999");
+    let staging = public_dir.join(".build-staging-999");
+    std::fs::rename(&music, &backup).unwrap();
+    write_text_file(&music.join("clips.min.json"), "{}");
+    write_text_file(&staging.join("search/search_index.bin"), "");
+    write_text_file(
+        &public_dir.join(".build.journal"),
+        &serde_json::json!({
+            "state": "prepared",
+            "entries": [
+                {"path": music, "tmp": staging.join("music"), "backup": backup},
+                {
+                    "path": public_dir.join("search"),
+                    "tmp": staging.join("search"),
+                    "backup": public_dir.join(".search.bak-999"),
+                },
+            ],
+        })
+        .to_string(),
+    );
+
+    fixture
+        .command()
+        .assert()
+        .success()
+        .stdout(format!("Up to date: {build_id}\n"));
+    assert_eq!(fixture.build_id(), build_id);
+    let mut entries = std::fs::read_dir(&public_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, [".build.lock", "engine.wasm", "music", "search"]);
+}
+
+#[test]
+fn test_build_skips_when_inputs_are_unchanged() {
+    let fixture = Fixture::new();
+    fixture.command().assert().success();
+    let build_id = fixture.build_id();
+    let clips_path = fixture.public_dir().join("music/clips.min.json");
+    let modified = std::fs::metadata(&clips_path).unwrap().modified().unwrap();
+
+    fixture
+        .command()
+        .assert()
+        .success()
+        .stdout(format!("Up to date: {build_id}\n"));
+    assert_eq!(
+        std::fs::metadata(&clips_path).unwrap().modified().unwrap(),
+        modified
+    );
+
+    fixture
+        .command()
+        .arg("--force")
+        .assert()
+        .success()
+        .stdout(format!("Built: {build_id}\n"));
+
+    // 入力が変わればビルドIDも変わる
+    let changed = MONTHLY_FILE_JSON.replace("Test Karaoke Stream", "Renamed Stream");
+    write_text_file(&fixture.tmp.path().join(MONTHLY_FILE_PATH), &changed);
+    fixture.command().assert().success();
+    assert_ne!(fixture.build_id(), build_id);
+}
+
+#[test]
+fn test_build_publishes_into_public_dir_override() {
+    let fixture = Fixture::new();
+    let public_dir = fixture.tmp.path().join("dist");
+
+    fixture
+        .command()
+        .arg("--public-dir")
+        .arg(&public_dir)
+        .assert()
+        .success();
+    assert!(public_dir.join("music/clips.min.json").exists());
+    assert!(!fixture.public_dir().join("music").exists());
+}
+
+#[test]
+fn test_build_fails_without_touching_outputs_when_int_ids_are_not_locked() {
+    let fixture = Fixture::new();
+    write_text_file(&fixture.tmp.path().join(TAG_LOCK_PATH), r#"{"tags": {}}"#);
+
+    fixture
+        .command()
+        .assert()
+        .failure()
+        .stdout(predicates::str::is_empty());
+    let entries = std::fs::read_dir(fixture.public_dir()).unwrap().count();
+    assert_eq!(entries, 1);
+}